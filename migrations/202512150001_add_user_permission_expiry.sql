@@ -0,0 +1,4 @@
+-- Time-bound direct grants: NULL means the grant never expires
+ALTER TABLE user_permissions ADD COLUMN expires_at DATETIME;
+
+CREATE INDEX IF NOT EXISTS idx_user_permissions_expires ON user_permissions(expires_at);
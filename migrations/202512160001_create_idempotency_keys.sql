@@ -0,0 +1,17 @@
+-- Generic Idempotency-Key store for create endpoints: the first response is kept
+-- and replayed for retries with the same key. `scope` names the endpoint (and
+-- its parent ids) so one key can be reused across endpoints. `response_body` is
+-- NULL while the first request is still in flight.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    user_id BLOB NOT NULL,
+    scope TEXT NOT NULL,
+    idempotency_key TEXT NOT NULL,
+    project_id BLOB NOT NULL,
+    resource_id BLOB NOT NULL,
+    response_body TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, scope, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys(created_at);
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_project ON idempotency_keys(project_id);
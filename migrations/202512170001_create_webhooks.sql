@@ -0,0 +1,12 @@
+-- Outbound webhook subscriptions for domain events
+CREATE TABLE IF NOT EXISTS webhooks (
+    id TEXT PRIMARY KEY NOT NULL,
+    url TEXT NOT NULL,
+    -- HMAC-SHA256 key for the X-Webhook-Signature header
+    secret TEXT NOT NULL,
+    -- Glob over event names, e.g. 'task.*'; '*' receives everything
+    event_name_filter TEXT NOT NULL DEFAULT '*',
+    created_by TEXT,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL
+);
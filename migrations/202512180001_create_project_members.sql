@@ -0,0 +1,10 @@
+-- Users other than the owner who may work on a project
+CREATE TABLE IF NOT EXISTS project_members (
+    project_id BLOB NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role TEXT NOT NULL DEFAULT 'member',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (project_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_members_user ON project_members(user_id);
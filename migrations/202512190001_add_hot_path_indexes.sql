@@ -0,0 +1,17 @@
+-- Composite indexes for the hot read paths.
+-- activity_log(occurred_at) already exists (idx_activity_log_occurred_at).
+
+-- list_tasks: WHERE project_id = ? AND deleted_at IS NULL ORDER BY start_date
+CREATE INDEX IF NOT EXISTS idx_tasks_project_deleted_start ON tasks(project_id, deleted_at, start_date);
+
+-- list_progress: WHERE task_id = ? AND deleted_at IS NULL ORDER BY created_at
+CREATE INDEX IF NOT EXISTS idx_task_progress_task_deleted_created ON task_progress(task_id, deleted_at, created_at);
+
+-- dashboard actuals: WHERE project_id = ? ... GROUP BY DATE(created_at)
+CREATE INDEX IF NOT EXISTS idx_task_progress_project_created ON task_progress(project_id, created_at);
+
+-- Superseded by the composites above (same leading columns)
+DROP INDEX IF EXISTS idx_tasks_project_id;
+DROP INDEX IF EXISTS idx_tasks_project_start;
+DROP INDEX IF EXISTS idx_task_progress_task_id;
+DROP INDEX IF EXISTS idx_task_progress_project_id;
@@ -0,0 +1,3 @@
+-- Optional estimate for tasks without a start/end range, so they still carry
+-- a duration in critical-path and scheduling calculations.
+ALTER TABLE tasks ADD COLUMN estimated_days INTEGER;
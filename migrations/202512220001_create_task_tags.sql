@@ -0,0 +1,9 @@
+-- Free-form labels on tasks (frontend, backend, bug, ...), stored lowercase
+CREATE TABLE IF NOT EXISTS task_tags (
+    task_id BLOB NOT NULL REFERENCES tasks(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (task_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_tags_tag ON task_tags(tag);
@@ -0,0 +1,33 @@
+-- Task status is now a closed set (TaskStatus in the API). Fold spelling
+-- variants and the legacy aliases listed in `TaskStatus::LEGACY_ALIASES` onto
+-- their canonical form.
+UPDATE tasks
+SET status = CASE lower(replace(replace(trim(status), ' ', '_'), '-', '_'))
+    WHEN 'pending' THEN 'pending'
+    WHEN 'todo' THEN 'pending'
+    WHEN 'to_do' THEN 'pending'
+    WHEN 'open' THEN 'pending'
+    WHEN 'new' THEN 'pending'
+    WHEN 'not_started' THEN 'pending'
+    WHEN 'in_progress' THEN 'in_progress'
+    WHEN 'doing' THEN 'in_progress'
+    WHEN 'started' THEN 'in_progress'
+    WHEN 'active' THEN 'in_progress'
+    WHEN 'done' THEN 'done'
+    WHEN 'completed' THEN 'done'
+    WHEN 'complete' THEN 'done'
+    WHEN 'finished' THEN 'done'
+    WHEN 'closed' THEN 'done'
+    WHEN 'blocked' THEN 'blocked'
+    WHEN 'on_hold' THEN 'blocked'
+    WHEN 'cancelled' THEN 'cancelled'
+    WHEN 'canceled' THEN 'cancelled'
+    ELSE status
+END
+WHERE status IS NOT NULL;
+
+-- A missing status, or anything still outside the set, has no known meaning;
+-- it becomes 'pending' (the column default) so every row keeps decoding.
+UPDATE tasks
+SET status = 'pending'
+WHERE status IS NULL OR status NOT IN ('pending', 'in_progress', 'done', 'blocked', 'cancelled');
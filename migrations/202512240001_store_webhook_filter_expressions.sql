@@ -0,0 +1,23 @@
+-- Webhooks now store a full filter expression (see events::filter), e.g.
+-- "event_name LIKE 'task.*' AND severity >= important", instead of a name glob.
+-- Existing globs become the equivalent LIKE clause, with any single quote
+-- doubled as the filter syntax expects. SQLite cannot change a column's name
+-- and default in place, so the table is rebuilt.
+CREATE TABLE webhooks_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    url TEXT NOT NULL,
+    -- HMAC-SHA256 key for the X-Webhook-Signature header
+    secret TEXT NOT NULL,
+    -- Filter expression; "event_name LIKE '*'" receives everything
+    filter TEXT NOT NULL DEFAULT 'event_name LIKE ''*''',
+    created_by TEXT,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+INSERT INTO webhooks_new (id, url, secret, filter, created_by, created_at, updated_at)
+SELECT id, url, secret, 'event_name LIKE ''' || replace(event_name_filter, '''', '''''') || '''', created_by, created_at, updated_at
+FROM webhooks;
+
+DROP TABLE webhooks;
+ALTER TABLE webhooks_new RENAME TO webhooks;
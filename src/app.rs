@@ -1,22 +1,39 @@
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
 
-use axum::http::Method;
-use axum::routing::{delete, get, post, put};
+use axum::middleware::{from_fn, from_fn_with_state};
+use axum::routing::{delete, get, patch, post, put};
 use axum::Router;
 use sqlx::SqlitePool;
-use tower_http::cors::{Any, CorsLayer};
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
 use tower_http::trace::TraceLayer;
 
+use crate::authz::{self, AuthzMode, PermissionCache};
 use crate::events::{self, EventBus};
 use crate::errors::AppError;
 use crate::jwt::JwtConfig;
-use crate::routes::{auth, projects, tasks, progress, health, rbac};
+use crate::middleware::{body_limit, concurrency, cors, metrics, rate_limit, request_id, versioning};
+use crate::routes::{self, activity, admin, auth, calendar, export, graphql, members, oauth, projects, tasks, progress, health, rbac, search, users, webhooks};
+
+/// Prefix of the current API version. Unprefixed paths remain as deprecated aliases.
+pub const API_PREFIX: &str = "/v1";
 
 #[derive(Clone)]
 pub struct AppState {
     pub pool: SqlitePool,
     pub jwt: Arc<JwtConfig>,
     pub event_bus: EventBus,
+    /// Permits for expensive endpoints; see `middleware::concurrency`
+    pub heavy_limiter: Arc<Semaphore>,
+    /// Effective permissions per user; see `invalidate_permissions`
+    pub permissions_cache: Arc<PermissionCache>,
+    /// Per-IP throttle for login/register; see `middleware::rate_limit`
+    pub auth_limiter: Arc<rate_limit::RateLimiter>,
+    /// Live enforcement mode, seeded from `AUTHZ_MODE`; see `set_authz_mode`
+    pub authz_mode: Arc<RwLock<AuthzMode>>,
+    /// Deleted accounts whose tokens are refused; see `revoke_user`
+    pub revoked_users: Arc<RwLock<HashSet<uuid::Uuid>>>,
 }
 
 impl AppState {
@@ -25,66 +42,209 @@ impl AppState {
             pool,
             jwt: Arc::new(jwt),
             event_bus,
+            heavy_limiter: Arc::new(Semaphore::new(concurrency::heavy_concurrency_limit())),
+            permissions_cache: Arc::new(PermissionCache::new()),
+            auth_limiter: Arc::new(rate_limit::RateLimiter::auth_from_env()),
+            authz_mode: Arc::new(RwLock::new(AuthzMode::from_env())),
+            revoked_users: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Current enforcement mode; read per request so changes apply immediately.
+    pub fn authz_mode(&self) -> AuthzMode {
+        *self.authz_mode.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Switch the enforcement mode, returning the previous one.
+    pub fn set_authz_mode(&self, mode: AuthzMode) -> AuthzMode {
+        let mut guard = self.authz_mode.write().unwrap_or_else(|e| e.into_inner());
+        std::mem::replace(&mut *guard, mode)
+    }
+
+    /// Drop a user's cached permissions after changing their roles or grants.
+    pub fn invalidate_permissions(&self, user_id: uuid::Uuid) {
+        self.permissions_cache.invalidate(user_id);
+    }
+
+    /// Drop every cached entry after a role-level change that may affect many users.
+    pub fn invalidate_all_permissions(&self) {
+        self.permissions_cache.clear();
+    }
+
+    /// Refuse every outstanding token for a deleted account.
+    pub fn revoke_user(&self, user_id: uuid::Uuid) {
+        let mut revoked = self.revoked_users.write().unwrap_or_else(|e| e.into_inner());
+        revoked.insert(user_id);
+    }
+
+    /// Whether `user_id`'s tokens were revoked; checked in memory on every request.
+    pub fn is_revoked(&self, user_id: uuid::Uuid) -> bool {
+        let revoked = self.revoked_users.read().unwrap_or_else(|e| e.into_inner());
+        revoked.contains(&user_id)
+    }
+
+    /// Revoke accounts deleted before this process started.
+    pub async fn load_revoked_users(&self) -> Result<(), AppError> {
+        let sql = format!(
+            "SELECT {} FROM users WHERE deleted_at IS NOT NULL",
+            crate::db::uuid_sql::case_uuid("id")
+        );
+        let ids: Vec<String> = sqlx::query_scalar(&sql).fetch_all(&self.pool).await?;
+        for id in ids {
+            match uuid::Uuid::parse_str(&id) {
+                Ok(user_id) => self.revoke_user(user_id),
+                Err(_) => tracing::warn!(%id, "skipping deleted user with an unparseable id"),
+            }
         }
+        Ok(())
     }
 }
 
 pub async fn create_app(pool: SqlitePool) -> Result<Router, AppError> {
+    let (router, _activity_listener) = create_app_with_listener(pool).await?;
+    Ok(router)
+}
+
+/// Build the router and also return the activity listener task.
+///
+/// The listener exits once every `EventBus` sender (held by the router's state)
+/// has been dropped and the buffered events are written, so awaiting it after
+/// the server stops drains in-flight events to `activity_log`/`event_store`.
+pub async fn create_app_with_listener(pool: SqlitePool) -> Result<(Router, JoinHandle<()>), AppError> {
     let jwt_config = JwtConfig::from_env()?;
 
+    // Make sure the well-known roles and permissions exist before serving requests
+    authz::seed(&pool).await?;
+
     // Initialize Event Bus and Listener
     let (event_bus, rx) = events::init_event_bus();
     let listener_pool = pool.clone();
-    tokio::spawn(events::start_activity_listener(rx, listener_pool));
+    let activity_listener = tokio::spawn(events::supervise_activity_listener(event_bus.downgrade(), rx, listener_pool));
+    // Webhook delivery is best-effort and not drained on shutdown
+    tokio::spawn(events::webhooks::start_webhook_dispatcher(event_bus.subscribe(), pool.clone()));
 
     let state = AppState::new(pool, jwt_config, event_bus);
+    state.load_revoked_users().await?;
+    let api = api_router(state.clone());
 
-    let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
-        .allow_origin(Any)
-        .allow_headers(Any);
+    let router = Router::new()
+        // Probes and metrics are operational, not part of the versioned API
+        .route("/api/health", get(health::health))
+        .route("/health", get(health::health))
+        .route("/health/live", get(health::live))
+        .route("/health/ready", get(health::ready))
+        .route("/metrics", get(routes::metrics::metrics))
+        .nest(API_PREFIX, api.clone())
+        // Unprefixed aliases for clients that predate /v1; responses are marked deprecated
+        .merge(api.route_layer(from_fn(versioning::deprecated_alias)))
+        .with_state(state)
+        .layer(body_limit::default_limit())
+        // Router::layer wraps each route, so the matched route pattern is available as a label
+        .layer(from_fn(metrics::track))
+        .layer(cors::cors_layer())
+        .layer(TraceLayer::new_for_http())
+        // Outermost so the id wraps tracing and every response, including errors
+        .layer(from_fn(request_id::propagate));
 
-    let auth_routes = Router::new()
+    Ok((router, activity_listener))
+}
+
+/// Every versioned endpoint, mounted under `API_PREFIX` (and, for now, at the root).
+fn api_router(state: AppState) -> Router<AppState> {
+    // Credential endpoints are throttled per client IP
+    let throttled_auth_routes = Router::new()
         .route("/register", post(auth::register))
         .route("/login", post(auth::login))
+        .route("/oauth/google", get(oauth::google_login))
+        .route("/oauth/google/callback", get(oauth::google_callback))
+        .route_layer(from_fn_with_state(state.clone(), rate_limit::limit_auth));
+
+    let auth_routes = Router::new()
+        .merge(throttled_auth_routes)
         .route("/me", get(auth::me))
+        .route("/me", put(auth::update_me))
+        .route("/me", delete(auth::delete_me))
         .route("/logout", post(auth::logout));
 
+    // Expensive read endpoints share a concurrency cap and return 503 when saturated
+    let heavy_project_routes = Router::new()
+        .route("/:id/dashboard", get(projects::get_project_dashboard))
+        .route("/:id/critical-path", get(projects::get_project_critical_path))
+        .route("/:id/schedule-analysis", get(projects::get_project_schedule_analysis))
+        .route("/:id/schedule", post(projects::schedule_project))
+        .route("/:id/gantt", get(projects::get_project_gantt))
+        .route_layer(from_fn_with_state(state.clone(), concurrency::limit_heavy));
+
+    // Imports carry whole schedules, so they get a larger body cap
+    let import_routes = Router::new()
+        .route("/import", post(export::import_project))
+        .route("/:id/import-schedule", post(tasks::import_schedule))
+        .route_layer(body_limit::import_limit());
+
     let project_routes = Router::new()
         .route("/", get(projects::list_projects))
         .route("/", post(projects::create_project))
-        .route("/:id/dashboard", get(projects::get_project_dashboard))
-        .route("/:id/critical-path", get(projects::get_project_critical_path))
+        .route("/trash", get(projects::list_trashed_projects))
+        .route("/:id/export", get(export::export_project))
+        .route("/:id/duplicate", post(export::duplicate_project))
+        .route("/:id/full", get(projects::get_project_full))
+        .route("/:id/stats", get(projects::get_project_stats))
+        .route("/:id/tasks.ics", get(calendar::export_project_calendar))
+        .route("/:id/progress/latest", get(progress::list_latest_progress))
         .route("/:id", get(projects::get_project))
         .route("/:id", put(projects::update_project))
         .route("/:id", delete(projects::delete_project))
+        .route("/:id/purge", delete(projects::purge_project))
         .route("/:id/plan", post(projects::update_project_plan))
-        .route("/:id/plan", delete(projects::clear_project_plan));
+        .route("/:id/plan", delete(projects::clear_project_plan))
+        .route("/:id/members", get(members::list_members))
+        .route("/:id/members", post(members::add_member))
+        .route("/:id/members/:user_id", delete(members::remove_member))
+        .merge(heavy_project_routes)
+        .merge(import_routes);
 
     // Tasks are scoped to a project: /projects/:project_id/tasks
     let task_routes = Router::new()
         .route("/batch", put(tasks::batch_update_tasks))
+        .route("/assign", post(tasks::assign_tasks))
+        .route("/tree", get(tasks::get_task_tree))
+        .route("/overdue", get(tasks::list_overdue_tasks))
+        .route("/trash", get(tasks::list_trashed_tasks))
+        .route("/:id/history", get(tasks::get_task_history))
+        .route("/:id/tags", post(tasks::add_task_tags))
+        .route("/:id/tags", delete(tasks::remove_task_tags))
         .route("/", get(tasks::list_tasks))
         .route("/", post(tasks::create_task))
         .route("/:id", get(tasks::get_task))
-        .route("/:id", put(tasks::update_task))
-        .route("/:id", delete(tasks::delete_task));
+        .route("/:id", put(tasks::replace_task))
+        .route("/:id", patch(tasks::update_task))
+        .route("/:id", delete(tasks::delete_task))
+        .route("/:id/purge", delete(tasks::purge_task));
 
     let progress_routes = Router::new()
         .route("/", get(progress::list_progress))
         .route("/", post(progress::create_progress))
+        .route("/batch", post(progress::create_progress_batch))
         .route("/:id", get(progress::get_progress))
         .route("/:id", put(progress::update_progress))
         .route("/:id", delete(progress::delete_progress));
 
+    // GraphQL can resolve dashboards, so it shares the heavy-endpoint cap
+    let graphql_routes = Router::new()
+        .route("/graphql", post(graphql::graphql_handler))
+        .route_layer(from_fn_with_state(state.clone(), concurrency::limit_heavy));
+
     let dependency_routes = Router::new()
         .route("/", get(tasks::list_dependencies))
         .route("/", post(tasks::create_dependency))
+        .route("/batch", post(tasks::create_dependencies_batch))
         .route("/:id", delete(tasks::delete_dependency));
 
-    let router = Router::new()
-        .route("/api/health", get(health::health))
+    Router::new()
         .nest("/auth", auth_routes)
+        .route("/me/export", get(export::export_my_projects))
+        .route("/users", get(users::list_users))
+        .route("/search", get(search::search))
         .nest("/projects", project_routes)
         // nest tasks under project scope
         .nest("/projects/:project_id/tasks", task_routes)
@@ -94,9 +254,11 @@ pub async fn create_app(pool: SqlitePool) -> Result<Router, AppError> {
         .nest("/projects/:project_id/dependencies", dependency_routes)
         // RBAC admin routes
         .nest("/rbac", rbac::routes())
-        .with_state(state)
-        .layer(cors)
-        .layer(TraceLayer::new_for_http());
-
-    Ok(router)
+        // Operator diagnostics (super_admin only)
+        .nest("/admin", admin::routes())
+        .route("/activity/export", get(activity::export_activity_log))
+        .nest("/webhooks", webhooks::routes())
+        .merge(graphql_routes)
+        // RBAC check for everything under /projects; advisory mode records would-be 403s
+        .route_layer(from_fn_with_state(state.clone(), authz::require_permission))
 }
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use crate::models::rbac::EffectivePermissions;
+
+struct CachedPermissions {
+    value: EffectivePermissions,
+    /// Earliest expiry among the user's time-bound grants; the entry is stale after it
+    valid_until: Option<DateTime<Utc>>,
+}
+
+/// Lazily populated per-user cache of effective permissions.
+///
+/// There is no TTL: every RBAC mutation must invalidate the affected users
+/// (or the whole cache for role-level changes) so the next request sees it.
+#[derive(Default)]
+pub struct PermissionCache {
+    entries: RwLock<HashMap<Uuid, CachedPermissions>>,
+}
+
+impl PermissionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, user_id: Uuid) -> Option<EffectivePermissions> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        let cached = entries.get(&user_id)?;
+        match cached.valid_until {
+            Some(until) if until <= Utc::now() => None,
+            _ => Some(cached.value.clone()),
+        }
+    }
+
+    pub fn insert(&self, value: EffectivePermissions, valid_until: Option<DateTime<Utc>>) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.insert(value.user_id, CachedPermissions { value, valid_until });
+    }
+
+    pub fn invalidate(&self, user_id: Uuid) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.remove(&user_id);
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.clear();
+    }
+}
@@ -0,0 +1,153 @@
+//! Mode-aware permission checks, the entry point for handlers and middleware.
+
+use std::collections::HashMap;
+
+use axum::extract::{FromRequestParts, MatchedPath, Path, Request, State};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+use uuid::Uuid;
+
+use super::permissions::*;
+use super::{AuthzMode, DefaultPolicyEvaluator, PolicyEvaluator, Principal, ResourceContext};
+use crate::app::AppState;
+use crate::errors::{AppError, AppResult};
+use crate::events;
+use crate::jwt::AuthUser;
+use crate::models::rbac::AuthzDenial;
+
+/// Check `permission` for `user_id` against the live `AuthzMode`.
+///
+/// - `Off`: always allowed, nothing is evaluated.
+/// - `Advisory`: always allowed, but a denial is published as an `authz.denied`
+///   event, so `activity_log` lists what strict mode would have rejected.
+/// - `Strict`: a denial is a 403.
+pub async fn authorize(
+    state: &AppState,
+    user_id: Uuid,
+    permission: &str,
+    ctx: &ResourceContext,
+    path: &str,
+) -> AppResult<()> {
+    let mode = state.authz_mode();
+    if mode == AuthzMode::Off {
+        return Ok(());
+    }
+
+    let principal = Principal::load(&state.pool, user_id).await?;
+    if DefaultPolicyEvaluator::new().can(&principal, permission, ctx).await {
+        return Ok(());
+    }
+
+    match mode {
+        AuthzMode::Strict => Err(AppError::forbidden(format!("missing permission: {permission}"))),
+        _ => {
+            tracing::info!(%user_id, permission, path, "authz denial (advisory, allowed)");
+            let denial = AuthzDenial {
+                user_id,
+                permission: permission.to_string(),
+                path: path.to_string(),
+                context: ctx.to_scope_json(),
+                mode,
+            };
+            events::log_activity_with_context(&state.event_bus, "denied", Some(user_id), &denial, None, None);
+            Ok(())
+        }
+    }
+}
+
+/// Permission a project-scoped route needs, from its method and route pattern
+/// (`/projects/:project_id/tasks/:id`, with or without the version prefix).
+///
+/// Reads need `*.view`; writes need `*.create` for new top-level entities,
+/// `*.delete` for deleting or purging one, and `*.update` for everything else
+/// (plans, members, tags, dependency edges, ...). `None` for routes outside `/projects`.
+pub fn required_permission(method: &Method, route: &str) -> Option<&'static str> {
+    let segments: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
+    let start = segments.iter().position(|s| *s == "projects")?;
+    let rest = &segments[start + 1..];
+    let read = method == Method::GET || method == Method::HEAD;
+
+    let permission = match rest {
+        [_, "tasks", _, "progress", ..] => if read { PROGRESS_VIEW } else { PROGRESS_CREATE },
+        [_, "tasks", rest @ ..] => match rest {
+            _ if read => TASK_VIEW,
+            [] => TASK_CREATE,
+            [_] | [_, "purge"] if method == Method::DELETE => TASK_DELETE,
+            _ => TASK_UPDATE,
+        },
+        [_, "dependencies", ..] => if read { TASK_VIEW } else { TASK_UPDATE },
+        [_, "import-schedule"] => TASK_CREATE,
+        _ if read => PROJECT_VIEW,
+        [] | ["import"] | [_, "duplicate"] => PROJECT_CREATE,
+        [_] | [_, "purge"] if method == Method::DELETE => PROJECT_DELETE,
+        _ => PROJECT_UPDATE,
+    };
+    Some(permission)
+}
+
+/// Route middleware running [`authorize`] for the permission
+/// [`required_permission`] picks; the request path is recorded with any denial.
+///
+/// The project and task come from the `:project_id`/`:id`/`:task_id` path
+/// parameters. Nothing is loaded while the mode is `Off`.
+pub async fn require_permission(State(state): State<AppState>, req: Request, next: Next) -> AppResult<Response> {
+    if state.authz_mode() == AuthzMode::Off {
+        return Ok(next.run(req).await);
+    }
+    let route = req.extensions().get::<MatchedPath>().map(|p| p.as_str().to_string()).unwrap_or_default();
+    let Some(permission) = required_permission(req.method(), &route) else {
+        return Ok(next.run(req).await);
+    };
+
+    let (mut parts, body) = req.into_parts();
+    let auth = AuthUser::from_request_parts(&mut parts, &state).await?;
+    let params = Path::<HashMap<String, String>>::from_request_parts(&mut parts, &state)
+        .await
+        .map(|Path(params)| params)
+        .unwrap_or_default();
+    let param = |name: &str| params.get(name).and_then(|v| Uuid::parse_str(v).ok());
+
+    // `:id` names the project on project routes and the task on task routes
+    let on_task = route.contains("/tasks/:id");
+    let project_id = param("project_id").or_else(|| if route.contains("/projects/:id") { param("id") } else { None });
+    let task_id = param("task_id").or_else(|| if on_task { param("id") } else { None });
+    let ctx = match (project_id, task_id) {
+        (Some(project_id), Some(task_id)) => ResourceContext::for_task(project_id, task_id),
+        (Some(project_id), None) => ResourceContext::for_project(project_id),
+        _ => ResourceContext::new(),
+    };
+
+    authorize(&state, auth.user_id, permission, &ctx, parts.uri.path()).await?;
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_map_to_permissions() {
+        let cases = [
+            (Method::GET, "/v1/projects", PROJECT_VIEW),
+            (Method::POST, "/projects/", PROJECT_CREATE),
+            (Method::POST, "/v1/projects/:id/duplicate", PROJECT_CREATE),
+            (Method::DELETE, "/v1/projects/:id/purge", PROJECT_DELETE),
+            (Method::DELETE, "/v1/projects/:id/members/:user_id", PROJECT_UPDATE),
+            (Method::POST, "/v1/projects/:id/plan", PROJECT_UPDATE),
+            (Method::POST, "/v1/projects/:id/import-schedule", TASK_CREATE),
+            (Method::POST, "/v1/projects/:project_id/tasks", TASK_CREATE),
+            (Method::PATCH, "/v1/projects/:project_id/tasks/:id", TASK_UPDATE),
+            (Method::DELETE, "/v1/projects/:project_id/tasks/:id", TASK_DELETE),
+            (Method::DELETE, "/v1/projects/:project_id/tasks/:id/tags", TASK_UPDATE),
+            (Method::GET, "/v1/projects/:project_id/tasks/overdue", TASK_VIEW),
+            (Method::POST, "/v1/projects/:project_id/dependencies/batch", TASK_UPDATE),
+            (Method::GET, "/v1/projects/:project_id/tasks/:task_id/progress", PROGRESS_VIEW),
+            (Method::DELETE, "/v1/projects/:project_id/tasks/:task_id/progress/:id", PROGRESS_CREATE),
+        ];
+        for (method, route, expected) in cases {
+            assert_eq!(required_permission(&method, route), Some(expected), "{} {}", method, route);
+        }
+        assert_eq!(required_permission(&Method::GET, "/v1/users"), None);
+    }
+}
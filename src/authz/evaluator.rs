@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use serde_json::Value;
+use uuid::Uuid;
 
 use super::principal::{Principal, ResourceContext};
 
@@ -14,9 +15,13 @@ pub trait PolicyEvaluator: Send + Sync {
 ///
 /// Evaluation order:
 /// 1. super_admin role -> allow
-/// 2. direct user permissions (global + scope match) -> allow
-/// 3. role permissions (global) -> allow
-/// 4. deny
+/// 2. explicitly denied permissions -> deny
+/// 3. direct user permissions (global + scope match) -> allow
+/// 4. role permissions (global) -> allow
+/// 5. deny
+///
+/// Held permissions may be wildcards: `project.*` matches any permission in
+/// the `project.` namespace and a bare `*` matches everything.
 #[derive(Debug, Clone, Default)]
 pub struct DefaultPolicyEvaluator;
 
@@ -25,6 +30,25 @@ impl DefaultPolicyEvaluator {
         Self
     }
 
+    /// Check if a held permission (possibly a wildcard) grants the requested one
+    fn permission_matches(held: &str, requested: &str) -> bool {
+        if held == requested || held == "*" {
+            return true;
+        }
+
+        match held.strip_suffix('*') {
+            // "project.*" -> prefix "project." so "projects.view" does not match
+            Some(prefix) if prefix.ends_with('.') => requested.starts_with(prefix),
+            _ => false,
+        }
+    }
+
+    /// Compare a UUID from scope JSON with one from the context, ignoring case and
+    /// hyphenation; an unparseable scope value never matches.
+    fn same_uuid(scope_value: &str, ctx_value: &Uuid) -> bool {
+        Uuid::parse_str(scope_value).map(|id| id == *ctx_value).unwrap_or(false)
+    }
+
     /// Check if a scope matches the resource context
     fn scope_matches(scope: &Value, ctx: &ResourceContext) -> bool {
         // Empty scope matches everything
@@ -40,7 +64,7 @@ impl DefaultPolicyEvaluator {
         // Check project_id scope
         if let Some(scope_project) = scope_obj.get("project_id").and_then(|v| v.as_str()) {
             if let Some(ctx_project) = &ctx.project_id {
-                if scope_project != ctx_project.to_string() {
+                if !Self::same_uuid(scope_project, ctx_project) {
                     return false;
                 }
             } else {
@@ -60,9 +84,11 @@ impl DefaultPolicyEvaluator {
             }
         }
 
+        // A resource-scoped grant never applies to project-level actions, and
+        // one scoped to a task never applies to its siblings
         if let Some(scope_rid) = scope_obj.get("resource_id").and_then(|v| v.as_str()) {
             if let Some(ctx_rid) = &ctx.resource_id {
-                if scope_rid != ctx_rid.to_string() {
+                if !Self::same_uuid(scope_rid, ctx_rid) {
                     return false;
                 }
             } else {
@@ -87,8 +113,20 @@ impl PolicyEvaluator for DefaultPolicyEvaluator {
             return true;
         }
 
-        // 2. Check direct user permissions (global)
-        if principal.has_permission(permission) {
+        // 2. Explicit denies beat every allow
+        if principal.denied_permissions.iter().any(|denied| Self::permission_matches(denied, permission)) {
+            tracing::debug!(
+                user_id = %principal.user_id,
+                permission = %permission,
+                "explicit deny"
+            );
+            return false;
+        }
+
+        // 3. Check direct user permissions (global, including wildcards)
+        if principal.has_permission(permission)
+            || principal.permissions.iter().any(|held| Self::permission_matches(held, permission))
+        {
             tracing::debug!(
                 user_id = %principal.user_id,
                 permission = %permission,
@@ -97,9 +135,9 @@ impl PolicyEvaluator for DefaultPolicyEvaluator {
             return true;
         }
 
-        // 3. Check scoped permissions
+        // 4. Check scoped permissions
         for (perm_name, scope) in &principal.scoped_permissions {
-            if perm_name == permission && Self::scope_matches(scope, ctx) {
+            if Self::permission_matches(perm_name, permission) && Self::scope_matches(scope, ctx) {
                 tracing::debug!(
                     user_id = %principal.user_id,
                     permission = %permission,
@@ -110,7 +148,7 @@ impl PolicyEvaluator for DefaultPolicyEvaluator {
             }
         }
 
-        // 4. Deny
+        // 5. Deny
         tracing::debug!(
             user_id = %principal.user_id,
             permission = %permission,
@@ -123,7 +161,6 @@ impl PolicyEvaluator for DefaultPolicyEvaluator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use uuid::Uuid;
 
     #[tokio::test]
     async fn test_super_admin_bypasses_all() {
@@ -175,6 +212,105 @@ mod tests {
         assert!(evaluator.can(&principal, "project.view", &ctx).await);
     }
 
+    #[tokio::test]
+    async fn test_wildcard_permission_matches_namespace() {
+        let evaluator = DefaultPolicyEvaluator::new();
+        let principal = Principal::new(Uuid::new_v4())
+            .with_permissions(vec!["project.*".to_string()]);
+        let ctx = ResourceContext::new();
+
+        assert!(evaluator.can(&principal, "project.create", &ctx).await);
+        assert!(evaluator.can(&principal, "project.view", &ctx).await);
+        assert!(!evaluator.can(&principal, "task.create", &ctx).await);
+        assert!(!evaluator.can(&principal, "projects.view", &ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_bare_wildcard_matches_everything() {
+        let evaluator = DefaultPolicyEvaluator::new();
+        let principal = Principal::new(Uuid::new_v4())
+            .with_permissions(vec!["*".to_string()]);
+        let ctx = ResourceContext::new();
+
+        assert!(evaluator.can(&principal, "task.delete", &ctx).await);
+        assert!(evaluator.can(&principal, "role.manage", &ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_scoped_wildcard_respects_scope() {
+        let evaluator = DefaultPolicyEvaluator::new();
+        let project_id = Uuid::new_v4();
+        let scope = serde_json::json!({"project_id": project_id.to_string()});
+
+        let principal = Principal::new(Uuid::new_v4())
+            .with_scoped_permissions(vec![("task.*".to_string(), scope)]);
+
+        let ctx = ResourceContext::new().with_project(project_id);
+        assert!(evaluator.can(&principal, "task.update", &ctx).await);
+        assert!(!evaluator.can(&principal, "project.update", &ctx).await);
+
+        let other_ctx = ResourceContext::new().with_project(Uuid::new_v4());
+        assert!(!evaluator.can(&principal, "task.update", &other_ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_deny_overrides_wildcard_allow() {
+        let evaluator = DefaultPolicyEvaluator::new();
+        let principal = Principal::new(Uuid::new_v4())
+            .with_permissions(vec!["project.*".to_string()])
+            .with_denied_permissions(vec!["project.delete".to_string()]);
+        let ctx = ResourceContext::new();
+
+        assert!(evaluator.can(&principal, "project.create", &ctx).await);
+        assert!(evaluator.can(&principal, "project.update", &ctx).await);
+        assert!(!evaluator.can(&principal, "project.delete", &ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_super_admin_ignores_denies() {
+        let evaluator = DefaultPolicyEvaluator::new();
+        let principal = Principal::new(Uuid::new_v4())
+            .with_roles(vec!["super_admin".to_string()])
+            .with_denied_permissions(vec!["*".to_string()]);
+        let ctx = ResourceContext::new();
+
+        assert!(evaluator.can(&principal, "project.delete", &ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_task_scoped_grant_does_not_leak_to_siblings() {
+        let evaluator = DefaultPolicyEvaluator::new();
+        let project_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let scope = serde_json::json!({
+            "project_id": project_id.to_string(),
+            "resource_type": "task",
+            "resource_id": task_id.to_string().to_uppercase(),
+        });
+
+        let principal = Principal::new(Uuid::new_v4())
+            .with_scoped_permissions(vec![("task.update".to_string(), scope)]);
+
+        assert!(evaluator.can(&principal, "task.update", &ResourceContext::for_task(project_id, task_id)).await);
+        // Sibling task in the same project
+        assert!(!evaluator.can(&principal, "task.update", &ResourceContext::for_task(project_id, Uuid::new_v4())).await);
+        // Project-level action
+        assert!(!evaluator.can(&principal, "task.update", &ResourceContext::for_project(project_id)).await);
+    }
+
+    #[tokio::test]
+    async fn test_project_scope_covers_its_tasks_only() {
+        let evaluator = DefaultPolicyEvaluator::new();
+        let project_id = Uuid::new_v4();
+        let scope = serde_json::json!({"project_id": project_id.to_string()});
+
+        let principal = Principal::new(Uuid::new_v4())
+            .with_scoped_permissions(vec![("task.view".to_string(), scope)]);
+
+        assert!(evaluator.can(&principal, "task.view", &ResourceContext::for_task(project_id, Uuid::new_v4())).await);
+        assert!(!evaluator.can(&principal, "task.view", &ResourceContext::for_task(Uuid::new_v4(), Uuid::new_v4())).await);
+    }
+
     #[tokio::test]
     async fn test_denial_when_no_permission() {
         let evaluator = DefaultPolicyEvaluator::new();
@@ -4,18 +4,29 @@
 //! - Role-based permissions
 //! - Direct user permissions (with optional scope)
 //! - Super admin bypass
+//! - Wildcard (`project.*`, `*`) and explicit-deny (`deny:<name>`) grants
 //! - Configurable enforcement modes (off/advisory/strict)
+//! - A per-user effective-permission cache, invalidated on RBAC changes
+//! - Idempotent seeding of the well-known roles and permissions
 
+mod cache;
+mod enforce;
 mod evaluator;
 mod principal;
+mod seed;
 
+pub use cache::PermissionCache;
+pub use enforce::{authorize, require_permission, required_permission};
 pub use evaluator::{DefaultPolicyEvaluator, PolicyEvaluator};
 pub use principal::{Principal, ResourceContext};
+pub use seed::seed;
 
-use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Authorization enforcement mode
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum AuthzMode {
     /// No permission checks (development mode)
     Off,
@@ -26,15 +37,22 @@ pub enum AuthzMode {
 }
 
 impl AuthzMode {
+    /// Initial mode from `AUTHZ_MODE`; the live value is `AppState::authz_mode`,
+    /// which `PUT /admin/authz-mode` can change without a restart.
     pub fn from_env() -> Self {
-        static MODE: OnceLock<AuthzMode> = OnceLock::new();
-        *MODE.get_or_init(|| {
-            match std::env::var("AUTHZ_MODE").unwrap_or_default().to_lowercase().as_str() {
-                "advisory" => AuthzMode::Advisory,
-                "strict" => AuthzMode::Strict,
-                _ => AuthzMode::Off,
-            }
-        })
+        match std::env::var("AUTHZ_MODE").unwrap_or_default().to_lowercase().as_str() {
+            "advisory" => AuthzMode::Advisory,
+            "strict" => AuthzMode::Strict,
+            _ => AuthzMode::Off,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthzMode::Off => "off",
+            AuthzMode::Advisory => "advisory",
+            AuthzMode::Strict => "strict",
+        }
     }
 }
 
@@ -47,8 +65,17 @@ pub mod roles {
     pub const VIEWER: &str = "viewer";
 }
 
+/// Resource types used in scoped grants (`{"resource_type": "task", "resource_id": ...}`)
+pub mod resources {
+    pub const PROJECT: &str = "project";
+    pub const TASK: &str = "task";
+}
+
 /// Well-known permission names
 pub mod permissions {
+    /// Prefix marking a grant as an explicit deny, e.g. `deny:project.delete`
+    pub const DENY_PREFIX: &str = "deny:";
+
     // Project
     pub const PROJECT_CREATE: &str = "project.create";
     pub const PROJECT_VIEW: &str = "project.view";
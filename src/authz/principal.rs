@@ -1,7 +1,10 @@
 use serde_json::Value;
+use sqlx::{Row, SqlitePool};
 use std::collections::HashSet;
 use uuid::Uuid;
 
+use crate::db::uuid_sql;
+
 /// Principal represents the authenticated user with their cached permissions
 #[derive(Debug, Clone)]
 pub struct Principal {
@@ -10,6 +13,9 @@ pub struct Principal {
     pub permissions: HashSet<String>,
     /// Scoped permissions: (permission_name, scope_json)
     pub scoped_permissions: Vec<(String, Value)>,
+    /// Explicitly denied permissions (stored as `deny:<name>` grants); these
+    /// override every allow except super_admin
+    pub denied_permissions: HashSet<String>,
 }
 
 impl Principal {
@@ -19,6 +25,82 @@ impl Principal {
             roles: HashSet::new(),
             permissions: HashSet::new(),
             scoped_permissions: Vec::new(),
+            denied_permissions: HashSet::new(),
+        }
+    }
+
+    /// Load a user's roles and permissions (role grants and direct grants) from the RBAC tables.
+    ///
+    /// Grants named `deny:<permission>` populate `denied_permissions`. Denies are
+    /// applied globally, regardless of any scope on the grant.
+    pub async fn load(pool: &SqlitePool, user_id: Uuid) -> Result<Self, sqlx::Error> {
+        let uid = user_id.to_string();
+
+        let role_sql = format!(
+            "SELECT r.name FROM roles r INNER JOIN user_roles ur ON r.id = ur.role_id WHERE {}",
+            uuid_sql::match_uuid_clause("ur.user_id")
+        );
+        let roles: Vec<String> = sqlx::query_scalar(&role_sql)
+            .bind(&uid)
+            .bind(&uid)
+            .fetch_all(pool)
+            .await?;
+
+        let role_perm_sql = format!(
+            "SELECT DISTINCT p.name FROM permissions p \
+             INNER JOIN role_permissions rp ON p.id = rp.permission_id \
+             INNER JOIN user_roles ur ON ur.role_id = rp.role_id \
+             WHERE {}",
+            uuid_sql::match_uuid_clause("ur.user_id")
+        );
+        let role_perms: Vec<String> = sqlx::query_scalar(&role_perm_sql)
+            .bind(&uid)
+            .bind(&uid)
+            .fetch_all(pool)
+            .await?;
+
+        // Expired time-bound grants are skipped entirely
+        let direct_sql = format!(
+            "SELECT p.name, up.scope FROM permissions p INNER JOIN user_permissions up ON p.id = up.permission_id \
+             WHERE {} AND (up.expires_at IS NULL OR up.expires_at > ?)",
+            uuid_sql::match_uuid_clause("up.user_id")
+        );
+        let direct_rows = sqlx::query(&direct_sql)
+            .bind(&uid)
+            .bind(&uid)
+            .bind(chrono::Utc::now())
+            .fetch_all(pool)
+            .await?;
+
+        let mut principal = Self::new(user_id).with_roles(roles);
+
+        for name in role_perms {
+            principal.add_grant(name, None);
+        }
+
+        for row in direct_rows {
+            let name: String = row.get("name");
+            let scope: Option<String> = row.get("scope");
+            let scope = scope
+                .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+                .filter(|v| !v.is_null() && !v.as_object().map(|o| o.is_empty()).unwrap_or(false));
+            principal.add_grant(name, scope);
+        }
+
+        Ok(principal)
+    }
+
+    fn add_grant(&mut self, name: String, scope: Option<Value>) {
+        if let Some(denied) = name.strip_prefix(super::permissions::DENY_PREFIX) {
+            self.denied_permissions.insert(denied.to_string());
+            return;
+        }
+
+        match scope {
+            Some(scope) => self.scoped_permissions.push((name, scope)),
+            None => {
+                self.permissions.insert(name);
+            }
         }
     }
 
@@ -37,6 +119,11 @@ impl Principal {
         self
     }
 
+    pub fn with_denied_permissions(mut self, denied: impl IntoIterator<Item = String>) -> Self {
+        self.denied_permissions = denied.into_iter().collect();
+        self
+    }
+
     pub fn has_role(&self, role: &str) -> bool {
         self.roles.contains(role)
     }
@@ -75,6 +162,16 @@ impl ResourceContext {
         self
     }
 
+    /// Context for an action on one task; a grant scoped to a sibling task will not match.
+    pub fn for_task(project_id: Uuid, task_id: Uuid) -> Self {
+        Self::new().with_project(project_id).with_resource(super::resources::TASK, task_id)
+    }
+
+    /// Context for a project-level action (listing or creating tasks, editing the project).
+    pub fn for_project(project_id: Uuid) -> Self {
+        Self::new().with_project(project_id)
+    }
+
     /// Convert to scope JSON for matching against scoped permissions
     pub fn to_scope_json(&self) -> Value {
         let mut map = serde_json::Map::new();
@@ -0,0 +1,115 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::{permissions, roles};
+
+/// Every well-known permission with a short description
+const PERMISSIONS: &[(&str, &str)] = &[
+    (permissions::PROJECT_CREATE, "Create projects"),
+    (permissions::PROJECT_VIEW, "View projects"),
+    (permissions::PROJECT_UPDATE, "Update projects"),
+    (permissions::PROJECT_DELETE, "Delete projects"),
+    (permissions::TASK_CREATE, "Create tasks"),
+    (permissions::TASK_VIEW, "View tasks"),
+    (permissions::TASK_UPDATE, "Update tasks"),
+    (permissions::TASK_DELETE, "Delete tasks"),
+    (permissions::PROGRESS_CREATE, "Record task progress"),
+    (permissions::PROGRESS_VIEW, "View task progress"),
+    (permissions::USER_VIEW, "View users"),
+    (permissions::USER_MANAGE, "Manage users"),
+    (permissions::ROLE_VIEW, "View roles"),
+    (permissions::ROLE_MANAGE, "Manage roles"),
+    (permissions::PERMISSION_VIEW, "View permissions"),
+    (permissions::PERMISSION_MANAGE, "Manage permissions"),
+];
+
+const PROJECT_MANAGER_PERMISSIONS: &[&str] = &[
+    permissions::PROJECT_CREATE,
+    permissions::PROJECT_VIEW,
+    permissions::PROJECT_UPDATE,
+    permissions::PROJECT_DELETE,
+    permissions::TASK_CREATE,
+    permissions::TASK_VIEW,
+    permissions::TASK_UPDATE,
+    permissions::TASK_DELETE,
+    permissions::PROGRESS_CREATE,
+    permissions::PROGRESS_VIEW,
+    permissions::USER_VIEW,
+    permissions::ROLE_VIEW,
+    permissions::PERMISSION_VIEW,
+];
+
+const MEMBER_PERMISSIONS: &[&str] = &[
+    permissions::PROJECT_VIEW,
+    permissions::TASK_CREATE,
+    permissions::TASK_VIEW,
+    permissions::TASK_UPDATE,
+    permissions::TASK_DELETE,
+    permissions::PROGRESS_CREATE,
+    permissions::PROGRESS_VIEW,
+];
+
+const VIEWER_PERMISSIONS: &[&str] = &[
+    permissions::PROJECT_VIEW,
+    permissions::TASK_VIEW,
+    permissions::PROGRESS_VIEW,
+];
+
+/// Well-known roles with their description and default permission set.
+/// `None` means the role receives every seeded permission.
+const ROLES: &[(&str, &str, Option<&[&str]>)] = &[
+    (roles::SUPER_ADMIN, "Unrestricted access to everything", None),
+    (roles::ADMIN, "Administers users, roles and all projects", None),
+    (roles::PROJECT_MANAGER, "Manages projects and their tasks", Some(PROJECT_MANAGER_PERMISSIONS)),
+    (roles::MEMBER, "Works on tasks within projects", Some(MEMBER_PERMISSIONS)),
+    (roles::VIEWER, "Read-only access to projects", Some(VIEWER_PERMISSIONS)),
+];
+
+/// Insert the well-known permissions, roles and default role→permission mappings.
+///
+/// Every statement is `INSERT OR IGNORE`, so this is safe to run on each startup:
+/// existing rows (including ones renamed or re-described by an admin) are left untouched.
+pub async fn seed(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    for (name, description) in PERMISSIONS {
+        sqlx::query(
+            "INSERT OR IGNORE INTO permissions (id, name, description, created_at, updated_at) VALUES (?, ?, ?, datetime('now'), datetime('now'))"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(name)
+        .bind(description)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for (role, description, grants) in ROLES {
+        sqlx::query(
+            "INSERT OR IGNORE INTO roles (id, name, description, created_at, updated_at) VALUES (?, ?, ?, datetime('now'), datetime('now'))"
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(role)
+        .bind(description)
+        .execute(&mut *tx)
+        .await?;
+
+        let names: Vec<&str> = match grants {
+            Some(list) => list.to_vec(),
+            None => PERMISSIONS.iter().map(|(name, _)| *name).collect(),
+        };
+
+        for permission in names {
+            sqlx::query(
+                "INSERT OR IGNORE INTO role_permissions (role_id, permission_id, created_at) \
+                 SELECT r.id, p.id, datetime('now') FROM roles r, permissions p WHERE r.name = ? AND p.name = ?"
+            )
+            .bind(role)
+            .bind(permission)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
@@ -29,6 +29,8 @@ enum Commands {
     MigrateRollback,
     /// Run integration/test suite via `cargo test`
     RunTests,
+    /// Rebuild the activity_log projection from event_store
+    ReplayEvents,
 }
 
 #[tokio::main]
@@ -67,6 +69,11 @@ async fn main() -> anyhow::Result<()> {
                 .context("no migrations were rolled back")?;
             println!("Rolled back last migration");
         }
+        Commands::ReplayEvents => {
+            let pool = get_pool().await?;
+            let replayed = s_curve::events::replay_activity_log(&pool).await?;
+            println!("Replayed {} events into activity_log", replayed);
+        }
         Commands::RunTests => {
             // Spawn cargo test for this crate
             use std::process::Command;
@@ -1,17 +1,37 @@
+use std::str::FromStr;
 use std::time::Duration;
 
 use anyhow::Context;
-use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
 use sqlx::SqlitePool;
 
 pub async fn init() -> anyhow::Result<SqlitePool> {
 	let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL not set")?;
 
+	// Enforce foreign keys (so ON DELETE CASCADE actually fires), use WAL for
+	// concurrent readers alongside a writer, and wait on locks instead of
+	// failing immediately with SQLITE_BUSY.
+	let options = SqliteConnectOptions::from_str(&database_url)
+		.context("invalid DATABASE_URL")?
+		.foreign_keys(true)
+		.journal_mode(SqliteJournalMode::Wal)
+		.busy_timeout(Duration::from_secs(5));
+
+	let max_connections = env_or("DB_MAX_CONNECTIONS", 10u32);
+	let min_connections = env_or("DB_MIN_CONNECTIONS", 1u32);
+	let acquire_timeout_secs = env_or("DB_ACQUIRE_TIMEOUT_SECS", 10u64);
+	tracing::info!(
+		max_connections,
+		min_connections,
+		acquire_timeout_secs,
+		"database pool configuration"
+	);
+
 	let pool = SqlitePoolOptions::new()
-		.max_connections(10)
-		.min_connections(1)
-		.acquire_timeout(Duration::from_secs(10))
-		.connect(&database_url)
+		.max_connections(max_connections)
+		.min_connections(min_connections)
+		.acquire_timeout(Duration::from_secs(acquire_timeout_secs))
+		.connect_with(options)
 		.await
 		.context("failed to connect to database")?;
 
@@ -23,5 +43,16 @@ pub async fn init() -> anyhow::Result<SqlitePool> {
 	Ok(pool)
 }
 
+/// Read a numeric setting from the environment, falling back to `default` when
+/// the variable is unset or does not parse (same tolerance as `APP_PORT`).
+fn env_or<T: FromStr>(key: &str, default: T) -> T {
+	std::env::var(key)
+		.ok()
+		.and_then(|value| value.parse::<T>().ok())
+		.unwrap_or(default)
+}
+
 pub mod uuid_sql;
 pub mod row_parsers;
+pub mod uuid_normalize;
+pub mod task_duration;
@@ -4,7 +4,7 @@ use sqlx::Row;
 use uuid::Uuid;
 
 use crate::errors::AppError;
-use crate::models::{progress::DbProgress, project::DbProject, project_plan::DbProjectPlanPoint, task::DbTask, user::DbUser, dependency::DbTaskDependency};
+use crate::models::{progress::DbProgress, project::DbProject, project_plan::DbProjectPlanPoint, task::{DbTask, TaskStatus}, user::DbUser, dependency::DbTaskDependency};
 
 fn parse_datetime(s: &str) -> Result<DateTime<Utc>, AppError> {
     let s = s.trim();
@@ -25,6 +25,18 @@ fn parse_datetime(s: &str) -> Result<DateTime<Utc>, AppError> {
         return Ok(Utc.from_utc_datetime(&ndt));
     }
 
+    // Unix epoch from JS clients and imports: 13 digits are millis, up to 11 are seconds
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        let parsed = s.parse::<i64>().ok().and_then(|n| {
+            if s.len() > 11 {
+                Utc.timestamp_millis_opt(n).single()
+            } else {
+                Utc.timestamp_opt(n, 0).single()
+            }
+        });
+        return parsed.ok_or_else(|| AppError::internal(format!("invalid datetime: epoch out of range: {}", s)));
+    }
+
     Err(AppError::internal(format!("invalid datetime: {}", s)))
 }
 
@@ -66,11 +78,12 @@ pub fn db_task_from_row(row: &SqliteRow) -> Result<DbTask, AppError> {
     let id_s: String = row.try_get("id").map_err(|e| AppError::internal(format!("missing id: {}", e)))?;
     let project_id_s: String = row.try_get("project_id").map_err(|e| AppError::internal(format!("missing project_id: {}", e)))?;
     let title: String = row.try_get("title").map_err(|e| AppError::internal(format!("missing title: {}", e)))?;
-    let status: String = row.try_get("status").map_err(|e| AppError::internal(format!("missing status: {}", e)))?;
+    let status: TaskStatus = row.try_get("status").map_err(|e| AppError::internal(format!("missing status: {}", e)))?;
     let due_date_s: Option<String> = row.try_get("due_date").map_err(|e| AppError::internal(format!("missing due_date: {}", e)))?;
     let start_date_s: Option<String> = row.try_get("start_date").map_err(|e| AppError::internal(format!("missing start_date: {}", e)))?;
     let end_date_s: Option<String> = row.try_get("end_date").map_err(|e| AppError::internal(format!("missing end_date: {}", e)))?;
     let duration_days: Option<i32> = row.try_get("duration_days").map_err(|e| AppError::internal(format!("missing duration_days: {}", e)))?;
+    let estimated_days: Option<i32> = row.try_get("estimated_days").map_err(|e| AppError::internal(format!("missing estimated_days: {}", e)))?;
     let assignee_s: Option<String> = row.try_get("assignee").map_err(|e| AppError::internal(format!("missing assignee: {}", e)))?;
     let parent_id_s: Option<String> = row.try_get("parent_id").map_err(|e| AppError::internal(format!("missing parent_id: {}", e)))?;
     let progress: i32 = row.try_get("progress").map_err(|e| AppError::internal(format!("missing progress: {}", e)))?;
@@ -89,7 +102,7 @@ pub fn db_task_from_row(row: &SqliteRow) -> Result<DbTask, AppError> {
     let updated_at = parse_datetime(&updated_at_s)?;
     let deleted_at = if let Some(s) = deleted_at_s { Some(parse_datetime(&s)?) } else { None };
 
-    Ok(DbTask { id, project_id, title, status, due_date, start_date, end_date, duration_days, assignee, parent_id, progress, created_at, updated_at, deleted_at })
+    Ok(DbTask { id, project_id, title, status, due_date, start_date, end_date, duration_days, estimated_days, assignee, parent_id, progress, created_at, updated_at, deleted_at })
 }
 
 pub fn db_project_from_row(row: &SqliteRow) -> Result<DbProject, AppError> {
@@ -165,4 +178,32 @@ pub fn db_task_dependency_from_row(row: &SqliteRow) -> Result<DbTaskDependency,
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_epoch_millis_and_seconds() {
+        let expected = Utc.with_ymd_and_hms(2025, 11, 19, 12, 34, 56).unwrap();
+        assert_eq!(parse_datetime("1763555696000").unwrap(), expected);
+        assert_eq!(parse_datetime("1763555696").unwrap(), expected);
+        assert_eq!(
+            parse_datetime("1763555696123").unwrap(),
+            expected + chrono::Duration::milliseconds(123)
+        );
+    }
+
+    #[test]
+    fn keeps_existing_formats() {
+        let expected = Utc.with_ymd_and_hms(2025, 11, 19, 0, 0, 0).unwrap();
+        assert_eq!(parse_datetime("2025-11-19T00:00:00Z").unwrap(), expected);
+        assert_eq!(parse_datetime("2025-11-19 00:00:00").unwrap(), expected);
+        assert_eq!(parse_datetime("2025-11-19").unwrap(), expected);
+    }
+
+    #[test]
+    fn rejects_malformed_epochs() {
+        for input in ["17635556960x0", "-1763555696000", "1763555696.5", "99999999999999999999", ""] {
+            let err = parse_datetime(input).unwrap_err();
+            assert_eq!(err.code(), "INTERNAL", "{}", input);
+        }
+    }
 }
@@ -0,0 +1,17 @@
+/// SQL expression for a task's scheduling length in whole days, for use by the
+/// critical path and any other dependency-based scheduling.
+///
+/// Precedence: the stored `duration_days`, then `end_date - start_date`, then the
+/// task's `estimated_days`, then `due_date - created_at`; anything else (or a
+/// negative span) counts as `0`. `table` is the alias the tasks table is selected
+/// under, e.g. `"t"`.
+pub fn effective_duration_days(table: &str) -> String {
+    format!(
+        "MAX(0, COALESCE({t}.duration_days, \
+         CAST(julianday({t}.end_date) - julianday({t}.start_date) AS INTEGER), \
+         {t}.estimated_days, \
+         CAST(julianday({t}.due_date) - julianday({t}.created_at) AS INTEGER), \
+         0))",
+        t = table
+    )
+}
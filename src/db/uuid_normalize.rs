@@ -0,0 +1,88 @@
+//! One-shot rewrite of text-stored UUIDs in the core tables into 16-byte blobs.
+//!
+//! The typed fast paths bind and decode `Uuid` as a blob (sqlx's SQLite
+//! encoding), so rows written as text by older builds or by hand are only
+//! reachable through the `uuid_sql` fallback queries. After normalization every
+//! UUID column listed in `UUID_COLUMNS` holds a blob and the fast paths match.
+//! RBAC and webhook tables store text by design and are left alone.
+
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppResult};
+
+/// Core tables and their UUID columns.
+pub const UUID_COLUMNS: &[(&str, &[&str])] = &[
+    ("users", &["id"]),
+    ("projects", &["id", "user_id"]),
+    ("tasks", &["id", "project_id", "assignee", "parent_id"]),
+    ("task_progress", &["id", "task_id", "project_id"]),
+    ("task_dependencies", &["id", "source_task_id", "target_task_id"]),
+    ("project_plan", &["id", "project_id"]),
+    ("project_members", &["project_id", "user_id"]),
+];
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TableNormalization {
+    pub table: String,
+    pub rows_before: i64,
+    pub rows_after: i64,
+    /// Column values rewritten from text to blob
+    pub converted: u64,
+    /// Text values that are not UUIDs; left untouched
+    pub invalid: u64,
+}
+
+/// Rewrite every text UUID in `UUID_COLUMNS` as a blob, in one transaction.
+///
+/// Row counts are taken before and after each table; any difference rolls the
+/// whole run back. Foreign keys are checked at commit, once parents and
+/// children have both been converted. Running it again converts nothing.
+pub async fn normalize_uuid_storage(pool: &SqlitePool) -> AppResult<Vec<TableNormalization>> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("PRAGMA defer_foreign_keys = ON").execute(&mut *tx).await?;
+
+    let mut report = Vec::with_capacity(UUID_COLUMNS.len());
+    for (table, columns) in UUID_COLUMNS {
+        let count_sql = format!("SELECT COUNT(*) FROM {table}");
+        let rows_before: i64 = sqlx::query_scalar(&count_sql).fetch_one(&mut *tx).await?;
+
+        let mut converted = 0;
+        let mut invalid = 0;
+        for column in columns.iter() {
+            let rows = sqlx::query(&format!("SELECT rowid, {column} FROM {table} WHERE typeof({column}) = 'text'"))
+                .fetch_all(&mut *tx)
+                .await?;
+            let update = format!("UPDATE {table} SET {column} = ? WHERE rowid = ?");
+            for row in rows {
+                let rowid: i64 = row.try_get(0)?;
+                let text: String = row.try_get(1)?;
+                match Uuid::parse_str(text.trim()) {
+                    Ok(id) => {
+                        sqlx::query(&update).bind(id).bind(rowid).execute(&mut *tx).await?;
+                        converted += 1;
+                    }
+                    Err(_) => {
+                        tracing::warn!(table = *table, column = *column, rowid, "skipping non-UUID text value");
+                        invalid += 1;
+                    }
+                }
+            }
+        }
+
+        let rows_after: i64 = sqlx::query_scalar(&count_sql).fetch_one(&mut *tx).await?;
+        if rows_after != rows_before {
+            // Dropping `tx` rolls back everything converted so far
+            return Err(AppError::internal(format!(
+                "{table}: row count changed from {rows_before} to {rows_after} during normalization"
+            )));
+        }
+
+        report.push(TableNormalization { table: table.to_string(), rows_before, rows_after, converted, invalid });
+    }
+
+    tx.commit().await?;
+    Ok(report)
+}
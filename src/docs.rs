@@ -5,6 +5,7 @@ use serde_json::{json, Map, Value};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+use crate::app::API_PREFIX;
 use crate::models;
 
 #[derive(OpenApi)]
@@ -15,27 +16,59 @@ use crate::models;
 			models::user::AuthResponse,
 			models::user::LoginRequest,
 			models::user::RegisterRequest,
+			models::user::ProfileUpdateRequest,
+			models::user::AccountDeleteRequest,
 			models::project::Project,
 			models::project::ProjectCreateRequest,
+			models::project::ProjectDuplicateRequest,
 			models::project::ProjectUpdateRequest,
 			models::task::Task,
+			models::task::TaskStatus,
 			models::task::TaskCreateRequest,
 			models::task::TaskUpdateRequest,
 			models::progress::Progress,
 			models::progress::ProgressCreateRequest,
+			models::progress::ProgressBatchCreateRequest,
 			models::progress::ProgressUpdateRequest,
+			crate::routes::progress::ProgressPage,
 			models::dependency::TaskDependency,
 			models::dependency::DependencyCreateRequest,
+			models::dependency::DependencyBatchCreateRequest,
+			models::dependency::ScheduleImportDependency,
+			models::dependency::ScheduleImportRequest,
+			models::dependency::ScheduleImportResponse,
+			models::task::TaskReplaceRequest,
 			models::task::TaskBatchUpdatePayload,
+			crate::errors::FieldError,
+			models::task::TaskAssignRequest,
+			models::task::TaskTagsRequest,
+			crate::routes::tasks::TaskTreeNode,
+			crate::routes::tasks::TaskTreeResponse,
+			crate::routes::tasks::TaskHistoryEntry,
+			crate::routes::tasks::TaskHistoryResponse,
 			models::task::TaskBatchUpdateRequest,
 			models::project_plan::ProjectPlanCreateRequest
 			,models::project_plan::ProjectPlanPoint
 			,crate::routes::projects::ActualPoint
 			,crate::routes::projects::DashboardResponse
+			,crate::routes::projects::ProjectStats
+			,crate::models::project_member::ProjectMember
+			,crate::models::project_member::ProjectMemberCreateRequest
+			,crate::routes::projects::ProjectFullResponse
+			,crate::routes::export::ProjectBundle
+			,crate::routes::export::UserExport
 			,crate::routes::projects::CriticalPathResponse
+			,crate::routes::projects::GanttResponse
+			,crate::routes::projects::TaskSchedule
+			,crate::routes::projects::ScheduleAnalysisResponse
+			,crate::routes::projects::ScheduleRequest
+			,crate::routes::search::SearchResult
+			,crate::routes::search::SearchResultKind
 			,crate::routes::health::HealthResponse
+			,crate::routes::health::LivenessResponse
 			,crate::models::rbac::Role
 			,crate::models::rbac::RoleCreateRequest
+			,crate::models::rbac::RoleUpdateRequest
 			,crate::models::rbac::Permission
 			,crate::models::rbac::PermissionCreateRequest
 			,crate::models::rbac::UserRole
@@ -43,66 +76,140 @@ use crate::models;
 			,crate::models::rbac::UserPermission
 			,crate::models::rbac::EffectivePermissions
 			,crate::models::rbac::EffectivePermission
+			,crate::models::rbac::PermissionHolder
 			,crate::models::rbac::AssignRoleRequest
+			,crate::models::rbac::ReplaceUserRolesRequest
+			,crate::models::rbac::UserRoleSet
 			,crate::models::rbac::AssignPermissionToRoleRequest
 			,crate::models::rbac::GrantPermissionRequest
+			,crate::routes::admin::DbStatsResponse
+			,crate::routes::admin::PoolStats
+			,crate::routes::admin::SqliteStats
+			,crate::events::ChainVerification
+			,crate::routes::admin::ReplayResponse
+			,crate::routes::activity::ActivityExportLine
+			,crate::routes::admin::NormalizeUuidsResponse
+			,crate::db::uuid_normalize::TableNormalization
+			,crate::routes::admin::AuthzModeResponse
+			,crate::models::rbac::SetAuthzModeRequest
+			,crate::models::rbac::AuthzModeChange
+			,crate::authz::AuthzMode
+			,crate::models::webhook::Webhook
+			,crate::models::webhook::WebhookCreateRequest
+			,crate::models::webhook::WebhookUpdateRequest
 		)
 	),
 	paths(
 		crate::routes::auth::register,
 		crate::routes::auth::login,
 		crate::routes::auth::me,
+		crate::routes::auth::update_me,
+		crate::routes::auth::delete_me,
 		crate::routes::auth::logout,
+		crate::routes::oauth::google_login,
+		crate::routes::oauth::google_callback,
 
 		crate::routes::projects::list_projects,
+		crate::routes::projects::list_trashed_projects,
 		crate::routes::projects::create_project,
 		crate::routes::projects::get_project,
 		crate::routes::projects::update_project,
 		crate::routes::projects::delete_project,
+		crate::routes::projects::purge_project,
 		crate::routes::projects::update_project_plan,
 		crate::routes::projects::clear_project_plan,
+		crate::routes::members::list_members,
+		crate::routes::members::add_member,
+		crate::routes::members::remove_member,
 		crate::routes::projects::get_project_dashboard,
+		crate::routes::projects::get_project_full,
+		crate::routes::projects::get_project_stats,
 		crate::routes::projects::get_project_critical_path,
+		crate::routes::projects::get_project_gantt,
+		crate::routes::projects::get_project_schedule_analysis,
+		crate::routes::projects::schedule_project,
+		crate::routes::export::export_my_projects,
+		crate::routes::search::search,
 
 		crate::routes::tasks::list_tasks,
+		crate::routes::tasks::list_trashed_tasks,
+		crate::routes::calendar::export_project_calendar,
+		crate::routes::export::export_project,
+		crate::routes::export::import_project,
+		crate::routes::export::duplicate_project,
 		crate::routes::tasks::create_task,
 		crate::routes::tasks::get_task,
 		crate::routes::tasks::update_task,
+		crate::routes::tasks::replace_task,
 		crate::routes::tasks::delete_task,
+		crate::routes::tasks::purge_task,
 		crate::routes::tasks::batch_update_tasks,
+		crate::routes::tasks::assign_tasks,
+		crate::routes::tasks::add_task_tags,
+		crate::routes::tasks::remove_task_tags,
+		crate::routes::tasks::get_task_tree,
+		crate::routes::tasks::list_overdue_tasks,
+		crate::routes::tasks::get_task_history,
 		crate::routes::tasks::list_dependencies,
 		crate::routes::tasks::create_dependency,
+		crate::routes::tasks::create_dependencies_batch,
 		crate::routes::tasks::delete_dependency,
+		crate::routes::tasks::import_schedule,
 
 		crate::routes::progress::list_progress,
+		crate::routes::progress::list_latest_progress,
 		crate::routes::progress::get_progress,
 		crate::routes::progress::create_progress,
+		crate::routes::progress::create_progress_batch,
 		crate::routes::progress::update_progress,
 		crate::routes::progress::delete_progress
 		,crate::routes::health::health,
+		crate::routes::health::live,
+		crate::routes::health::ready,
+		crate::routes::metrics::metrics,
 
 		crate::routes::rbac::list_roles,
 		crate::routes::rbac::create_role,
 		crate::routes::rbac::get_role,
+		crate::routes::rbac::update_role,
 		crate::routes::rbac::delete_role,
 		crate::routes::rbac::get_role_permissions,
 		crate::routes::rbac::assign_permission_to_role,
         crate::routes::rbac::delete_permission_from_role,
 		crate::routes::rbac::list_permissions,
 		crate::routes::rbac::create_permission,
+		crate::routes::rbac::delete_permission,
 		crate::routes::rbac::get_user_roles,
 		crate::routes::rbac::assign_role_to_user,
+		crate::routes::rbac::replace_user_roles,
 		crate::routes::rbac::revoke_role_from_user,
 		crate::routes::rbac::get_user_permissions,
 		crate::routes::rbac::grant_permission_to_user,
-		crate::routes::rbac::get_effective_permissions
+		crate::routes::rbac::get_effective_permissions,
+		crate::routes::rbac::get_permission_holders,
+		crate::routes::admin::get_db_stats,
+		crate::routes::admin::verify_event_store,
+		crate::routes::admin::replay_activity_log,
+		crate::routes::activity::export_activity_log,
+		crate::routes::admin::normalize_uuids,
+		crate::routes::admin::get_authz_mode,
+		crate::routes::admin::set_authz_mode,
+		crate::routes::webhooks::list_webhooks,
+		crate::routes::webhooks::create_webhook,
+		crate::routes::webhooks::get_webhook,
+		crate::routes::webhooks::update_webhook,
+		crate::routes::webhooks::delete_webhook,
+		crate::routes::users::list_users
 	),
 	tags(
 		(name = "Auth", description = "Authentication endpoints"),
 		(name = "Projects", description = "Project management"),
 		(name = "Tasks", description = "Task management"),
 		(name = "Progress", description = "Task progress entries"),
-		(name = "RBAC", description = "Role-Based Access Control")
+		(name = "RBAC", description = "Role-Based Access Control"),
+		(name = "Admin", description = "Operator diagnostics"),
+		(name = "Users", description = "User directory"),
+		(name = "Webhooks", description = "Outbound event delivery")
 	)
 )]
 pub struct ApiDoc;
@@ -111,6 +218,7 @@ pub fn build_openapi(port: u16) -> anyhow::Result<utoipa::openapi::OpenApi> {
 	let mut doc = serde_json::to_value(&ApiDoc::openapi())?;
 
 	ensure_paths(&mut doc);
+	prefix_api_paths(&mut doc);
 	// ensure_additional_paths(&mut doc); // Removed as get_project_dashboard is now in paths macro
 	normalize_path_operations(&mut doc);
 	ensure_security_components(&mut doc);
@@ -184,6 +292,29 @@ fn ensure_paths(doc: &mut Value) {
 	}
 }
 
+/// Paths served outside the versioned API (probes, metrics).
+const UNVERSIONED_PATHS: [&str; 5] = ["/api/health", "/health", "/health/live", "/health/ready", "/metrics"];
+
+/// Document the `/v1` routes so Swagger "Try it out" calls the versioned API;
+/// the unprefixed aliases are deprecated and intentionally left out.
+fn prefix_api_paths(doc: &mut Value) {
+	let Some(paths) = doc.get_mut("paths").and_then(Value::as_object_mut) else {
+		return;
+	};
+
+	let prefixed = std::mem::take(paths)
+		.into_iter()
+		.map(|(path, item)| {
+			if UNVERSIONED_PATHS.contains(&path.as_str()) || path.starts_with(API_PREFIX) {
+				(path, item)
+			} else {
+				(format!("{}{}", API_PREFIX, path), item)
+			}
+		})
+		.collect();
+	*paths = prefixed;
+}
+
 fn synthetic_paths() -> Map<String, Value> {
 	let mut paths = Map::new();
 
@@ -193,7 +324,10 @@ fn synthetic_paths() -> Map<String, Value> {
 			"get": {
 				"tags": ["Projects"],
 				"security": [{"bearerAuth": []}],
-				"parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}}],
+				"parameters": [
+					{"name": "id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}},
+					{"name": "velocity_days", "in": "query", "required": false, "description": "Window, in days, used to compute velocity (default 7; larger values are clamped to 36500)", "schema": {"type": "integer", "minimum": 1}}
+				],
 				"responses": {
 					"200": {
 						"description": "Project dashboard (plan vs actual)",
@@ -319,12 +453,31 @@ fn synthetic_paths() -> Map<String, Value> {
 				"parameters": [
 					{"name": "project_id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}},
 					{"name": "progress", "in": "query", "required": false, "schema": {"type": "boolean"}, "description": "Set to true to list progress entries instead of tasks"},
-					{"name": "task_id", "in": "query", "required": false, "schema": {"type": "string", "format": "uuid"}, "description": "Optional task id to filter progress"}
+					{"name": "task_id", "in": "query", "required": false, "schema": {"type": "string", "format": "uuid"}, "description": "Optional task id to filter progress"},
+					{"name": "envelope", "in": "query", "required": false, "schema": {"type": "boolean"}, "description": "Wrap the task list in the {kind, items} envelope (always used when progress=true)"}
 				],
 				"responses": {
 					"200": {
-						"description": "List tasks or progress entries",
-						"content": {"application/json": {"schema": {"oneOf": [{"type": "array", "items": {"$ref": "#/components/schemas/Task"}}, {"type": "array", "items": {"$ref": "#/components/schemas/Progress"}}]}}}
+						"description": "Bare task array, or a {kind, items} envelope discriminated by kind",
+						"content": {"application/json": {"schema": {"oneOf": [
+							{"type": "array", "items": {"$ref": "#/components/schemas/Task"}},
+							{
+								"type": "object",
+								"required": ["kind", "items"],
+								"properties": {
+									"kind": {"type": "string", "enum": ["tasks"]},
+									"items": {"type": "array", "items": {"$ref": "#/components/schemas/Task"}}
+								}
+							},
+							{
+								"type": "object",
+								"required": ["kind", "items"],
+								"properties": {
+									"kind": {"type": "string", "enum": ["progress"]},
+									"items": {"type": "array", "items": {"$ref": "#/components/schemas/Progress"}}
+								}
+							}
+						]}}}
 					}
 				}
 			},
@@ -343,6 +496,27 @@ fn synthetic_paths() -> Map<String, Value> {
 		}),
 	);
 
+	paths.insert(
+		"/graphql".to_string(),
+		json!({
+			"post": {
+				"tags": ["GraphQL"],
+				"security": [{"bearerAuth": []}],
+				"description": "Read-only GraphQL queries: project(id), tasks(projectId), progress(taskId), dashboard(id, velocityDays). Tasks expose latestProgress.",
+				"requestBody": {"required": true, "content": {"application/json": {"schema": {
+					"type": "object",
+					"required": ["query"],
+					"properties": {
+						"query": {"type": "string", "example": "{ project(id: \"...\") { name tasks { title latestProgress { progress } } } }"},
+						"variables": {"type": "object"},
+						"operationName": {"type": "string"}
+					}
+				}}}},
+				"responses": {"200": {"description": "GraphQL response with `data` and/or `errors` (each error has `extensions.code`)", "content": {"application/json": {"schema": {"type": "object"}}}}}
+			}
+		}),
+	);
+
 	paths.insert(
 		"/projects/{project_id}/tasks/{id}".to_string(),
 		json!({
@@ -360,9 +534,19 @@ fn synthetic_paths() -> Map<String, Value> {
 				"security": [{"bearerAuth": []}],
 				"parameters": [
 					{"name": "project_id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}},
-					{"name": "id", "in": "path", "required": false, "schema": {"type": "string", "format": "uuid"}}
+					{"name": "id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}}
+				],
+				"requestBody": {"required": true, "content": {"application/json": {"schema": {"$ref": "#/components/schemas/TaskReplaceRequest"}}}},
+				"responses": {"200": {"description": "Task replaced", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Task"}}}}, "400": {"description": "Missing or invalid field"}}
+			},
+			"patch": {
+				"tags": ["Tasks"],
+				"security": [{"bearerAuth": []}],
+				"parameters": [
+					{"name": "project_id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}},
+					{"name": "id", "in": "path", "required": true, "schema": {"type": "string", "format": "uuid"}}
 				],
-				"requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/TaskUpdateRequest"}}}},
+				"requestBody": {"required": true, "content": {"application/json": {"schema": {"$ref": "#/components/schemas/TaskUpdateRequest"}}}},
 				"responses": {"200": {"description": "Task updated", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Task"}}}}}
 			},
 			"delete": {
@@ -630,6 +814,7 @@ fn apply_response_examples(operation: &mut Value) {
 						"start_date": "2025-10-01T09:00:00Z",
 						"end_date": "2025-10-10T17:00:00Z",
 						"duration_days": 9,
+						"estimated_days": null,
 						"assignee": null,
 						"parent_id": null,
 						"progress": 0,
@@ -677,7 +862,8 @@ fn apply_response_examples(operation: &mut Value) {
 						],
 						"actual": [
 							{"date": "2025-10-05", "actual": 50}
-						]
+						],
+						"velocity": 0.0
 					})),
 					_ => None,
 				}
@@ -709,13 +895,9 @@ fn apply_response_examples(operation: &mut Value) {
 }
 
 fn ensure_servers(doc: &mut Value, port: u16) {
-	// Determine whether the running server will use TLS. If CERT_PATH+KEY_PATH are
-	// provided (or USE_SELF_SIGNED_TLS is set), prefer https so Swagger Try-it-out
-	// will call the backend over TLS.
-	let tls_enabled = std::env::var("CERT_PATH").is_ok() && std::env::var("KEY_PATH").is_ok()
-		|| std::env::var("USE_SELF_SIGNED_TLS").is_ok();
-
-	let scheme = if tls_enabled { "https" } else { "http" };
+	// Advertise the scheme the listener in main.rs actually uses, so Swagger
+	// Try-it-out calls the backend over TLS exactly when TLS is on.
+	let scheme = crate::tls::TlsMode::from_env().scheme();
 
 	let server_url = format!("{}://localhost:{}", scheme, port);
 	let internal_url = "https://rust-service:8800".to_string();
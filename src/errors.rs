@@ -1,3 +1,4 @@
+use axum::extract::rejection::{JsonRejection, QueryRejection};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -26,6 +27,42 @@ pub enum AppError {
     Database(#[from] sqlx::Error),
     #[error("internal server error: {0}")]
     Internal(String),
+    #[error("service unavailable: {0}")]
+    ServiceUnavailable(String),
+    #[error("too many requests: {0}")]
+    TooManyRequests(String),
+    #[error("payload too large: {0}")]
+    PayloadTooLarge(String),
+    #[error("validation failed: {}", describe_field_errors(.0))]
+    Validation(Vec<FieldError>),
+}
+
+/// One invalid input field, reported in the `errors` array of a 422 response.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct FieldError {
+    /// Path of the offending field, e.g. `end_date` or `tasks[2].progress`
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { field: field.into(), message: message.into() }
+    }
+
+    /// Nest the field under a parent path, e.g. `progress` -> `tasks[2].progress`.
+    pub fn within(mut self, parent: &str) -> Self {
+        self.field = format!("{}.{}", parent, self.field);
+        self
+    }
+}
+
+fn describe_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl AppError {
@@ -60,14 +97,58 @@ impl AppError {
     pub fn internal(message: impl Into<String>) -> Self {
         Self::Internal(message.into())
     }
+
+    pub fn service_unavailable(message: impl Into<String>) -> Self {
+        Self::ServiceUnavailable(message.into())
+    }
+
+    pub fn too_many_requests(message: impl Into<String>) -> Self {
+        Self::TooManyRequests(message.into())
+    }
+
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::PayloadTooLarge(message.into())
+    }
+
+    /// 422 listing every invalid field at once.
+    pub fn validation(errors: Vec<FieldError>) -> Self {
+        Self::Validation(errors)
+    }
+
+    /// Stable machine-readable code, e.g. `VALIDATION` for any 400 or 422.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::BadRequest(_) => "VALIDATION",
+            AppError::Configuration(_) => "CONFIGURATION",
+            AppError::Token(_) => "TOKEN",
+            AppError::Database(_) => "DATABASE",
+            AppError::Internal(_) => "INTERNAL",
+            AppError::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
+            AppError::TooManyRequests(_) => "TOO_MANY_REQUESTS",
+            AppError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            AppError::Validation(_) => "VALIDATION",
+        }
+    }
 }
 
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
+    /// Stable machine-readable code, e.g. `VALIDATION` for any 400
+    code: &'static str,
     message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     detail: Option<String>,
+    /// Per-field problems; only present on 422 validation errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<Vec<FieldError>>,
+    /// Matches the `x-request-id` response header and the `request` log span
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
 }
 
 impl IntoResponse for AppError {
@@ -85,6 +166,10 @@ impl IntoResponse for AppError {
             AppError::Token(_) => StatusCode::UNAUTHORIZED,
             AppError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
         };
 
         let message = self.to_string();
@@ -98,8 +183,14 @@ impl IntoResponse for AppError {
             AppError::Token(_) => "token",
             AppError::Database(_) => "database",
             AppError::Internal(_) => "internal",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::TooManyRequests(_) => "too_many_requests",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::Validation(_) => "validation",
         };
 
+        let code = self.code();
+
         // Optionally include debug detail in the JSON response when
         // SHOW_ERRORS env var is set to `1` or `true`. This is intended
         // for local debugging only; avoid enabling in production.
@@ -114,16 +205,46 @@ impl IntoResponse for AppError {
             None
         };
 
+        let errors = match &self {
+            AppError::Validation(errors) => Some(errors.clone()),
+            _ => None,
+        };
+
         let payload = ErrorResponse {
             error: error.to_string(),
+            code,
             message,
             detail,
+            errors,
+            request_id: crate::middleware::request_id::current(),
         };
 
         (status, Json(payload)).into_response()
     }
 }
 
+impl From<JsonRejection> for AppError {
+    fn from(rejection: JsonRejection) -> Self {
+        // The body limit trips while buffering, before any JSON is parsed
+        if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            return Self::PayloadTooLarge(rejection.body_text());
+        }
+        let message = match &rejection {
+            JsonRejection::JsonSyntaxError(_) => format!("malformed JSON body: {}", rejection.body_text()),
+            JsonRejection::JsonDataError(_) => format!("invalid JSON body: {}", rejection.body_text()),
+            JsonRejection::MissingJsonContentType(_) => "expected `Content-Type: application/json`".to_string(),
+            _ => rejection.body_text(),
+        };
+        Self::BadRequest(message)
+    }
+}
+
+impl From<QueryRejection> for AppError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::BadRequest(format!("invalid query string: {}", rejection.body_text()))
+    }
+}
+
 impl From<anyhow::Error> for AppError {
     fn from(value: anyhow::Error) -> Self {
         Self::Internal(value.to_string())
@@ -0,0 +1,427 @@
+//! Event filter expressions.
+//!
+//! A tiny, side-effect free expression language used to decide whether a
+//! `DomainEvent` (as it travels on the bus, i.e. JSON) should be delivered to
+//! a subscriber such as a webhook. Example:
+//!
+//! ```text
+//! event_name LIKE 'task.*' AND severity >= important
+//! ```
+//!
+//! Grammar (keywords are case-insensitive):
+//!
+//! ```text
+//! expr    := and ( OR and )*
+//! and     := unary ( AND unary )*
+//! unary   := NOT unary | '(' expr ')' | field op value
+//! field   := event_name | name | severity | actor_id | subject_id
+//! op      := = | != | LIKE | > | >= | < | <=
+//! value   := 'quoted' | "quoted" | bare_word
+//! ```
+//!
+//! Inside a quoted value, a doubled quote (`'it''s'`) stands for one literal quote.
+//!
+//! `LIKE` accepts `*` or `%` as a wildcard for any run of characters. Ordering
+//! operators are only meaningful for `severity` (noise < important < critical).
+
+use serde_json::Value;
+
+use super::Severity;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("unexpected end of filter expression")]
+    UnexpectedEnd,
+    #[error("unexpected token '{0}' in filter expression")]
+    UnexpectedToken(String),
+    #[error("unterminated string in filter expression")]
+    UnterminatedString,
+    #[error("unknown filter field '{0}'")]
+    UnknownField(String),
+    #[error("invalid severity '{0}'")]
+    InvalidSeverity(String),
+    #[error("operator '{0}' is only supported for severity")]
+    UnsupportedOperator(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    EventName,
+    Severity,
+    ActorId,
+    SubjectId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Like,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare { field: Field, op: Op, value: String },
+}
+
+/// A parsed filter expression. Parse once (e.g. when a webhook is registered)
+/// and evaluate against every event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventFilter {
+    source: String,
+    expr: Expr,
+}
+
+impl EventFilter {
+    pub fn parse(source: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(tok) = parser.peek() {
+            return Err(FilterError::UnexpectedToken(tok.text()));
+        }
+        Ok(Self { source: source.to_string(), expr })
+    }
+
+    /// The original expression text, suitable for storing alongside the subscriber.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate the filter against a serialized `DomainEvent`.
+    pub fn matches(&self, event: &Value) -> bool {
+        eval(&self.expr, event)
+    }
+}
+
+fn severity_rank(s: Severity) -> u8 {
+    match s {
+        Severity::Noise => 0,
+        Severity::Important => 1,
+        Severity::Critical => 2,
+    }
+}
+
+fn parse_severity(s: &str) -> Option<Severity> {
+    serde_json::from_value(Value::String(s.to_ascii_lowercase())).ok()
+}
+
+fn event_field(field: Field, event: &Value) -> Option<&str> {
+    match field {
+        Field::EventName => event.get("name").and_then(Value::as_str),
+        Field::Severity => event
+            .get("payload")
+            .and_then(|p| p.get("severity"))
+            .and_then(Value::as_str),
+        Field::ActorId => event.get("actor_id").and_then(Value::as_str),
+        Field::SubjectId => event.get("subject_id").and_then(Value::as_str),
+    }
+}
+
+fn eval(expr: &Expr, event: &Value) -> bool {
+    match expr {
+        Expr::And(a, b) => eval(a, event) && eval(b, event),
+        Expr::Or(a, b) => eval(a, event) || eval(b, event),
+        Expr::Not(e) => !eval(e, event),
+        Expr::Compare { field, op, value } => {
+            if *field == Field::Severity {
+                // Events without a (recognised) severity are treated as important,
+                // matching how the activity listener stores them.
+                let actual = event_field(*field, event).and_then(parse_severity);
+                let lhs = severity_rank(actual.unwrap_or_default());
+                let rhs = match parse_severity(value) {
+                    Some(s) => severity_rank(s),
+                    None => return false,
+                };
+                return match op {
+                    Op::Eq | Op::Like => lhs == rhs,
+                    Op::Ne => lhs != rhs,
+                    Op::Gt => lhs > rhs,
+                    Op::Ge => lhs >= rhs,
+                    Op::Lt => lhs < rhs,
+                    Op::Le => lhs <= rhs,
+                };
+            }
+
+            let actual = match event_field(*field, event) {
+                Some(v) => v,
+                None => return *op == Op::Ne,
+            };
+            match op {
+                Op::Eq => actual.eq_ignore_ascii_case(value),
+                Op::Ne => !actual.eq_ignore_ascii_case(value),
+                Op::Like => wildcard_match(value, actual),
+                // rejected at parse time
+                Op::Gt | Op::Ge | Op::Lt | Op::Le => false,
+            }
+        }
+    }
+}
+
+/// Case-insensitive glob match where `*` and `%` match any run of characters.
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_ascii_lowercase().chars().collect();
+    let t: Vec<char> = text.to_ascii_lowercase().chars().collect();
+
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let mut star: Option<usize> = None;
+    let mut mark = 0usize;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '*' || p[pi] == '%') {
+            star = Some(pi);
+            mark = ti;
+            pi += 1;
+        } else if pi < p.len() && p[pi] == t[ti] {
+            pi += 1;
+            ti += 1;
+        } else if let Some(s) = star {
+            pi = s + 1;
+            mark += 1;
+            ti = mark;
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && (p[pi] == '*' || p[pi] == '%') {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+// =============================================================================
+// TOKENIZER / PARSER
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match self {
+            Token::Word(w) => w.clone(),
+            Token::Str(s) => format!("'{}'", s.replace('\'', "''")),
+            Token::Op(o) => o.to_string(),
+            Token::LParen => "(".to_string(),
+            Token::RParen => ")".to_string(),
+        }
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self, Token::Word(w) if w.eq_ignore_ascii_case(kw))
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => { tokens.push(Token::LParen); i += 1; }
+            ')' => { tokens.push(Token::RParen); i += 1; }
+            '\'' | '"' => {
+                // A doubled quote inside the string stands for one literal quote, as in SQL
+                let quote = c;
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(FilterError::UnterminatedString),
+                        Some(&ch) if ch == quote && chars.get(i + 1) == Some(&quote) => {
+                            value.push(quote);
+                            i += 2;
+                        }
+                        Some(&ch) if ch == quote => {
+                            i += 1;
+                            break;
+                        }
+                        Some(&ch) => {
+                            value.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '=' => { tokens.push(Token::Op("=")); i += 1; }
+            '!' if chars.get(i + 1) == Some(&'=') => { tokens.push(Token::Op("!=")); i += 2; }
+            '>' | '<' => {
+                let eq = chars.get(i + 1) == Some(&'=');
+                let op = match (c, eq) {
+                    ('>', true) => ">=",
+                    ('>', false) => ">",
+                    ('<', true) => "<=",
+                    _ => "<",
+                };
+                tokens.push(Token::Op(op));
+                i += if eq { 2 } else { 1 };
+            }
+            c if c.is_alphanumeric() || matches!(c, '_' | '.' | '*' | '%' | '-') => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '*' | '%' | '-')) {
+                    i += 1;
+                }
+                tokens.push(Token::Word(chars[start..i].iter().collect()));
+            }
+            other => return Err(FilterError::UnexpectedToken(other.to_string())),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, FilterError> {
+        let tok = self.tokens.get(self.pos).cloned().ok_or(FilterError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(tok)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.parse_and()?;
+        while self.peek().map(|t| t.is_keyword("OR")).unwrap_or(false) {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, FilterError> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek().map(|t| t.is_keyword("AND")).unwrap_or(false) {
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, FilterError> {
+        match self.next()? {
+            t if t.is_keyword("NOT") => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Token::LParen => {
+                let inner = self.parse_or()?;
+                match self.next()? {
+                    Token::RParen => Ok(inner),
+                    other => Err(FilterError::UnexpectedToken(other.text())),
+                }
+            }
+            Token::Word(w) => self.parse_comparison(&w),
+            other => Err(FilterError::UnexpectedToken(other.text())),
+        }
+    }
+
+    fn parse_comparison(&mut self, field_name: &str) -> Result<Expr, FilterError> {
+        let field = match field_name.to_ascii_lowercase().as_str() {
+            "event_name" | "name" | "event" => Field::EventName,
+            "severity" => Field::Severity,
+            "actor_id" => Field::ActorId,
+            "subject_id" => Field::SubjectId,
+            _ => return Err(FilterError::UnknownField(field_name.to_string())),
+        };
+
+        let op = match self.next()? {
+            Token::Op("=") => Op::Eq,
+            Token::Op("!=") => Op::Ne,
+            Token::Op(">") => Op::Gt,
+            Token::Op(">=") => Op::Ge,
+            Token::Op("<") => Op::Lt,
+            Token::Op("<=") => Op::Le,
+            t if t.is_keyword("LIKE") => Op::Like,
+            other => return Err(FilterError::UnexpectedToken(other.text())),
+        };
+
+        let value = match self.next()? {
+            Token::Word(w) | Token::Str(w) => w,
+            other => return Err(FilterError::UnexpectedToken(other.text())),
+        };
+
+        if field == Field::Severity {
+            if parse_severity(&value).is_none() {
+                return Err(FilterError::InvalidSeverity(value));
+            }
+        } else if matches!(op, Op::Gt | Op::Ge | Op::Lt | Op::Le) {
+            let sym = match op { Op::Gt => ">", Op::Ge => ">=", Op::Lt => "<", _ => "<=" };
+            return Err(FilterError::UnsupportedOperator(sym.to_string()));
+        }
+
+        Ok(Expr::Compare { field, op, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(name: &str, severity: &str) -> Value {
+        json!({ "name": name, "payload": { "severity": severity } })
+    }
+
+    #[test]
+    fn test_like_and_severity() {
+        let f = EventFilter::parse("event_name LIKE 'task.*' AND severity >= important").unwrap();
+        assert!(f.matches(&event("task.created", "important")));
+        assert!(f.matches(&event("task.deleted", "critical")));
+        assert!(!f.matches(&event("task.updated", "noise")));
+        assert!(!f.matches(&event("project.deleted", "critical")));
+    }
+
+    #[test]
+    fn test_or_not_and_parentheses() {
+        let f = EventFilter::parse("NOT (name = project.created OR name = project.updated)").unwrap();
+        assert!(!f.matches(&event("project.created", "important")));
+        assert!(f.matches(&event("project.deleted", "critical")));
+    }
+
+    #[test]
+    fn test_missing_severity_counts_as_important() {
+        let event = json!({ "name": "user.login", "payload": {} });
+        assert!(EventFilter::parse("severity >= important").unwrap().matches(&event));
+        assert!(EventFilter::parse("severity = important").unwrap().matches(&event));
+        assert!(!EventFilter::parse("severity >= critical").unwrap().matches(&event));
+        assert!(!EventFilter::parse("severity != important").unwrap().matches(&event));
+    }
+
+    #[test]
+    fn test_doubled_quotes_escape() {
+        let f = EventFilter::parse("event_name LIKE 'task''s.*'").unwrap();
+        assert!(f.matches(&event("task's.created", "important")));
+        assert!(!f.matches(&event("task.created", "important")));
+        assert_eq!(EventFilter::parse("name = 'a''"), Err(FilterError::UnterminatedString));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(EventFilter::parse("color = red"), Err(FilterError::UnknownField("color".into())));
+        assert_eq!(EventFilter::parse("severity >= loud"), Err(FilterError::InvalidSeverity("loud".into())));
+        assert_eq!(EventFilter::parse("name > task"), Err(FilterError::UnsupportedOperator(">".into())));
+        assert_eq!(EventFilter::parse("name = 'task"), Err(FilterError::UnterminatedString));
+        assert_eq!(EventFilter::parse("name ="), Err(FilterError::UnexpectedEnd));
+        assert!(EventFilter::parse("name = a extra").is_err());
+    }
+}
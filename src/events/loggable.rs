@@ -46,10 +46,10 @@ pub trait Loggable: Serialize + Send + Sync {
         Severity::Important
     }
 
-    /// Override severity based on action (e.g., "deleted" -> Critical)
+    /// Override severity based on action (e.g., "deleted"/"purged" -> Critical)
     fn severity_for_action(&self, action: &str) -> Severity {
         match action {
-            "deleted" => Severity::Critical,
+            "deleted" | "purged" => Severity::Critical,
             "created" | "updated" => self.severity(),
             _ => Severity::Important,
         }
@@ -1,12 +1,21 @@
 use chrono::{DateTime, Utc};
+use futures_util::FutureExt;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tokio::sync::broadcast;
+use tokio::sync::broadcast::{self, error::RecvError};
 use uuid::Uuid;
 use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Mutex, OnceLock};
 
 pub mod loggable;
 pub use loggable::{Loggable, Severity};
+pub mod filter;
+pub use filter::{EventFilter, FilterError};
+pub mod suppression;
+pub use suppression::{activity_log_rules, ActivityLogRules};
+pub mod webhooks;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DomainEvent<T> {
@@ -33,8 +42,39 @@ impl<T> DomainEvent<T> {
 
 pub type EventBus = broadcast::Sender<Value>;
 
+/// Return a `&'static str` for `name`, leaking it only the first time it is seen.
+///
+/// `DomainEvent::name` is `'static`, so each distinct name costs one allocation for
+/// the life of the process; repeated names (`task.updated`, ...) reuse that entry.
+pub(crate) fn intern_event_name(name: String) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut names = NAMES
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(&existing) = names.get(name.as_str()) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(leaked);
+    leaked
+}
+
+pub const DEFAULT_EVENT_BUS_CAPACITY: usize = 1024;
+
+/// Events buffered per subscriber before a slow one starts losing them
+/// (`EVENT_BUS_CAPACITY`, default 1024; zero or unparseable values use the default).
+pub fn event_bus_capacity() -> usize {
+    std::env::var("EVENT_BUS_CAPACITY")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|&capacity| capacity > 0)
+        .unwrap_or(DEFAULT_EVENT_BUS_CAPACITY)
+}
+
 pub fn init_event_bus() -> (EventBus, broadcast::Receiver<Value>) {
-    broadcast::channel(1024)
+    broadcast::channel(event_bus_capacity())
 }
 
 /// Request context for activity logging (IP, User-Agent, etc.)
@@ -120,6 +160,9 @@ pub fn log_activity<T: Loggable>(
 
 /// Enhanced activity logging with old/new tracking and request context.
 ///
+/// Events switched off through `ACTIVITY_LOG_DISABLE` are dropped here, before
+/// they reach the bus (see [`suppression`]).
+///
 /// # Arguments
 /// * `event_bus` - The event bus to send the event to.
 /// * `action` - The action performed (e.g., "created", "updated", "deleted").
@@ -138,12 +181,13 @@ pub fn log_activity_with_context<T: Loggable>(
     // Build event name like "task.created"
     let event_name = format!("{}.{}", T::entity_type(), action);
 
-    // We need a 'static lifetime for name, so we leak the string.
-    // This is acceptable because event names are a small, bounded set.
-    let static_name: &'static str = Box::leak(event_name.into_boxed_str());
-
     // Build structured payload with dynamic severity
     let severity = entity.severity_for_action(action);
+    if !activity_log_rules().allows(&event_name, severity) {
+        return;
+    }
+
+    let static_name = intern_event_name(event_name);
     let payload = ActivityPayload {
         current: serde_json::to_value(entity).unwrap_or_default(),
         old: old_entity.map(|e| serde_json::to_value(e).unwrap_or_default()),
@@ -162,13 +206,109 @@ pub fn log_activity_with_context<T: Loggable>(
     let _ = event_bus.send(serde_json::to_value(event).unwrap_or_default());
 }
 
+/// Write every event on the bus to `activity_log` and `event_store` until the bus closes.
+///
+/// Events of any shape are accepted (see [`ActivityRow::from_event`]); a write
+/// error or even a panic while recording one event is logged and counted, and
+/// the listener moves on to the next. It returns only once every sender is
+/// gone, after draining what is still buffered.
 pub async fn start_activity_listener(mut rx: broadcast::Receiver<Value>, pool: SqlitePool) {
     tracing::info!("Activity listener started");
-    while let Ok(event) = rx.recv().await {
-        // We clone the event to use it for properties JSON, while extracting fields for columns
-        let event_json = event.clone();
+    loop {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            // The oldest events were overwritten before we got to them; keep going with the rest
+            Err(RecvError::Lagged(skipped)) => {
+                crate::metrics::registry().event_bus_lagged(skipped);
+                tracing::warn!(skipped, "activity listener fell behind; events missing from activity_log");
+                continue;
+            }
+            Err(RecvError::Closed) => {
+                tracing::info!("event bus closed; activity listener stopped");
+                break;
+            }
+        };
+
+        if AssertUnwindSafe(record_event(&pool, &event)).catch_unwind().await.is_err() {
+            crate::metrics::registry().activity_listener_panicked();
+            tracing::error!(event = %event, "activity listener panicked while recording an event; skipped it");
+        }
+    }
+}
+
+/// Run [`start_activity_listener`] and start it again if the task itself dies.
+///
+/// Only a weak handle to the bus is kept, so the supervisor never holds the
+/// channel open: once the app drops its senders the listener drains and this
+/// returns, which is what graceful shutdown waits on. Events published between
+/// a crash and the new subscription are not recorded.
+pub async fn supervise_activity_listener(
+    bus: broadcast::WeakSender<Value>,
+    rx: broadcast::Receiver<Value>,
+    pool: SqlitePool,
+) {
+    let mut rx = rx;
+    loop {
+        match tokio::spawn(start_activity_listener(rx, pool.clone())).await {
+            Ok(()) => return,
+            Err(e) => {
+                crate::metrics::registry().activity_listener_restarted();
+                tracing::error!(error = %e, "activity listener died; restarting");
+            }
+        }
+        rx = match bus.upgrade() {
+            Some(sender) => sender.subscribe(),
+            None => {
+                tracing::info!("event bus closed; activity listener not restarted");
+                return;
+            }
+        };
+    }
+}
+
+async fn record_event(pool: &SqlitePool, event: &Value) {
+    let row = ActivityRow::from_event(event, Utc::now());
+
+    // Phase 3: Insert into activity_log (projection)
+    if let Err(e) = insert_activity_row(pool, &row, event).await {
+        crate::metrics::registry().activity_log_write_failed();
+        tracing::error!("Failed to save activity log: {}", e);
+    }
+
+    // Phase 6: Insert into event_store with hash chain
+    let payload_str = serde_json::to_string(event).unwrap_or_default();
+    let entry = EventStoreEntry {
+        event_name: &row.event_name,
+        occurred_at: row.occurred_at,
+        actor_id: row.actor_id,
+        subject_id: row.subject_id,
+        payload: &payload_str,
+        severity: &row.severity,
+    };
+    if let Err(e) = append_to_event_store(pool, &entry).await {
+        crate::metrics::registry().event_store_write_failed();
+        tracing::error!("Failed to save to event store: {}", e);
+    }
+}
 
-        // Basic extraction (tolerant)
+/// Columns of one `activity_log` row, derived from a serialized `DomainEvent`.
+///
+/// Shared by the live listener and `replay_activity_log` so a rebuilt projection
+/// matches what the listener wrote.
+#[derive(Debug, Clone)]
+pub struct ActivityRow {
+    pub event_name: String,
+    pub description: String,
+    pub actor_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+    pub severity: String,
+}
+
+impl ActivityRow {
+    /// Extract the projection columns (tolerantly); `default_occurred_at` is used when the
+    /// event carries no parseable timestamp.
+    pub fn from_event(event: &Value, default_occurred_at: DateTime<Utc>) -> Self {
         let name = event.get("name").and_then(|v| v.as_str()).unwrap_or("unknown");
         let actor_id_str = event.get("actor_id").and_then(|v| v.as_str());
         let subject_id_str = event.get("subject_id").and_then(|v| v.as_str());
@@ -183,6 +323,8 @@ pub async fn start_activity_listener(mut rx: broadcast::Receiver<Value>, pool: S
             "project.deleted" => "Project deleted",
             "user.registered" => "New user registered",
             "user.login" => "User logged in",
+            "authz_mode.updated" => "Authorization mode changed",
+            "authz.denied" => "Permission check failed",
             _ => "System event",
         }.to_string();
 
@@ -191,88 +333,191 @@ pub async fn start_activity_listener(mut rx: broadcast::Receiver<Value>, pool: S
             .get("payload")
             .and_then(|p| p.get("severity"))
             .and_then(|s| s.as_str())
-            .unwrap_or("important");
+            .unwrap_or("important")
+            .to_string();
 
         // We store actor_id and subject_id as proper UUIDs if they parse, otherwise NULL
         let actor_id = actor_id_str.and_then(|s| Uuid::parse_str(s).ok());
         let subject_id = subject_id_str.and_then(|s| Uuid::parse_str(s).ok());
 
-        // Ensure we have a valid timestamp, or default to now
         let occurred_at = occurred_at_str
             .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
             .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(Utc::now);
-
-        let id = Uuid::new_v4();
+            .unwrap_or(default_occurred_at);
 
-        // Phase 3: Insert into activity_log (projection)
-        let result = sqlx::query!(
-            r#"
-            INSERT INTO activity_log (id, event_name, description, actor_id, subject_id, occurred_at, properties, severity)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-            id,
-            name,
-            description,
-            actor_id,
-            subject_id,
-            occurred_at,
-            event_json,
-            severity
-        )
-        .execute(&pool)
-        .await;
+        Self { event_name: name.to_string(), description, actor_id, subject_id, occurred_at, severity }
+    }
+}
 
-        if let Err(e) = result {
-            tracing::error!("Failed to save activity log: {}", e);
-        }
+async fn insert_activity_row<'e, E>(executor: E, row: &ActivityRow, properties: &Value) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO activity_log (id, event_name, description, actor_id, subject_id, occurred_at, properties, severity)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(&row.event_name)
+    .bind(&row.description)
+    .bind(row.actor_id)
+    .bind(row.subject_id)
+    .bind(row.occurred_at)
+    .bind(properties)
+    .bind(&row.severity)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
 
-        // Phase 6: Insert into event_store with hash chain
-        let event_store_id = Uuid::new_v4();
-        let payload_str = serde_json::to_string(&event_json).unwrap_or_default();
+/// Rebuild `activity_log` from `event_store`, the source of truth.
+///
+/// Runs in one transaction: the projection is truncated and every stored event is
+/// re-projected in insertion order. Returns the number of rows written.
+pub async fn replay_activity_log(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM activity_log").execute(&mut *tx).await?;
+
+    let events: Vec<(String, DateTime<Utc>)> =
+        sqlx::query_as("SELECT payload, occurred_at FROM event_store ORDER BY rowid ASC")
+            .fetch_all(&mut *tx)
+            .await?;
+
+    let mut written = 0;
+    for (payload, occurred_at) in events {
+        let event: Value = match serde_json::from_str(&payload) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("Skipping unparseable event_store payload during replay: {}", e);
+                continue;
+            }
+        };
+        let row = ActivityRow::from_event(&event, occurred_at);
+        insert_activity_row(&mut *tx, &row, &event).await?;
+        written += 1;
+    }
 
-        // Get the previous hash from the last event
-        let prev_hash_result: Option<String> = sqlx::query_scalar(
-            "SELECT hash FROM event_store ORDER BY created_at DESC LIMIT 1"
-        )
-        .fetch_optional(&pool)
-        .await
-        .ok()
-        .flatten();
+    tx.commit().await?;
+    Ok(written)
+}
 
-        // Compute SHA256(prev_hash || payload)
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        if let Some(ref ph) = prev_hash_result {
-            hasher.update(ph.as_bytes());
-        }
-        hasher.update(payload_str.as_bytes());
-        let hash = hex::encode(hasher.finalize());
+struct EventStoreEntry<'a> {
+    event_name: &'a str,
+    occurred_at: DateTime<Utc>,
+    actor_id: Option<Uuid>,
+    subject_id: Option<Uuid>,
+    payload: &'a str,
+    severity: &'a str,
+}
 
-        let actor_id_str_for_store = actor_id.map(|u| u.to_string());
-        let subject_id_str_for_store = subject_id.map(|u| u.to_string());
-        let event_store_id_str = event_store_id.to_string();
+/// `hash = SHA256(prev_hash || payload)`; the first event has no `prev_hash`.
+fn chain_hash(prev_hash: Option<&str>, payload: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    if let Some(ph) = prev_hash {
+        hasher.update(ph.as_bytes());
+    }
+    hasher.update(payload.as_bytes());
+    hex::encode(hasher.finalize())
+}
 
-        let event_store_result = sqlx::query(
+/// Append one event to the hash chain.
+///
+/// Reading the tail and inserting the new link happen inside `BEGIN IMMEDIATE`, which takes
+/// SQLite's write lock up front, so concurrent writers (even a second listener on the same
+/// database) queue up instead of both chaining off the same `prev_hash`.
+async fn append_to_event_store(pool: &SqlitePool, entry: &EventStoreEntry<'_>) -> Result<(), sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+    let result = async {
+        // rowid follows insertion order; created_at has one-second resolution and ties under load
+        let prev_hash: Option<String> = sqlx::query_scalar("SELECT hash FROM event_store ORDER BY rowid DESC LIMIT 1")
+            .fetch_optional(&mut *conn)
+            .await?;
+        let hash = chain_hash(prev_hash.as_deref(), entry.payload);
+
+        sqlx::query(
             r#"
             INSERT INTO event_store (id, event_name, occurred_at, actor_id, subject_id, payload, severity, prev_hash, hash)
             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
-        .bind(&event_store_id_str)
-        .bind(name)
-        .bind(occurred_at)
-        .bind(&actor_id_str_for_store)
-        .bind(&subject_id_str_for_store)
-        .bind(&payload_str)
-        .bind(severity)
-        .bind(&prev_hash_result)
+        .bind(Uuid::new_v4().to_string())
+        .bind(entry.event_name)
+        .bind(entry.occurred_at)
+        .bind(entry.actor_id.map(|u| u.to_string()))
+        .bind(entry.subject_id.map(|u| u.to_string()))
+        .bind(entry.payload)
+        .bind(entry.severity)
+        .bind(&prev_hash)
         .bind(&hash)
-        .execute(&pool)
-        .await;
+        .execute(&mut *conn)
+        .await?;
 
-        if let Err(e) = event_store_result {
-            tracing::error!("Failed to save to event store: {}", e);
+        Ok::<_, sqlx::Error>(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => {
+            sqlx::query("COMMIT").execute(&mut *conn).await?;
+            Ok(())
+        }
+        Err(e) => {
+            // Never hand a connection back to the pool mid-transaction
+            let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+            Err(e)
         }
     }
 }
+
+/// Outcome of walking the event-store hash chain from the first event.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ChainVerification {
+    /// Number of events checked
+    pub events: i64,
+    /// True when every event links to its predecessor and its hash matches its payload
+    pub valid: bool,
+    /// Id of the first event that breaks the chain, if any
+    pub first_broken_id: Option<String>,
+}
+
+/// Recompute the event-store hash chain in insertion order.
+pub async fn verify_event_chain(pool: &SqlitePool) -> Result<ChainVerification, sqlx::Error> {
+    let rows: Vec<(String, String, Option<String>, String)> =
+        sqlx::query_as("SELECT id, payload, prev_hash, hash FROM event_store ORDER BY rowid ASC")
+            .fetch_all(pool)
+            .await?;
+
+    let mut expected_prev: Option<String> = None;
+    let mut events = 0;
+    for (id, payload, prev_hash, hash) in rows {
+        events += 1;
+        if prev_hash != expected_prev || chain_hash(prev_hash.as_deref(), &payload) != hash {
+            return Ok(ChainVerification { events, valid: false, first_broken_id: Some(id) });
+        }
+        expected_prev = Some(hash);
+    }
+
+    Ok(ChainVerification { events, valid: true, first_broken_id: None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::intern_event_name;
+
+    #[test]
+    fn interning_reuses_the_first_allocation() {
+        let first = intern_event_name("task.updated".to_string());
+        let second = intern_event_name(format!("{}.{}", "task", "updated"));
+        assert_eq!(first, "task.updated");
+        assert!(std::ptr::eq(first, second));
+
+        let other = intern_event_name("task.deleted".to_string());
+        assert!(!std::ptr::eq(first, other));
+    }
+}
@@ -0,0 +1,86 @@
+//! Operator control over which events are logged at all.
+//!
+//! `ACTIVITY_LOG_DISABLE` is a comma-separated list of event-name globs
+//! (`task.updated,progress.*`); a matching event is dropped before it reaches
+//! the bus, so it lands in neither `activity_log` nor `event_store` and no
+//! webhook sees it. `ACTIVITY_LOG_ENABLE` takes the same syntax and wins over
+//! the denylist, e.g. disable `task.*` but keep `task.deleted`.
+//!
+//! Critical events (deletions, purges, every RBAC change) are always logged,
+//! whatever the lists say.
+
+use std::sync::OnceLock;
+
+use super::filter::wildcard_match;
+use super::Severity;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivityLogRules {
+    disabled: Vec<String>,
+    enabled: Vec<String>,
+}
+
+impl ActivityLogRules {
+    /// Build from the two comma-separated glob lists; blank entries are ignored.
+    pub fn parse(disable: &str, enable: &str) -> Self {
+        Self { disabled: split_globs(disable), enabled: split_globs(enable) }
+    }
+
+    /// Read `ACTIVITY_LOG_DISABLE` and `ACTIVITY_LOG_ENABLE` (both default empty).
+    pub fn from_env() -> Self {
+        Self::parse(
+            &std::env::var("ACTIVITY_LOG_DISABLE").unwrap_or_default(),
+            &std::env::var("ACTIVITY_LOG_ENABLE").unwrap_or_default(),
+        )
+    }
+
+    /// Whether an event with this name and severity should be published.
+    pub fn allows(&self, event_name: &str, severity: Severity) -> bool {
+        if severity == Severity::Critical {
+            return true;
+        }
+        let matches = |globs: &[String]| globs.iter().any(|glob| wildcard_match(glob, event_name));
+        !matches(&self.disabled) || matches(&self.enabled)
+    }
+}
+
+/// The process-wide rules, read from the environment on first use.
+pub fn activity_log_rules() -> &'static ActivityLogRules {
+    static RULES: OnceLock<ActivityLogRules> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let rules = ActivityLogRules::from_env();
+        if !rules.disabled.is_empty() {
+            tracing::info!(disabled = ?rules.disabled, enabled = ?rules.enabled, "activity logging suppressed for matching events");
+        }
+        rules
+    })
+}
+
+fn split_globs(list: &str) -> Vec<String> {
+    list.split(',').map(str::trim).filter(|glob| !glob.is_empty()).map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denylist_globs_with_allowlist_exceptions() {
+        let rules = ActivityLogRules::parse(" task.updated, progress.* ,,task.*", "task.deleted");
+
+        assert!(!rules.allows("progress.created", Severity::Important));
+        assert!(!rules.allows("task.updated", Severity::Noise));
+        assert!(!rules.allows("task.created", Severity::Important));
+        assert!(rules.allows("project.updated", Severity::Important));
+        // Allowlisted and Critical events survive a matching deny
+        assert!(rules.allows("task.deleted", Severity::Important));
+        assert!(rules.allows("progress.purged", Severity::Critical));
+    }
+
+    #[test]
+    fn empty_lists_allow_everything() {
+        let rules = ActivityLogRules::parse("", "");
+        assert_eq!(rules, ActivityLogRules::default());
+        assert!(rules.allows("task.updated", Severity::Noise));
+    }
+}
@@ -0,0 +1,174 @@
+//! Outbound webhook delivery.
+//!
+//! `start_webhook_dispatcher` subscribes to the `EventBus` next to the activity
+//! listener. Each event is matched against every registered webhook's filter
+//! expression (see `events::filter`) and POSTed as JSON to the hooks that match, signed with
+//! `X-Webhook-Signature: sha256=<hex HMAC-SHA256(secret, body)>`.
+//!
+//! Failed deliveries (transport errors or non-2xx responses) are retried with
+//! exponential backoff up to `WEBHOOK_MAX_ATTEMPTS` (default 5), starting at
+//! `WEBHOOK_BACKOFF_MS` (default 500ms). Deliveries run on their own tasks so a
+//! slow endpoint never holds up the bus.
+
+use std::time::Duration;
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::{Row, SqlitePool};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use super::EventFilter;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_BACKOFF_MS: u64 = 500;
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+pub const EVENT_HEADER: &str = "x-webhook-event";
+
+fn max_attempts() -> u32 {
+    std::env::var("WEBHOOK_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_ATTEMPTS)
+}
+
+fn base_backoff() -> Duration {
+    Duration::from_millis(
+        std::env::var("WEBHOOK_BACKOFF_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_BACKOFF_MS),
+    )
+}
+
+/// HMAC-SHA256 (RFC 2104) of `body` keyed by `secret`, hex encoded.
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    const BLOCK: usize = 64;
+
+    let mut key = [0u8; BLOCK];
+    if secret.len() > BLOCK {
+        key[..32].copy_from_slice(&Sha256::digest(secret));
+    } else {
+        key[..secret.len()].copy_from_slice(secret);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(key.map(|b| b ^ 0x36));
+    inner.update(body);
+    let inner = inner.finalize();
+
+    let mut outer = Sha256::new();
+    outer.update(key.map(|b| b ^ 0x5c));
+    outer.update(inner);
+    hex::encode(outer.finalize())
+}
+
+struct Target {
+    id: String,
+    url: String,
+    secret: String,
+}
+
+async fn matching_targets(pool: &SqlitePool, event: &Value) -> Result<Vec<Target>, sqlx::Error> {
+    let rows = sqlx::query("SELECT id, url, secret, filter FROM webhooks")
+        .fetch_all(pool)
+        .await?;
+
+    let mut targets = Vec::new();
+    for row in rows {
+        let id: String = row.get("id");
+        let source: String = row.get("filter");
+        match EventFilter::parse(&source) {
+            Ok(filter) if filter.matches(event) => targets.push(Target { id, url: row.get("url"), secret: row.get("secret") }),
+            Ok(_) => {}
+            Err(e) => tracing::warn!(webhook_id = %id, error = %e, "skipping webhook with invalid filter"),
+        }
+    }
+    Ok(targets)
+}
+
+async fn deliver(client: reqwest::Client, target: Target, event_name: String, body: Vec<u8>) {
+    let signature = format!("sha256={}", sign_payload(target.secret.as_bytes(), &body));
+    let attempts = max_attempts();
+    let mut backoff = base_backoff();
+
+    for attempt in 1..=attempts {
+        let result = client
+            .post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &signature)
+            .header(EVENT_HEADER, &event_name)
+            .body(body.clone())
+            .send()
+            .await;
+
+        let error = match result {
+            Ok(resp) if resp.status().is_success() => return,
+            Ok(resp) => format!("status {}", resp.status()),
+            Err(e) => e.to_string(),
+        };
+
+        if attempt == attempts {
+            tracing::error!(webhook_id = %target.id, event = %event_name, attempts, error = %error, "webhook delivery failed");
+            return;
+        }
+        tracing::warn!(webhook_id = %target.id, event = %event_name, attempt, error = %error, "webhook delivery failed; retrying");
+        tokio::time::sleep(backoff).await;
+        backoff = backoff.saturating_mul(2);
+    }
+}
+
+pub async fn start_webhook_dispatcher(mut rx: broadcast::Receiver<Value>, pool: SqlitePool) {
+    let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Webhook dispatcher disabled: {}", e);
+            return;
+        }
+    };
+
+    loop {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                crate::metrics::registry().event_bus_lagged(skipped);
+                tracing::warn!(skipped, "webhook dispatcher fell behind; events dropped");
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        let targets = match matching_targets(&pool, &event).await {
+            Ok(t) => t,
+            Err(e) => {
+                tracing::error!("Failed to load webhooks: {}", e);
+                continue;
+            }
+        };
+        if targets.is_empty() {
+            continue;
+        }
+
+        let event_name = event.get("name").and_then(Value::as_str).unwrap_or("unknown").to_string();
+        let body = serde_json::to_vec(&event).unwrap_or_default();
+        for target in targets {
+            tokio::spawn(deliver(client.clone(), target, event_name.clone(), body.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign_payload;
+
+    #[test]
+    fn signature_matches_rfc4231_vector() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
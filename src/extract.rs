@@ -0,0 +1,18 @@
+//! Request extractors that report failures in the `AppError` JSON shape.
+
+use axum::extract::{FromRequest, FromRequestParts};
+
+use crate::errors::AppError;
+
+/// Drop-in replacement for `axum::Json` as a request extractor: malformed or
+/// mistyped bodies become `AppError::BadRequest` (code `VALIDATION`) instead of
+/// axum's plain-text rejection. Responses keep using `axum::Json`.
+#[derive(Debug, FromRequest)]
+#[from_request(via(axum::Json), rejection(AppError))]
+pub struct AppJson<T>(pub T);
+
+/// Drop-in replacement for `axum::extract::Query`: unparsable query strings
+/// become `AppError::BadRequest` instead of axum's plain-text rejection.
+#[derive(Debug, FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(AppError))]
+pub struct AppQuery<T>(pub T);
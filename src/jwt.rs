@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use axum::async_trait;
@@ -11,60 +12,185 @@ use crate::errors::AppError;
 
 #[derive(Debug, Clone)]
 pub struct JwtConfig {
+    /// Secret that signs new tokens
     pub secret: Arc<Vec<u8>>,
     pub exp_hours: i64,
+    /// `kid` header written into new tokens; `None` with a single `JWT_SECRET`
+    pub kid: Option<String>,
+    /// Secrets accepted for verification, by `kid`
+    pub keys: Arc<HashMap<String, Arc<Vec<u8>>>>,
+    /// Secret for tokens without a `kid` (issued before key ids were configured)
+    pub unkeyed_secret: Option<Arc<Vec<u8>>>,
 }
 
 impl JwtConfig {
+    /// A single signing secret, no key ids.
+    pub fn new(secret: impl Into<Vec<u8>>, exp_hours: i64) -> Self {
+        let secret = Arc::new(secret.into());
+        Self {
+            secret: secret.clone(),
+            exp_hours,
+            kid: None,
+            keys: Arc::new(HashMap::new()),
+            unkeyed_secret: Some(secret),
+        }
+    }
+
+    /// Keyed secrets; new tokens are signed with `current_kid` and any listed key verifies.
+    pub fn from_keys(keys: Vec<(String, Vec<u8>)>, current_kid: &str, exp_hours: i64) -> Result<Self, AppError> {
+        let mut by_id = HashMap::with_capacity(keys.len());
+        for (id, secret) in keys {
+            if id.is_empty() || secret.is_empty() {
+                return Err(AppError::configuration("JWT_KEYS entries must be non-empty id:secret pairs"));
+            }
+            if by_id.insert(id.clone(), Arc::new(secret)).is_some() {
+                return Err(AppError::configuration(format!("JWT_KEYS lists key id '{}' twice", id)));
+            }
+        }
+        let secret = by_id
+            .get(current_kid)
+            .cloned()
+            .ok_or_else(|| AppError::configuration(format!("JWT_CURRENT_KID '{}' is not in JWT_KEYS", current_kid)))?;
+
+        Ok(Self {
+            secret,
+            exp_hours,
+            kid: Some(current_kid.to_string()),
+            keys: Arc::new(by_id),
+            unkeyed_secret: None,
+        })
+    }
+
+    /// `JWT_KEYS` (`id:secret,id:secret`) with `JWT_CURRENT_KID` naming the signing
+    /// key (optional when only one key is listed), or a single `JWT_SECRET`.
+    ///
+    /// With both set, `JWT_SECRET` keeps verifying tokens that carry no `kid`, so
+    /// moving from a single secret to key ids does not log everyone out.
     pub fn from_env() -> Result<Self, AppError> {
-        let secret = std::env::var("JWT_SECRET").map_err(|_| AppError::configuration("JWT_SECRET not set"))?;
         let exp_hours = std::env::var("JWT_EXP_HOURS")
             .map(|val| val.parse::<i64>())
             .unwrap_or(Ok(24))
             .map_err(|_| AppError::configuration("JWT_EXP_HOURS must be a valid integer"))?;
+        if exp_hours <= 0 {
+            return Err(AppError::configuration("JWT_EXP_HOURS must be positive"));
+        }
+
+        let legacy = std::env::var("JWT_SECRET").ok().filter(|s| !s.is_empty());
+        let keys = match std::env::var("JWT_KEYS").ok().filter(|s| !s.trim().is_empty()) {
+            Some(raw) => parse_jwt_keys(&raw)?,
+            None => {
+                let secret = legacy.ok_or_else(|| AppError::configuration("JWT_SECRET not set"))?;
+                return Ok(Self::new(secret.into_bytes(), exp_hours));
+            }
+        };
 
-        Ok(Self {
-            secret: Arc::new(secret.into_bytes()),
-            exp_hours,
-        })
+        let current = match std::env::var("JWT_CURRENT_KID").ok().filter(|s| !s.is_empty()) {
+            Some(kid) => kid,
+            None if keys.len() == 1 => keys[0].0.clone(),
+            None => return Err(AppError::configuration("JWT_CURRENT_KID must be set when JWT_KEYS lists several keys")),
+        };
+
+        let mut config = Self::from_keys(keys, &current, exp_hours)?;
+        config.unkeyed_secret = legacy.map(|s| Arc::new(s.into_bytes()));
+        Ok(config)
     }
 
     pub fn encode(&self, user_id: Uuid) -> Result<String, AppError> {
+        self.encode_with_roles(user_id, Vec::new())
+    }
+
+    /// Encode a token carrying the user's role names as the `roles` claim.
+    ///
+    /// At most `MAX_ROLE_CLAIMS` roles are embedded (`super_admin` first); the
+    /// claim is only ever used to allow early, so a truncated list is still safe.
+    pub fn encode_with_roles(&self, user_id: Uuid, mut roles: Vec<String>) -> Result<String, AppError> {
         use chrono::{Duration, Utc};
 
         let now = Utc::now();
         let exp = now + Duration::hours(self.exp_hours);
 
+        let super_admin = crate::authz::roles::SUPER_ADMIN;
+        roles.sort_by(|a, b| (a != super_admin).cmp(&(b != super_admin)).then_with(|| a.cmp(b)));
+        roles.dedup();
+        roles.truncate(MAX_ROLE_CLAIMS);
+
         let claims = Claims {
             sub: user_id,
             exp: exp.timestamp() as usize,
             iat: now.timestamp() as usize,
+            roles,
         };
 
-        jsonwebtoken::encode(&Header::default(), &claims, &EncodingKey::from_secret(&self.secret))
+        let header = Header { kid: self.kid.clone(), ..Header::default() };
+        jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(&self.secret))
             .map_err(|err| AppError::token(err.to_string()))
     }
 
+    /// Verify against the key named by the token's `kid`, or the unkeyed secret when it has none.
     pub fn decode(&self, token: &str) -> Result<Claims, AppError> {
+        let header = jsonwebtoken::decode_header(token).map_err(|err| AppError::token(err.to_string()))?;
+        let secret = match header.kid.as_deref() {
+            Some(kid) => self.keys.get(kid).ok_or_else(|| AppError::token("unknown signing key"))?,
+            None => self.unkeyed_secret.as_ref().ok_or_else(|| AppError::token("token has no key id"))?,
+        };
+
         let mut validation = Validation::default();
         validation.validate_exp = true;
 
-        jsonwebtoken::decode::<Claims>(token, &DecodingKey::from_secret(&self.secret), &validation)
+        jsonwebtoken::decode::<Claims>(token, &DecodingKey::from_secret(secret), &validation)
             .map(|data| data.claims)
             .map_err(|err| AppError::token(err.to_string()))
     }
 }
 
+fn parse_jwt_keys(raw: &str) -> Result<Vec<(String, Vec<u8>)>, AppError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, secret) = entry
+                .split_once(':')
+                .ok_or_else(|| AppError::configuration("JWT_KEYS entries must look like id:secret"))?;
+            Ok((id.trim().to_string(), secret.as_bytes().to_vec()))
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Claims {
     pub sub: Uuid,
     pub exp: usize,
     pub iat: usize,
+    /// Role names at issue time; absent unless `JWT_ROLE_CLAIMS` is enabled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+}
+
+/// Upper bound on roles embedded in a token, to keep headers small.
+pub const MAX_ROLE_CLAIMS: usize = 8;
+
+/// Whether login/register embed the user's roles in the token (`JWT_ROLE_CLAIMS`, default off).
+///
+/// Claims are a snapshot: a revoked role keeps granting its fast-path allow
+/// until the token expires, so keep `JWT_EXP_HOURS` short when enabling this.
+pub fn role_claims_enabled() -> bool {
+    std::env::var("JWT_ROLE_CLAIMS")
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
 }
 
 #[derive(Debug, Clone)]
 pub struct AuthUser {
     pub user_id: Uuid,
+    /// Roles from the token's `roles` claim (empty when not embedded)
+    pub roles: Vec<String>,
+}
+
+impl AuthUser {
+    /// Whether the token itself vouches for `role`; `false` means "unknown", not "absent".
+    pub fn has_role_claim(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
 }
 
 #[async_trait]
@@ -81,8 +207,14 @@ impl FromRequestParts<AppState> for AuthUser {
 
         let claims = state.jwt.decode(token)?;
 
+        // Tokens outlive the account; `delete_me` revokes them
+        if state.is_revoked(claims.sub) {
+            return Err(AppError::unauthorized("account has been deleted"));
+        }
+
         Ok(AuthUser {
             user_id: claims.sub,
+            roles: claims.roles,
         })
     }
 }
@@ -3,12 +3,16 @@ pub mod app;
 pub mod db;
 pub mod docs;
 pub mod errors;
+pub mod extract;
 pub mod jwt;
 pub mod models;
 pub mod routes;
 pub mod utils;
 pub mod events;
 pub mod authz;
+pub mod middleware;
+pub mod metrics;
+pub mod tls;
 
 // Re-export commonly used items for tests
-pub use app::create_app;
+pub use app::{create_app, API_PREFIX};
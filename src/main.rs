@@ -3,11 +3,17 @@ mod app;
 mod db;
 mod docs;
 mod errors;
+mod extract;
 mod jwt;
 mod models;
 mod routes;
 mod utils;
 mod events;
+#[allow(dead_code)]
+mod authz;
+mod middleware;
+mod metrics;
+mod tls;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -15,38 +21,99 @@ async fn main() -> anyhow::Result<()> {
     init_tracing();
 
     let pool = db::init().await?;
-    let router = app::create_app(pool).await?;
+    let (router, activity_listener) = app::create_app_with_listener(pool).await?;
     let port = resolve_port();
 
     let openapi = docs::build_openapi(port)?;
-    let router = router.merge(docs::swagger_routes(openapi));
+    // The API router already carries its CORS layer; give Swagger the same policy
+    let router = router.merge(docs::swagger_routes(openapi).layer(middleware::cors::cors_layer()));
 
-    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+    let addr = std::net::SocketAddr::new(resolve_bind_addr(), port);
     tracing::info!("listening on {}", addr);
 
-    // Prefer TLS if cert and key are provided via environment (CERT_PATH, KEY_PATH)
-    // which are loaded by `load_env()` earlier. When TLS is enabled axum-server
-    // will advertise ALPN and support HTTP/2 to browsers.
-    let cert = std::env::var("CERT_PATH").ok();
-    let key = std::env::var("KEY_PATH").ok();
+    // TLS comes from CERT_PATH/KEY_PATH or, failing those, USE_SELF_SIGNED_TLS; the
+    // OpenAPI `servers` entry reads the same `TlsMode`. With TLS, axum-server
+    // advertises ALPN and browsers negotiate HTTP/2.
+    let tls_config = match tls::TlsMode::from_env() {
+        tls::TlsMode::Files { cert_path, key_path } => {
+            tracing::info!("starting TLS with cert={} key={}", cert_path, key_path);
+            Some(axum_server::tls_rustls::RustlsConfig::from_pem_file(cert_path, key_path).await?)
+        }
+        tls::TlsMode::SelfSigned => {
+            tracing::warn!("starting TLS with a generated self-signed certificate (USE_SELF_SIGNED_TLS); not for production");
+            let (cert_pem, key_pem) = tls::self_signed_pem()?;
+            Some(axum_server::tls_rustls::RustlsConfig::from_pem(cert_pem.into_bytes(), key_pem.into_bytes()).await?)
+        }
+        tls::TlsMode::Plaintext => None,
+    };
 
-    if let (Some(cert_path), Some(key_path)) = (cert, key) {
-        tracing::info!("starting TLS with cert={} key={}", cert_path, key_path);
-        let cfg = axum_server::tls_rustls::RustlsConfig::from_pem_file(cert_path, key_path).await?;
+    if let Some(cfg) = tls_config {
+        let handle = axum_server::Handle::new();
+        let shutdown_handle = handle.clone();
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            shutdown_handle.graceful_shutdown(Some(SHUTDOWN_GRACE));
+        });
         // Rustls+ALPN will negotiate HTTP/2 with clients (browsers) automatically.
         axum_server::bind_rustls(addr, cfg)
-            .serve(router.into_make_service())
+            .handle(handle)
+            .serve(router.into_make_service_with_connect_info::<std::net::SocketAddr>())
             .await?;
     } else {
-        tracing::info!("starting plaintext HTTP (no CERT_PATH/KEY_PATH provided)");
+        tracing::info!("starting plaintext HTTP (no CERT_PATH/KEY_PATH or USE_SELF_SIGNED_TLS)");
         // plaintext (no TLS)
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, router.into_make_service()).await?;
+        axum::serve(listener, router.into_make_service_with_connect_info::<std::net::SocketAddr>())
+            .with_graceful_shutdown(shutdown_signal())
+            .await?;
+    }
+
+    // The router (and with it every event bus sender) is gone now; let the
+    // listener write whatever is still buffered before the process exits.
+    tracing::info!("server stopped, draining activity listener");
+    if tokio::time::timeout(SHUTDOWN_GRACE, activity_listener).await.is_err() {
+        tracing::warn!("activity listener did not drain within {:?}", SHUTDOWN_GRACE);
     }
 
     Ok(())
 }
 
+/// How long in-flight requests and the activity listener get to finish on shutdown
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Resolves on SIGINT (Ctrl+C) or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!("failed to listen for Ctrl+C: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sig) => {
+                sig.recv().await;
+            }
+            Err(err) => {
+                tracing::error!("failed to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("shutdown signal received, finishing in-flight requests");
+}
+
 fn resolve_port() -> u16 {
     std::env::var("APP_PORT")
         .ok()
@@ -54,6 +121,18 @@ fn resolve_port() -> u16 {
         .unwrap_or(8000)
 }
 
+/// `APP_BIND_ADDR` (default `0.0.0.0`); `127.0.0.1` keeps the server off external interfaces.
+fn resolve_bind_addr() -> std::net::IpAddr {
+    let default = std::net::IpAddr::from([0, 0, 0, 0]);
+    match std::env::var("APP_BIND_ADDR") {
+        Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!("APP_BIND_ADDR {:?} is not an IP address; binding {}", value, default);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
 fn load_env() {
     if dotenvy::dotenv().is_ok() {
         return;
@@ -63,20 +142,39 @@ fn load_env() {
     let _ = dotenvy::from_path(crate_env);
 }
 
+/// `LOG_FORMAT=json` emits one JSON object per line, including the fields of
+/// enclosing spans (such as `request_id`); anything else keeps the text format.
 fn init_tracing() {
     use tracing_subscriber::layer::SubscriberExt;
     use tracing_subscriber::util::SubscriberInitExt;
 
-    let fmt_layer = tracing_subscriber::fmt::layer()
-        .with_target(false)
-        .with_thread_ids(false)
-        .with_thread_names(false);
+    let json = std::env::var("LOG_FORMAT")
+        .map(|v| v.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+
+    let (text_layer, json_layer) = if json {
+        let layer = tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_target(false)
+            .with_thread_ids(false)
+            .with_thread_names(false);
+        (None, Some(layer))
+    } else {
+        let layer = tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .with_thread_ids(false)
+            .with_thread_names(false);
+        (Some(layer), None)
+    };
 
     let filter_layer = tracing_subscriber::EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
 
     tracing_subscriber::registry()
         .with(filter_layer)
-        .with(fmt_layer)
+        .with(text_layer)
+        .with(json_layer)
         .init();
 }
@@ -0,0 +1,197 @@
+//! Process-wide Prometheus metrics.
+//!
+//! Counters live in a single registry (see [`registry`]) so background tasks
+//! such as the activity listener can record failures without access to
+//! `AppState`. Gauges that describe live state (pool usage, bus backlog) are
+//! sampled when `/metrics` is rendered.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the request latency histogram buckets.
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+#[derive(Default)]
+struct RequestMetrics {
+    /// (method, route, status) -> requests
+    counts: BTreeMap<(String, String, u16), u64>,
+    /// (method, route) -> latency
+    latency: BTreeMap<(String, String), Histogram>,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    requests: Mutex<RequestMetrics>,
+    activity_log_write_failures: AtomicU64,
+    event_store_write_failures: AtomicU64,
+    event_bus_lagged_events: AtomicU64,
+    activity_listener_panics: AtomicU64,
+    activity_listener_restarts: AtomicU64,
+}
+
+/// Live values sampled by the `/metrics` handler.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gauges {
+    pub db_pool_size: u32,
+    pub db_pool_idle: usize,
+    pub event_bus_queued: usize,
+}
+
+pub fn registry() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::default)
+}
+
+impl Metrics {
+    pub fn record_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let mut requests = self.requests.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        *requests.counts.entry((method.to_string(), route.to_string(), status)).or_default() += 1;
+
+        let histogram = requests.latency.entry((method.to_string(), route.to_string())).or_default();
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if secs <= bound {
+                *bucket += 1;
+            }
+        }
+        histogram.count += 1;
+        histogram.sum += secs;
+    }
+
+    pub fn activity_log_write_failed(&self) {
+        self.activity_log_write_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn event_store_write_failed(&self) {
+        self.event_store_write_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A bus subscriber fell behind and `skipped` events were dropped for it.
+    pub fn event_bus_lagged(&self, skipped: u64) {
+        self.event_bus_lagged_events.fetch_add(skipped, Ordering::Relaxed);
+    }
+
+    /// Recording one event panicked; the listener skipped it.
+    pub fn activity_listener_panicked(&self) {
+        self.activity_listener_panics.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn activity_listener_restarted(&self) {
+        self.activity_listener_restarts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn activity_log_write_failures(&self) -> u64 {
+        self.activity_log_write_failures.load(Ordering::Relaxed)
+    }
+
+    pub fn event_bus_lagged_events(&self) -> u64 {
+        self.event_bus_lagged_events.load(Ordering::Relaxed)
+    }
+
+    pub fn activity_listener_panics(&self) -> u64 {
+        self.activity_listener_panics.load(Ordering::Relaxed)
+    }
+
+    /// Render everything in the Prometheus text exposition format (0.0.4).
+    pub fn render(&self, gauges: Gauges) -> String {
+        let mut out = String::new();
+
+        {
+            let requests = self.requests.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            out.push_str("# HELP http_requests_total HTTP requests by method, matched route and status.\n");
+            out.push_str("# TYPE http_requests_total counter\n");
+            for ((method, route, status), count) in &requests.counts {
+                let _ = writeln!(
+                    out,
+                    "http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                    escape(method),
+                    escape(route),
+                    status,
+                    count
+                );
+            }
+
+            out.push_str("# HELP http_request_duration_seconds HTTP request latency by method and matched route.\n");
+            out.push_str("# TYPE http_request_duration_seconds histogram\n");
+            for ((method, route), histogram) in &requests.latency {
+                let labels = format!("method=\"{}\",route=\"{}\"", escape(method), escape(route));
+                for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                    let _ = writeln!(out, "http_request_duration_seconds_bucket{{{},le=\"{}\"}} {}", labels, bound, count);
+                }
+                let _ = writeln!(out, "http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, histogram.count);
+                let _ = writeln!(out, "http_request_duration_seconds_sum{{{}}} {}", labels, histogram.sum);
+                let _ = writeln!(out, "http_request_duration_seconds_count{{{}}} {}", labels, histogram.count);
+            }
+        }
+
+        let in_use = (gauges.db_pool_size as usize).saturating_sub(gauges.db_pool_idle);
+        write_metric(&mut out, "db_pool_connections", "gauge", "Open database connections.", gauges.db_pool_size as u64);
+        write_metric(&mut out, "db_pool_connections_in_use", "gauge", "Database connections currently checked out.", in_use as u64);
+        write_metric(
+            &mut out,
+            "event_bus_queued_events",
+            "gauge",
+            "Events buffered on the bus that the slowest subscriber has not received yet.",
+            gauges.event_bus_queued as u64,
+        );
+        write_metric(
+            &mut out,
+            "event_bus_lagged_events_total",
+            "counter",
+            "Events dropped because a bus subscriber fell too far behind.",
+            self.event_bus_lagged_events.load(Ordering::Relaxed),
+        );
+        write_metric(
+            &mut out,
+            "activity_log_write_failures_total",
+            "counter",
+            "Events the activity listener failed to write to activity_log.",
+            self.activity_log_write_failures(),
+        );
+        write_metric(
+            &mut out,
+            "event_store_write_failures_total",
+            "counter",
+            "Events the activity listener failed to append to event_store.",
+            self.event_store_write_failures.load(Ordering::Relaxed),
+        );
+        write_metric(
+            &mut out,
+            "activity_listener_panics_total",
+            "counter",
+            "Events skipped because recording them panicked.",
+            self.activity_listener_panics(),
+        );
+        write_metric(
+            &mut out,
+            "activity_listener_restarts_total",
+            "counter",
+            "Times the activity listener task died and was started again.",
+            self.activity_listener_restarts.load(Ordering::Relaxed),
+        );
+
+        out
+    }
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,34 @@
+//! Request body caps. Bodies are buffered by the JSON extractors, so an
+//! oversized upload is refused with 413 while being read, before it is parsed.
+
+use axum::extract::DefaultBodyLimit;
+
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+pub const DEFAULT_MAX_IMPORT_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+fn env_bytes(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|bytes| *bytes > 0)
+        .unwrap_or(default)
+}
+
+/// Cap for every endpoint (`MAX_BODY_BYTES`, default 1 MiB).
+pub fn max_body_bytes() -> usize {
+    env_bytes("MAX_BODY_BYTES", DEFAULT_MAX_BODY_BYTES)
+}
+
+/// Cap for project/schedule imports (`MAX_IMPORT_BODY_BYTES`, default 10 MiB).
+pub fn max_import_body_bytes() -> usize {
+    env_bytes("MAX_IMPORT_BODY_BYTES", DEFAULT_MAX_IMPORT_BODY_BYTES)
+}
+
+pub fn default_limit() -> DefaultBodyLimit {
+    DefaultBodyLimit::max(max_body_bytes())
+}
+
+/// Overrides `default_limit` on the routes it is layered on.
+pub fn import_limit() -> DefaultBodyLimit {
+    DefaultBodyLimit::max(max_import_body_bytes())
+}
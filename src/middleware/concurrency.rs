@@ -0,0 +1,31 @@
+//! Concurrency cap for expensive read endpoints (dashboard, critical path,
+//! schedule analysis). Requests beyond the cap are rejected with 503 instead of
+//! queueing on the database pool.
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::app::AppState;
+use crate::errors::AppError;
+
+pub const DEFAULT_HEAVY_CONCURRENCY: usize = 8;
+
+/// Number of expensive requests allowed in flight (`HEAVY_ENDPOINT_CONCURRENCY`, default 8).
+pub fn heavy_concurrency_limit() -> usize {
+    std::env::var("HEAVY_ENDPOINT_CONCURRENCY")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_HEAVY_CONCURRENCY)
+}
+
+pub async fn limit_heavy(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let _permit = match state.heavy_limiter.clone().try_acquire_owned() {
+        Ok(permit) => permit,
+        Err(_) => {
+            return AppError::service_unavailable("server busy, retry shortly").into_response();
+        }
+    };
+
+    next.run(req).await
+}
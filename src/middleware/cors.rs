@@ -0,0 +1,67 @@
+//! CORS policy built from `CORS_ALLOWED_ORIGINS`.
+//!
+//! `*` (the default) allows any origin without credentials. A comma-separated
+//! list of origins allows only those and sends `Access-Control-Allow-Credentials`,
+//! matching the Swagger UI's `with_credentials` setting.
+//!
+//! Preflight `OPTIONS` requests are answered here, before routing, so they never
+//! reach extractors such as `AuthUser`. Browsers may cache the preflight for
+//! `CORS_MAX_AGE_SECS` (default 600).
+
+use std::time::Duration;
+
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use super::request_id::REQUEST_ID_HEADER;
+
+const METHODS: [Method; 6] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+    Method::OPTIONS,
+];
+
+pub const DEFAULT_CORS_MAX_AGE_SECS: u64 = 600;
+
+fn max_age() -> Duration {
+    let secs = std::env::var("CORS_MAX_AGE_SECS")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CORS_MAX_AGE_SECS);
+    Duration::from_secs(secs)
+}
+
+pub fn cors_layer() -> CorsLayer {
+    let configured = std::env::var("CORS_ALLOWED_ORIGINS").unwrap_or_default();
+    let origins: Vec<HeaderValue> = configured
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty() && *origin != "*")
+        .filter_map(|origin| match HeaderValue::from_str(origin) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!(origin, "ignoring invalid CORS origin");
+                None
+            }
+        })
+        .collect();
+
+    let layer = CorsLayer::new()
+        .allow_methods(METHODS)
+        .allow_headers([AUTHORIZATION, CONTENT_TYPE, HeaderName::from_static(REQUEST_ID_HEADER)])
+        .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER)])
+        .max_age(max_age());
+
+    // Browsers reject credentials combined with a wildcard origin
+    if origins.is_empty() || configured.split(',').any(|origin| origin.trim() == "*") {
+        layer.allow_origin(Any)
+    } else {
+        layer
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_credentials(true)
+    }
+}
@@ -0,0 +1,28 @@
+//! Request count and latency per matched route, exported on `/metrics`.
+//!
+//! Routes are labelled by their pattern (`/projects/:id`) rather than the raw
+//! path so label cardinality stays bounded; unmatched requests share one label.
+
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::metrics;
+
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+pub async fn track(req: Request, next: Next) -> Response {
+    let method = req.method().as_str().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+    let started = Instant::now();
+    let res = next.run(req).await;
+    metrics::registry().record_request(&method, &route, res.status().as_u16(), started.elapsed());
+    res
+}
@@ -0,0 +1,9 @@
+//! Request middleware shared by the router in `app.rs`.
+
+pub mod body_limit;
+pub mod concurrency;
+pub mod cors;
+pub mod metrics;
+pub mod rate_limit;
+pub mod request_id;
+pub mod versioning;
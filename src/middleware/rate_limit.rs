@@ -0,0 +1,157 @@
+//! In-memory token-bucket throttling for the unauthenticated auth endpoints
+//! (`/auth/login`, `/auth/register`), keyed by client IP.
+//!
+//! The client IP is the socket peer address. Forwarding headers are client
+//! supplied, so they are only believed when the peer is listed in
+//! `TRUSTED_PROXIES`: then the key is the rightmost `x-forwarded-for` hop that
+//! is not itself a trusted proxy (or `x-real-ip` without that header), and
+//! clients behind the proxy get their own buckets.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::header::RETRY_AFTER;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::app::AppState;
+use crate::errors::AppError;
+
+pub const DEFAULT_AUTH_RATE_LIMIT_BURST: u32 = 10;
+pub const DEFAULT_AUTH_RATE_LIMIT_PER_MINUTE: u32 = 10;
+
+/// Buckets are pruned once this many clients are tracked
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+/// When pruning full buckets is not enough, the least recently seen clients
+/// are evicted down to this many
+const EVICT_TO_CLIENTS: usize = MAX_TRACKED_CLIENTS * 3 / 4;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimiter {
+    pub fn new(burst: u32, per_minute: u32) -> Self {
+        Self {
+            capacity: f64::from(burst.max(1)),
+            refill_per_sec: f64::from(per_minute.max(1)) / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+            trusted_proxies: Vec::new(),
+        }
+    }
+
+    /// Believe forwarding headers on requests whose peer is one of `proxies`.
+    pub fn with_trusted_proxies(mut self, proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = proxies;
+        self
+    }
+
+    /// Limiter for the auth endpoints (`AUTH_RATE_LIMIT_BURST`, default 10;
+    /// `AUTH_RATE_LIMIT_PER_MINUTE`, default 10; `TRUSTED_PROXIES`, a
+    /// comma-separated list of proxy IPs, default none; unparseable entries are skipped).
+    pub fn auth_from_env() -> Self {
+        let burst = std::env::var("AUTH_RATE_LIMIT_BURST")
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_AUTH_RATE_LIMIT_BURST);
+        let per_minute = std::env::var("AUTH_RATE_LIMIT_PER_MINUTE")
+            .ok()
+            .and_then(|value| value.parse::<u32>().ok())
+            .unwrap_or(DEFAULT_AUTH_RATE_LIMIT_PER_MINUTE);
+        let trusted_proxies = std::env::var("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| match entry.parse::<IpAddr>() {
+                Ok(ip) => Some(ip),
+                Err(_) => {
+                    tracing::warn!(entry, "ignoring unparseable TRUSTED_PROXIES entry");
+                    None
+                }
+            })
+            .collect();
+        Self::new(burst, per_minute).with_trusted_proxies(trusted_proxies)
+    }
+
+    /// Take one token for `key`; on refusal returns how long until one is available.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(key) {
+            // Drop clients whose bucket has refilled completely; they are indistinguishable from new ones
+            let (capacity, rate) = (self.capacity, self.refill_per_sec);
+            buckets.retain(|_, b| b.tokens + now.duration_since(b.updated).as_secs_f64() * rate < capacity);
+            if buckets.len() >= MAX_TRACKED_CLIENTS {
+                // Still full of active clients: forget the ones seen longest ago
+                let mut by_age: Vec<(Instant, String)> = buckets.iter().map(|(k, b)| (b.updated, k.clone())).collect();
+                by_age.sort_unstable();
+                for (_, stale) in by_age.into_iter().take(buckets.len() - EVICT_TO_CLIENTS) {
+                    buckets.remove(&stale);
+                }
+            }
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket { tokens: self.capacity, updated: now });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.refill_per_sec))
+        }
+    }
+
+    /// Bucket key for `req`: the peer address, or the client a trusted proxy forwarded for.
+    fn client_key(&self, req: &Request) -> String {
+        let Some(peer) = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| addr.ip()) else {
+            return "unknown".to_string();
+        };
+        if !self.trusted_proxies.contains(&peer) {
+            return peer.to_string();
+        }
+
+        let headers = req.headers();
+        let forwarded = headers.get("x-forwarded-for").and_then(|v| v.to_str().ok()).map(|chain| {
+            // Walk back from the proxy; the first hop we don't run is the client
+            chain
+                .rsplit(',')
+                .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+                .find(|hop| !self.trusted_proxies.contains(hop))
+        });
+        let client = match forwarded {
+            Some(hop) => hop,
+            None => headers.get("x-real-ip").and_then(|v| v.to_str().ok()).and_then(|v| v.trim().parse().ok()),
+        };
+        client.unwrap_or(peer).to_string()
+    }
+}
+
+pub async fn limit_auth(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let key = state.auth_limiter.client_key(&req);
+    if let Err(wait) = state.auth_limiter.check(&key) {
+        tracing::warn!(client = %key, path = %req.uri().path(), "auth rate limit exceeded");
+        let mut res = AppError::too_many_requests("too many attempts, retry later").into_response();
+        let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        res.headers_mut().insert(RETRY_AFTER, HeaderValue::from(secs.max(1)));
+        return res;
+    }
+
+    next.run(req).await
+}
@@ -0,0 +1,23 @@
+//! Deprecation markers for the unprefixed route aliases.
+//!
+//! Everything under `/v1` is also reachable at the root for clients written
+//! before versioning. Those alias responses carry `Deprecation: true` and a
+//! `Link` to the `/v1` equivalent so clients can find and migrate stragglers.
+
+use axum::extract::Request;
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::app::API_PREFIX;
+
+pub async fn deprecated_alias(req: Request, next: Next) -> Response {
+    let successor = format!("<{}{}>; rel=\"successor-version\"", API_PREFIX, req.uri().path());
+
+    let mut res = next.run(req).await;
+    res.headers_mut().insert("deprecation", HeaderValue::from_static("true"));
+    if let Ok(value) = HeaderValue::from_str(&successor) {
+        res.headers_mut().insert(header::LINK, value);
+    }
+    res
+}
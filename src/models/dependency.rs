@@ -18,6 +18,7 @@ pub struct TaskDependency {
 impl crate::events::Loggable for TaskDependency {
     fn entity_type() -> &'static str { "dependency" }
     fn subject_id(&self) -> Uuid { self.id }
+    fn severity(&self) -> crate::events::Severity { crate::events::Severity::Important }
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -51,6 +52,34 @@ pub struct DependencyCreateRequest {
     pub type_: String,
 }
 
+/// Many dependencies created together, e.g. when importing a plan.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DependencyBatchCreateRequest {
+    pub dependencies: Vec<DependencyCreateRequest>,
+}
+
 fn default_type() -> String {
     "finish_to_start".to_string()
 }
+
+/// An edge in a schedule import, referencing tasks by their position in `tasks`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ScheduleImportDependency {
+    pub source_index: usize,
+    pub target_index: usize,
+    #[serde(rename = "type", default = "default_type")]
+    pub type_: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ScheduleImportRequest {
+    pub tasks: Vec<crate::models::task::TaskCreateRequest>,
+    #[serde(default)]
+    pub dependencies: Vec<ScheduleImportDependency>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScheduleImportResponse {
+    pub tasks: Vec<crate::models::task::Task>,
+    pub dependencies: Vec<TaskDependency>,
+}
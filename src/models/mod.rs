@@ -5,3 +5,5 @@ pub mod progress;
 pub mod project_plan;
 pub mod dependency;
 pub mod rbac;
+pub mod webhook;
+pub mod project_member;
@@ -21,6 +21,9 @@ pub struct Progress {
 impl crate::events::Loggable for Progress {
     fn entity_type() -> &'static str { "progress" }
     fn subject_id(&self) -> Uuid { self.id }
+    fn severity(&self) -> crate::events::Severity { crate::events::Severity::Noise }
+    // Progress churn is high-volume; keep even deletes prunable by the noise retention policy
+    fn severity_for_action(&self, _action: &str) -> crate::events::Severity { crate::events::Severity::Noise }
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -58,6 +61,15 @@ pub struct ProgressCreateRequest {
     pub progress: i32,
     #[schema(example = "Halfway done - waiting on review")]
     pub note: Option<String>,
+    /// When the progress was actually reached; stored as `created_at` (defaults to now, must not be in the future)
+    #[schema(example = "2025-10-01T09:00:00Z")]
+    pub occurred_at: Option<DateTime<Utc>>,
+}
+
+/// Several progress entries for one task, e.g. to backfill a history.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProgressBatchCreateRequest {
+    pub entries: Vec<ProgressCreateRequest>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
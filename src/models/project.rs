@@ -62,6 +62,13 @@ pub struct ProjectCreateRequest {
     pub theme_color: Option<String>,
 }
 
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ProjectDuplicateRequest {
+    /// Name for the copy; defaults to "<original name> (copy)"
+    #[schema(example = "Launch Planning 2026")]
+    pub name: Option<String>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ProjectUpdateRequest {
     #[schema(example = "Launch Planning")]
@@ -70,4 +77,7 @@ pub struct ProjectUpdateRequest {
     pub description: Option<String>,
     #[schema(example = "#2ecc71")]
     pub theme_color: Option<String>,
+    /// `updated_at` the client last read; the update fails with 409 if the project changed since
+    #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
+    pub expected_updated_at: Option<DateTime<Utc>>,
 }
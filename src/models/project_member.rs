@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Roles a collaborator can hold. Every member currently has the same rights:
+/// full access to tasks, progress and the plan, but not to the project itself.
+pub const PROJECT_MEMBER_ROLES: [&str; 1] = ["member"];
+
+pub const DEFAULT_PROJECT_MEMBER_ROLE: &str = "member";
+
+/// A user other than the owner who has been given access to a project.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, FromRow)]
+pub struct ProjectMember {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    #[schema(example = "member")]
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl crate::events::Loggable for ProjectMember {
+    fn entity_type() -> &'static str { "project_member" }
+    fn subject_id(&self) -> Uuid { self.project_id }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProjectMemberCreateRequest {
+    pub user_id: Uuid,
+    /// Defaults to `member`
+    pub role: Option<String>,
+}
+
+pub fn validate_member_role(role: &str) -> Result<(), AppError> {
+    if PROJECT_MEMBER_ROLES.contains(&role) {
+        Ok(())
+    } else {
+        Err(AppError::bad_request(format!(
+            "role must be one of: {}",
+            PROJECT_MEMBER_ROLES.join(", ")
+        )))
+    }
+}
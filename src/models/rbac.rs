@@ -2,9 +2,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::FromRow;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::authz::AuthzMode;
 use crate::events::{Loggable, Severity};
 
 // =============================================================================
@@ -25,6 +26,7 @@ impl Loggable for Role {
     fn entity_type() -> &'static str { "role" }
     fn subject_id(&self) -> Uuid { self.id }
     fn severity(&self) -> Severity { Severity::Critical }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Critical }
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -56,8 +58,15 @@ pub struct RoleCreateRequest {
     pub description: Option<String>,
 }
 
+/// Query options shared by `create_role` and `create_permission`.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CreateOptions {
+    /// Return the existing entity (200) instead of 409 when the name is taken
+    pub get_or_create: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
-#[allow(dead_code)]
 pub struct RoleUpdateRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -81,6 +90,7 @@ impl Loggable for Permission {
     fn entity_type() -> &'static str { "permission" }
     fn subject_id(&self) -> Uuid { self.id }
     fn severity(&self) -> Severity { Severity::Critical }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Critical }
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -127,6 +137,7 @@ impl Loggable for UserRole {
     fn entity_type() -> &'static str { "user_role" }
     fn subject_id(&self) -> Uuid { self.user_id }
     fn severity(&self) -> Severity { Severity::Critical }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Critical }
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -134,6 +145,26 @@ pub struct AssignRoleRequest {
     pub role_id: Uuid,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReplaceUserRolesRequest {
+    /// The user's complete role set; an empty list removes every role
+    pub role_ids: Vec<Uuid>,
+}
+
+/// A user's whole role set, logged when it is replaced in one request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserRoleSet {
+    pub user_id: Uuid,
+    pub role_ids: Vec<Uuid>,
+}
+
+impl Loggable for UserRoleSet {
+    fn entity_type() -> &'static str { "user_role" }
+    fn subject_id(&self) -> Uuid { self.user_id }
+    fn severity(&self) -> Severity { Severity::Critical }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Critical }
+}
+
 // =============================================================================
 // USER-PERMISSION DIRECT GRANT
 // =============================================================================
@@ -148,12 +179,16 @@ pub struct UserPermission {
     #[schema(value_type = Object)]
     pub scope: Value,
     pub created_at: DateTime<Utc>,
+    /// When set, the grant stops applying after this instant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl Loggable for UserPermission {
     fn entity_type() -> &'static str { "user_permission" }
     fn subject_id(&self) -> Uuid { self.user_id }
     fn severity(&self) -> Severity { Severity::Critical }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Critical }
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -163,6 +198,9 @@ pub struct GrantPermissionRequest {
     #[serde(default)]
     #[schema(value_type = Object)]
     pub scope: Option<Value>,
+    /// Optional expiry for temporary access; omitted means permanent
+    #[schema(format = DateTime, example = "2025-12-31T23:59:59Z")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 // =============================================================================
@@ -180,6 +218,7 @@ impl Loggable for RolePermission {
     fn entity_type() -> &'static str { "role_permission" }
     fn subject_id(&self) -> Uuid { self.role_id }
     fn severity(&self) -> Severity { Severity::Critical }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Critical }
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -187,18 +226,58 @@ pub struct AssignPermissionToRoleRequest {
     pub permission_id: Uuid,
 }
 
+// =============================================================================
+// AUTHZ MODE
+// =============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetAuthzModeRequest {
+    pub mode: AuthzMode,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuthzModeChange {
+    pub mode: AuthzMode,
+    pub previous: AuthzMode,
+    pub changed_by: Uuid,
+}
+
+impl Loggable for AuthzModeChange {
+    fn entity_type() -> &'static str { "authz_mode" }
+    fn subject_id(&self) -> Uuid { self.changed_by }
+    fn severity(&self) -> Severity { Severity::Critical }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Critical }
+}
+
+/// A permission check that failed while enforcement was not strict; strict mode would have returned 403
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthzDenial {
+    pub user_id: Uuid,
+    pub permission: String,
+    pub path: String,
+    /// The resolved `ResourceContext`, as scope JSON
+    pub context: Value,
+    pub mode: AuthzMode,
+}
+
+impl Loggable for AuthzDenial {
+    fn entity_type() -> &'static str { "authz" }
+    fn subject_id(&self) -> Uuid { self.user_id }
+    fn severity_for_action(&self, _action: &str) -> Severity { Severity::Important }
+}
+
 // =============================================================================
 // EFFECTIVE PERMISSIONS (computed)
 // =============================================================================
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct EffectivePermissions {
     pub user_id: Uuid,
     pub roles: Vec<String>,
     pub permissions: Vec<EffectivePermission>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct EffectivePermission {
     pub name: String,
     /// Source of the permission: "role" or "direct"
@@ -211,3 +290,19 @@ pub struct EffectivePermission {
     #[schema(value_type = Object)]
     pub scope: Option<Value>,
 }
+
+/// One user holding a permission through one source; see `GET /rbac/permissions/{id}/users`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PermissionHolder {
+    pub user_id: Uuid,
+    /// "direct" or "role"
+    #[schema(example = "role")]
+    pub source: String,
+    /// Roles carrying the permission, when source is "role"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub role_names: Vec<String>,
+    /// Scope of each unexpired direct grant, when source is "direct"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<Object>)]
+    pub scopes: Vec<Value>,
+}
@@ -1,29 +1,189 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
 use sqlx::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 
+/// The wire and storage spelling of every `TaskStatus`, in declaration order.
+pub const TASK_STATUSES: [&str; 5] = ["pending", "in_progress", "done", "blocked", "cancelled"];
+
+/// Where a task stands.
+///
+/// Stored as its snake_case name in `tasks.status`. Request bodies with any
+/// other value are rejected while deserializing (400), so an unknown status can
+/// never be written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    #[default]
+    Pending,
+    InProgress,
+    Done,
+    Blocked,
+    Cancelled,
+}
+
+impl TaskStatus {
+    pub const ALL: [TaskStatus; 5] =
+        [TaskStatus::Pending, TaskStatus::InProgress, TaskStatus::Done, TaskStatus::Blocked, TaskStatus::Cancelled];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Done => "done",
+            TaskStatus::Blocked => "blocked",
+            TaskStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Older spellings folded onto each status by the migration that introduced
+    /// `TaskStatus` (keep the two lists in sync).
+    pub const LEGACY_ALIASES: [(&'static str, TaskStatus); 14] = [
+        ("todo", TaskStatus::Pending),
+        ("to_do", TaskStatus::Pending),
+        ("open", TaskStatus::Pending),
+        ("new", TaskStatus::Pending),
+        ("not_started", TaskStatus::Pending),
+        ("doing", TaskStatus::InProgress),
+        ("started", TaskStatus::InProgress),
+        ("active", TaskStatus::InProgress),
+        ("completed", TaskStatus::Done),
+        ("complete", TaskStatus::Done),
+        ("finished", TaskStatus::Done),
+        ("closed", TaskStatus::Done),
+        ("on_hold", TaskStatus::Blocked),
+        ("canceled", TaskStatus::Cancelled),
+    ];
+
+    /// A stored value in a legacy spelling: case, surrounding whitespace and
+    /// `-`/space separators are ignored, as in the migration.
+    pub fn from_legacy(stored: &str) -> Option<TaskStatus> {
+        let normalized = stored.trim().to_lowercase().replace([' ', '-'], "_");
+        TaskStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == normalized)
+            .or_else(|| {
+                TaskStatus::LEGACY_ALIASES
+                    .into_iter()
+                    .find(|(alias, _)| *alias == normalized)
+                    .map(|(_, status)| status)
+            })
+    }
+
+    /// Done or cancelled: nothing left to do, so never overdue.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, TaskStatus::Done | TaskStatus::Cancelled)
+    }
+}
+
+impl std::fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TaskStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TaskStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| format!("invalid status '{}'; expected one of: {}", s, TASK_STATUSES.join(", ")))
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl sqlx::Type<Sqlite> for TaskStatus {
+    fn type_info() -> SqliteTypeInfo {
+        <str as sqlx::Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <str as sqlx::Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q> sqlx::Encode<'q, Sqlite> for TaskStatus {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        <&str as sqlx::Encode<'q, Sqlite>>::encode(self.as_str(), args)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Sqlite> for TaskStatus {
+    /// Values outside the enum can only come from writes that bypassed the API
+    /// (the migration that introduced `TaskStatus` folded older ones in). Legacy
+    /// aliases read as their canonical status; anything else reads as `pending`,
+    /// and is saved as such on the next edit, rather than failing every query
+    /// that touches the row.
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let stored = <&str as sqlx::Decode<'r, Sqlite>>::decode(value)?;
+        if let Ok(status) = stored.parse() {
+            return Ok(status);
+        }
+        Ok(TaskStatus::from_legacy(stored).unwrap_or_else(|| {
+            tracing::warn!(status = stored, "unknown task status in database; reading it as pending");
+            TaskStatus::Pending
+        }))
+    }
+}
+
+/// Longest tag accepted, in characters.
+pub const MAX_TAG_LEN: usize = 50;
+
+/// Trim and lowercase `tags`, rejecting empty or overlong ones, and drop duplicates.
+pub fn normalize_tags(tags: &[String]) -> Result<Vec<String>, AppError> {
+    let mut normalized = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Err(AppError::bad_request("tags must not be empty"));
+        }
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(AppError::bad_request(format!("tag '{}' is longer than {} characters", tag, MAX_TAG_LEN)));
+        }
+        normalized.push(tag);
+    }
+    normalized.sort();
+    normalized.dedup();
+    Ok(normalized)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Task {
     pub id: Uuid,
     pub project_id: Uuid,
     pub title: String,
-    pub status: String,
+    pub status: TaskStatus,
     pub due_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
     pub start_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-15T17:00:00Z")]
     pub end_date: Option<DateTime<Utc>>,
     pub duration_days: Option<i32>,
+    pub estimated_days: Option<i32>,
     pub assignee: Option<Uuid>,
     pub parent_id: Option<Uuid>,
     pub progress: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    /// Lowercase labels, sorted; bundles exported before tags existed omit the field
+    #[serde(default)]
+    #[schema(example = json!(["backend", "bug"]))]
+    pub tags: Vec<String>,
 }
 
 impl crate::events::Loggable for Task {
@@ -36,11 +196,12 @@ pub struct DbTask {
     pub id: Uuid,
     pub project_id: Uuid,
     pub title: String,
-    pub status: String,
+    pub status: TaskStatus,
     pub due_date: Option<DateTime<Utc>>,
     pub start_date: Option<DateTime<Utc>>,
     pub end_date: Option<DateTime<Utc>>,
     pub duration_days: Option<i32>,
+    pub estimated_days: Option<i32>,
     pub assignee: Option<Uuid>,
     pub parent_id: Option<Uuid>,
     pub progress: i32,
@@ -62,12 +223,15 @@ impl TryFrom<DbTask> for Task {
             start_date: value.start_date,
             end_date: value.end_date,
             duration_days: value.duration_days,
+            estimated_days: value.estimated_days,
             assignee: value.assignee,
             parent_id: value.parent_id,
             progress: value.progress,
             created_at: value.created_at,
             updated_at: value.updated_at,
             deleted_at: value.deleted_at,
+            // Not a column; loaded separately from task_tags
+            tags: Vec::new(),
         })
     }
 }
@@ -76,14 +240,17 @@ impl TryFrom<DbTask> for Task {
 pub struct TaskCreateRequest {
     #[schema(example = "Define launch checklist")]
     pub title: String,
-    #[schema(example = "pending")]
-    pub status: Option<String>,
+    /// Defaults to `pending`
+    pub status: Option<TaskStatus>,
     #[schema(format = DateTime, example = "2025-10-10T10:00:00Z")]
     pub due_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
     pub start_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-15T17:00:00Z")]
     pub end_date: Option<DateTime<Utc>>,
+    /// Estimated length in days; scheduling uses it when the task has no start/end dates
+    #[schema(example = 3)]
+    pub estimated_days: Option<i32>,
     pub assignee: Option<Uuid>,
     pub parent_id: Option<Uuid>,
     #[schema(example = 0)]
@@ -93,29 +260,57 @@ pub struct TaskCreateRequest {
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct TaskUpdateRequest {
     pub title: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<TaskStatus>,
     #[schema(format = DateTime, example = "2025-11-01T10:00:00Z")]
     pub due_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
     pub start_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-15T17:00:00Z")]
     pub end_date: Option<DateTime<Utc>>,
+    /// Estimated length in days; scheduling uses it when the task has no start/end dates
+    pub estimated_days: Option<i32>,
     pub assignee: Option<Uuid>,
     pub parent_id: Option<Uuid>,
     pub progress: Option<i32>,
+    /// `updated_at` the client last read; the update fails with 409 if the task changed since
+    #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
+    pub expected_updated_at: Option<DateTime<Utc>>,
+}
+
+/// Full task representation for `PUT`; optional fields left out are cleared.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TaskReplaceRequest {
+    pub title: String,
+    pub status: TaskStatus,
+    #[schema(format = DateTime, example = "2025-11-01T10:00:00Z")]
+    pub due_date: Option<DateTime<Utc>>,
+    #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
+    pub start_date: Option<DateTime<Utc>>,
+    #[schema(format = DateTime, example = "2025-10-15T17:00:00Z")]
+    pub end_date: Option<DateTime<Utc>>,
+    /// Estimated length in days; scheduling uses it when the task has no start/end dates
+    pub estimated_days: Option<i32>,
+    pub assignee: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    pub progress: i32,
+    /// `updated_at` the client last read; the update fails with 409 if the task changed since
+    #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
+    pub expected_updated_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct TaskBatchUpdateRequest {
     pub id: Uuid,
     pub title: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<TaskStatus>,
     #[schema(format = DateTime, example = "2025-11-01T10:00:00Z")]
     pub due_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-01T09:00:00Z")]
     pub start_date: Option<DateTime<Utc>>,
     #[schema(format = DateTime, example = "2025-10-15T17:00:00Z")]
     pub end_date: Option<DateTime<Utc>>,
+    /// Estimated length in days; scheduling uses it when the task has no start/end dates
+    pub estimated_days: Option<i32>,
     pub assignee: Option<Uuid>,
     pub parent_id: Option<Uuid>,
     pub progress: Option<i32>,
@@ -125,3 +320,17 @@ pub struct TaskBatchUpdateRequest {
 pub struct TaskBatchUpdatePayload {
     pub tasks: Vec<TaskBatchUpdateRequest>,
 }
+
+/// Tags to add to or remove from a task; matched case-insensitively.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TaskTagsRequest {
+    #[schema(example = json!(["frontend", "bug"]))]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TaskAssignRequest {
+    /// User to set as `assignee` on every listed task
+    pub user_id: Uuid,
+    pub task_ids: Vec<Uuid>,
+}
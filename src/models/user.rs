@@ -63,6 +63,22 @@ pub struct RegisterRequest {
     pub password: String,
 }
 
+/// Partial profile update for `PUT /auth/me`; omitted fields are left unchanged.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProfileUpdateRequest {
+    #[schema(example = "Ada King")]
+    pub name: Option<String>,
+    #[schema(example = "ada.king@example.com")]
+    pub email: Option<String>,
+}
+
+/// Confirmation body for `DELETE /auth/me`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AccountDeleteRequest {
+    #[schema(example = "S3cureP@ssw0rd")]
+    pub password: String,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginRequest {
     #[schema(example = "ada@example.com")]
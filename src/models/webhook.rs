@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::events::{EventFilter, FilterError};
+
+/// A registered webhook. The signing secret is write-only and never returned.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    /// Filter expression deciding which events are delivered
+    #[schema(example = "event_name LIKE 'task.*' AND severity >= important")]
+    pub filter: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebhookCreateRequest {
+    #[schema(example = "https://hooks.example.com/s-curve")]
+    pub url: String,
+    pub secret: String,
+    /// Filter expression over `event_name`, `severity`, `actor_id` and
+    /// `subject_id`; defaults to every event
+    #[schema(example = "event_name LIKE 'task.*' AND severity >= important")]
+    pub filter: Option<String>,
+    /// Shorthand for `event_name LIKE '<glob>'`; cannot be combined with `filter`
+    #[schema(example = "task.*")]
+    pub event_name_filter: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebhookUpdateRequest {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub filter: Option<String>,
+    pub event_name_filter: Option<String>,
+}
+
+/// The filter of a webhook registered without one: every event.
+pub const MATCH_ALL_FILTER: &str = "event_name LIKE '*'";
+
+/// Compile an event-name glob into an `EventFilter`.
+///
+/// Only name characters and wildcards are accepted so the glob can be embedded
+/// in a filter expression verbatim.
+pub fn event_name_filter(glob: &str) -> Result<EventFilter, FilterError> {
+    if glob.is_empty() || !glob.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '*' | '%')) {
+        return Err(FilterError::UnexpectedToken(glob.to_string()));
+    }
+    EventFilter::parse(&format!("event_name LIKE '{}'", glob))
+}
@@ -0,0 +1,154 @@
+//! Bulk export of the audit trail for log shippers.
+//!
+//! `GET /activity/export` writes `activity_log` as newline-delimited JSON, oldest
+//! first. Rows are read off a single cursor and pushed through a small bounded
+//! channel, so memory stays flat however large the log is and a slow client
+//! simply slows the reader down.
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::Row;
+use tokio::sync::mpsc;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::db::uuid_sql::case_uuid;
+use crate::errors::{AppError, AppResult};
+use crate::jwt::AuthUser;
+use crate::routes::admin::require_super_admin;
+
+/// Lines buffered ahead of a slow client.
+const EXPORT_CHANNEL_CAPACITY: usize = 64;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ActivityExportQuery {
+    /// Only entries that occurred at or after this instant
+    pub since: Option<DateTime<Utc>>,
+    /// Only entries that occurred strictly before this instant
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// One line of the export.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActivityExportLine {
+    pub id: Uuid,
+    pub event_name: String,
+    pub description: String,
+    pub actor_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+    pub severity: String,
+    /// The full serialized event
+    #[schema(value_type = Object)]
+    pub properties: serde_json::Value,
+}
+
+impl ActivityExportLine {
+    fn from_row(row: &SqliteRow) -> AppResult<Self> {
+        let id: String = row.try_get("id")?;
+        let actor_id: Option<String> = row.try_get("actor_id")?;
+        let subject_id: Option<String> = row.try_get("subject_id")?;
+        let properties: String = row.try_get("properties")?;
+        Ok(Self {
+            id: Uuid::parse_str(&id).map_err(|e| AppError::internal(format!("invalid uuid: {}", e)))?,
+            event_name: row.try_get("event_name")?,
+            description: row.try_get("description")?,
+            actor_id: actor_id.and_then(|s| Uuid::parse_str(&s).ok()),
+            subject_id: subject_id.and_then(|s| Uuid::parse_str(&s).ok()),
+            occurred_at: row.try_get("occurred_at")?,
+            severity: row.try_get("severity")?,
+            properties: serde_json::from_str(&properties).unwrap_or(serde_json::Value::Null),
+        })
+    }
+}
+
+/// Stream the activity log as NDJSON, one `ActivityExportLine` per line
+#[utoipa::path(
+    get,
+    path = "/activity/export",
+    tag = "Admin",
+    params(ActivityExportQuery),
+    responses(
+        (status = 200, description = "Newline-delimited ActivityExportLine objects ordered by occurred_at", content_type = "application/x-ndjson", body = ActivityExportLine),
+        (status = 400, description = "`since` is not before `until`"),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn export_activity_log(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<ActivityExportQuery>,
+) -> AppResult<Response> {
+    require_super_admin(&state, &auth).await?;
+    if let (Some(since), Some(until)) = (query.since, query.until) {
+        if since >= until {
+            return Err(AppError::bad_request("since must be before until"));
+        }
+    }
+
+    let mut clauses = vec!["1 = 1"];
+    if query.since.is_some() {
+        clauses.push("occurred_at >= ?");
+    }
+    if query.until.is_some() {
+        clauses.push("occurred_at < ?");
+    }
+    let sql = format!(
+        "SELECT {} , event_name, description, {} , {} , occurred_at, severity, properties FROM activity_log \
+         WHERE {} ORDER BY occurred_at ASC, rowid ASC",
+        case_uuid("id"),
+        case_uuid("actor_id"),
+        case_uuid("subject_id"),
+        clauses.join(" AND "),
+    );
+
+    let (tx, rx) = mpsc::channel::<Result<Bytes, std::io::Error>>(EXPORT_CHANNEL_CAPACITY);
+    let pool = state.pool.clone();
+
+    tokio::spawn(async move {
+        let mut q = sqlx::query(&sql);
+        if let Some(since) = query.since {
+            q = q.bind(since);
+        }
+        if let Some(until) = query.until {
+            q = q.bind(until);
+        }
+        let mut rows = q.fetch(&pool);
+
+        loop {
+            let line = match rows.try_next().await {
+                Ok(Some(row)) => ActivityExportLine::from_row(&row)
+                    .and_then(|line| serde_json::to_vec(&line).map_err(|e| AppError::internal(e.to_string()))),
+                Ok(None) => return,
+                Err(e) => Err(e.into()),
+            };
+            let mut bytes = match line {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    // Headers are already sent; aborting the body is the only way to signal failure
+                    tracing::error!(error = %e, "activity export failed mid-stream");
+                    let _ = tx.send(Err(std::io::Error::other(e.to_string()))).await;
+                    return;
+                }
+            };
+            bytes.push(b'\n');
+            if tx.send(Ok(Bytes::from(bytes))).await.is_err() {
+                // client went away
+                return;
+            }
+        }
+    });
+
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) });
+
+    Ok(([(header::CONTENT_TYPE, "application/x-ndjson")], Body::from_stream(stream)).into_response())
+}
@@ -0,0 +1,250 @@
+//! Operator endpoints for diagnosing the running service.
+//!
+//! Every route here requires the `super_admin` role.
+
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+use crate::app::AppState;
+use crate::authz::{AuthzMode, Principal};
+use crate::db::uuid_normalize::{self, TableNormalization};
+use crate::errors::{AppError, AppResult};
+use crate::events::{self, ChainVerification, RequestContext};
+use crate::extract::AppJson;
+use crate::jwt::AuthUser;
+use crate::models::rbac::{AuthzModeChange, SetAuthzModeRequest};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/db/stats", get(get_db_stats))
+        .route("/events/verify", get(verify_event_store))
+        .route("/events/replay", post(replay_activity_log))
+        .route("/normalize-uuids", post(normalize_uuids))
+        .route("/authz-mode", get(get_authz_mode).put(set_authz_mode))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PoolStats {
+    /// Configured `max_connections`
+    pub max_connections: u32,
+    /// Connections currently open (idle + in use)
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SqliteStats {
+    pub page_size: i64,
+    pub page_count: i64,
+    pub freelist_count: i64,
+    /// `page_size * page_count`
+    pub database_bytes: i64,
+    pub journal_mode: String,
+    /// Size of the `-wal` file, when the database is file-backed and in WAL mode
+    pub wal_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DbStatsResponse {
+    pub pool: PoolStats,
+    pub sqlite: SqliteStats,
+}
+
+/// Pool and SQLite storage metrics, for diagnosing connection exhaustion
+#[utoipa::path(
+    get,
+    path = "/admin/db/stats",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Database statistics", body = DbStatsResponse),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn get_db_stats(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<DbStatsResponse>> {
+    require_super_admin(&state, &auth).await?;
+
+    // Sample the pool before we take a connection of our own for the PRAGMAs
+    let size = state.pool.size();
+    let idle = u32::try_from(state.pool.num_idle()).unwrap_or(u32::MAX);
+    let pool = PoolStats {
+        max_connections: state.pool.options().get_max_connections(),
+        size,
+        idle,
+        in_use: size.saturating_sub(idle),
+    };
+
+    let page_size: i64 = sqlx::query_scalar("PRAGMA page_size").fetch_one(&state.pool).await?;
+    let page_count: i64 = sqlx::query_scalar("PRAGMA page_count").fetch_one(&state.pool).await?;
+    let freelist_count: i64 = sqlx::query_scalar("PRAGMA freelist_count").fetch_one(&state.pool).await?;
+    let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode").fetch_one(&state.pool).await?;
+
+    // `file` is empty for in-memory databases
+    let main_file: Option<String> = sqlx::query("PRAGMA database_list")
+        .fetch_all(&state.pool)
+        .await?
+        .iter()
+        .find(|row| row.get::<String, _>("name") == "main")
+        .map(|row| row.get::<String, _>("file"))
+        .filter(|file| !file.is_empty());
+    let wal_bytes = main_file
+        .and_then(|file| std::fs::metadata(format!("{}-wal", file)).ok())
+        .map(|meta| meta.len());
+
+    Ok(Json(DbStatsResponse {
+        pool,
+        sqlite: SqliteStats {
+            page_size,
+            page_count,
+            freelist_count,
+            database_bytes: page_size * page_count,
+            journal_mode,
+            wal_bytes,
+        },
+    }))
+}
+
+/// Walk the event-store hash chain and report the first broken link, if any
+#[utoipa::path(
+    get,
+    path = "/admin/events/verify",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Hash chain verification result", body = ChainVerification),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn verify_event_store(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<ChainVerification>> {
+    require_super_admin(&state, &auth).await?;
+    Ok(Json(events::verify_event_chain(&state.pool).await?))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReplayResponse {
+    /// Rows written to `activity_log`
+    pub replayed: u64,
+}
+
+/// Rebuild the `activity_log` projection from the event store
+#[utoipa::path(
+    post,
+    path = "/admin/events/replay",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Projection rebuilt", body = ReplayResponse),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn replay_activity_log(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<ReplayResponse>> {
+    require_super_admin(&state, &auth).await?;
+    let replayed = events::replay_activity_log(&state.pool).await?;
+    tracing::info!(replayed, "activity_log rebuilt from event_store");
+    Ok(Json(ReplayResponse { replayed }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NormalizeUuidsResponse {
+    pub tables: Vec<TableNormalization>,
+}
+
+/// Rewrite text-stored UUIDs in the core tables as blobs so the typed fast paths always match
+#[utoipa::path(
+    post,
+    path = "/admin/normalize-uuids",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Per-table row counts and converted values", body = NormalizeUuidsResponse),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn normalize_uuids(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<NormalizeUuidsResponse>> {
+    require_super_admin(&state, &auth).await?;
+    let tables = uuid_normalize::normalize_uuid_storage(&state.pool).await?;
+    let converted: u64 = tables.iter().map(|t| t.converted).sum();
+    tracing::info!(converted, "uuid storage normalized");
+    Ok(Json(NormalizeUuidsResponse { tables }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthzModeResponse {
+    pub mode: AuthzMode,
+}
+
+/// Current authorization enforcement mode
+#[utoipa::path(
+    get,
+    path = "/admin/authz-mode",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Live enforcement mode", body = AuthzModeResponse),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn get_authz_mode(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<AuthzModeResponse>> {
+    require_super_admin(&state, &auth).await?;
+    Ok(Json(AuthzModeResponse { mode: state.authz_mode() }))
+}
+
+/// Change the authorization enforcement mode without a restart
+///
+/// Takes effect on the next request. Every change is recorded as a Critical
+/// `authz_mode.updated` event.
+#[utoipa::path(
+    put,
+    path = "/admin/authz-mode",
+    tag = "Admin",
+    request_body = SetAuthzModeRequest,
+    responses(
+        (status = 200, description = "Previous and new mode", body = AuthzModeChange),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn set_authz_mode(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    AppJson(payload): AppJson<SetAuthzModeRequest>,
+) -> AppResult<Json<AuthzModeChange>> {
+    require_super_admin(&state, &auth).await?;
+    let previous = state.set_authz_mode(payload.mode);
+    let change = AuthzModeChange { mode: payload.mode, previous, changed_by: auth.user_id };
+
+    if previous != payload.mode {
+        tracing::warn!(from = previous.as_str(), to = payload.mode.as_str(), user_id = %auth.user_id, "authz mode changed");
+        events::log_activity_with_context(
+            &state.event_bus,
+            "updated",
+            Some(auth.user_id),
+            &change,
+            None,
+            Some(RequestContext::from_headers(&headers)),
+        );
+    }
+    Ok(Json(change))
+}
+
+pub(crate) async fn require_super_admin(state: &AppState, auth: &AuthUser) -> AppResult<()> {
+    // A role claim in the token is enough to allow; its absence still needs the DB
+    if auth.has_role_claim(crate::authz::roles::SUPER_ADMIN) {
+        return Ok(());
+    }
+    let principal = Principal::load(&state.pool, auth.user_id).await?;
+    if principal.is_super_admin() {
+        Ok(())
+    } else {
+        Err(AppError::forbidden("super_admin role required"))
+    }
+}
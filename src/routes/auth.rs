@@ -6,10 +6,13 @@ use sqlx::SqlitePool;
 
 
 use crate::app::AppState;
+use crate::authz::{roles, Principal};
 use crate::errors::{AppError, AppResult};
-use crate::jwt::AuthUser;
-use crate::models::user::{AuthResponse, DbUser, LoginRequest, RegisterRequest, User};
-use crate::utils::{hash_password, utc_now, verify_password};
+use crate::extract::AppJson;
+use crate::jwt::{role_claims_enabled, AuthUser};
+use crate::models::rbac::UserRole;
+use crate::models::user::{AccountDeleteRequest, AuthResponse, DbUser, LoginRequest, ProfileUpdateRequest, RegisterRequest, User};
+use crate::utils::{hash_password, utc_now, validate_email, validate_password, verify_password};
 use crate::db::row_parsers;
 
 #[derive(Debug, Serialize)]
@@ -24,15 +27,17 @@ pub struct MessageResponse {
     request_body = RegisterRequest,
     responses(
         (status = 201, description = "User registered", body = AuthResponse),
+        (status = 400, description = "Password too short or too common"),
         (status = 409, description = "Email already in use")
     )
 )]
 pub async fn register(
     State(state): State<AppState>,
     headers: axum::http::HeaderMap,
-    Json(payload): Json<RegisterRequest>,
+    AppJson(payload): AppJson<RegisterRequest>,
 ) -> AppResult<(StatusCode, Json<AuthResponse>)> {
-    ensure_email_available(&state.pool, &payload.email).await?;
+    validate_password(&payload.password)?;
+    ensure_email_available(&state.pool, &payload.email, None).await?;
 
     let password_hash = hash_password(&payload.password)?;
     let now = utc_now();
@@ -54,7 +59,6 @@ pub async fn register(
 
     let db_user = fetch_user_by_id(&state.pool, user_id).await?;
     let user: User = db_user.try_into()?;
-    let token = state.jwt.encode(user.id)?;
 
     // Log activity with request context
     let ctx = crate::events::RequestContext::from_headers(&headers);
@@ -64,9 +68,27 @@ pub async fn register(
         Some(user.id),
         &user,
         None,
-        Some(ctx),
+        Some(ctx.clone()),
     );
 
+    if bootstrap_super_admin_enabled() {
+        if let Some(assignment) = bootstrap_super_admin(&state.pool, user.id).await? {
+            state.invalidate_permissions(user.id);
+            tracing::warn!(user_id = %user.id, "first registered user granted super_admin (BOOTSTRAP_SUPER_ADMIN)");
+            crate::events::log_activity_with_context(
+                &state.event_bus,
+                "assigned",
+                Some(user.id),
+                &assignment,
+                None,
+                Some(ctx),
+            );
+        }
+    }
+
+    // Issued after the bootstrap grant so the roles claim includes it
+    let token = issue_token(&state, user.id).await?;
+
     Ok((StatusCode::CREATED, Json(AuthResponse { token, user })))
 }
 
@@ -82,7 +104,7 @@ pub async fn register(
 )]
 pub async fn login(
     State(state): State<AppState>,
-    Json(payload): Json<LoginRequest>,
+    AppJson(payload): AppJson<LoginRequest>,
 ) -> AppResult<Json<AuthResponse>> {
     // Try typed mapping first
     let simple = sqlx::query_as::<_, DbUser>(
@@ -117,7 +139,7 @@ pub async fn login(
         return Err(AppError::unauthorized("invalid credentials"));
     }
 
-    let token = state.jwt.encode(db_user.id)?;
+    let token = issue_token(&state, db_user.id).await?;
     let user: User = db_user.try_into()?;
 
     Ok(Json(AuthResponse { token, user }))
@@ -135,6 +157,147 @@ pub async fn me(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json
     Ok(Json(user))
 }
 
+#[utoipa::path(
+    put,
+    path = "/auth/me",
+    tag = "Auth",
+    request_body = ProfileUpdateRequest,
+    responses(
+        (status = 200, description = "Updated profile", body = User),
+        (status = 400, description = "Empty name or malformed email"),
+        (status = 409, description = "Email already in use by another user")
+    )
+)]
+pub async fn update_me(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    AppJson(payload): AppJson<ProfileUpdateRequest>,
+) -> AppResult<Json<User>> {
+    let current: User = fetch_user_by_id(&state.pool, auth.user_id).await?.try_into()?;
+
+    let name = match payload.name {
+        Some(name) if name.trim().is_empty() => return Err(AppError::bad_request("name cannot be empty")),
+        Some(name) => name,
+        None => current.name.clone(),
+    };
+    let email = match payload.email {
+        Some(email) => {
+            let email = email.trim().to_string();
+            validate_email(&email)?;
+            ensure_email_available(&state.pool, &email, Some(auth.user_id)).await?;
+            email
+        }
+        None => current.email.clone(),
+    };
+
+    if name == current.name && email == current.email {
+        return Ok(Json(current));
+    }
+
+    let sql = format!(
+        "UPDATE users SET name = ?, email = ?, updated_at = ? WHERE {}",
+        crate::db::uuid_sql::match_uuid_clause("id")
+    );
+    sqlx::query(&sql)
+        .bind(&name)
+        .bind(&email)
+        .bind(utc_now())
+        .bind(auth.user_id.to_string())
+        .bind(auth.user_id.to_string())
+        .execute(&state.pool)
+        .await?;
+
+    let user: User = fetch_user_by_id(&state.pool, auth.user_id).await?.try_into()?;
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "updated", Some(auth.user_id), &user, Some(&current), Some(ctx));
+
+    Ok(Json(user))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/auth/me",
+    tag = "Auth",
+    request_body = AccountDeleteRequest,
+    responses(
+        (status = 204, description = "Account and owned projects deleted"),
+        (status = 401, description = "Password does not match")
+    )
+)]
+pub async fn delete_me(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    AppJson(payload): AppJson<AccountDeleteRequest>,
+) -> AppResult<StatusCode> {
+    let db_user = fetch_user_by_id(&state.pool, auth.user_id).await?;
+    if !verify_password(&payload.password, &db_user.password_hash)? {
+        return Err(AppError::unauthorized("invalid password"));
+    }
+    let user: User = db_user.try_into()?;
+
+    // Soft-delete the account together with everything it owns; user ids are
+    // matched in either stored form
+    let now = utc_now();
+    let user_id = user.id.to_string();
+    let owned_by_user = crate::db::uuid_sql::match_uuid_clause("user_id");
+    let owned = format!("SELECT id FROM projects WHERE {owned_by_user} AND deleted_at IS NULL");
+    let mut tx = state.pool.begin().await?;
+    sqlx::query(&format!(
+        "UPDATE task_progress SET deleted_at = ?, updated_at = ? WHERE deleted_at IS NULL AND project_id IN ({owned})"
+    ))
+    .bind(now)
+    .bind(now)
+    .bind(&user_id)
+    .bind(&user_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(&format!(
+        "UPDATE tasks SET deleted_at = ?, updated_at = ? WHERE deleted_at IS NULL AND project_id IN ({owned})"
+    ))
+    .bind(now)
+    .bind(now)
+    .bind(&user_id)
+    .bind(&user_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(&format!(
+        "UPDATE projects SET deleted_at = ?, updated_at = ? WHERE {owned_by_user} AND deleted_at IS NULL"
+    ))
+    .bind(now)
+    .bind(now)
+    .bind(&user_id)
+    .bind(&user_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(&format!("DELETE FROM project_members WHERE {owned_by_user}"))
+        .bind(&user_id)
+        .bind(&user_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(&format!(
+        "UPDATE users SET deleted_at = ?, updated_at = ? WHERE {}",
+        crate::db::uuid_sql::match_uuid_clause("id")
+    ))
+    .bind(now)
+    .bind(now)
+    .bind(&user_id)
+    .bind(&user_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    state.revoke_user(user.id);
+    state.invalidate_permissions(user.id);
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "deleted", Some(user.id), &user, None, Some(ctx));
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[utoipa::path(
     post,
     path = "/auth/logout",
@@ -147,11 +310,68 @@ pub async fn logout(_auth: AuthUser) -> AppResult<Json<MessageResponse>> {
     }))
 }
 
-async fn ensure_email_available(pool: &SqlitePool, email: &str) -> AppResult<()> {
-    let count: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM users WHERE email = ? AND deleted_at IS NULL")
-        .bind(email)
-        .fetch_one(pool)
+/// Issue a JWT for `user_id`, embedding their role names when `JWT_ROLE_CLAIMS` is on.
+pub(crate) async fn issue_token(state: &AppState, user_id: uuid::Uuid) -> AppResult<String> {
+    if !role_claims_enabled() {
+        return state.jwt.encode(user_id);
+    }
+    let principal = Principal::load(&state.pool, user_id).await?;
+    state.jwt.encode_with_roles(user_id, principal.roles.into_iter().collect())
+}
+
+fn bootstrap_super_admin_enabled() -> bool {
+    std::env::var("BOOTSTRAP_SUPER_ADMIN")
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Give the very first user the `super_admin` role so a fresh deployment has someone
+/// who can manage RBAC. Returns `None` when other users already exist or the role is missing.
+async fn bootstrap_super_admin(pool: &SqlitePool, user_id: uuid::Uuid) -> AppResult<Option<UserRole>> {
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users").fetch_one(pool).await?;
+    if users != 1 {
+        return Ok(None);
+    }
+
+    let role_id: Option<String> = sqlx::query_scalar("SELECT id FROM roles WHERE name = ?")
+        .bind(roles::SUPER_ADMIN)
+        .fetch_optional(pool)
         .await?;
+    let Some(role_id) = role_id else {
+        return Ok(None);
+    };
+    let role_id = uuid::Uuid::parse_str(&role_id)
+        .map_err(|e| AppError::internal(format!("invalid role id: {}", e)))?;
+
+    let now = utc_now();
+    // Same write as the RBAC endpoints, so they can list, replace and revoke this role
+    crate::routes::rbac::insert_user_role(&mut *pool.acquire().await?, user_id, role_id, now).await?;
+
+    Ok(Some(UserRole { user_id, role_id, created_at: now }))
+}
+
+/// Fail with 409 if another active user has `email` (case-insensitive); `exclude` skips the caller's own row.
+async fn ensure_email_available(pool: &SqlitePool, email: &str, exclude: Option<uuid::Uuid>) -> AppResult<()> {
+    let count: i64 = match exclude {
+        None => {
+            sqlx::query_scalar("SELECT COUNT(1) FROM users WHERE lower(email) = lower(?) AND deleted_at IS NULL")
+                .bind(email)
+                .fetch_one(pool)
+                .await?
+        }
+        Some(user_id) => {
+            let sql = format!(
+                "SELECT COUNT(1) FROM users WHERE lower(email) = lower(?) AND deleted_at IS NULL AND NOT {}",
+                crate::db::uuid_sql::match_uuid_clause("id")
+            );
+            sqlx::query_scalar(&sql)
+                .bind(email)
+                .bind(user_id.to_string())
+                .bind(user_id.to_string())
+                .fetch_one(pool)
+                .await?
+        }
+    };
 
     if count > 0 {
         return Err(AppError::conflict("email already in use"));
@@ -160,7 +380,7 @@ async fn ensure_email_available(pool: &SqlitePool, email: &str) -> AppResult<()>
     Ok(())
 }
 
-async fn fetch_user_by_id(pool: &SqlitePool, user_id: uuid::Uuid) -> AppResult<DbUser> {
+pub(crate) async fn fetch_user_by_id(pool: &SqlitePool, user_id: uuid::Uuid) -> AppResult<DbUser> {
     let simple = sqlx::query_as::<_, DbUser>(
         "SELECT id, name, email, password_hash, provider, provider_id, created_at, updated_at, deleted_at FROM users WHERE id = ? AND deleted_at IS NULL",
     )
@@ -0,0 +1,160 @@
+//! iCalendar (RFC 5545) export of a project's dated tasks.
+//!
+//! Each task with dates becomes one VEVENT:
+//! - `start_date` and `end_date` on the same UTC day (a milestone) or only one
+//!   date known (e.g. just `due_date`): an all-day event on that day;
+//! - both dates at midnight (how task dates are normally stored): an all-day
+//!   event spanning start through end inclusive;
+//! - otherwise a timed event from `start_date` to `end_date`.
+//!
+//! Tasks without any date are left out.
+
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::errors::AppResult;
+use crate::jwt::AuthUser;
+use crate::models::task::Task;
+use crate::routes::projects::fetch_project;
+use crate::routes::tasks::fetch_project_tasks;
+use crate::utils::utc_now;
+
+const PRODID: &str = "-//s-curve//project tasks//EN";
+/// Content lines longer than this many octets are folded (RFC 5545 §3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+enum EventTime {
+    /// First and last day, inclusive
+    AllDay(NaiveDate, NaiveDate),
+    Timed(DateTime<Utc>, DateTime<Utc>),
+}
+
+fn is_midnight(value: DateTime<Utc>) -> bool {
+    value.time() == NaiveTime::MIN
+}
+
+fn event_time(task: &Task) -> Option<EventTime> {
+    let single_day = |value: DateTime<Utc>| EventTime::AllDay(value.date_naive(), value.date_naive());
+
+    match (task.start_date, task.end_date, task.due_date) {
+        (Some(start), Some(end), _) if start.date_naive() == end.date_naive() => Some(single_day(start)),
+        (Some(start), Some(end), _) if end < start => Some(single_day(start)),
+        (Some(start), Some(end), _) if is_midnight(start) && is_midnight(end) => {
+            Some(EventTime::AllDay(start.date_naive(), end.date_naive()))
+        }
+        (Some(start), Some(end), _) => Some(EventTime::Timed(start, end)),
+        (_, _, Some(due)) => Some(single_day(due)),
+        (Some(date), None, None) | (None, Some(date), None) => Some(single_day(date)),
+        (None, None, None) => None,
+    }
+}
+
+fn format_utc(value: DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn format_date(value: NaiveDate) -> String {
+    value.format("%Y%m%d").to_string()
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append `line` terminated by CRLF, folding it so no physical line exceeds 75 octets.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // the leading space counts towards the continuation line
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+fn render_calendar(calendar_name: &str, tasks: &[Task], stamp: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, &format!("PRODID:{}", PRODID));
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(calendar_name)));
+
+    for task in tasks {
+        let Some(time) = event_time(task) else {
+            continue;
+        };
+
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}@s-curve", task.id));
+        push_line(&mut out, &format!("DTSTAMP:{}", format_utc(stamp)));
+        push_line(&mut out, &format!("LAST-MODIFIED:{}", format_utc(task.updated_at)));
+        match time {
+            EventTime::AllDay(first, last) => {
+                push_line(&mut out, &format!("DTSTART;VALUE=DATE:{}", format_date(first)));
+                // DTEND is exclusive, so the event ends the day after its last day
+                push_line(&mut out, &format!("DTEND;VALUE=DATE:{}", format_date(last + Duration::days(1))));
+            }
+            EventTime::Timed(start, end) => {
+                push_line(&mut out, &format!("DTSTART:{}", format_utc(start)));
+                push_line(&mut out, &format!("DTEND:{}", format_utc(end)));
+            }
+        }
+        push_line(&mut out, &format!("SUMMARY:{}", escape_text(&task.title)));
+        push_line(&mut out, &format!("DESCRIPTION:{}", escape_text(&format!("Status: {}, progress: {}%", task.status, task.progress))));
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/tasks.ics",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "RFC 5545 calendar with one event per dated task", content_type = "text/calendar", body = String),
+        (status = 404, description = "Project not found"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn export_project_calendar(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(project_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let project = fetch_project(&state.pool, auth.user_id, project_id).await?;
+    let tasks = fetch_project_tasks(&state.pool, project_id).await?;
+
+    let body = render_calendar(&project.name, &tasks, utc_now());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"project-{}.ics\"", project_id)),
+        ],
+        body,
+    ))
+}
@@ -0,0 +1,47 @@
+//! Weak ETags for polled GET endpoints.
+//!
+//! The tag is derived from the number of returned rows and their newest
+//! `updated_at`, so any edit, insert or removal changes it without hashing the
+//! serialized body.
+
+use axum::http::header::{ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// `W/"<count>-<newest updated_at in µs, hex>"` for the given rows.
+pub fn weak_etag<'a>(updated_at: impl IntoIterator<Item = &'a DateTime<Utc>>) -> String {
+    let (count, newest) = updated_at
+        .into_iter()
+        .fold((0usize, i64::MIN), |(count, newest), at| (count + 1, newest.max(at.timestamp_micros())));
+    let newest = if count == 0 { 0 } else { newest };
+    format!("W/\"{}-{:x}\"", count, newest)
+}
+
+/// Whether `If-None-Match` lists `etag` (weak comparison, `*` matches anything).
+fn matches_if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let wanted = strip(etag);
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || strip(tag) == wanted)
+}
+
+/// `304 Not Modified` when the client already holds `etag`, otherwise `body` as
+/// JSON. Both carry the `ETag` header.
+pub fn conditional_json<T: Serialize>(headers: &HeaderMap, etag: String, body: T) -> Response {
+    let mut response = if matches_if_none_match(headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        Json(body).into_response()
+    };
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response.headers_mut().insert(ETAG, value);
+    }
+    response
+}
@@ -0,0 +1,448 @@
+//! Portable project bundles.
+//!
+//! A bundle is a project together with everything hanging off it (tasks,
+//! dependencies, plan points and progress entries). `GET /me/export` streams one
+//! bundle per project so exporting a large account never holds the whole
+//! document in memory.
+//!
+//! `GET /projects/{id}/export` returns a single bundle and `POST /projects/import`
+//! recreates one under the caller. Imports get fresh UUIDs throughout; parent
+//! links, dependency endpoints and progress entries are rewritten through an
+//! old→new id map, and the whole bundle is written in one transaction; task
+//! tags travel with their tasks.
+//! `POST /projects/{id}/duplicate` runs an existing project through the same
+//! path without its progress entries.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::body::{Body, Bytes};
+use axum::extract::{FromRequest, Path, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tokio::sync::mpsc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::errors::{AppError, AppResult};
+use crate::extract::AppJson;
+use crate::jwt::AuthUser;
+use crate::models::dependency::TaskDependency;
+use crate::models::progress::Progress;
+use crate::models::project::{Project, ProjectDuplicateRequest};
+use crate::models::project_plan::ProjectPlanPoint;
+use crate::models::task::{normalize_tags, Task};
+use crate::routes::progress::fetch_project_progress;
+use crate::routes::projects::{fetch_plan_points, fetch_project, fetch_user_projects};
+use crate::routes::tasks::{fetch_project_dependencies, fetch_project_tasks};
+use crate::utils::utc_now;
+
+/// Bundles buffered between the database reader and the response writer.
+const EXPORT_CHANNEL_CAPACITY: usize = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProjectBundle {
+    pub project: Project,
+    pub tasks: Vec<Task>,
+    pub dependencies: Vec<TaskDependency>,
+    pub plan: Vec<ProjectPlanPoint>,
+    pub progress: Vec<Progress>,
+}
+
+/// Shape of the `GET /me/export` document (documentation only; the body is streamed).
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserExport {
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub projects: Vec<ProjectBundle>,
+}
+
+/// Load everything belonging to `project` (no ownership check).
+pub(crate) async fn build_project_bundle(pool: &SqlitePool, project: Project) -> AppResult<ProjectBundle> {
+    let tasks = fetch_project_tasks(pool, project.id).await?;
+    let dependencies = fetch_project_dependencies(pool, project.id).await?;
+    let plan = fetch_plan_points(pool, project.id).await?;
+    let progress = fetch_project_progress(pool, project.id).await?;
+
+    Ok(ProjectBundle { project, tasks, dependencies, plan, progress })
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/export",
+    tag = "Projects",
+    responses((status = 200, description = "All of the caller's projects with nested data", body = UserExport))
+)]
+pub async fn export_my_projects(State(state): State<AppState>, auth: AuthUser) -> AppResult<Response> {
+    // Resolve the project list up front so auth/database failures still produce a normal error response
+    let projects = fetch_user_projects(&state.pool, auth.user_id, false).await?;
+
+    let (tx, rx) = mpsc::channel::<Result<Bytes, std::io::Error>>(EXPORT_CHANNEL_CAPACITY);
+    let pool = state.pool.clone();
+
+    tokio::spawn(async move {
+        let head = format!("{{\"exported_at\":{},\"projects\":[", serde_json::json!(utc_now()));
+        if tx.send(Ok(Bytes::from(head))).await.is_err() {
+            return;
+        }
+
+        for (index, project) in projects.into_iter().enumerate() {
+            let project_id = project.id;
+            let chunk = build_project_bundle(&pool, project)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|bundle| serde_json::to_vec(&bundle).map_err(|e| e.to_string()));
+
+            let mut bytes = match chunk {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    // Headers are already sent; aborting the body is the only way to signal failure
+                    tracing::error!(%project_id, error = %e, "export failed mid-stream");
+                    let _ = tx.send(Err(std::io::Error::other(e))).await;
+                    return;
+                }
+            };
+            if index > 0 {
+                bytes.insert(0, b',');
+            }
+            if tx.send(Ok(Bytes::from(bytes))).await.is_err() {
+                // client went away
+                return;
+            }
+        }
+
+        let _ = tx.send(Ok(Bytes::from_static(b"]}"))).await;
+    });
+
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) });
+
+    Ok(([(header::CONTENT_TYPE, "application/json")], Body::from_stream(stream)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/export",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id")),
+    responses(
+        (status = 200, description = "Project with tasks, dependencies, plan and progress", body = ProjectBundle),
+        (status = 404, description = "Project not found"),
+    )
+)]
+pub async fn export_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(project_id): Path<Uuid>,
+) -> AppResult<Json<ProjectBundle>> {
+    let project: Project = fetch_project(&state.pool, auth.user_id, project_id).await?.try_into()?;
+    Ok(Json(build_project_bundle(&state.pool, project).await?))
+}
+
+/// Reject bundles whose internal references do not line up, before anything is written.
+fn validate_bundle(bundle: &ProjectBundle) -> AppResult<()> {
+    if bundle.project.name.trim().is_empty() {
+        return Err(AppError::bad_request("project.name must not be empty"));
+    }
+
+    let mut task_ids = HashSet::with_capacity(bundle.tasks.len());
+    for task in &bundle.tasks {
+        if !task_ids.insert(task.id) {
+            return Err(AppError::bad_request(format!("duplicate task id {}", task.id)));
+        }
+        if !(0..=100).contains(&task.progress) {
+            return Err(AppError::bad_request(format!("task {}: progress must be between 0 and 100", task.id)));
+        }
+        normalize_tags(&task.tags)?;
+    }
+
+    let parents: HashMap<Uuid, Uuid> = bundle.tasks.iter().filter_map(|t| t.parent_id.map(|p| (t.id, p))).collect();
+    for (&task_id, &parent_id) in &parents {
+        if !task_ids.contains(&parent_id) {
+            return Err(AppError::bad_request(format!("task {}: parent {} is not in the bundle", task_id, parent_id)));
+        }
+        // Walk up the chain; revisiting a task means the hierarchy loops
+        let mut seen = HashSet::from([task_id]);
+        let mut current = parent_id;
+        while let Some(&next) = parents.get(&current) {
+            if !seen.insert(current) {
+                return Err(AppError::bad_request(format!("task {}: parent chain forms a cycle", task_id)));
+            }
+            current = next;
+        }
+    }
+
+    let mut edges = HashSet::with_capacity(bundle.dependencies.len());
+    let mut adjacency: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let mut in_degree: HashMap<Uuid, usize> = task_ids.iter().map(|&id| (id, 0)).collect();
+    for dep in &bundle.dependencies {
+        if !task_ids.contains(&dep.source_task_id) || !task_ids.contains(&dep.target_task_id) {
+            return Err(AppError::bad_request(format!("dependency {} references a task outside the bundle", dep.id)));
+        }
+        if dep.source_task_id == dep.target_task_id {
+            return Err(AppError::bad_request(format!("dependency {} links a task to itself", dep.id)));
+        }
+        if !edges.insert((dep.source_task_id, dep.target_task_id)) {
+            return Err(AppError::bad_request(format!("dependency {} duplicates another dependency", dep.id)));
+        }
+        adjacency.entry(dep.source_task_id).or_default().push(dep.target_task_id);
+        *in_degree.entry(dep.target_task_id).or_default() += 1;
+    }
+
+    // Kahn's algorithm, as in `validate_import_edges`: tasks left unvisited sit on a cycle
+    let mut queue: Vec<Uuid> = in_degree.iter().filter(|&(_, &n)| n == 0).map(|(&id, _)| id).collect();
+    let mut visited = 0;
+    while let Some(node) = queue.pop() {
+        visited += 1;
+        for next in adjacency.get(&node).into_iter().flatten() {
+            if let Some(degree) = in_degree.get_mut(next) {
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push(*next);
+                }
+            }
+        }
+    }
+    if visited != task_ids.len() {
+        return Err(AppError::bad_request("Cycle detected: bundle dependencies are circular"));
+    }
+
+    for entry in &bundle.progress {
+        if !task_ids.contains(&entry.task_id) {
+            return Err(AppError::bad_request(format!("progress {} references a task outside the bundle", entry.id)));
+        }
+        if !(0..=100).contains(&entry.progress) {
+            return Err(AppError::bad_request(format!("progress {}: progress must be between 0 and 100", entry.id)));
+        }
+    }
+
+    for point in &bundle.plan {
+        if !(0..=100).contains(&point.planned_progress) {
+            return Err(AppError::bad_request(format!("plan point {}: planned_progress must be between 0 and 100", point.id)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Drop links to tasks that are not in the bundle.
+///
+/// `fetch_project_tasks` skips trashed tasks, but soft deletion leaves their
+/// dependency rows and their subtasks' `parent_id` in place; a copy keeps the
+/// live tasks and forgets those links (orphaned subtasks become top level).
+fn drop_dangling_references(bundle: &mut ProjectBundle) {
+    let task_ids: HashSet<Uuid> = bundle.tasks.iter().map(|t| t.id).collect();
+    for task in &mut bundle.tasks {
+        if task.parent_id.is_some_and(|parent_id| !task_ids.contains(&parent_id)) {
+            task.parent_id = None;
+        }
+    }
+    bundle
+        .dependencies
+        .retain(|dep| task_ids.contains(&dep.source_task_id) && task_ids.contains(&dep.target_task_id));
+    bundle.progress.retain(|entry| task_ids.contains(&entry.task_id));
+}
+
+/// Write `bundle` as a new project owned by `owner_id`, remapping every id.
+///
+/// Returns the new project id. The caller owns the transaction and must have
+/// validated the bundle.
+async fn insert_bundle(
+    conn: &mut sqlx::SqliteConnection,
+    owner_id: Uuid,
+    bundle: &ProjectBundle,
+) -> AppResult<Uuid> {
+    let now = utc_now();
+    let project_id = Uuid::new_v4();
+    let task_map: HashMap<Uuid, Uuid> = bundle.tasks.iter().map(|t| (t.id, Uuid::new_v4())).collect();
+
+    sqlx::query(
+        "INSERT INTO projects (id, user_id, name, description, theme_color, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(project_id)
+    .bind(owner_id)
+    .bind(&bundle.project.name)
+    .bind(&bundle.project.description)
+    .bind(&bundle.project.theme_color)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+
+    // Parents may appear after their children, so link them once every task exists
+    for task in &bundle.tasks {
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, title, status, due_date, start_date, end_date, duration_days, estimated_days, assignee, progress, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(task_map[&task.id])
+        .bind(project_id)
+        .bind(&task.title)
+        .bind(task.status)
+        .bind(task.due_date)
+        .bind(task.start_date)
+        .bind(task.end_date)
+        .bind(task.duration_days)
+        .bind(task.estimated_days)
+        .bind(task.assignee)
+        .bind(task.progress)
+        .bind(task.created_at)
+        .bind(task.updated_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+    for task in &bundle.tasks {
+        if let Some(parent_id) = task.parent_id {
+            sqlx::query("UPDATE tasks SET parent_id = ? WHERE id = ?")
+                .bind(task_map[&parent_id])
+                .bind(task_map[&task.id])
+                .execute(&mut *conn)
+                .await?;
+        }
+        for tag in normalize_tags(&task.tags)? {
+            sqlx::query("INSERT INTO task_tags (task_id, tag, created_at) VALUES (?, ?, ?)")
+                .bind(task_map[&task.id])
+                .bind(tag)
+                .bind(now)
+                .execute(&mut *conn)
+                .await?;
+        }
+    }
+
+    for dep in &bundle.dependencies {
+        sqlx::query(
+            "INSERT INTO task_dependencies (id, source_task_id, target_task_id, type, created_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(task_map[&dep.source_task_id])
+        .bind(task_map[&dep.target_task_id])
+        .bind(&dep.type_)
+        .bind(dep.created_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    for point in &bundle.plan {
+        sqlx::query(
+            "INSERT INTO project_plan (id, project_id, date, planned_progress, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(project_id)
+        .bind(point.date)
+        .bind(point.planned_progress)
+        .bind(point.created_at)
+        .bind(point.updated_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    // Progress history keeps its timestamps so the imported S-curve matches the original
+    for entry in &bundle.progress {
+        sqlx::query(
+            "INSERT INTO task_progress (id, task_id, project_id, progress, note, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(task_map[&entry.task_id])
+        .bind(project_id)
+        .bind(entry.progress)
+        .bind(&entry.note)
+        .bind(entry.created_at)
+        .bind(entry.updated_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(project_id)
+}
+
+#[utoipa::path(
+    post,
+    path = "/projects/import",
+    tag = "Projects",
+    request_body = ProjectBundle,
+    responses(
+        (status = 201, description = "Project recreated with new ids", body = ProjectBundle),
+        (status = 400, description = "Bundle is malformed or internally inconsistent; nothing was imported"),
+    )
+)]
+pub async fn import_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    AppJson(bundle): AppJson<ProjectBundle>,
+) -> AppResult<(StatusCode, Json<ProjectBundle>)> {
+    validate_bundle(&bundle)?;
+
+    let mut tx = state.pool.begin().await?;
+    let project_id = insert_bundle(&mut tx, auth.user_id, &bundle).await?;
+    tx.commit().await?;
+
+    let project: Project = fetch_project(&state.pool, auth.user_id, project_id).await?.try_into()?;
+    crate::events::log_activity_with_context(
+        &state.event_bus,
+        "created",
+        Some(auth.user_id),
+        &project,
+        None,
+        Some(crate::events::RequestContext::from_headers(&headers)),
+    );
+
+    Ok((StatusCode::CREATED, Json(build_project_bundle(&state.pool, project).await?)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/duplicate",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project to copy")),
+    request_body = ProjectDuplicateRequest,
+    responses(
+        (status = 201, description = "Copy of the project's tasks, dependencies and plan, without progress entries", body = ProjectBundle),
+        (status = 404, description = "Project not found"),
+    )
+)]
+pub async fn duplicate_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    Path(source_id): Path<Uuid>,
+    body: Bytes,
+) -> AppResult<(StatusCode, Json<ProjectBundle>)> {
+    // The body is optional: a bare POST copies under the default name, but a
+    // body that is present must parse like any other JSON request
+    let payload = if body.is_empty() {
+        ProjectDuplicateRequest::default()
+    } else {
+        let mut request = axum::extract::Request::new(Body::from(body));
+        *request.headers_mut() = headers.clone();
+        let AppJson(payload) = AppJson::<ProjectDuplicateRequest>::from_request(request, &state).await?;
+        payload
+    };
+    let source: Project = fetch_project(&state.pool, auth.user_id, source_id).await?.try_into()?;
+    let mut bundle = build_project_bundle(&state.pool, source).await?;
+    // A fresh copy starts without recorded history
+    bundle.progress.clear();
+    drop_dangling_references(&mut bundle);
+    bundle.project.name = match payload.name {
+        Some(name) if name.trim().is_empty() => return Err(AppError::bad_request("name must not be empty")),
+        Some(name) => name,
+        None => format!("{} (copy)", bundle.project.name),
+    };
+    validate_bundle(&bundle)?;
+
+    let mut tx = state.pool.begin().await?;
+    let project_id = insert_bundle(&mut tx, auth.user_id, &bundle).await?;
+    tx.commit().await?;
+
+    let project: Project = fetch_project(&state.pool, auth.user_id, project_id).await?.try_into()?;
+    let copy = build_project_bundle(&state.pool, project).await?;
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "created", Some(auth.user_id), &copy.project, None, Some(ctx.clone()));
+    for task in &copy.tasks {
+        crate::events::log_activity_with_context(&state.event_bus, "created", Some(auth.user_id), task, None, Some(ctx.clone()));
+    }
+
+    Ok((StatusCode::CREATED, Json(copy)))
+}
@@ -0,0 +1,355 @@
+//! Read-only GraphQL view over projects, tasks and progress.
+//!
+//! Every resolver goes through the same ownership checks as the REST handlers
+//! (`fetch_project` scoped to the caller), so a query can only reach projects the
+//! authenticated user owns. Nested fields let the Gantt view load a project, its
+//! tasks and each task's latest progress entry in a single request.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema, ID};
+use axum::extract::State;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::db::uuid_sql;
+use crate::errors::AppError;
+use crate::extract::AppJson;
+use crate::jwt::AuthUser;
+use crate::models::progress::Progress;
+use crate::models::project::Project;
+use crate::models::project_plan::ProjectPlanPoint;
+use crate::models::task::Task;
+use crate::routes::progress::{fetch_latest_progress, fetch_project_progress};
+use crate::routes::projects::{fetch_project, load_dashboard, ActualPoint, DashboardResponse};
+use crate::routes::tasks::fetch_project_tasks;
+
+/// Nesting limit for incoming queries; the schema itself is only a few levels deep.
+const MAX_QUERY_DEPTH: usize = 10;
+
+/// Field budget per query (each selected field costs 1), so aliasing one root
+/// field hundreds of times cannot fan out into hundreds of database reads.
+const MAX_QUERY_COMPLEXITY: usize = 250;
+
+pub type GraphQLSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn schema() -> &'static GraphQLSchema {
+    static SCHEMA: OnceLock<GraphQLSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_QUERY_DEPTH)
+            .limit_complexity(MAX_QUERY_COMPLEXITY)
+            .finish()
+    })
+}
+
+/// Per-request data handed to resolvers.
+struct Caller {
+    pool: SqlitePool,
+    user_id: Uuid,
+}
+
+/// Surface an `AppError` as a GraphQL error carrying the same `code` as the REST body.
+fn gql_error(err: AppError) -> async_graphql::Error {
+    let code = err.code();
+    async_graphql::Error::new(err.to_string()).extend_with(|_, ext| ext.set("code", code))
+}
+
+fn parse_id(id: &ID) -> async_graphql::Result<Uuid> {
+    Uuid::parse_str(id.as_str()).map_err(|_| gql_error(AppError::bad_request(format!("invalid id: {}", id.as_str()))))
+}
+
+async fn owned_project(caller: &Caller, project_id: Uuid) -> async_graphql::Result<Project> {
+    let project = fetch_project(&caller.pool, caller.user_id, project_id).await.map_err(gql_error)?;
+    Project::try_from(project).map_err(gql_error)
+}
+
+/// Tasks of a project, with the latest progress entries attached when the query asks for them.
+async fn project_tasks(ctx: &Context<'_>, caller: &Caller, project_id: Uuid) -> async_graphql::Result<Vec<TaskNode>> {
+    let tasks = fetch_project_tasks(&caller.pool, project_id).await.map_err(gql_error)?;
+
+    // One query for the whole project instead of one per task
+    let mut latest: HashMap<Uuid, Progress> = if ctx.look_ahead().field("latestProgress").exists() {
+        fetch_latest_progress(&caller.pool, project_id)
+            .await
+            .map_err(gql_error)?
+            .into_iter()
+            .map(|entry| (entry.task_id, entry))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    Ok(tasks
+        .into_iter()
+        .map(|task| {
+            let latest_progress = latest.remove(&task.id);
+            TaskNode { task, latest_progress }
+        })
+        .collect())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// A project owned by the caller.
+    async fn project(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<ProjectNode> {
+        let caller = ctx.data::<Caller>()?;
+        Ok(ProjectNode(owned_project(caller, parse_id(&id)?).await?))
+    }
+
+    /// Live tasks of a project owned by the caller, ordered by start date.
+    async fn tasks(&self, ctx: &Context<'_>, project_id: ID) -> async_graphql::Result<Vec<TaskNode>> {
+        let caller = ctx.data::<Caller>()?;
+        let project = owned_project(caller, parse_id(&project_id)?).await?;
+        project_tasks(ctx, caller, project.id).await
+    }
+
+    /// Progress entries of a task, newest first.
+    async fn progress(&self, ctx: &Context<'_>, task_id: ID) -> async_graphql::Result<Vec<ProgressNode>> {
+        let caller = ctx.data::<Caller>()?;
+        let task_id = parse_id(&task_id)?;
+
+        // Match and read the ids in either stored form
+        let sql = format!(
+            "SELECT {} FROM tasks WHERE {} AND deleted_at IS NULL",
+            uuid_sql::case_uuid("project_id"),
+            uuid_sql::match_uuid_clause("id")
+        );
+        let project_id = sqlx::query_scalar::<_, String>(&sql)
+            .bind(task_id.to_string())
+            .bind(task_id.to_string())
+            .fetch_optional(&caller.pool)
+            .await
+            .map_err(|e| gql_error(e.into()))?
+            .ok_or_else(|| gql_error(AppError::not_found("task not found")))?;
+        let project_id = Uuid::parse_str(&project_id)
+            .map_err(|_| gql_error(AppError::internal(format!("invalid project id stored for task {}", task_id))))?;
+        owned_project(caller, project_id).await?;
+
+        let mut entries: Vec<ProgressNode> = fetch_project_progress(&caller.pool, project_id)
+            .await
+            .map_err(gql_error)?
+            .into_iter()
+            .filter(|entry| entry.task_id == task_id)
+            .map(ProgressNode)
+            .collect();
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// Plan, daily actuals and velocity of a project owned by the caller.
+    async fn dashboard(&self, ctx: &Context<'_>, id: ID, velocity_days: Option<i64>) -> async_graphql::Result<DashboardNode> {
+        let caller = ctx.data::<Caller>()?;
+        let dashboard = load_dashboard(&caller.pool, caller.user_id, parse_id(&id)?, velocity_days)
+            .await
+            .map_err(gql_error)?;
+        Ok(DashboardNode(dashboard))
+    }
+}
+
+pub struct ProjectNode(Project);
+
+#[Object(name = "Project")]
+impl ProjectNode {
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn theme_color(&self) -> &str {
+        &self.0.theme_color
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn tasks(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<TaskNode>> {
+        let caller = ctx.data::<Caller>()?;
+        project_tasks(ctx, caller, self.0.id).await
+    }
+}
+
+pub struct TaskNode {
+    task: Task,
+    latest_progress: Option<Progress>,
+}
+
+#[Object(name = "Task")]
+impl TaskNode {
+    async fn id(&self) -> ID {
+        ID(self.task.id.to_string())
+    }
+
+    async fn project_id(&self) -> ID {
+        ID(self.task.project_id.to_string())
+    }
+
+    async fn title(&self) -> &str {
+        &self.task.title
+    }
+
+    async fn status(&self) -> &str {
+        self.task.status.as_str()
+    }
+
+    async fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.task.due_date
+    }
+
+    async fn start_date(&self) -> Option<DateTime<Utc>> {
+        self.task.start_date
+    }
+
+    async fn end_date(&self) -> Option<DateTime<Utc>> {
+        self.task.end_date
+    }
+
+    async fn duration_days(&self) -> Option<i32> {
+        self.task.duration_days
+    }
+
+    async fn estimated_days(&self) -> Option<i32> {
+        self.task.estimated_days
+    }
+
+    async fn assignee(&self) -> Option<ID> {
+        self.task.assignee.map(|id| ID(id.to_string()))
+    }
+
+    async fn parent_id(&self) -> Option<ID> {
+        self.task.parent_id.map(|id| ID(id.to_string()))
+    }
+
+    async fn progress(&self) -> i32 {
+        self.task.progress
+    }
+
+    async fn tags(&self) -> &Vec<String> {
+        &self.task.tags
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.task.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.task.updated_at
+    }
+
+    /// Newest progress entry of the task, if any.
+    async fn latest_progress(&self) -> Option<ProgressNode> {
+        self.latest_progress.clone().map(ProgressNode)
+    }
+}
+
+pub struct ProgressNode(Progress);
+
+#[Object(name = "Progress")]
+impl ProgressNode {
+    async fn id(&self) -> ID {
+        ID(self.0.id.to_string())
+    }
+
+    async fn task_id(&self) -> ID {
+        ID(self.0.task_id.to_string())
+    }
+
+    async fn progress(&self) -> i32 {
+        self.0.progress
+    }
+
+    async fn note(&self) -> Option<&str> {
+        self.0.note.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+}
+
+pub struct PlanPointNode(ProjectPlanPoint);
+
+#[Object(name = "PlanPoint")]
+impl PlanPointNode {
+    async fn date(&self) -> DateTime<Utc> {
+        self.0.date
+    }
+
+    async fn planned_progress(&self) -> i32 {
+        self.0.planned_progress
+    }
+}
+
+pub struct ActualPointNode(ActualPoint);
+
+#[Object(name = "ActualPoint")]
+impl ActualPointNode {
+    /// Calendar day (`YYYY-MM-DD`)
+    async fn date(&self) -> &str {
+        &self.0.date
+    }
+
+    async fn actual(&self) -> i32 {
+        self.0.actual
+    }
+}
+
+pub struct DashboardNode(DashboardResponse);
+
+#[Object(name = "Dashboard")]
+impl DashboardNode {
+    async fn project(&self) -> ProjectNode {
+        ProjectNode(self.0.project.clone())
+    }
+
+    async fn plan(&self) -> Vec<PlanPointNode> {
+        self.0.plan.iter().cloned().map(PlanPointNode).collect()
+    }
+
+    async fn actual(&self) -> Vec<ActualPointNode> {
+        self.0
+            .actual
+            .iter()
+            .map(|point| ActualPointNode(ActualPoint { date: point.date.clone(), actual: point.actual }))
+            .collect()
+    }
+
+    async fn velocity(&self) -> f64 {
+        self.0.velocity
+    }
+}
+
+/// `POST /graphql`: execute a read-only query as the authenticated user.
+///
+/// Errors from resolvers come back in the GraphQL `errors` array with a 200
+/// status, each carrying `extensions.code` (`NOT_FOUND`, `VALIDATION`, ...).
+pub async fn graphql_handler(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AppJson(request): AppJson<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let request = request.data(Caller { pool: state.pool.clone(), user_id: auth.user_id });
+    Json(schema().execute(request).await)
+}
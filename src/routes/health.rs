@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use axum::extract::State;
+use axum::http::StatusCode;
 use axum::Json;
 use serde::Serialize;
 use utoipa::ToSchema;
@@ -7,6 +10,8 @@ use crate::app::AppState;
 use crate::errors::AppResult;
 use sqlx::query_scalar;
 
+const DEFAULT_READY_TIMEOUT_MS: u64 = 1000;
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct HealthResponse {
     pub status: &'static str,
@@ -14,6 +19,22 @@ pub struct HealthResponse {
     pub db_error: Option<String>,
 }
 
+/// Liveness never checks the database, so it reports no database state.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessResponse {
+    pub status: &'static str,
+}
+
+/// How long readiness waits for a pooled connection and `SELECT 1` (`HEALTH_READY_TIMEOUT_MS`, default 1000).
+fn ready_timeout() -> Duration {
+    Duration::from_millis(
+        std::env::var("HEALTH_READY_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_READY_TIMEOUT_MS),
+    )
+}
+
 #[utoipa::path(
     get,
     path = "/api/health",
@@ -29,3 +50,43 @@ pub async fn health(State(state): State<AppState>) -> AppResult<Json<HealthRespo
         Err(e) => Ok(Json(HealthResponse { status: "ok", db_ok: false, db_error: Some(e.to_string()) })),
     }
 }
+
+/// Liveness: the process is up and serving requests. Never touches the database.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "Health",
+    responses((status = 200, description = "Process is alive", body = LivenessResponse))
+)]
+pub async fn live() -> Json<LivenessResponse> {
+    Json(LivenessResponse { status: "ok" })
+}
+
+/// Readiness: a pooled connection can be acquired and answers `SELECT 1` within the timeout.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = HealthResponse),
+        (status = 503, description = "Database unreachable or pool exhausted", body = HealthResponse),
+    )
+)]
+pub async fn ready(State(state): State<AppState>) -> (StatusCode, Json<HealthResponse>) {
+    let check = async {
+        let mut conn = state.pool.acquire().await?;
+        query_scalar::<_, i64>("SELECT 1").fetch_one(&mut *conn).await
+    };
+
+    let error = match tokio::time::timeout(ready_timeout(), check).await {
+        Ok(Ok(_)) => return (StatusCode::OK, Json(HealthResponse { status: "ok", db_ok: true, db_error: None })),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => "timed out waiting for a database connection".to_string(),
+    };
+
+    tracing::warn!(error = %error, "readiness check failed");
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(HealthResponse { status: "unavailable", db_ok: false, db_error: Some(error) }),
+    )
+}
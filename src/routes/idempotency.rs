@@ -0,0 +1,124 @@
+//! `Idempotency-Key` support for create endpoints.
+//!
+//! The first successful response for a (user, scope, key) is stored and
+//! returned again for retries, so a client that lost the response can repeat
+//! the request without creating a duplicate. `scope` identifies the endpoint
+//! including its parent ids, e.g. `POST /projects/{id}/tasks`. Keys expire
+//! after `IDEMPOTENCY_KEY_TTL_HOURS` (default 24).
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppResult};
+use crate::utils::utc_now;
+
+pub const DEFAULT_TTL_HOURS: i64 = 24;
+const MAX_KEY_LEN: usize = 255;
+
+/// How long a stored response is replayed (`IDEMPOTENCY_KEY_TTL_HOURS`, default 24).
+pub fn ttl_hours() -> i64 {
+    std::env::var("IDEMPOTENCY_KEY_TTL_HOURS")
+        .ok()
+        .and_then(|value| value.parse::<i64>().ok())
+        .filter(|hours| *hours > 0)
+        .unwrap_or(DEFAULT_TTL_HOURS)
+}
+
+fn cutoff() -> chrono::DateTime<chrono::Utc> {
+    utc_now() - chrono::Duration::hours(ttl_hours())
+}
+
+/// Read the optional `Idempotency-Key` header, rejecting empty or oversized keys.
+pub fn key_from_headers(headers: &axum::http::HeaderMap) -> AppResult<Option<String>> {
+    let Some(value) = headers.get("idempotency-key") else {
+        return Ok(None);
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| AppError::bad_request("Idempotency-Key must be visible ASCII"))?
+        .trim();
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(AppError::bad_request(format!(
+            "Idempotency-Key must be 1-{} characters",
+            MAX_KEY_LEN
+        )));
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// The stored response for an unexpired key, if any. A key whose first request
+/// has not finished yet is reported as a conflict.
+pub async fn replay<T: DeserializeOwned>(pool: &SqlitePool, user_id: Uuid, scope: &str, key: &str) -> AppResult<Option<T>> {
+    let row = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT response_body FROM idempotency_keys WHERE user_id = ? AND scope = ? AND idempotency_key = ? AND created_at >= ?",
+    )
+    .bind(user_id)
+    .bind(scope)
+    .bind(key)
+    .bind(cutoff())
+    .fetch_optional(pool)
+    .await?;
+
+    match row {
+        None => Ok(None),
+        Some(None) => Err(AppError::conflict("a request with this Idempotency-Key is still being processed")),
+        Some(Some(body)) => serde_json::from_str(&body)
+            .map(Some)
+            .map_err(|e| AppError::internal(format!("stored idempotent response is invalid: {}", e))),
+    }
+}
+
+/// Reserve `key` for the resource being created, inside the creating transaction.
+///
+/// Returns `false` when another request already holds the key; the caller
+/// should roll back and [`replay`] instead. Expired keys are cleared first.
+pub async fn claim(
+    conn: &mut SqliteConnection,
+    user_id: Uuid,
+    scope: &str,
+    key: &str,
+    project_id: Uuid,
+    resource_id: Uuid,
+) -> AppResult<bool> {
+    sqlx::query("DELETE FROM idempotency_keys WHERE created_at < ?")
+        .bind(cutoff())
+        .execute(&mut *conn)
+        .await?;
+
+    let claimed = sqlx::query(
+        "INSERT OR IGNORE INTO idempotency_keys (user_id, scope, idempotency_key, project_id, resource_id, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(user_id)
+    .bind(scope)
+    .bind(key)
+    .bind(project_id)
+    .bind(resource_id)
+    .bind(utc_now())
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    Ok(claimed == 1)
+}
+
+/// Store the response for a claimed key once the resource is committed.
+pub async fn record<T: Serialize>(pool: &SqlitePool, user_id: Uuid, scope: &str, key: &str, body: &T) -> AppResult<()> {
+    let body = serde_json::to_string(body).map_err(|e| AppError::internal(format!("failed to serialize response: {}", e)))?;
+    sqlx::query("UPDATE idempotency_keys SET response_body = ? WHERE user_id = ? AND scope = ? AND idempotency_key = ?")
+        .bind(body)
+        .bind(user_id)
+        .bind(scope)
+        .bind(key)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Replay after losing the claim race to a concurrent retry.
+pub async fn replay_claimed<T: DeserializeOwned>(pool: &SqlitePool, user_id: Uuid, scope: &str, key: &str) -> AppResult<T> {
+    replay(pool, user_id, scope, key)
+        .await?
+        .ok_or_else(|| AppError::internal("idempotency key mapping vanished"))
+}
@@ -0,0 +1,159 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::db::uuid_sql;
+use crate::errors::{AppError, AppResult};
+use crate::extract::AppJson;
+use crate::jwt::AuthUser;
+use crate::models::project_member::{validate_member_role, ProjectMember, ProjectMemberCreateRequest, DEFAULT_PROJECT_MEMBER_ROLE};
+use crate::routes::projects::fetch_project;
+use crate::utils::utc_now;
+
+/// Whether `user_id` has been added to `project_id` as a collaborator (owners are not listed).
+pub(crate) async fn is_project_member(pool: &SqlitePool, project_id: Uuid, user_id: Uuid) -> AppResult<bool> {
+    let member: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM project_members WHERE project_id = ? AND user_id = ?)",
+    )
+    .bind(project_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(member)
+}
+
+/// Owner-only guard for managing a project's members.
+async fn ensure_project_owner(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> AppResult<()> {
+    let project = fetch_project(pool, user_id, project_id).await?;
+    if project.user_id != user_id {
+        return Err(AppError::forbidden("only the project owner can manage members"));
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/members",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id")),
+    responses((status = 200, description = "Collaborators of the project (the owner is not included)", body = [ProjectMember]))
+)]
+pub async fn list_members(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(project_id): Path<Uuid>,
+) -> AppResult<Json<Vec<ProjectMember>>> {
+    fetch_project(&state.pool, auth.user_id, project_id).await?;
+
+    let members = sqlx::query_as::<_, ProjectMember>(
+        "SELECT project_id, user_id, role, created_at FROM project_members WHERE project_id = ? ORDER BY created_at ASC",
+    )
+    .bind(project_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(Json(members))
+}
+
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/members",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id")),
+    request_body = ProjectMemberCreateRequest,
+    responses(
+        (status = 201, description = "Member added", body = ProjectMember),
+        (status = 403, description = "Caller is not the project owner"),
+        (status = 409, description = "User is already a member")
+    )
+)]
+pub async fn add_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    Path(project_id): Path<Uuid>,
+    AppJson(payload): AppJson<ProjectMemberCreateRequest>,
+) -> AppResult<(StatusCode, Json<ProjectMember>)> {
+    ensure_project_owner(&state.pool, auth.user_id, project_id).await?;
+
+    let role = payload.role.unwrap_or_else(|| DEFAULT_PROJECT_MEMBER_ROLE.to_string());
+    validate_member_role(&role)?;
+
+    if payload.user_id == auth.user_id {
+        return Err(AppError::bad_request("the owner already has access to the project"));
+    }
+
+    // Live accounts only, whichever form their id is stored in
+    let user_exists: bool = sqlx::query_scalar(&format!(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE {} AND deleted_at IS NULL)",
+        uuid_sql::match_uuid_clause("id")
+    ))
+    .bind(payload.user_id.to_string())
+    .bind(payload.user_id.to_string())
+    .fetch_one(&state.pool)
+    .await?;
+    if !user_exists {
+        return Err(AppError::not_found("user not found"));
+    }
+
+    if is_project_member(&state.pool, project_id, payload.user_id).await? {
+        return Err(AppError::conflict("user is already a member of this project"));
+    }
+
+    let member = ProjectMember { project_id, user_id: payload.user_id, role, created_at: utc_now() };
+    sqlx::query("INSERT INTO project_members (project_id, user_id, role, created_at) VALUES (?, ?, ?, ?)")
+        .bind(member.project_id)
+        .bind(member.user_id)
+        .bind(&member.role)
+        .bind(member.created_at)
+        .execute(&state.pool)
+        .await?;
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "created", Some(auth.user_id), &member, None, Some(ctx));
+
+    Ok((StatusCode::CREATED, Json(member)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/projects/{id}/members/{user_id}",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id"), ("user_id" = Uuid, Path, description = "Member's user id")),
+    responses(
+        (status = 204, description = "Member removed"),
+        (status = 403, description = "Caller is not the project owner"),
+        (status = 404, description = "User is not a member")
+    )
+)]
+pub async fn remove_member(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<StatusCode> {
+    ensure_project_owner(&state.pool, auth.user_id, project_id).await?;
+
+    let member = sqlx::query_as::<_, ProjectMember>(
+        "SELECT project_id, user_id, role, created_at FROM project_members WHERE project_id = ? AND user_id = ?",
+    )
+    .bind(project_id)
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::not_found("user is not a member of this project"))?;
+
+    sqlx::query("DELETE FROM project_members WHERE project_id = ? AND user_id = ?")
+        .bind(project_id)
+        .bind(user_id)
+        .execute(&state.pool)
+        .await?;
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "deleted", Some(auth.user_id), &member, None, Some(ctx));
+
+    Ok(StatusCode::NO_CONTENT)
+}
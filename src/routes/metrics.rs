@@ -0,0 +1,47 @@
+//! Prometheus scrape endpoint.
+//!
+//! Unauthenticated by default so scrapers need no user account. Setting
+//! `METRICS_TOKEN` requires `Authorization: Bearer <token>` instead; this token
+//! is separate from user JWTs.
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap};
+use axum::response::IntoResponse;
+
+use crate::app::AppState;
+use crate::errors::{AppError, AppResult};
+use crate::metrics::{self, Gauges};
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+fn metrics_token() -> Option<String> {
+    std::env::var("METRICS_TOKEN").ok().filter(|token| !token.is_empty())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Prometheus text exposition format", content_type = "text/plain", body = String),
+        (status = 401, description = "METRICS_TOKEN is set and the bearer token does not match"),
+    )
+)]
+pub async fn metrics(State(state): State<AppState>, headers: HeaderMap) -> AppResult<impl IntoResponse> {
+    if let Some(expected) = metrics_token() {
+        let supplied = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if supplied != Some(expected.as_str()) {
+            return Err(AppError::unauthorized("invalid metrics token"));
+        }
+    }
+
+    let gauges = Gauges {
+        db_pool_size: state.pool.size(),
+        db_pool_idle: state.pool.num_idle(),
+        event_bus_queued: state.event_bus.len(),
+    };
+    Ok(([(header::CONTENT_TYPE, CONTENT_TYPE)], metrics::registry().render(gauges)))
+}
@@ -4,3 +4,18 @@ pub mod tasks;
 pub mod progress;
 pub mod health;
 pub mod rbac;
+pub mod admin;
+pub mod pagination;
+pub mod etag;
+pub mod preconditions;
+pub mod idempotency;
+pub mod export;
+pub mod webhooks;
+pub mod metrics;
+pub mod calendar;
+pub mod graphql;
+pub mod members;
+pub mod oauth;
+pub mod users;
+pub mod search;
+pub mod activity;
@@ -0,0 +1,317 @@
+//! Google sign-in (OpenID Connect authorization code flow).
+//!
+//! `GET /auth/oauth/google` redirects the browser to Google's consent screen and
+//! drops a short-lived `oauth_state` cookie; the callback checks that cookie,
+//! exchanges the code for an ID token and signs the user in with a regular JWT.
+//! Users are matched on `provider = 'google'` + the Google subject, so a returning
+//! user always lands on the same account; a first sign-in with the email of an
+//! existing local account links that account instead of creating a new one.
+
+use std::time::Duration;
+
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use jsonwebtoken::{DecodingKey, Validation};
+use rand_core::{OsRng, RngCore};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::db::row_parsers;
+use crate::errors::{AppError, AppResult};
+use crate::models::user::{AuthResponse, DbUser, User};
+use crate::routes::auth::{fetch_user_by_id, issue_token};
+use crate::utils::{hash_password, utc_now};
+
+pub const GOOGLE_PROVIDER: &str = "google";
+
+const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const GOOGLE_ISSUERS: [&str; 2] = ["accounts.google.com", "https://accounts.google.com"];
+const STATE_COOKIE: &str = "oauth_state";
+const STATE_TTL_SECS: u64 = 600;
+const TOKEN_EXCHANGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Client credentials read from `GOOGLE_CLIENT_ID`, `GOOGLE_CLIENT_SECRET` and
+/// `GOOGLE_REDIRECT_URI`. `GOOGLE_TOKEN_URL` overrides the token endpoint (tests).
+#[derive(Debug, Clone)]
+pub struct GoogleOAuthConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub token_url: String,
+}
+
+impl GoogleOAuthConfig {
+    /// `None` when any of the client settings is missing; the endpoints then answer 503.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Some(Self {
+            client_id: var("GOOGLE_CLIENT_ID")?,
+            client_secret: var("GOOGLE_CLIENT_SECRET")?,
+            redirect_uri: var("GOOGLE_REDIRECT_URI")?,
+            token_url: var("GOOGLE_TOKEN_URL").unwrap_or_else(|| GOOGLE_TOKEN_URL.to_string()),
+        })
+    }
+
+    fn require() -> AppResult<Self> {
+        Self::from_env().ok_or_else(|| AppError::service_unavailable("Google sign-in is not configured"))
+    }
+}
+
+/// Claims of the Google ID token that we rely on.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GoogleIdentity {
+    pub sub: String,
+    pub email: String,
+    #[serde(default)]
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct GoogleCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Set by Google when the user declines consent
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+fn random_state() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn state_cookie(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(name, _)| *name == STATE_COOKIE)
+        .map(|(_, value)| value)
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/oauth/google",
+    tag = "Auth",
+    responses(
+        (status = 302, description = "Redirect to Google's consent screen"),
+        (status = 503, description = "Google sign-in is not configured")
+    )
+)]
+pub async fn google_login() -> AppResult<Response> {
+    let config = GoogleOAuthConfig::require()?;
+    let state = random_state();
+
+    let url = reqwest::Url::parse_with_params(
+        GOOGLE_AUTH_URL,
+        &[
+            ("client_id", config.client_id.as_str()),
+            ("redirect_uri", config.redirect_uri.as_str()),
+            ("response_type", "code"),
+            ("scope", "openid email profile"),
+            ("state", state.as_str()),
+        ],
+    )
+    .map_err(|e| AppError::internal(format!("failed to build Google authorization url: {e}")))?;
+
+    let cookie = format!("{STATE_COOKIE}={state}; Path=/; Max-Age={STATE_TTL_SECS}; HttpOnly; Secure; SameSite=Lax");
+    let mut response = StatusCode::FOUND.into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        header::LOCATION,
+        HeaderValue::from_str(url.as_str()).map_err(|e| AppError::internal(e.to_string()))?,
+    );
+    headers.insert(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&cookie).map_err(|e| AppError::internal(e.to_string()))?,
+    );
+    Ok(response)
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/oauth/google/callback",
+    tag = "Auth",
+    params(GoogleCallbackQuery),
+    responses(
+        (status = 200, description = "Signed in with Google", body = AuthResponse),
+        (status = 401, description = "State mismatch, consent declined or code exchange failed"),
+        (status = 503, description = "Google sign-in is not configured")
+    )
+)]
+pub async fn google_callback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<GoogleCallbackQuery>,
+) -> AppResult<Response> {
+    let config = GoogleOAuthConfig::require()?;
+
+    if let Some(error) = query.error {
+        return Err(AppError::unauthorized(format!("Google sign-in was not completed: {error}")));
+    }
+    let expected = state_cookie(&headers).ok_or_else(|| AppError::unauthorized("missing oauth state cookie"))?;
+    if query.state.as_deref() != Some(expected) {
+        return Err(AppError::unauthorized("oauth state mismatch"));
+    }
+    let code = query.code.ok_or_else(|| AppError::bad_request("missing authorization code"))?;
+
+    let identity = exchange_code(&config, &code).await?;
+    let (db_user, created) = upsert_google_user(&state.pool, &identity).await?;
+
+    let token = issue_token(&state, db_user.id).await?;
+    let user: User = db_user.try_into()?;
+
+    if created {
+        let ctx = crate::events::RequestContext::from_headers(&headers);
+        crate::events::log_activity_with_context(&state.event_bus, "registered", Some(user.id), &user, None, Some(ctx));
+    }
+
+    // Clear the one-shot state cookie
+    let mut response = Json(AuthResponse { token, user }).into_response();
+    response.headers_mut().insert(
+        header::SET_COOKIE,
+        HeaderValue::from_static("oauth_state=; Path=/; Max-Age=0; HttpOnly; Secure; SameSite=Lax"),
+    );
+    Ok(response)
+}
+
+/// Trade the authorization code for an ID token and read the identity out of it.
+///
+/// The token comes straight from Google's token endpoint over TLS, so (as the
+/// OpenID Connect spec allows) its signature is not re-checked; issuer, audience
+/// and expiry still are.
+async fn exchange_code(config: &GoogleOAuthConfig, code: &str) -> AppResult<GoogleIdentity> {
+    let client = reqwest::Client::builder()
+        .timeout(TOKEN_EXCHANGE_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::internal(format!("failed to build http client: {e}")))?;
+
+    let response = client
+        .post(&config.token_url)
+        .form(&[
+            ("code", code),
+            ("client_id", config.client_id.as_str()),
+            ("client_secret", config.client_secret.as_str()),
+            ("redirect_uri", config.redirect_uri.as_str()),
+            ("grant_type", "authorization_code"),
+        ])
+        .send()
+        .await
+        .map_err(|e| AppError::service_unavailable(format!("Google token endpoint unreachable: {e}")))?;
+
+    if !response.status().is_success() {
+        tracing::warn!(status = %response.status(), "Google rejected the authorization code");
+        return Err(AppError::unauthorized("Google rejected the authorization code"));
+    }
+
+    let tokens: TokenResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::unauthorized(format!("invalid Google token response: {e}")))?;
+
+    decode_id_token(&tokens.id_token, &config.client_id)
+}
+
+fn decode_id_token(id_token: &str, client_id: &str) -> AppResult<GoogleIdentity> {
+    let mut validation = Validation::default();
+    validation.insecure_disable_signature_validation();
+    validation.set_audience(&[client_id]);
+    validation.set_issuer(&GOOGLE_ISSUERS);
+
+    jsonwebtoken::decode::<GoogleIdentity>(id_token, &DecodingKey::from_secret(&[]), &validation)
+        .map(|data| data.claims)
+        .map_err(|e| AppError::unauthorized(format!("invalid Google ID token: {e}")))
+}
+
+const USER_COLUMNS: &str = "name, email, password_hash, provider, provider_id, created_at, updated_at, deleted_at";
+
+/// Look up a live user by a text column (`email`, or `provider_id` for a provider).
+async fn find_user(pool: &SqlitePool, filter: &str, values: &[&str]) -> AppResult<Option<DbUser>> {
+    let sql = format!("SELECT id, {USER_COLUMNS} FROM users WHERE {filter} AND deleted_at IS NULL");
+    let mut simple = sqlx::query_as::<_, DbUser>(&sql);
+    for value in values {
+        simple = simple.bind(*value);
+    }
+    if let Ok(user) = simple.fetch_optional(pool).await {
+        return Ok(user);
+    }
+
+    // Fallback: handle blob/text mixed UUID storage by selecting textified id
+    let sql = format!(
+        "SELECT {} , {USER_COLUMNS} FROM users WHERE {filter} AND deleted_at IS NULL",
+        crate::db::uuid_sql::case_uuid("id")
+    );
+    let mut fallback = sqlx::query(&sql);
+    for value in values {
+        fallback = fallback.bind(*value);
+    }
+    match fallback.fetch_optional(pool).await? {
+        Some(row) => Ok(Some(row_parsers::db_user_from_row(&row)?)),
+        None => Ok(None),
+    }
+}
+
+/// Resolve the account for a Google identity, returning it and whether it was just created.
+///
+/// Order: an account already bound to this Google subject, then a local account
+/// with the same (Google-verified) email which gets linked, then a new account.
+pub async fn upsert_google_user(pool: &SqlitePool, identity: &GoogleIdentity) -> AppResult<(DbUser, bool)> {
+    if let Some(user) = find_user(pool, "provider = ? AND provider_id = ?", &[GOOGLE_PROVIDER, &identity.sub]).await? {
+        return Ok((user, false));
+    }
+
+    let now = utc_now();
+
+    if let Some(existing) = find_user(pool, "lower(email) = lower(?)", &[&identity.email]).await? {
+        if !identity.email_verified {
+            return Err(AppError::conflict("email already in use"));
+        }
+        if existing.provider == GOOGLE_PROVIDER {
+            // Bound to a different Google subject; never re-point it silently
+            return Err(AppError::conflict("email is linked to another Google account"));
+        }
+        sqlx::query("UPDATE users SET provider = ?, provider_id = ?, updated_at = ? WHERE id = ?")
+            .bind(GOOGLE_PROVIDER)
+            .bind(&identity.sub)
+            .bind(now)
+            .bind(existing.id)
+            .execute(pool)
+            .await?;
+        return Ok((fetch_user_by_id(pool, existing.id).await?, false));
+    }
+
+    // Google users have no password; store a hash of a random secret nobody knows
+    let password_hash = hash_password(&random_state())?;
+    let user_id = Uuid::new_v4();
+    let name = identity.name.clone().filter(|n| !n.trim().is_empty()).unwrap_or_else(|| identity.email.clone());
+
+    sqlx::query(
+        "INSERT INTO users (id, name, email, password_hash, provider, provider_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(user_id)
+    .bind(name)
+    .bind(&identity.email)
+    .bind(password_hash)
+    .bind(GOOGLE_PROVIDER)
+    .bind(&identity.sub)
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await?;
+
+    Ok((fetch_user_by_id(pool, user_id).await?, true))
+}
@@ -0,0 +1,26 @@
+//! Shared `limit`/`offset` handling for list endpoints.
+
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+pub const DEFAULT_LIMIT: i64 = 50;
+pub const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Pagination {
+    /// Maximum number of items to return (default 50, capped at 500)
+    pub limit: Option<i64>,
+    /// Number of items to skip (default 0)
+    pub offset: Option<i64>,
+}
+
+impl Pagination {
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+}
@@ -0,0 +1,62 @@
+//! Optimistic concurrency for updates.
+//!
+//! A client may send back the `updated_at` it last read, either exactly as
+//! `expected_updated_at` in the body or as an `If-Unmodified-Since` header
+//! (HTTP date, whole seconds). When one is given the handler only writes if the
+//! row still carries the version it just loaded (`AND updated_at = ?`), so a
+//! stale editor gets a 409 instead of overwriting a newer change. Without either
+//! the update stays last-write-wins.
+
+use axum::http::header::IF_UNMODIFIED_SINCE;
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+
+use crate::errors::{AppError, AppResult};
+
+/// Error for a row that changed after the client read it.
+pub fn stale(entity: &str) -> AppError {
+    AppError::conflict(format!(
+        "{} was modified since you fetched it; refetch and retry",
+        entity
+    ))
+}
+
+/// The version an update must still match, if the client asked for a check.
+///
+/// `current` is the `updated_at` the handler just loaded. Returns it when a
+/// precondition was given and holds, `None` when none was given, and a 409
+/// when the client's copy is already out of date.
+pub fn expected_version(
+    headers: &HeaderMap,
+    expected_updated_at: Option<DateTime<Utc>>,
+    current: DateTime<Utc>,
+    entity: &str,
+) -> AppResult<Option<DateTime<Utc>>> {
+    let header = match headers.get(IF_UNMODIFIED_SINCE) {
+        Some(value) => {
+            let value = value
+                .to_str()
+                .ok()
+                .and_then(|v| DateTime::parse_from_rfc2822(v.trim()).ok())
+                .ok_or_else(|| AppError::bad_request("If-Unmodified-Since must be an HTTP date"))?;
+            Some(value.with_timezone(&Utc))
+        }
+        None => None,
+    };
+
+    if expected_updated_at.is_none() && header.is_none() {
+        return Ok(None);
+    }
+    if let Some(expected) = expected_updated_at {
+        if expected != current {
+            return Err(stale(entity));
+        }
+    }
+    // HTTP dates drop sub-second precision, so compare whole seconds
+    if let Some(since) = header {
+        if current.timestamp() > since.timestamp() {
+            return Err(stale(entity));
+        }
+    }
+    Ok(Some(current))
+}
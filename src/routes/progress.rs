@@ -1,5 +1,7 @@
 use axum::extract::{Path, State, Query};
-use serde::Deserialize;
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use axum::http::StatusCode;
 use axum::Json;
 use sqlx::SqlitePool;
@@ -7,29 +9,108 @@ use uuid::Uuid;
 use crate::db::{uuid_sql, row_parsers};
 
 use crate::app::AppState;
-use crate::errors::{AppError, AppResult};
+use crate::errors::{AppError, AppResult, FieldError};
+use crate::extract::{AppJson, AppQuery};
 use crate::jwt::AuthUser;
-use crate::models::progress::{DbProgress, Progress, ProgressCreateRequest, ProgressUpdateRequest};
+use crate::models::progress::{DbProgress, Progress, ProgressBatchCreateRequest, ProgressCreateRequest, ProgressUpdateRequest};
+use crate::models::task::Task;
+use crate::routes::idempotency;
+use crate::routes::members::is_project_member;
+use crate::routes::tasks::{ensure_project_membership, fetch_task};
 use crate::utils::utc_now;
 
+/// Whether a new progress entry also overwrites `tasks.progress` (`SYNC_TASK_PROGRESS`, default on).
+///
+/// `tasks.progress` is what the task DTO exposes while the dashboard reads `task_progress`
+/// entries; syncing keeps the two in step. Set `SYNC_TASK_PROGRESS=false` to decouple them.
+fn sync_task_progress() -> bool {
+    std::env::var("SYNC_TASK_PROGRESS")
+        .map(|v| !matches!(v.to_lowercase().as_str(), "0" | "false" | "no"))
+        .unwrap_or(true)
+}
+
+const DEFAULT_PROGRESS_LIMIT: i64 = 50;
+const MAX_PROGRESS_LIMIT: i64 = 200;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    fn sql(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProgressListQuery {
+    /// Maximum number of entries to return (default 50, capped at 200)
+    pub limit: Option<i64>,
+    /// Number of entries to skip (default 0)
+    pub offset: Option<i64>,
+    /// `desc` (newest first, the default) or `asc` by `created_at`
+    #[param(value_type = Option<String>)]
+    pub order: Option<SortOrder>,
+}
+
+/// One page of a task's progress entries.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProgressPage {
+    pub items: Vec<Progress>,
+    /// Non-deleted entries of the task across all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
 #[utoipa::path(
     get,
     path = "/projects/{project_id}/tasks/{task_id}/progress",
     tag = "Progress",
-    params(("project_id" = Uuid, Path, description = "Project id"), ("task_id" = Uuid, Path, description = "Task id")),
-    responses((status = 200, description = "List progress entries", body = [Progress]))
+    params(("project_id" = Uuid, Path, description = "Project id"), ("task_id" = Uuid, Path, description = "Task id"), ProgressListQuery),
+    responses((status = 200, description = "A page of progress entries", body = ProgressPage))
 )]
 pub async fn list_progress(
     State(state): State<AppState>,
     Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+    AppQuery(query): AppQuery<ProgressListQuery>,
     auth: AuthUser,
-) -> AppResult<Json<Vec<Progress>>> {
+) -> AppResult<Json<ProgressPage>> {
     ensure_task_belongs_to_user(&state.pool, auth.user_id, project_id, task_id).await?;
 
-    let simple = sqlx::query_as::<_, DbProgress>(
-        "SELECT id, project_id, task_id, progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE task_id = ? AND deleted_at IS NULL ORDER BY created_at DESC",
-    )
-    .bind(task_id)
+    let limit = query.limit.unwrap_or(DEFAULT_PROGRESS_LIMIT).clamp(1, MAX_PROGRESS_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+    // rowid keeps pages stable when several entries share a timestamp
+    let order = query.order.unwrap_or_default().sql();
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM task_progress WHERE {} AND deleted_at IS NULL",
+        uuid_sql::match_uuid_clause("task_id")
+    ))
+    .bind(task_id.to_string())
+    .bind(task_id.to_string())
+    .fetch_one(&state.pool)
+    .await?;
+
+    // Match the task id in either stored form, as `total` does, so the page and
+    // the count always agree
+    let task_clause = uuid_sql::match_uuid_clause("task_id");
+    let simple = sqlx::query_as::<_, DbProgress>(&format!(
+        "SELECT id, project_id, task_id, progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE {1} AND deleted_at IS NULL ORDER BY created_at {0}, rowid {0} LIMIT ? OFFSET ?",
+        order, task_clause
+    ))
+    .bind(task_id.to_string())
+    .bind(task_id.to_string())
+    .bind(limit)
+    .bind(offset)
     .fetch_all(&state.pool)
     .await;
 
@@ -40,12 +121,15 @@ pub async fn list_progress(
             let project_case = uuid_sql::case_uuid("project_id");
             let task_case = uuid_sql::case_uuid("task_id");
             let sql = format!(
-                "SELECT {} , {} , {} , progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE task_id = ? AND deleted_at IS NULL ORDER BY created_at DESC",
-                id_case, project_case, task_case
+                "SELECT {} , {} , {} , progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE {4} AND deleted_at IS NULL ORDER BY created_at {3}, rowid {3} LIMIT ? OFFSET ?",
+                id_case, project_case, task_case, order, task_clause
             );
 
             let rows = sqlx::query(&sql)
                 .bind(task_id.to_string())
+                .bind(task_id.to_string())
+                .bind(limit)
+                .bind(offset)
                 .fetch_all(&state.pool)
                 .await?;
 
@@ -59,7 +143,7 @@ pub async fn list_progress(
     };
 
     let items = rows.into_iter().map(Progress::try_from).collect::<Result<_, _>>()?;
-    Ok(Json(items))
+    Ok(Json(ProgressPage { items, total, limit, offset }))
 }
 
 #[derive(Debug, Deserialize)]
@@ -83,32 +167,11 @@ pub async fn list_project_progress(
     auth: AuthUser,
 ) -> AppResult<Json<Vec<Progress>>> {
     // verify project belongs to user
-    let owner = sqlx::query_scalar::<_, Uuid>(
-        "SELECT user_id FROM projects WHERE id = ? AND deleted_at IS NULL",
-    )
-    .bind(project_id)
-    .fetch_optional(&state.pool)
-    .await?;
-
-    let owner = owner.ok_or_else(|| AppError::not_found("project not found"))?;
-    if owner != auth.user_id {
-        return Err(AppError::forbidden("not allowed to access this project"));
-    }
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
     let rows = if let Some(task_id) = filter.task_id {
         // ensure task belongs to project
-        let t_owner = sqlx::query_scalar::<_, Uuid>(
-            "SELECT p.user_id FROM projects p INNER JOIN tasks t ON t.project_id = p.id WHERE p.id = ? AND t.id = ? AND p.deleted_at IS NULL AND t.deleted_at IS NULL",
-        )
-        .bind(project_id)
-        .bind(task_id)
-        .fetch_optional(&state.pool)
-        .await?;
-
-        let t_owner = t_owner.ok_or_else(|| AppError::not_found("task not found"))?;
-        if t_owner != auth.user_id {
-            return Err(AppError::forbidden("not allowed to access this task"));
-        }
+        ensure_task_belongs_to_user(&state.pool, auth.user_id, project_id, task_id).await?;
 
         let simple = sqlx::query_as::<_, DbProgress>(
             "SELECT id, project_id, task_id, progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE task_id = ? AND deleted_at IS NULL ORDER BY created_at DESC",
@@ -179,28 +242,144 @@ pub async fn list_project_progress(
     Ok(Json(items))
 }
 
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/progress/latest",
+    tag = "Progress",
+    params(("id" = Uuid, Path, description = "Project id")),
+    responses((status = 200, description = "`task_id -> progress` of each task's newest entry; tasks without entries are omitted", body = std::collections::HashMap<String, i32>))
+)]
+pub async fn list_latest_progress(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    auth: AuthUser,
+) -> AppResult<Json<BTreeMap<Uuid, i32>>> {
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
+
+    let latest = fetch_latest_progress(&state.pool, project_id)
+        .await?
+        .into_iter()
+        .map(|entry| (entry.task_id, entry.progress))
+        .collect();
+    Ok(Json(latest))
+}
+
+/// The newest progress entry of each live task in a project, newest first (no ownership check).
+pub(crate) async fn fetch_latest_progress(pool: &SqlitePool, project_id: Uuid) -> AppResult<Vec<Progress>> {
+    // Rank each task's entries newest-first; rowid breaks ties between identical timestamps.
+    let ranked = |project_clause: &str| {
+        format!(
+            "SELECT tp.id, tp.project_id, tp.task_id, tp.progress, tp.note, tp.created_at, tp.updated_at, tp.deleted_at, \
+             ROW_NUMBER() OVER (PARTITION BY tp.task_id ORDER BY tp.created_at DESC, tp.rowid DESC) AS rn \
+             FROM task_progress tp INNER JOIN tasks t ON t.id = tp.task_id \
+             WHERE {} AND tp.deleted_at IS NULL AND t.deleted_at IS NULL",
+            project_clause
+        )
+    };
+
+    let simple = sqlx::query_as::<_, DbProgress>(&format!(
+        "SELECT id, project_id, task_id, progress, note, created_at, updated_at, deleted_at FROM ({}) WHERE rn = 1 ORDER BY created_at DESC",
+        ranked("tp.project_id = ?")
+    ))
+    .bind(project_id)
+    .fetch_all(pool)
+    .await;
+
+    let rows = match simple {
+        Ok(r) => r,
+        Err(_) => {
+            let id_case = uuid_sql::case_uuid("id");
+            let project_case = uuid_sql::case_uuid("project_id");
+            let task_case = uuid_sql::case_uuid("task_id");
+            let sql = format!(
+                "SELECT {} , {} , {} , progress, note, created_at, updated_at, deleted_at FROM ({}) WHERE rn = 1 ORDER BY created_at DESC",
+                id_case, project_case, task_case, ranked(&uuid_sql::match_uuid_clause("tp.project_id"))
+            );
+
+            let rows = sqlx::query(&sql)
+                .bind(project_id.to_string())
+                .bind(project_id.to_string())
+                .fetch_all(pool)
+                .await?;
+
+            let mut parsed = Vec::with_capacity(rows.len());
+            for row in rows {
+                parsed.push(row_parsers::db_progress_from_row(&row)?);
+            }
+
+            parsed
+        }
+    };
+
+    let items = rows.into_iter().map(Progress::try_from).collect::<Result<_, _>>()?;
+    Ok(items)
+}
+
 #[utoipa::path(
     post,
     path = "/projects/{project_id}/tasks/{task_id}/progress",
     tag = "Progress",
-    params(("project_id" = Uuid, Path, description = "Project id"), ("task_id" = Uuid, Path, description = "Task id")),
+    params(
+        ("project_id" = Uuid, Path, description = "Project id"),
+        ("task_id" = Uuid, Path, description = "Task id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retry-safe key; repeating it returns the entry it created")
+    ),
     request_body = ProgressCreateRequest,
-    responses((status = 201, description = "Progress created", body = Progress))
+    responses(
+        (status = 201, description = "Progress created", body = Progress),
+        (status = 200, description = "Progress previously created with the same Idempotency-Key", body = Progress)
+    )
 )]
 pub async fn create_progress(
     State(state): State<AppState>,
     Path((project_id, task_id)): Path<(Uuid, Uuid)>,
     auth: AuthUser,
-    Json(payload): Json<ProgressCreateRequest>,
+    headers: axum::http::HeaderMap,
+    AppJson(payload): AppJson<ProgressCreateRequest>,
 ) -> AppResult<(StatusCode, Json<Progress>)> {
     ensure_task_belongs_to_user(&state.pool, auth.user_id, project_id, task_id).await?;
 
+    let idempotency_key = idempotency::key_from_headers(&headers)?;
+    let scope = format!("POST /projects/{}/tasks/{}/progress", project_id, task_id);
+    if let Some(key) = idempotency_key.as_deref() {
+        if let Some(existing) = idempotency::replay::<Progress>(&state.pool, auth.user_id, &scope, key).await? {
+            return Ok((StatusCode::OK, Json(existing)));
+        }
+    }
+
     if payload.progress < 0 || payload.progress > 100 {
         return Err(AppError::bad_request("progress must be between 0 and 100"));
     }
 
     let id = Uuid::new_v4();
     let now = utc_now();
+    let created_at = match payload.occurred_at {
+        Some(at) if at > now => return Err(AppError::bad_request("occurred_at must not be in the future")),
+        Some(at) => at,
+        None => now,
+    };
+
+    // A backdated entry only becomes the task's progress if nothing newer was recorded
+    let newer_exists = if payload.occurred_at.is_some() {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM task_progress WHERE task_id = ? AND deleted_at IS NULL AND created_at > ?)",
+        )
+        .bind(task_id)
+        .bind(created_at)
+        .fetch_one(&state.pool)
+        .await?
+    } else {
+        false
+    };
+
+    let sync = sync_task_progress() && !newer_exists;
+    let old_task: Option<Task> = if sync {
+        Some(fetch_task(&state.pool, auth.user_id, project_id, task_id).await?.try_into()?)
+    } else {
+        None
+    };
+
+    let mut tx = state.pool.begin().await?;
 
     sqlx::query(
         "INSERT INTO task_progress (id, task_id, project_id, progress, note, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
@@ -210,11 +389,36 @@ pub async fn create_progress(
     .bind(project_id)
     .bind(payload.progress)
     .bind(payload.note)
+    .bind(created_at)
     .bind(now)
-    .bind(now)
-    .execute(&state.pool)
+    .execute(&mut *tx)
     .await?;
 
+    // The new entry is the latest one, so mirror it onto the task column
+    if sync {
+        sqlx::query(&format!(
+            "UPDATE tasks SET progress = ?, updated_at = ? WHERE {}",
+            uuid_sql::match_uuid_clause("id")
+        ))
+        .bind(payload.progress)
+        .bind(now)
+        .bind(task_id.to_string())
+        .bind(task_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(key) = idempotency_key.as_deref() {
+        if !idempotency::claim(&mut tx, auth.user_id, &scope, key, project_id, id).await? {
+            // A concurrent retry won the race; discard our insert and return its entry
+            tx.rollback().await?;
+            let existing: Progress = idempotency::replay_claimed(&state.pool, auth.user_id, &scope, key).await?;
+            return Ok((StatusCode::OK, Json(existing)));
+        }
+    }
+
+    tx.commit().await?;
+
     let simple = sqlx::query_as::<_, DbProgress>(
         "SELECT id, project_id, task_id, progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE id = ?",
     )
@@ -245,9 +449,143 @@ pub async fn create_progress(
     };
 
     let item: Progress = row.try_into()?;
+    if let Some(key) = idempotency_key.as_deref() {
+        idempotency::record(&state.pool, auth.user_id, &scope, key, &item).await?;
+    }
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "created", Some(auth.user_id), &item, None, Some(ctx.clone()));
+
+    if let Some(old_task) = old_task {
+        let task: Task = fetch_task(&state.pool, auth.user_id, project_id, task_id).await?.try_into()?;
+        crate::events::log_activity_with_context(&state.event_bus, "updated", Some(auth.user_id), &task, Some(&old_task), Some(ctx));
+    }
+
     Ok((StatusCode::CREATED, Json(item)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/projects/{project_id}/tasks/{task_id}/progress/batch",
+    tag = "Progress",
+    params(("project_id" = Uuid, Path, description = "Project id"), ("task_id" = Uuid, Path, description = "Task id")),
+    request_body = ProgressBatchCreateRequest,
+    responses(
+        (status = 201, description = "Progress entries created, in request order", body = [Progress]),
+        (status = 400, description = "No entries"),
+        (status = 422, description = "Invalid entries; nothing is stored and fields are `entries[i].<field>`")
+    )
+)]
+pub async fn create_progress_batch(
+    State(state): State<AppState>,
+    Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    AppJson(payload): AppJson<ProgressBatchCreateRequest>,
+) -> AppResult<(StatusCode, Json<Vec<Progress>>)> {
+    ensure_task_belongs_to_user(&state.pool, auth.user_id, project_id, task_id).await?;
+
+    if payload.entries.is_empty() {
+        return Err(AppError::bad_request("entries must not be empty"));
+    }
+
+    // Check every entry up front so all problems are reported and nothing is stored
+    let now = utc_now();
+    let mut invalid = Vec::new();
+    for (index, entry) in payload.entries.iter().enumerate() {
+        let parent = format!("entries[{}]", index);
+        if entry.progress < 0 || entry.progress > 100 {
+            invalid.push(FieldError::new("progress", "progress must be between 0 and 100").within(&parent));
+        }
+        if entry.occurred_at.is_some_and(|at| at > now) {
+            invalid.push(FieldError::new("occurred_at", "occurred_at must not be in the future").within(&parent));
+        }
+    }
+    if !invalid.is_empty() {
+        return Err(AppError::validation(invalid));
+    }
+
+    let items: Vec<Progress> = payload
+        .entries
+        .into_iter()
+        .map(|entry| Progress {
+            id: Uuid::new_v4(),
+            project_id,
+            task_id,
+            progress: entry.progress,
+            note: entry.note,
+            created_at: entry.occurred_at.unwrap_or(now),
+            updated_at: now,
+            deleted_at: None,
+        })
+        .collect();
+
+    // Only the batch's latest entry can become the task's progress, and only if nothing newer exists
+    let latest = items.iter().max_by_key(|item| item.created_at).map(|item| (item.progress, item.created_at));
+    let sync_progress = match latest {
+        Some((progress, latest_at)) if sync_task_progress() => {
+            let newer_exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM task_progress WHERE task_id = ? AND deleted_at IS NULL AND created_at > ?)",
+            )
+            .bind(task_id)
+            .bind(latest_at)
+            .fetch_one(&state.pool)
+            .await?;
+            (!newer_exists).then_some(progress)
+        }
+        _ => None,
+    };
+    let old_task: Option<Task> = if sync_progress.is_some() {
+        Some(fetch_task(&state.pool, auth.user_id, project_id, task_id).await?.try_into()?)
+    } else {
+        None
+    };
+
+    let mut tx = state.pool.begin().await?;
+
+    for item in items.iter() {
+        sqlx::query(
+            "INSERT INTO task_progress (id, task_id, project_id, progress, note, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(item.id)
+        .bind(task_id)
+        .bind(project_id)
+        .bind(item.progress)
+        .bind(&item.note)
+        .bind(item.created_at)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if let Some(progress) = sync_progress {
+        sqlx::query(&format!(
+            "UPDATE tasks SET progress = ?, updated_at = ? WHERE {}",
+            uuid_sql::match_uuid_clause("id")
+        ))
+        .bind(progress)
+        .bind(now)
+        .bind(task_id.to_string())
+        .bind(task_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    for item in items.iter() {
+        crate::events::log_activity_with_context(&state.event_bus, "created", Some(auth.user_id), item, None, Some(ctx.clone()));
+    }
+
+    if let Some(old_task) = old_task {
+        let task: Task = fetch_task(&state.pool, auth.user_id, project_id, task_id).await?.try_into()?;
+        crate::events::log_activity_with_context(&state.event_bus, "updated", Some(auth.user_id), &task, Some(&old_task), Some(ctx));
+    }
+
+    Ok((StatusCode::CREATED, Json(items)))
+}
+
 #[utoipa::path(
     put,
     path = "/projects/{project_id}/tasks/{task_id}/progress/{id}",
@@ -260,40 +598,13 @@ pub async fn update_progress(
     State(state): State<AppState>,
     Path((project_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
     auth: AuthUser,
-    Json(payload): Json<ProgressUpdateRequest>,
+    headers: axum::http::HeaderMap,
+    AppJson(payload): AppJson<ProgressUpdateRequest>,
 ) -> AppResult<Json<Progress>> {
     ensure_task_belongs_to_user(&state.pool, auth.user_id, project_id, task_id).await?;
 
-    let simple = sqlx::query_as::<_, DbProgress>(
-        "SELECT id, project_id, task_id, progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE id = ? AND task_id = ? AND deleted_at IS NULL",
-    )
-    .bind(id)
-    .bind(task_id)
-    .fetch_optional(&state.pool)
-    .await?;
-
-    let mut row = match simple {
-        Some(r) => r,
-        None => {
-            // try fallback selecting textified UUIDs
-            let fallback = sqlx::query(
-                "SELECT \
-                   CASE WHEN typeof(id)='blob' THEN lower(substr(hex(id),1,8) || '-' || substr(hex(id),9,4) || '-' || substr(hex(id),13,4) || '-' || substr(hex(id),17,4) || '-' || substr(hex(id),21)) ELSE id END as id, \
-                   CASE WHEN typeof(project_id)='blob' THEN lower(substr(hex(project_id),1,8) || '-' || substr(hex(project_id),9,4) || '-' || substr(hex(project_id),13,4) || '-' || substr(hex(project_id),17,4) || '-' || substr(hex(project_id),21)) ELSE project_id END as project_id, \
-                   CASE WHEN typeof(task_id)='blob' THEN lower(substr(hex(task_id),1,8) || '-' || substr(hex(task_id),9,4) || '-' || substr(hex(task_id),13,4) || '-' || substr(hex(task_id),17,4) || '-' || substr(hex(task_id),21)) ELSE task_id END as task_id, \
-                   progress, note, created_at, updated_at, deleted_at \
-                 FROM task_progress WHERE ((typeof(id)='blob' AND hex(id)=upper(replace(?,'-',''))) OR (typeof(id)='text' AND id = ?)) AND task_id = ? AND deleted_at IS NULL",
-            )
-            .bind(id.to_string())
-            .bind(id.to_string())
-            .bind(task_id.to_string())
-            .fetch_optional(&state.pool)
-            .await?;
-
-            let row = fallback.ok_or_else(|| AppError::not_found("progress entry not found"))?;
-            row_parsers::db_progress_from_row(&row)?
-        }
-    };
+    let mut row = fetch_progress_entry(&state.pool, task_id, id).await?;
+    let old_item: Progress = row.clone().try_into()?;
 
     if let Some(p) = payload.progress {
         if p < 0 || p > 100 {
@@ -319,6 +630,10 @@ pub async fn update_progress(
 
     row.updated_at = now;
     let item: Progress = row.try_into()?;
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "updated", Some(auth.user_id), &item, Some(&old_item), Some(ctx));
+
     Ok(Json(item))
 }
 
@@ -333,9 +648,12 @@ pub async fn delete_progress(
     State(state): State<AppState>,
     Path((project_id, task_id, id)): Path<(Uuid, Uuid, Uuid)>,
     auth: AuthUser,
+    headers: axum::http::HeaderMap,
 ) -> AppResult<StatusCode> {
     ensure_task_belongs_to_user(&state.pool, auth.user_id, project_id, task_id).await?;
 
+    let old_item: Progress = fetch_progress_entry(&state.pool, task_id, id).await?.try_into()?;
+
     let now = utc_now();
     let affected = sqlx::query("UPDATE task_progress SET deleted_at = ?, updated_at = ? WHERE id = ? AND task_id = ? AND deleted_at IS NULL")
         .bind(now)
@@ -349,6 +667,9 @@ pub async fn delete_progress(
         return Err(AppError::not_found("progress entry not found"));
     }
 
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "deleted", Some(auth.user_id), &old_item, None, Some(ctx));
+
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -410,8 +731,95 @@ async fn ensure_task_belongs_to_user(pool: &SqlitePool, user_id: Uuid, project_i
     .await?;
 
     let owner = owner.ok_or_else(|| AppError::not_found("task or project not found"))?;
-    if owner != user_id {
+    if owner != user_id && !is_project_member(pool, project_id, user_id).await? {
         return Err(AppError::forbidden("not allowed to access this task"));
     }
     Ok(())
 }
+
+/// A live progress entry of `task_id` (no ownership check).
+async fn fetch_progress_entry(pool: &SqlitePool, task_id: Uuid, id: Uuid) -> AppResult<DbProgress> {
+    let simple = sqlx::query_as::<_, DbProgress>(
+        "SELECT id, project_id, task_id, progress, note, created_at, updated_at, deleted_at FROM task_progress WHERE id = ? AND task_id = ? AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .bind(task_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let row = match simple {
+        Some(r) => r,
+        None => {
+            // try fallback selecting textified UUIDs
+            let fallback = sqlx::query(
+                "SELECT \
+                   CASE WHEN typeof(id)='blob' THEN lower(substr(hex(id),1,8) || '-' || substr(hex(id),9,4) || '-' || substr(hex(id),13,4) || '-' || substr(hex(id),17,4) || '-' || substr(hex(id),21)) ELSE id END as id, \
+                   CASE WHEN typeof(project_id)='blob' THEN lower(substr(hex(project_id),1,8) || '-' || substr(hex(project_id),9,4) || '-' || substr(hex(project_id),13,4) || '-' || substr(hex(project_id),17,4) || '-' || substr(hex(project_id),21)) ELSE project_id END as project_id, \
+                   CASE WHEN typeof(task_id)='blob' THEN lower(substr(hex(task_id),1,8) || '-' || substr(hex(task_id),9,4) || '-' || substr(hex(task_id),13,4) || '-' || substr(hex(task_id),17,4) || '-' || substr(hex(task_id),21)) ELSE task_id END as task_id, \
+                   progress, note, created_at, updated_at, deleted_at \
+                 FROM task_progress WHERE ((typeof(id)='blob' AND hex(id)=upper(replace(?,'-',''))) OR (typeof(id)='text' AND id = ?)) AND task_id = ? AND deleted_at IS NULL",
+            )
+            .bind(id.to_string())
+            .bind(id.to_string())
+            .bind(task_id.to_string())
+            .fetch_optional(pool)
+            .await?;
+
+            let row = fallback.ok_or_else(|| AppError::not_found("progress entry not found"))?;
+            row_parsers::db_progress_from_row(&row)?
+        }
+    };
+
+    Ok(row)
+}
+
+/// Every non-deleted progress entry of a project's live tasks, oldest first (no ownership check).
+pub(crate) async fn fetch_project_progress(pool: &SqlitePool, project_id: Uuid) -> AppResult<Vec<Progress>> {
+    let base = |project_clause: &str| {
+        format!(
+            "FROM task_progress tp INNER JOIN tasks t ON t.id = tp.task_id \
+             WHERE {} AND tp.deleted_at IS NULL AND t.deleted_at IS NULL ORDER BY tp.created_at ASC",
+            project_clause
+        )
+    };
+
+    let simple = sqlx::query_as::<_, DbProgress>(&format!(
+        "SELECT tp.id, tp.project_id, tp.task_id, tp.progress, tp.note, tp.created_at, tp.updated_at, tp.deleted_at {}",
+        base("tp.project_id = ?")
+    ))
+    .bind(project_id)
+    .fetch_all(pool)
+    .await;
+
+    let rows = match simple {
+        Ok(r) => r,
+        Err(_) => {
+            let id_case = uuid_sql::case_uuid("tp.id");
+            let project_case = uuid_sql::case_uuid("tp.project_id");
+            let task_case = uuid_sql::case_uuid("tp.task_id");
+            let sql = format!(
+                "SELECT {} , {} , {} , tp.progress, tp.note, tp.created_at, tp.updated_at, tp.deleted_at {}",
+                id_case,
+                project_case,
+                task_case,
+                base(&uuid_sql::match_uuid_clause("tp.project_id"))
+            );
+
+            let rows = sqlx::query(&sql)
+                .bind(project_id.to_string())
+                .bind(project_id.to_string())
+                .fetch_all(pool)
+                .await?;
+
+            let mut parsed = Vec::with_capacity(rows.len());
+            for row in rows {
+                parsed.push(row_parsers::db_progress_from_row(&row)?);
+            }
+
+            parsed
+        }
+    };
+
+    let items = rows.into_iter().map(Progress::try_from).collect::<Result<_, _>>()?;
+    Ok(items)
+}
@@ -1,21 +1,39 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
+use axum::response::Response;
 use sqlx::SqlitePool;
 use sqlx::Row;
 use uuid::Uuid;
-use crate::db::{uuid_sql, row_parsers};
+use crate::db::{uuid_sql, row_parsers, task_duration};
 
 use crate::app::AppState;
-use crate::errors::{AppError, AppResult};
+use crate::errors::{AppError, AppResult, FieldError};
+use crate::extract::AppJson;
 use crate::jwt::AuthUser;
 use crate::models::project::{DbProject, Project, ProjectCreateRequest, ProjectUpdateRequest};
-use crate::models::project_plan::{DbProjectPlanPoint, ProjectPlanPoint};
-use serde::Serialize;
-use utoipa::ToSchema;
-use crate::utils::utc_now;
+use crate::models::project_plan::{DbProjectPlanPoint, ProjectPlanCreateRequest, ProjectPlanPoint};
+use crate::models::task::{Task, TaskStatus};
+use crate::models::dependency::TaskDependency;
+use crate::routes::{etag, idempotency, preconditions};
+use crate::routes::members::is_project_member;
+use crate::routes::tasks::{fetch_project_dependencies, fetch_project_tasks};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use crate::utils::{duration_days, normalize_to_midnight, utc_now};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 const DEFAULT_THEME: &str = "#3498db";
+const DEFAULT_MAX_PLAN_POINTS: usize = 1000;
+
+/// Upper bound on plan points accepted per project (`MAX_PLAN_POINTS`, default 1000).
+fn max_plan_points() -> usize {
+    std::env::var("MAX_PLAN_POINTS")
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_PLAN_POINTS)
+}
 
 #[utoipa::path(
     get,
@@ -24,14 +42,30 @@ const DEFAULT_THEME: &str = "#3498db";
     responses((status = 200, description = "List projects", body = [Project]))
 )]
 pub async fn list_projects(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<Vec<Project>>> {
+    let projects = fetch_user_projects(&state.pool, auth.user_id, true).await?;
+    Ok(Json(projects))
+}
+
+/// All non-deleted projects owned by `user_id` (plus those shared with them when
+/// `include_shared` is set), newest first.
+pub(crate) async fn fetch_user_projects(pool: &SqlitePool, user_id: Uuid, include_shared: bool) -> AppResult<Vec<Project>> {
+    let shared = if include_shared {
+        " OR id IN (SELECT project_id FROM project_members WHERE user_id = ?)"
+    } else {
+        ""
+    };
+
     // Try the simple, direct SELECT first (fast path). If decoding fails due to mixed UUID storage
     // (BLOB vs TEXT), fall back to a query that returns text UUIDs and map manually.
-    let simple = sqlx::query_as::<_, DbProject>(
-        "SELECT id, user_id, name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE user_id = ? AND deleted_at IS NULL ORDER BY created_at DESC",
-    )
-    .bind(auth.user_id)
-    .fetch_all(&state.pool)
-    .await;
+    let sql = format!(
+        "SELECT id, user_id, name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE (user_id = ?{}) AND deleted_at IS NULL ORDER BY created_at DESC",
+        shared
+    );
+    let mut query = sqlx::query_as::<_, DbProject>(&sql).bind(user_id);
+    if include_shared {
+        query = query.bind(user_id);
+    }
+    let simple = query.fetch_all(pool).await;
 
     let projects: Vec<DbProject> = match simple {
         Ok(rows) => rows,
@@ -40,16 +74,22 @@ pub async fn list_projects(State(state): State<AppState>, auth: AuthUser) -> App
             let id_case = uuid_sql::case_uuid("id");
             let user_case = uuid_sql::case_uuid("user_id");
             let match_user = uuid_sql::match_uuid_clause("user_id");
+            let shared = if include_shared {
+                format!(" OR id IN (SELECT m.project_id FROM project_members m WHERE {})", uuid_sql::match_uuid_clause("m.user_id"))
+            } else {
+                String::new()
+            };
             let sql = format!(
-                "SELECT {} , {} , name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE {} AND deleted_at IS NULL ORDER BY created_at DESC",
-                id_case, user_case, match_user
+                "SELECT {} , {} , name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE ({}{}) AND deleted_at IS NULL ORDER BY created_at DESC",
+                id_case, user_case, match_user, shared
             );
 
-            let rows = sqlx::query(&sql)
-                .bind(auth.user_id.to_string())
-                .bind(auth.user_id.to_string())
-                .fetch_all(&state.pool)
-                .await?;
+            let binds = if include_shared { 4 } else { 2 };
+            let mut query = sqlx::query(&sql);
+            for _ in 0..binds {
+                query = query.bind(user_id.to_string());
+            }
+            let rows = query.fetch_all(pool).await?;
 
             // Map each row from sqlx::Row to DbProject by extracting columns and parsing types
             let mut parsed = Vec::with_capacity(rows.len());
@@ -66,6 +106,44 @@ pub async fn list_projects(State(state): State<AppState>, auth: AuthUser) -> App
         .map(Project::try_from)
         .collect::<Result<_, _>>()?;
 
+    Ok(projects)
+}
+
+/// Soft-deleted projects owned by the caller, most recently deleted first
+#[utoipa::path(
+    get,
+    path = "/projects/trash",
+    tag = "Projects",
+    responses((status = 200, description = "Deleted projects with `deleted_at` set", body = [Project]))
+)]
+pub async fn list_trashed_projects(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<Vec<Project>>> {
+    // Owner only: collaborators lose access when a project is deleted
+    let simple = sqlx::query_as::<_, DbProject>(
+        "SELECT id, user_id, name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE user_id = ? AND deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+    )
+    .bind(auth.user_id)
+    .fetch_all(&state.pool)
+    .await;
+
+    let projects: Vec<DbProject> = match simple {
+        Ok(rows) => rows,
+        Err(_) => {
+            let sql = format!(
+                "SELECT {} , {} , name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE {} AND deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+                uuid_sql::case_uuid("id"),
+                uuid_sql::case_uuid("user_id"),
+                uuid_sql::match_uuid_clause("user_id")
+            );
+            let rows = sqlx::query(&sql)
+                .bind(auth.user_id.to_string())
+                .bind(auth.user_id.to_string())
+                .fetch_all(&state.pool)
+                .await?;
+            rows.iter().map(row_parsers::db_project_from_row).collect::<Result<_, _>>()?
+        }
+    };
+
+    let projects: Vec<Project> = projects.into_iter().map(Project::try_from).collect::<Result<_, _>>()?;
     Ok(Json(projects))
 }
 
@@ -73,19 +151,32 @@ pub async fn list_projects(State(state): State<AppState>, auth: AuthUser) -> App
     post,
     path = "/projects",
     tag = "Projects",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Retry-safe key; repeating it returns the project it created")),
     request_body = ProjectCreateRequest,
-    responses((status = 201, description = "Project created", body = Project))
+    responses(
+        (status = 201, description = "Project created", body = Project),
+        (status = 200, description = "Project previously created with the same Idempotency-Key", body = Project)
+    )
 )]
 pub async fn create_project(
     State(state): State<AppState>,
     auth: AuthUser,
     headers: axum::http::HeaderMap,
-    Json(payload): Json<ProjectCreateRequest>,
+    AppJson(payload): AppJson<ProjectCreateRequest>,
 ) -> AppResult<(StatusCode, Json<Project>)> {
+    let idempotency_key = idempotency::key_from_headers(&headers)?;
+    let scope = "POST /projects";
+    if let Some(key) = idempotency_key.as_deref() {
+        if let Some(existing) = idempotency::replay::<Project>(&state.pool, auth.user_id, scope, key).await? {
+            return Ok((StatusCode::OK, Json(existing)));
+        }
+    }
+
     let now = utc_now();
     let project_id = Uuid::new_v4();
     let theme_color = payload.theme_color.clone().unwrap_or_else(|| DEFAULT_THEME.to_string());
 
+    let mut tx = state.pool.begin().await?;
     sqlx::query(
         "INSERT INTO projects (id, user_id, name, description, theme_color, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
     )
@@ -96,11 +187,24 @@ pub async fn create_project(
     .bind(&theme_color)
     .bind(now)
     .bind(now)
-    .execute(&state.pool)
+    .execute(&mut *tx)
     .await?;
 
+    if let Some(key) = idempotency_key.as_deref() {
+        if !idempotency::claim(&mut tx, auth.user_id, scope, key, project_id, project_id).await? {
+            // A concurrent retry won the race; discard our insert and return its project
+            tx.rollback().await?;
+            let existing: Project = idempotency::replay_claimed(&state.pool, auth.user_id, scope, key).await?;
+            return Ok((StatusCode::OK, Json(existing)));
+        }
+    }
+    tx.commit().await?;
+
     let project = fetch_project(&state.pool, auth.user_id, project_id).await?;
     let project: Project = project.try_into()?;
+    if let Some(key) = idempotency_key.as_deref() {
+        idempotency::record(&state.pool, auth.user_id, scope, key, &project).await?;
+    }
 
     // Log activity with request context
     let ctx = crate::events::RequestContext::from_headers(&headers);
@@ -120,37 +224,53 @@ pub async fn create_project(
     get,
     path = "/projects/{id}",
     tag = "Projects",
-    params(("id" = Uuid, Path, description = "Project id")),
-    responses((status = 200, description = "Project detail", body = Project))
+    params(
+        ("id" = Uuid, Path, description = "Project id"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from an earlier response; a match returns 304")
+    ),
+    responses(
+        (status = 200, description = "Project detail, with an `ETag` header", body = Project),
+        (status = 304, description = "Project unchanged since the given ETag")
+    )
 )]
 pub async fn get_project(
     State(state): State<AppState>,
     auth: AuthUser,
+    headers: axum::http::HeaderMap,
     Path(id): Path<Uuid>,
-) -> AppResult<Json<Project>> {
+) -> AppResult<Response> {
     let project = fetch_project(&state.pool, auth.user_id, id).await?;
     let project: Project = project.try_into()?;
-    Ok(Json(project))
+    let tag = etag::weak_etag([&project.updated_at]);
+    Ok(etag::conditional_json(&headers, tag, project))
 }
 
 #[utoipa::path(
     put,
     path = "/projects/{id}",
     tag = "Projects",
-    params(("id" = Uuid, Path, description = "Project id")),
+    params(
+        ("id" = Uuid, Path, description = "Project id"),
+        ("If-Unmodified-Since" = Option<String>, Header, description = "HTTP date; the update fails with 409 if the project changed after it")
+    ),
     request_body = ProjectUpdateRequest,
-    responses((status = 200, description = "Project updated", body = Project))
+    responses(
+        (status = 200, description = "Project updated", body = Project),
+        (status = 409, description = "Project changed since `expected_updated_at` / `If-Unmodified-Since`; refetch and retry")
+    )
 )]
 pub async fn update_project(
     State(state): State<AppState>,
     auth: AuthUser,
     headers: axum::http::HeaderMap,
     Path(id): Path<Uuid>,
-    Json(payload): Json<ProjectUpdateRequest>,
+    AppJson(payload): AppJson<ProjectUpdateRequest>,
 ) -> AppResult<Json<Project>> {
     // Capture old state before modifications
     let old_project = fetch_project(&state.pool, auth.user_id, id).await?;
+    ensure_owner(&old_project, auth.user_id, "only the project owner can edit it")?;
     let old_dto: Project = old_project.clone().try_into()?;
+    let expected = preconditions::expected_version(&headers, payload.expected_updated_at, old_dto.updated_at, "project")?;
 
     let mut project = old_project;
 
@@ -166,17 +286,29 @@ pub async fn update_project(
 
     let now = utc_now();
 
-    sqlx::query(
+    let mut sql = String::from(
         "UPDATE projects SET name = ?, description = ?, theme_color = ?, updated_at = ? WHERE id = ? AND user_id = ?",
-    )
-    .bind(&project.name)
-    .bind(&project.description)
-    .bind(&project.theme_color)
-    .bind(now)
-    .bind(project.id)
-    .bind(auth.user_id)
-    .execute(&state.pool)
-    .await?;
+    );
+    if expected.is_some() {
+        sql.push_str(" AND updated_at = ?");
+    }
+    let mut query = sqlx::query(&sql)
+        .bind(&project.name)
+        .bind(&project.description)
+        .bind(&project.theme_color)
+        .bind(now)
+        .bind(project.id)
+        .bind(auth.user_id);
+    if let Some(version) = expected {
+        query = query.bind(version);
+    }
+    let result = query.execute(&state.pool).await?;
+
+    if expected.is_some() && result.rows_affected() == 0 {
+        // Deleted in the meantime stays a 404; anything else is a newer edit
+        fetch_project(&state.pool, auth.user_id, id).await?;
+        return Err(preconditions::stale("project"));
+    }
 
     project.updated_at = now;
     let project: Project = project.try_into()?;
@@ -208,8 +340,9 @@ pub async fn delete_project(
     headers: axum::http::HeaderMap,
     Path(id): Path<Uuid>,
 ) -> AppResult<StatusCode> {
-    // Ensure project exists and belongs to user
+    // Ensure project exists and belongs to user; members may not delete it
     let db_project = fetch_project(&state.pool, auth.user_id, id).await?;
+    ensure_owner(&db_project, auth.user_id, "only the project owner can delete it")?;
     let project: Project = db_project.clone().try_into()?;
 
     let now = utc_now();
@@ -239,14 +372,113 @@ pub async fn delete_project(
     Ok(StatusCode::NO_CONTENT)
 }
 
-async fn fetch_project(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> AppResult<DbProject> {
+/// `?confirm=true`, required by the permanent-delete endpoints
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PurgeQuery {
+    pub confirm: Option<bool>,
+}
+
+impl PurgeQuery {
+    pub(crate) fn require_confirmation(&self) -> AppResult<()> {
+        if self.confirm == Some(true) {
+            Ok(())
+        } else {
+            Err(AppError::bad_request("permanent deletion requires ?confirm=true"))
+        }
+    }
+}
+
+/// Permanently delete a soft-deleted project with its tasks, progress, plan, dependencies and members
+#[utoipa::path(
+    delete,
+    path = "/projects/{id}/purge",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id"), PurgeQuery),
+    responses(
+        (status = 204, description = "Project and all its rows removed"),
+        (status = 400, description = "Missing ?confirm=true"),
+        (status = 404, description = "Project not found"),
+        (status = 409, description = "Project is not in the trash; delete it first")
+    )
+)]
+pub async fn purge_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PurgeQuery>,
+) -> AppResult<StatusCode> {
+    query.require_confirmation()?;
+
+    // Owner only, in either state; collaborators never see a deleted project
+    let db_project = sqlx::query_as::<_, DbProject>(
+        "SELECT id, user_id, name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE id = ? AND user_id = ?",
+    )
+    .bind(id)
+    .bind(auth.user_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::not_found("project not found"))?;
+    if db_project.deleted_at.is_none() {
+        return Err(AppError::conflict("project is not deleted; delete it before purging"));
+    }
+    let project: Project = db_project.try_into()?;
+
+    // Writes only from here, so the transaction takes the write lock with its first statement
+    let mut tx = state.pool.begin().await?;
+
+    // Children first, so nothing dangles even if FK enforcement is off
+    let project_tasks = "SELECT id FROM tasks WHERE project_id = ?";
+    for sql in [
+        format!("DELETE FROM task_dependencies WHERE source_task_id IN ({project_tasks}) OR target_task_id IN ({project_tasks})"),
+        format!("DELETE FROM task_progress WHERE project_id = ? OR task_id IN ({project_tasks})"),
+    ] {
+        sqlx::query(&sql).bind(id).bind(id).execute(&mut *tx).await?;
+    }
+    for sql in [
+        "DELETE FROM task_tags WHERE task_id IN (SELECT id FROM tasks WHERE project_id = ?)",
+        "DELETE FROM idempotency_keys WHERE project_id = ?",
+        "DELETE FROM tasks WHERE project_id = ?",
+        "DELETE FROM project_plan WHERE project_id = ?",
+        "DELETE FROM project_members WHERE project_id = ?",
+        "DELETE FROM projects WHERE id = ?",
+    ] {
+        sqlx::query(sql).bind(id).execute(&mut *tx).await?;
+    }
+
+    tx.commit().await?;
+
+    crate::events::log_activity_with_context(
+        &state.event_bus,
+        "purged",
+        Some(auth.user_id),
+        &project,
+        None,
+        Some(crate::events::RequestContext::from_headers(&headers)),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn ensure_owner(project: &DbProject, user_id: Uuid, message: &str) -> AppResult<()> {
+    if project.user_id != user_id {
+        return Err(AppError::forbidden(message));
+    }
+    Ok(())
+}
+
+/// A live project that `user_id` owns or is a member of.
+pub(crate) async fn fetch_project(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> AppResult<DbProject> {
     // Try the simple (original) path first. If row conversion fails (e.g., mixed UUID storage blob/text),
     // fall back to a query that handles both blob and text UUID representations.
     let simple = sqlx::query_as::<_, DbProject>(
-        "SELECT id, user_id, name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE id = ? AND user_id = ? AND deleted_at IS NULL",
+        "SELECT id, user_id, name, description, theme_color, created_at, updated_at, deleted_at FROM projects \
+         WHERE id = ? AND (user_id = ? OR id IN (SELECT project_id FROM project_members WHERE user_id = ?)) AND deleted_at IS NULL",
     )
     .bind(project_id)
     .bind(user_id)
+    .bind(user_id)
     .fetch_optional(pool)
     .await;
 
@@ -259,10 +491,12 @@ async fn fetch_project(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> Ap
             let user_case = uuid_sql::case_uuid("user_id");
             let match_id = uuid_sql::match_uuid_clause("id");
             let match_user = uuid_sql::match_uuid_clause("user_id");
+            let match_member = uuid_sql::match_uuid_clause("m.user_id");
 
             let sql = format!(
-                "SELECT {} , {} , name, description, theme_color, created_at, updated_at, deleted_at FROM projects WHERE {} AND {} AND deleted_at IS NULL",
-                id_case, user_case, match_id, match_user
+                "SELECT {} , {} , name, description, theme_color, created_at, updated_at, deleted_at FROM projects \
+                 WHERE {} AND ({} OR id IN (SELECT m.project_id FROM project_members m WHERE {})) AND deleted_at IS NULL",
+                id_case, user_case, match_id, match_user, match_member
             );
 
             let fallback = sqlx::query(&sql)
@@ -270,6 +504,8 @@ async fn fetch_project(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> Ap
                 .bind(project_id.to_string())
                 .bind(user_id.to_string())
                 .bind(user_id.to_string())
+                .bind(user_id.to_string())
+                .bind(user_id.to_string())
                 .fetch_optional(pool)
                 .await?;
 
@@ -282,6 +518,48 @@ async fn fetch_project(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> Ap
     }
 }
 
+/// Load a project's planned points ordered by date (no ownership check).
+pub(crate) async fn fetch_plan_points(pool: &SqlitePool, project_id: Uuid) -> AppResult<Vec<ProjectPlanPoint>> {
+    // fetch planned points (try fast-path mapping then fallback to tolerant parsing)
+    let simple = sqlx::query_as::<_, DbProjectPlanPoint>(
+        "SELECT id, project_id, date, planned_progress, created_at, updated_at FROM project_plan WHERE project_id = ? ORDER BY date ASC",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await;
+
+    let plan_rows: Vec<DbProjectPlanPoint> = match simple {
+        Ok(r) => r,
+        Err(_) => {
+            let id_case = uuid_sql::case_uuid("id");
+            let proj_case = uuid_sql::case_uuid("project_id");
+            let sql = format!(
+                "SELECT {} , {} , date, planned_progress, created_at, updated_at FROM project_plan WHERE project_id = ? ORDER BY date ASC",
+                id_case, proj_case
+            );
+
+            let rows = sqlx::query(&sql)
+                .bind(project_id.to_string())
+                .fetch_all(pool)
+                .await?;
+
+            let mut parsed = Vec::with_capacity(rows.len());
+            for row in rows {
+                parsed.push(row_parsers::db_project_plan_point_from_row(&row)?);
+            }
+
+            parsed
+        }
+    };
+
+    let plan: Vec<ProjectPlanPoint> = plan_rows
+        .into_iter()
+        .map(ProjectPlanPoint::try_from)
+        .collect::<Result<_, _>>()?;
+
+    Ok(plan)
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ActualPoint {
     pub date: String,
@@ -293,122 +571,369 @@ pub struct DashboardResponse {
     pub project: Project,
     pub plan: Vec<ProjectPlanPoint>,
     pub actual: Vec<ActualPoint>,
+    /// Average change in `actual` per calendar day over the last `velocity_days` days (0 without enough history)
+    pub velocity: f64,
+}
+
+const DEFAULT_VELOCITY_DAYS: i64 = 7;
+/// Larger `velocity_days` windows are clamped to this (about 100 years)
+const MAX_VELOCITY_DAYS: i64 = 36_500;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DashboardQuery {
+    /// Window, in days, used to compute `velocity` (default 7, at most 36500)
+    pub velocity_days: Option<i64>,
+}
+
+/// Average daily change of the aggregated actual progress across the days in
+/// `(today - days, today]`. Each point is one calendar day, so the change is
+/// divided by the number of days between the first and last point, not by the
+/// number of points; gaps without entries are spread evenly.
+fn progress_velocity(actual: &[ActualPoint], today: NaiveDate, days: i64) -> AppResult<f64> {
+    let since = today
+        .checked_sub_signed(chrono::Duration::days(days))
+        .ok_or_else(|| AppError::bad_request("velocity_days reaches past the earliest supported date"))?;
+    let window: Vec<(NaiveDate, i32)> = actual
+        .iter()
+        .filter_map(|point| NaiveDate::parse_from_str(&point.date, "%Y-%m-%d").ok().map(|d| (d, point.actual)))
+        .filter(|(date, _)| *date > since && *date <= today)
+        .collect();
+
+    Ok(match (window.first(), window.last()) {
+        (Some((first_day, first)), Some((last_day, last))) if last_day > first_day => {
+            (last - first) as f64 / (*last_day - *first_day).num_days() as f64
+        }
+        _ => 0.0,
+    })
 }
 
 #[utoipa::path(
     get,
     path = "/projects/{id}/dashboard",
     tag = "Projects",
-    params(("id" = Uuid, Path, description = "Project id")),
+    params(("id" = Uuid, Path, description = "Project id"), DashboardQuery),
     responses((status = 200, description = "Project dashboard", body = DashboardResponse))
 )]
 pub async fn get_project_dashboard(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(id): Path<Uuid>,
+    Query(query): Query<DashboardQuery>,
 ) -> AppResult<Json<DashboardResponse>> {
+    let resp = load_dashboard(&state.pool, auth.user_id, id, query.velocity_days).await?;
+    Ok(Json(resp))
+}
+
+/// Plan, daily actuals and velocity of a project owned by `user_id`.
+pub(crate) async fn load_dashboard(
+    pool: &SqlitePool,
+    user_id: Uuid,
+    id: Uuid,
+    velocity_days: Option<i64>,
+) -> AppResult<DashboardResponse> {
+    let velocity_days = velocity_days.unwrap_or(DEFAULT_VELOCITY_DAYS);
+    if velocity_days < 1 {
+        return Err(AppError::bad_request("velocity_days must be at least 1"));
+    }
+    let velocity_days = velocity_days.min(MAX_VELOCITY_DAYS);
+
     // ensure project exists and belongs to user
-    let db_project = fetch_project(&state.pool, auth.user_id, id).await?;
+    let db_project = fetch_project(pool, user_id, id).await?;
     let project: Project = db_project.try_into()?;
 
-    // fetch planned points (try fast-path mapping then fallback to tolerant parsing)
-    let simple = sqlx::query_as::<_, DbProjectPlanPoint>(
-        "SELECT id, project_id, date, planned_progress, created_at, updated_at FROM project_plan WHERE project_id = ? ORDER BY date ASC",
+    let plan = fetch_plan_points(pool, id).await?;
+
+    // fetch actual aggregated progress per day; checked against the schema at build time
+    let actual = sqlx::query_as!(
+        ActualPoint,
+        r#"SELECT DATE(created_at) as "date!: String", CAST(ROUND(AVG(progress)) AS INTEGER) as "actual!: i32"
+           FROM task_progress WHERE project_id = ? AND deleted_at IS NULL
+           GROUP BY DATE(created_at) ORDER BY DATE(created_at) ASC"#,
+        id
     )
-    .bind(id)
-    .fetch_all(&state.pool)
-    .await;
+    .fetch_all(pool)
+    .await?;
 
-    let plan_rows: Vec<DbProjectPlanPoint> = match simple {
-        Ok(r) => r,
-        Err(_) => {
-            let id_case = uuid_sql::case_uuid("id");
-            let proj_case = uuid_sql::case_uuid("project_id");
-            let sql = format!(
-                "SELECT {} , {} , date, planned_progress, created_at, updated_at FROM project_plan WHERE project_id = ? ORDER BY date ASC",
-                id_case, proj_case
-            );
+    let velocity = progress_velocity(&actual, utc_now().date_naive(), velocity_days)?;
 
-            let rows = sqlx::query(&sql)
-                .bind(id.to_string())
-                .fetch_all(&state.pool)
-                .await?;
+    Ok(DashboardResponse { project, plan, actual, velocity })
+}
 
-            let mut parsed = Vec::with_capacity(rows.len());
-            for row in rows {
-                parsed.push(row_parsers::db_project_plan_point_from_row(&row)?);
-            }
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProjectStats {
+    pub total_tasks: i64,
+    /// Tasks with status `done`
+    pub completed_tasks: i64,
+    /// Mean task progress (0 when the project has no tasks)
+    pub average_progress: f64,
+    /// Task count per status value
+    pub by_status: BTreeMap<TaskStatus, i64>,
+}
 
-            parsed
+fn project_stats(tasks: &[Task]) -> ProjectStats {
+    stats_from(tasks.iter().map(|t| (t.status, t.progress as f64)))
+}
+
+/// Stats over top-level tasks only, with each parent's status and progress
+/// rolled up from its subtasks (recursively). Progress is the mean of the
+/// children; a parent counts as `done` once every child is done.
+fn rolled_up_project_stats(tasks: &[Task]) -> ProjectStats {
+    let ids: HashSet<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let mut children: HashMap<Uuid, Vec<&Task>> = HashMap::new();
+    for t in tasks {
+        if let Some(parent) = t.parent_id.filter(|p| ids.contains(p)) {
+            children.entry(parent).or_default().push(t);
         }
-    };
+    }
 
-    let plan: Vec<ProjectPlanPoint> = plan_rows
-        .into_iter()
-        .map(ProjectPlanPoint::try_from)
-        .collect::<Result<_, _>>()?;
+    fn roll_up(task: &Task, children: &HashMap<Uuid, Vec<&Task>>, path: &mut HashSet<Uuid>) -> (TaskStatus, f64) {
+        let kids = match children.get(&task.id) {
+            // A parent cycle is treated as a leaf rather than recursing forever
+            Some(kids) if path.insert(task.id) => kids,
+            _ => return (task.status, task.progress as f64),
+        };
+        let rolled: Vec<(TaskStatus, f64)> = kids.iter().map(|k| roll_up(k, children, path)).collect();
+        path.remove(&task.id);
+
+        let progress = rolled.iter().map(|(_, p)| p).sum::<f64>() / rolled.len() as f64;
+        let status = if rolled.iter().all(|(s, _)| *s == TaskStatus::Done) {
+            TaskStatus::Done
+        } else if task.status == TaskStatus::Done {
+            TaskStatus::InProgress
+        } else {
+            task.status
+        };
+        (status, progress)
+    }
 
-    // fetch actual aggregated progress per day
-    let actual_rows = sqlx::query_as::<_, (String, i64)>(
-        "SELECT DATE(created_at) as date, CAST(ROUND(AVG(progress)) AS INTEGER) as actual FROM task_progress WHERE project_id = ? AND deleted_at IS NULL GROUP BY DATE(created_at) ORDER BY DATE(created_at) ASC",
+    let mut path = HashSet::new();
+    stats_from(
+        tasks
+            .iter()
+            .filter(|t| !matches!(t.parent_id, Some(p) if ids.contains(&p)))
+            .map(|t| roll_up(t, &children, &mut path))
+            .collect::<Vec<_>>(),
     )
-    .bind(id)
-    .fetch_all(&state.pool)
-    .await?;
+}
 
-    let actual: Vec<ActualPoint> = actual_rows
-        .into_iter()
-        .map(|(date, actual)| ActualPoint { date, actual: actual as i32 })
-        .collect();
+fn stats_from(entries: impl IntoIterator<Item = (TaskStatus, f64)>) -> ProjectStats {
+    let mut by_status: BTreeMap<TaskStatus, i64> = BTreeMap::new();
+    let mut total_tasks = 0i64;
+    let mut progress_sum = 0.0;
+    for (status, progress) in entries {
+        *by_status.entry(status).or_default() += 1;
+        total_tasks += 1;
+        progress_sum += progress;
+    }
 
-    let resp = DashboardResponse { project, plan, actual };
+    let average_progress = if total_tasks == 0 { 0.0 } else { progress_sum / total_tasks as f64 };
 
-    Ok(Json(resp))
+    ProjectStats {
+        total_tasks,
+        completed_tasks: by_status.get(&TaskStatus::Done).cloned().unwrap_or(0),
+        average_progress,
+        by_status,
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectStatsQuery {
+    /// Count only top-level tasks, rolling subtask progress and status into their parents
+    pub include_subtasks: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/stats",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id"), ProjectStatsQuery),
+    responses((status = 200, description = "Task counts and average progress", body = ProjectStats))
+)]
+pub async fn get_project_stats(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ProjectStatsQuery>,
+) -> AppResult<Json<ProjectStats>> {
+    // ensure project exists and belongs to user
+    fetch_project(&state.pool, auth.user_id, id).await?;
+
+    let tasks = fetch_project_tasks(&state.pool, id).await?;
+    let stats = if query.include_subtasks.unwrap_or(false) {
+        rolled_up_project_stats(&tasks)
+    } else {
+        project_stats(&tasks)
+    };
+
+    Ok(Json(stats))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectFullQuery {
+    /// Comma-separated sections to include: `tasks`, `deps`, `plan`, `stats` (default: all)
+    pub include: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
-pub struct CriticalPathResponse {
-    pub task_ids: Vec<Uuid>,
+pub struct ProjectFullResponse {
+    pub project: Project,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<Vec<Task>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<TaskDependency>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<Vec<ProjectPlanPoint>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<ProjectStats>,
 }
 
 #[utoipa::path(
     get,
-    path = "/projects/{id}/critical-path",
+    path = "/projects/{id}/full",
     tag = "Projects",
-    params(("id" = Uuid, Path, description = "Project id")),
-    responses((status = 200, description = "Critical path task ids", body = CriticalPathResponse))
+    params(("id" = Uuid, Path, description = "Project id"), ProjectFullQuery),
+    responses(
+        (status = 200, description = "Project with tasks, dependencies, plan and stats", body = ProjectFullResponse),
+        (status = 400, description = "Unknown include section")
+    )
 )]
-pub async fn get_project_critical_path(
+pub async fn get_project_full(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(id): Path<Uuid>,
-) -> AppResult<Json<CriticalPathResponse>> {
+    Query(query): Query<ProjectFullQuery>,
+) -> AppResult<Json<ProjectFullResponse>> {
+    let (mut want_tasks, mut want_deps, mut want_plan, mut want_stats) = (true, true, true, true);
+    if let Some(include) = query.include.as_deref() {
+        want_tasks = false;
+        want_deps = false;
+        want_plan = false;
+        want_stats = false;
+        for section in include.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match section {
+                "tasks" => want_tasks = true,
+                "deps" | "dependencies" => want_deps = true,
+                "plan" => want_plan = true,
+                "stats" => want_stats = true,
+                other => return Err(AppError::bad_request(format!("unknown include section '{}'", other))),
+            }
+        }
+    }
+
     // ensure project exists and belongs to user
-    let _ = fetch_project(&state.pool, auth.user_id, id).await?;
+    let db_project = fetch_project(&state.pool, auth.user_id, id).await?;
+    let project: Project = db_project.try_into()?;
 
-    // Fetch tasks with computed duration (fallback to 0)
+    // stats are derived from the same task snapshot that is returned
+    let tasks = if want_tasks || want_stats {
+        Some(fetch_project_tasks(&state.pool, id).await?)
+    } else {
+        None
+    };
+    let stats = if want_stats { tasks.as_deref().map(project_stats) } else { None };
+    let tasks = if want_tasks { tasks } else { None };
+
+    let dependencies = if want_deps { Some(fetch_project_dependencies(&state.pool, id).await?) } else { None };
+    let plan = if want_plan { Some(fetch_plan_points(&state.pool, id).await?) } else { None };
+
+    Ok(Json(ProjectFullResponse { project, tasks, dependencies, plan, stats }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CriticalPathResponse {
+    pub task_ids: Vec<Uuid>,
+}
+
+/// Task dependency network of a project: per-task durations plus `source -> target` edges.
+pub(crate) struct DependencyGraph {
+    durations: HashMap<Uuid, i64>,
+    adj: HashMap<Uuid, Vec<Uuid>>,
+    nodes: HashSet<Uuid>,
+}
+
+impl DependencyGraph {
+    /// Edges between `nodes` only (others are dropped), with every duration 0.
+    pub(crate) fn from_edges(nodes: HashSet<Uuid>, edges: impl IntoIterator<Item = (Uuid, Uuid)>) -> Self {
+        let mut adj: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (source, target) in edges {
+            if nodes.contains(&source) && nodes.contains(&target) {
+                adj.entry(source).or_default().push(target);
+            }
+        }
+        Self { durations: HashMap::new(), adj, nodes }
+    }
+
+    fn duration(&self, id: &Uuid) -> i64 {
+        self.durations.get(id).cloned().unwrap_or(0)
+    }
+}
+
+async fn load_dependency_graph(pool: &SqlitePool, project_id: Uuid) -> AppResult<DependencyGraph> {
+    // Blob-bound queries cover rows written by the API; an empty or undecodable
+    // result falls through to the tolerant text-based queries below.
+    let sql_tasks = format!(
+        "SELECT t.id, {} as duration_days FROM tasks t WHERE t.project_id = ? AND t.deleted_at IS NULL",
+        task_duration::effective_duration_days("t")
+    );
+    let tasks: Option<Vec<(Uuid, i64)>> = sqlx::query_as(&sql_tasks)
+        .bind(project_id)
+        .fetch_all(pool)
+        .await
+        .ok()
+        .filter(|rows: &Vec<(Uuid, i64)>| !rows.is_empty());
+
+    if let Some(tasks) = tasks {
+        let edges = sqlx::query!(
+            r#"SELECT d.source_task_id as "source_task_id!: Uuid", d.target_task_id as "target_task_id!: Uuid"
+               FROM task_dependencies d INNER JOIN tasks t ON t.id = d.source_task_id
+               WHERE t.project_id = ? AND t.deleted_at IS NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await;
+
+        if let Ok(edges) = edges {
+            let durations: HashMap<Uuid, i64> = tasks.into_iter().collect();
+            let nodes: HashSet<Uuid> = durations.keys().copied().collect();
+            let mut adj: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+            for edge in edges {
+                if nodes.contains(&edge.source_task_id) && nodes.contains(&edge.target_task_id) {
+                    adj.entry(edge.source_task_id).or_default().push(edge.target_task_id);
+                }
+            }
+            return Ok(DependencyGraph { durations, adj, nodes });
+        }
+    }
+
+    load_dependency_graph_tolerant(pool, project_id).await
+}
+
+/// Fallback for legacy databases where task/dependency ids are stored as text.
+async fn load_dependency_graph_tolerant(pool: &SqlitePool, project_id: Uuid) -> AppResult<DependencyGraph> {
+    // Fetch tasks with their effective scheduling duration
     let id_case = uuid_sql::case_uuid("t.id");
     let match_proj = uuid_sql::match_uuid_clause("t.project_id");
     let sql_tasks = format!(
-        "SELECT {} , COALESCE(t.duration_days, CAST(julianday(t.end_date) - julianday(t.start_date) AS INTEGER), 0) as duration_days FROM tasks t WHERE {} AND t.deleted_at IS NULL",
-        id_case, match_proj
+        "SELECT {} , {} as duration_days FROM tasks t WHERE {} AND t.deleted_at IS NULL",
+        id_case, task_duration::effective_duration_days("t"), match_proj
     );
 
     let task_rows = sqlx::query(&sql_tasks)
-        .bind(id.to_string())
-        .bind(id.to_string())
-        .fetch_all(&state.pool)
+        .bind(project_id.to_string())
+        .bind(project_id.to_string())
+        .fetch_all(pool)
         .await?;
 
-    use std::collections::{HashMap, HashSet, VecDeque};
-
-    let mut durations: HashMap<Uuid, i32> = HashMap::new();
+    let mut durations: HashMap<Uuid, i64> = HashMap::new();
     let mut nodes: HashSet<Uuid> = HashSet::new();
     for row in task_rows.iter() {
         let id_s: String = row.try_get("id").map_err(|e| AppError::internal(format!("missing id: {}", e)))?;
         let dur: i64 = row.try_get("duration_days").map_err(|e| AppError::internal(format!("missing duration_days: {}", e)))?;
         let tu = Uuid::parse_str(&id_s).map_err(|e| AppError::internal(format!("invalid uuid: {}", e)))?;
-        durations.insert(tu, dur as i32);
+        durations.insert(tu, dur);
         nodes.insert(tu);
     }
 
@@ -422,19 +947,14 @@ pub async fn get_project_critical_path(
     );
 
     let dep_rows = sqlx::query(&sql_deps)
-        .bind(id.to_string())
-        .bind(id.to_string())
-        .bind(id.to_string())
-        .bind(id.to_string())
-        .fetch_all(&state.pool)
+        .bind(project_id.to_string())
+        .bind(project_id.to_string())
+        .bind(project_id.to_string())
+        .bind(project_id.to_string())
+        .fetch_all(pool)
         .await?;
 
     let mut adj: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
-    let mut indeg: HashMap<Uuid, usize> = HashMap::new();
-    for n in nodes.iter() {
-        indeg.insert(*n, 0);
-    }
-
     for row in dep_rows.iter() {
         let src_s: String = row.try_get("source_task_id").map_err(|e| AppError::internal(format!("missing source_task_id: {}", e)))?;
         let tgt_s: String = row.try_get("target_task_id").map_err(|e| AppError::internal(format!("missing target_task_id: {}", e)))?;
@@ -442,10 +962,42 @@ pub async fn get_project_critical_path(
         let tgt = Uuid::parse_str(&tgt_s).map_err(|e| AppError::internal(format!("invalid uuid: {}", e)))?;
         if !nodes.contains(&src) || !nodes.contains(&tgt) { continue; }
         adj.entry(src).or_default().push(tgt);
-        *indeg.entry(tgt).or_default() += 1;
     }
 
-    // Kahn's algorithm for topological order
+    Ok(DependencyGraph { durations, adj, nodes })
+}
+
+/// Kahn's algorithm for topological order. Fails if the graph contains a cycle.
+fn topological_order(graph: &DependencyGraph) -> AppResult<Vec<Uuid>> {
+    let topo = kahn_order(graph);
+    if topo.len() != graph.nodes.len() {
+        return Err(AppError::internal("dependency graph is not a DAG".to_string()));
+    }
+
+    Ok(topo)
+}
+
+/// Nodes Kahn's algorithm never reaches: those on a cycle or downstream of one,
+/// sorted. Empty for a DAG.
+pub(crate) fn unorderable_nodes(graph: &DependencyGraph) -> Vec<Uuid> {
+    let ordered: HashSet<Uuid> = kahn_order(graph).into_iter().collect();
+    let mut stuck: Vec<Uuid> = graph.nodes.difference(&ordered).copied().collect();
+    stuck.sort();
+    stuck
+}
+
+/// As much of a topological order as exists; stops short of any cycle.
+fn kahn_order(graph: &DependencyGraph) -> Vec<Uuid> {
+    let mut indeg: HashMap<Uuid, usize> = HashMap::new();
+    for n in graph.nodes.iter() {
+        indeg.insert(*n, 0);
+    }
+    for targets in graph.adj.values() {
+        for t in targets {
+            *indeg.entry(*t).or_default() += 1;
+        }
+    }
+
     let mut q: VecDeque<Uuid> = VecDeque::new();
     for (&n, &d) in indeg.iter() {
         if d == 0 {
@@ -456,27 +1008,45 @@ pub async fn get_project_critical_path(
     let mut topo: Vec<Uuid> = Vec::new();
     while let Some(n) = q.pop_front() {
         topo.push(n);
-        if let Some(neis) = adj.get(&n) {
+        if let Some(neis) = graph.adj.get(&n) {
             for &m in neis {
                 if let Some(e) = indeg.get_mut(&m) { *e -= 1; if *e == 0 { q.push_back(m); } }
             }
         }
     }
 
-    if topo.len() != nodes.len() {
-        return Err(AppError::internal("dependency graph is not a DAG".to_string()));
+    topo
+}
+
+/// Forward pass over a topological order: ES = max(EF of predecessors), EF = ES + duration.
+fn forward_pass(graph: &DependencyGraph, topo: &[Uuid]) -> (HashMap<Uuid, i64>, HashMap<Uuid, i64>) {
+    let mut early_start: HashMap<Uuid, i64> = topo.iter().map(|n| (*n, 0)).collect();
+    let mut early_finish: HashMap<Uuid, i64> = HashMap::new();
+    for &u in topo.iter() {
+        let ef = early_start[&u] + graph.duration(&u);
+        early_finish.insert(u, ef);
+        if let Some(neis) = graph.adj.get(&u) {
+            for &v in neis {
+                let es = early_start.entry(v).or_insert(0);
+                if ef > *es { *es = ef; }
+            }
+        }
     }
+    (early_start, early_finish)
+}
 
+/// Task ids along the longest (by duration) path through the network, in order.
+fn longest_path(graph: &DependencyGraph, topo: &[Uuid]) -> Vec<Uuid> {
     // DP for longest path (by duration). Initialize best[node] = duration[node]
     let mut best: HashMap<Uuid, i64> = HashMap::new();
     let mut prev: HashMap<Uuid, Option<Uuid>> = HashMap::new();
-    for &n in topo.iter() { best.insert(n, durations.get(&n).cloned().unwrap_or(0) as i64); prev.insert(n, None); }
+    for &n in topo.iter() { best.insert(n, graph.duration(&n)); prev.insert(n, None); }
 
     for &u in topo.iter() {
         let bu = *best.get(&u).unwrap_or(&0);
-        if let Some(neis) = adj.get(&u) {
+        if let Some(neis) = graph.adj.get(&u) {
             for &v in neis {
-                let cand = bu + durations.get(&v).cloned().unwrap_or(0) as i64;
+                let cand = bu + graph.duration(&v);
                 if cand > *best.get(&v).unwrap_or(&0) {
                     best.insert(v, cand);
                     prev.insert(v, Some(u));
@@ -502,14 +1072,271 @@ pub async fn get_project_critical_path(
         path.reverse();
     }
 
+    path
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/critical-path",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id")),
+    responses((status = 200, description = "Critical path task ids", body = CriticalPathResponse))
+)]
+pub async fn get_project_critical_path(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<CriticalPathResponse>> {
+    // ensure project exists and belongs to user
+    let _ = fetch_project(&state.pool, auth.user_id, id).await?;
+
+    let graph = load_dependency_graph(&state.pool, id).await?;
+    let topo = topological_order(&graph)?;
+    let path = longest_path(&graph, &topo);
+
     Ok(Json(CriticalPathResponse { task_ids: path }))
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GanttResponse {
+    pub tasks: Vec<Task>,
+    /// Dependencies between the returned tasks
+    pub dependencies: Vec<TaskDependency>,
+    /// Critical path in order; every id is one of `tasks`
+    pub critical_task_ids: Vec<Uuid>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/gantt",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id")),
+    responses((status = 200, description = "Tasks, dependencies and critical path for a Gantt chart", body = GanttResponse))
+)]
+pub async fn get_project_gantt(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<GanttResponse>> {
+    // ensure project exists and belongs to user
+    let _ = fetch_project(&state.pool, auth.user_id, id).await?;
+
+    let tasks = fetch_project_tasks(&state.pool, id).await?;
+    let graph = load_dependency_graph(&state.pool, id).await?;
+    let topo = topological_order(&graph)?;
+
+    // The three reads filter soft-deleted tasks slightly differently; keep only
+    // edges and critical ids that point at a returned task
+    let task_ids: HashSet<Uuid> = tasks.iter().map(|t| t.id).collect();
+    let dependencies = fetch_project_dependencies(&state.pool, id)
+        .await?
+        .into_iter()
+        .filter(|d| task_ids.contains(&d.source_task_id) && task_ids.contains(&d.target_task_id))
+        .collect();
+    let critical_task_ids = longest_path(&graph, &topo)
+        .into_iter()
+        .filter(|task_id| task_ids.contains(task_id))
+        .collect();
+
+    Ok(Json(GanttResponse { tasks, dependencies, critical_task_ids }))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskSchedule {
+    pub task_id: Uuid,
+    pub duration_days: i64,
+    /// Earliest day (offset from project start) the task can begin.
+    pub early_start: i64,
+    pub early_finish: i64,
+    /// Latest day the task can begin without delaying the project.
+    pub late_start: i64,
+    pub late_finish: i64,
+    /// Total float: `late_start - early_start`. Zero means the task is critical.
+    pub slack: i64,
+    pub is_critical: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScheduleAnalysisResponse {
+    /// Length of the longest path through the network, in days.
+    pub project_duration: i64,
+    pub tasks: Vec<TaskSchedule>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{id}/schedule-analysis",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id")),
+    responses((status = 200, description = "Per-task early/late start and finish with slack", body = ScheduleAnalysisResponse))
+)]
+pub async fn get_project_schedule_analysis(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ScheduleAnalysisResponse>> {
+    // ensure project exists and belongs to user
+    let _ = fetch_project(&state.pool, auth.user_id, id).await?;
+
+    let graph = load_dependency_graph(&state.pool, id).await?;
+    let topo = topological_order(&graph)?;
+
+    let (early_start, early_finish) = forward_pass(&graph, &topo);
+
+    let project_duration = early_finish.values().cloned().max().unwrap_or(0);
+
+    // Backward pass: LF = min(LS of successors), LS = LF - duration
+    let mut late_start: HashMap<Uuid, i64> = HashMap::new();
+    let mut late_finish: HashMap<Uuid, i64> = HashMap::new();
+    for &u in topo.iter().rev() {
+        let lf = graph
+            .adj
+            .get(&u)
+            .and_then(|neis| neis.iter().filter_map(|v| late_start.get(v)).cloned().min())
+            .unwrap_or(project_duration);
+        late_finish.insert(u, lf);
+        late_start.insert(u, lf - graph.duration(&u));
+    }
+
+    let mut tasks: Vec<TaskSchedule> = topo
+        .iter()
+        .map(|&n| {
+            let slack = late_start[&n] - early_start[&n];
+            TaskSchedule {
+                task_id: n,
+                duration_days: graph.duration(&n),
+                early_start: early_start[&n],
+                early_finish: early_finish[&n],
+                late_start: late_start[&n],
+                late_finish: late_finish[&n],
+                slack,
+                is_critical: slack == 0,
+            }
+        })
+        .collect();
+    tasks.sort_by(|a, b| a.early_start.cmp(&b.early_start).then(a.task_id.cmp(&b.task_id)));
+
+    Ok(Json(ScheduleAnalysisResponse { project_duration, tasks }))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ScheduleRequest {
+    /// Day the first tasks start; normalized to midnight UTC
+    #[schema(format = DateTime, example = "2025-10-01T00:00:00Z")]
+    pub project_start: DateTime<Utc>,
+}
+
 #[utoipa::path(
     post,
-    path = "/projects/{id}/plan",
+    path = "/projects/{id}/schedule",
     tag = "Projects",
     params(("id" = Uuid, Path, description = "Project id")),
+    request_body = ScheduleRequest,
+    responses(
+        (status = 200, description = "Tasks with their computed start_date/end_date", body = [Task]),
+        (status = 422, description = "The computed dates fall outside the supported range; nothing is updated"),
+        (status = 500, description = "The dependency graph contains a cycle")
+    )
+)]
+pub async fn schedule_project(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<Uuid>,
+    AppJson(payload): AppJson<ScheduleRequest>,
+) -> AppResult<Json<Vec<Task>>> {
+    // ensure project exists and belongs to user
+    let _ = fetch_project(&state.pool, auth.user_id, id).await?;
+
+    let graph = load_dependency_graph(&state.pool, id).await?;
+    let topo = topological_order(&graph)?;
+    let (early_start, early_finish) = forward_pass(&graph, &topo);
+
+    // Same midnight normalization as task edits, so zero-length tasks stay milestones
+    let project_start = normalize_to_midnight(payload.project_start);
+    let now = utc_now();
+
+    let mut tx = state.pool.begin().await?;
+    // Long chains of long tasks can land past the last representable date
+    let offset = |days: i64| {
+        chrono::Duration::try_days(days)
+            .and_then(|days| project_start.checked_add_signed(days))
+            .ok_or_else(|| {
+                AppError::validation(vec![FieldError::new(
+                    "project_start",
+                    format!("the schedule would run {} days past project_start, beyond the supported date range", days),
+                )])
+            })
+    };
+    for task_id in topo.iter() {
+        let start_date = offset(early_start[task_id])?;
+        let end_date = offset(early_finish[task_id])?;
+        sqlx::query(
+            "UPDATE tasks SET start_date = ?, end_date = ?, duration_days = ?, updated_at = ? WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(duration_days(Some(start_date), Some(end_date)))
+        .bind(now)
+        .bind(task_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    let tasks = fetch_project_tasks(&state.pool, id).await?;
+    Ok(Json(tasks))
+}
+
+/// Check plan points and return them normalized to midnight UTC, sorted by date.
+///
+/// Each day may appear only once, since two points on the same day make the
+/// S-curve ambiguous.
+fn normalize_plan_points(payload: Vec<ProjectPlanCreateRequest>) -> AppResult<Vec<ProjectPlanCreateRequest>> {
+    let mut points = Vec::with_capacity(payload.len());
+    for point in payload {
+        if point.planned_progress < 0 || point.planned_progress > 100 {
+            return Err(AppError::bad_request("planned_progress must be between 0 and 100"));
+        }
+        points.push(ProjectPlanCreateRequest { date: normalize_to_midnight(point.date), ..point });
+    }
+
+    points.sort_by_key(|p| p.date);
+    if let Some(pair) = points.windows(2).find(|pair| pair[0].date == pair[1].date) {
+        return Err(AppError::bad_request(format!(
+            "duplicate plan date {}; each date may appear only once",
+            pair[0].date.date_naive()
+        )));
+    }
+
+    Ok(points)
+}
+
+/// Reject a date-sorted plan whose `planned_progress` goes down between consecutive points.
+fn ensure_monotonic_plan(points: &[ProjectPlanCreateRequest]) -> AppResult<()> {
+    if let Some(pair) = points.windows(2).find(|pair| pair[1].planned_progress < pair[0].planned_progress) {
+        return Err(AppError::bad_request(format!(
+            "planned_progress decreases on {} ({} after {} on {}); the plan must be non-decreasing",
+            pair[1].date.date_naive(),
+            pair[1].planned_progress,
+            pair[0].planned_progress,
+            pair[0].date.date_naive()
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PlanUpdateQuery {
+    /// Reject plans whose planned progress decreases from one date to the next
+    pub enforce_monotonic: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/projects/{id}/plan",
+    tag = "Projects",
+    params(("id" = Uuid, Path, description = "Project id"), PlanUpdateQuery),
     request_body = [ProjectPlanCreateRequest],
     responses((status = 200, description = "Project plan updated", body = [ProjectPlanPoint]))
 )]
@@ -517,7 +1344,8 @@ pub async fn update_project_plan(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(id): Path<Uuid>,
-    Json(payload): Json<Vec<crate::models::project_plan::ProjectPlanCreateRequest>>,
+    Query(query): Query<PlanUpdateQuery>,
+    AppJson(payload): AppJson<Vec<ProjectPlanCreateRequest>>,
 ) -> AppResult<Json<Vec<ProjectPlanPoint>>> {
     // ensure project exists and belongs to user
     let owner = sqlx::query_scalar::<_, Uuid>(
@@ -528,10 +1356,24 @@ pub async fn update_project_plan(
     .await?;
 
     let owner = owner.ok_or_else(|| AppError::not_found("project not found"))?;
-    if owner != auth.user_id {
+    if owner != auth.user_id && !is_project_member(&state.pool, id, auth.user_id).await? {
         return Err(AppError::forbidden("not allowed to access this project"));
     }
 
+    let max_points = max_plan_points();
+    if payload.len() > max_points {
+        return Err(AppError::bad_request(format!(
+            "plan has {} points; at most {} are allowed",
+            payload.len(),
+            max_points
+        )));
+    }
+
+    let points = normalize_plan_points(payload)?;
+    if query.enforce_monotonic.unwrap_or(false) {
+        ensure_monotonic_plan(&points)?;
+    }
+
     let mut tx = state.pool.begin().await?;
     let now = utc_now();
 
@@ -543,11 +1385,7 @@ pub async fn update_project_plan(
 
     // 2. Insert new points
     let mut inserted_ids = Vec::new();
-    for point in payload {
-        if point.planned_progress < 0 || point.planned_progress > 100 {
-             return Err(AppError::bad_request("planned_progress must be between 0 and 100"));
-        }
-
+    for point in points {
         let pid = Uuid::new_v4();
         sqlx::query(
             "INSERT INTO project_plan (id, project_id, date, planned_progress, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)"
@@ -626,7 +1464,7 @@ pub async fn clear_project_plan(
     .await?;
 
     let owner = owner.ok_or_else(|| AppError::not_found("project not found"))?;
-    if owner != auth.user_id {
+    if owner != auth.user_id && !is_project_member(&state.pool, id, auth.user_id).await? {
         return Err(AppError::forbidden("not allowed to access this project"));
     }
 
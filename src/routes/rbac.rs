@@ -4,7 +4,7 @@
 //! All RBAC modifications are logged to the activity log with Critical severity.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     routing::{get, delete},
     Json, Router,
@@ -15,10 +15,13 @@ use sqlx::Row;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::db::uuid_sql::{case_uuid, match_uuid_clause};
 use crate::errors::AppError;
+use crate::extract::AppJson;
 use crate::events::{log_activity_with_context, RequestContext};
 use crate::jwt::AuthUser;
 use crate::models::rbac::*;
+use crate::routes::pagination::Pagination;
 
 // =============================================================================
 // ROUTER
@@ -29,7 +32,7 @@ pub fn routes() -> Router<AppState> {
         // Roles
         // Roles
         .route("/roles", get(list_roles).post(create_role))
-        .route("/roles/:role_id", get(get_role).delete(delete_role))
+        .route("/roles/:role_id", get(get_role).put(update_role).delete(delete_role))
         .route("/roles/:role_id/permissions", get(get_role_permissions).post(assign_permission_to_role))
         .route(
             "/roles/:role_id/permissions/:permission_id",
@@ -37,8 +40,10 @@ pub fn routes() -> Router<AppState> {
         )
         // Permissions
         .route("/permissions", get(list_permissions).post(create_permission))
+        .route("/permissions/:permission_id", delete(delete_permission))
+        .route("/permissions/:permission_id/users", get(get_permission_holders))
         // User role assignments
-        .route("/users/:user_id/roles", get(get_user_roles).post(assign_role_to_user))
+        .route("/users/:user_id/roles", get(get_user_roles).post(assign_role_to_user).put(replace_user_roles))
         .route("/users/:user_id/roles/:role_id", delete(revoke_role_from_user))
         // User direct permissions
         .route("/users/:user_id/permissions", get(get_user_permissions).post(grant_permission_to_user))
@@ -86,9 +91,11 @@ async fn list_roles(
     post,
     path = "/rbac/roles",
     tag = "RBAC",
+    params(CreateOptions),
     request_body = RoleCreateRequest,
     responses(
         (status = 201, description = "Role created", body = Role),
+        (status = 200, description = "Existing role returned (get_or_create=true)", body = Role),
         (status = 409, description = "Role name already exists"),
     ),
     security(("bearerAuth" = []))
@@ -97,8 +104,31 @@ async fn create_role(
     State(state): State<AppState>,
     auth: AuthUser,
     headers: HeaderMap,
-    Json(req): Json<RoleCreateRequest>,
+    Query(opts): Query<CreateOptions>,
+    AppJson(req): AppJson<RoleCreateRequest>,
 ) -> Result<(StatusCode, Json<Role>), AppError> {
+    let existing = sqlx::query(
+        "SELECT id, name, description, created_at, updated_at FROM roles WHERE name = ?"
+    )
+    .bind(&req.name)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if let Some(row) = existing {
+        if !opts.get_or_create.unwrap_or(false) {
+            return Err(AppError::conflict("Role name already exists"));
+        }
+
+        let role = Role {
+            id: Uuid::parse_str(row.get::<&str, _>("id")).unwrap_or_default(),
+            name: row.get("name"),
+            description: row.get("description"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        };
+        return Ok((StatusCode::OK, Json(role)));
+    }
+
     let id = Uuid::new_v4();
     let now = Utc::now();
 
@@ -171,6 +201,92 @@ async fn get_role(
     Ok(Json(role))
 }
 
+/// Update a role's name and/or description
+#[utoipa::path(
+    put,
+    path = "/rbac/roles/{role_id}",
+    tag = "RBAC",
+    params(
+        ("role_id" = Uuid, Path, description = "Role ID"),
+    ),
+    request_body = RoleUpdateRequest,
+    responses(
+        (status = 200, description = "Role updated", body = Role),
+        (status = 404, description = "Role not found"),
+        (status = 409, description = "Role name already exists"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn update_role(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    Path(role_id): Path<Uuid>,
+    AppJson(req): AppJson<RoleUpdateRequest>,
+) -> Result<Json<Role>, AppError> {
+    let row = sqlx::query(
+        "SELECT id, name, description, created_at, updated_at FROM roles WHERE id = ?"
+    )
+    .bind(role_id.to_string())
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::not_found("Role not found"))?;
+
+    let old = Role {
+        id: Uuid::parse_str(row.get::<&str, _>("id")).unwrap_or_default(),
+        name: row.get("name"),
+        description: row.get("description"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    };
+
+    if let Some(ref name) = req.name {
+        if name.trim().is_empty() {
+            return Err(AppError::bad_request("role name must not be empty"));
+        }
+
+        if *name != old.name {
+            let taken: Option<String> = sqlx::query_scalar("SELECT id FROM roles WHERE name = ? AND id != ?")
+                .bind(name)
+                .bind(role_id.to_string())
+                .fetch_optional(&state.pool)
+                .await?;
+            if taken.is_some() {
+                return Err(AppError::conflict("Role name already exists"));
+            }
+        }
+    }
+
+    let role = Role {
+        id: old.id,
+        name: req.name.unwrap_or_else(|| old.name.clone()),
+        description: req.description.or_else(|| old.description.clone()),
+        created_at: old.created_at,
+        updated_at: Utc::now(),
+    };
+
+    sqlx::query("UPDATE roles SET name = ?, description = ?, updated_at = ? WHERE id = ?")
+        .bind(&role.name)
+        .bind(&role.description)
+        .bind(role.updated_at)
+        .bind(role_id.to_string())
+        .execute(&state.pool)
+        .await?;
+
+    log_activity_with_context(
+        &state.event_bus,
+        "updated",
+        Some(auth.user_id),
+        &role,
+        Some(&old),
+        Some(RequestContext::from_headers(&headers)),
+    );
+
+    state.invalidate_all_permissions();
+
+    Ok(Json(role))
+}
+
 /// Delete a role
 #[utoipa::path(
     delete,
@@ -212,6 +328,8 @@ async fn delete_role(
         .execute(&state.pool)
         .await?;
 
+    state.invalidate_all_permissions();
+
     log_activity_with_context(
         &state.event_bus,
         "deleted",
@@ -244,7 +362,7 @@ async fn assign_permission_to_role(
     auth: AuthUser,
     headers: HeaderMap,
     Path(role_id): Path<Uuid>,
-    Json(req): Json<AssignPermissionToRoleRequest>,
+    AppJson(req): AppJson<AssignPermissionToRoleRequest>,
 ) -> Result<StatusCode, AppError> {
     let now = Utc::now();
 
@@ -263,6 +381,8 @@ async fn assign_permission_to_role(
         created_at: now,
     };
 
+    state.invalidate_all_permissions();
+
     log_activity_with_context(
         &state.event_bus,
         "assigned",
@@ -282,6 +402,7 @@ async fn assign_permission_to_role(
     tag = "RBAC",
     params(
         ("role_id" = Uuid, Path, description = "Role ID"),
+        Pagination,
     ),
     responses(
         (status = 200, description = "List of assigned permissions", body = Vec<Permission>),
@@ -292,6 +413,7 @@ async fn get_role_permissions(
     State(state): State<AppState>,
     _auth: AuthUser,
     Path(role_id): Path<Uuid>,
+    Query(page): Query<Pagination>,
 ) -> Result<Json<Vec<Permission>>, AppError> {
     let rows = sqlx::query(
         r#"
@@ -300,9 +422,12 @@ async fn get_role_permissions(
         INNER JOIN role_permissions rp ON p.id = rp.permission_id
         WHERE rp.role_id = ?
         ORDER BY p.name
+        LIMIT ? OFFSET ?
         "#
     )
     .bind(role_id.to_string())
+    .bind(page.limit())
+    .bind(page.offset())
     .fetch_all(&state.pool)
     .await?;
 
@@ -351,6 +476,8 @@ async fn delete_permission_from_role(
         created_at: now,
     };
 
+    state.invalidate_all_permissions();
+
     log_activity_with_context(
         &state.event_bus,
         "revoked",
@@ -403,9 +530,11 @@ async fn list_permissions(
     post,
     path = "/rbac/permissions",
     tag = "RBAC",
+    params(CreateOptions),
     request_body = PermissionCreateRequest,
     responses(
         (status = 201, description = "Permission created", body = Permission),
+        (status = 200, description = "Existing permission returned (get_or_create=true)", body = Permission),
         (status = 409, description = "Permission name already exists"),
     ),
     security(("bearerAuth" = []))
@@ -414,8 +543,31 @@ async fn create_permission(
     State(state): State<AppState>,
     auth: AuthUser,
     headers: HeaderMap,
-    Json(req): Json<PermissionCreateRequest>,
+    Query(opts): Query<CreateOptions>,
+    AppJson(req): AppJson<PermissionCreateRequest>,
 ) -> Result<(StatusCode, Json<Permission>), AppError> {
+    let existing = sqlx::query(
+        "SELECT id, name, description, created_at, updated_at FROM permissions WHERE name = ?"
+    )
+    .bind(&req.name)
+    .fetch_optional(&state.pool)
+    .await?;
+
+    if let Some(row) = existing {
+        if !opts.get_or_create.unwrap_or(false) {
+            return Err(AppError::conflict("Permission name already exists"));
+        }
+
+        let permission = Permission {
+            id: Uuid::parse_str(row.get::<&str, _>("id")).unwrap_or_default(),
+            name: row.get("name"),
+            description: row.get("description"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        };
+        return Ok((StatusCode::OK, Json(permission)));
+    }
+
     let id = Uuid::new_v4();
     let now = Utc::now();
 
@@ -450,10 +602,183 @@ async fn create_permission(
     Ok((StatusCode::CREATED, Json(permission)))
 }
 
+/// Delete a permission and every role/user grant that references it
+#[utoipa::path(
+    delete,
+    path = "/rbac/permissions/{permission_id}",
+    tag = "RBAC",
+    params(
+        ("permission_id" = Uuid, Path, description = "Permission ID"),
+    ),
+    responses(
+        (status = 204, description = "Permission deleted"),
+        (status = 404, description = "Permission not found"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_permission(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    Path(permission_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    // Read outside the transaction so it starts with a write and takes the lock up front
+    let row = sqlx::query(
+        "SELECT id, name, description, created_at, updated_at FROM permissions WHERE id = ?"
+    )
+    .bind(permission_id.to_string())
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::not_found("Permission not found"))?;
+
+    let permission = Permission {
+        id: Uuid::parse_str(row.get::<&str, _>("id")).unwrap_or_default(),
+        name: row.get("name"),
+        description: row.get("description"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    };
+
+    let mut tx = state.pool.begin().await?;
+
+    // Remove join rows explicitly so nothing dangles even if FK enforcement is off.
+    sqlx::query("DELETE FROM role_permissions WHERE permission_id = ?")
+        .bind(permission_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM user_permissions WHERE permission_id = ?")
+        .bind(permission_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query("DELETE FROM permissions WHERE id = ?")
+        .bind(permission_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    state.invalidate_all_permissions();
+
+    log_activity_with_context(
+        &state.event_bus,
+        "deleted",
+        Some(auth.user_id),
+        &permission,
+        None,
+        Some(RequestContext::from_headers(&headers)),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List users holding a permission, directly or through a role
+///
+/// Each user appears at most once per source: role holders list every role that
+/// carries the permission, direct holders list the scope of every unexpired grant.
+#[utoipa::path(
+    get,
+    path = "/rbac/permissions/{permission_id}/users",
+    tag = "RBAC",
+    params(
+        ("permission_id" = Uuid, Path, description = "Permission ID"),
+    ),
+    responses(
+        (status = 200, description = "Holders ordered by user id, direct before role", body = Vec<PermissionHolder>),
+        (status = 404, description = "Permission not found"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_permission_holders(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(permission_id): Path<Uuid>,
+) -> Result<Json<Vec<PermissionHolder>>, AppError> {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM permissions WHERE id = ?)")
+        .bind(permission_id.to_string())
+        .fetch_one(&state.pool)
+        .await?;
+    if !exists {
+        return Err(AppError::not_found("Permission not found"));
+    }
+
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT {}, 'direct' as source, NULL as role_name, up.scope
+        FROM user_permissions up
+        WHERE up.permission_id = ? AND (up.expires_at IS NULL OR up.expires_at > ?)
+        UNION ALL
+        SELECT {}, 'role' as source, r.name as role_name, NULL as scope
+        FROM user_roles ur
+        INNER JOIN role_permissions rp ON rp.role_id = ur.role_id
+        INNER JOIN roles r ON r.id = ur.role_id
+        WHERE rp.permission_id = ?
+        ORDER BY 1, 2, 3
+        "#,
+        case_uuid("up.user_id"),
+        case_uuid("ur.user_id"),
+    ))
+    .bind(permission_id.to_string())
+    .bind(Utc::now())
+    .bind(permission_id.to_string())
+    .fetch_all(&state.pool)
+    .await?;
+
+    // Rows arrive grouped by (user, source); fold each group into one holder
+    let mut holders: Vec<PermissionHolder> = Vec::new();
+    for r in rows {
+        let user_id = Uuid::parse_str(r.get::<&str, _>("user_id")).unwrap_or_default();
+        let source: String = r.get("source");
+        let holder = match holders.last_mut() {
+            Some(last) if last.user_id == user_id && last.source == source => last,
+            _ => {
+                holders.push(PermissionHolder { user_id, source, role_names: Vec::new(), scopes: Vec::new() });
+                holders.last_mut().expect("just pushed")
+            }
+        };
+        if let Some(role_name) = r.get::<Option<String>, _>("role_name") {
+            holder.role_names.push(role_name);
+        }
+        if holder.source == "direct" {
+            let scope_str: Option<String> = r.get("scope");
+            holder.scopes.push(scope_str.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or(Value::Object(Default::default())));
+        }
+    }
+
+    Ok(Json(holders))
+}
+
 // =============================================================================
 // USER-ROLE ENDPOINTS
 // =============================================================================
 
+/// Assign `role_id` to `user_id`, keying the row by the user's id exactly as
+/// `users` stores it (blob, or text in older databases) so the foreign key
+/// resolves; the `match_uuid_clause` lookups below find either form. A user
+/// id with no `users` row falls back to its text form.
+pub(crate) async fn insert_user_role(
+    conn: &mut sqlx::SqliteConnection,
+    user_id: Uuid,
+    role_id: Uuid,
+    now: chrono::DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    let sql = format!(
+        "INSERT OR IGNORE INTO user_roles (user_id, role_id, created_at) \
+         VALUES (COALESCE((SELECT id FROM users WHERE {}), ?), ?, ?)",
+        match_uuid_clause("id")
+    );
+    sqlx::query(&sql)
+        .bind(user_id.to_string())
+        .bind(user_id.to_string())
+        .bind(user_id.to_string())
+        .bind(role_id.to_string())
+        .bind(now)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
 /// Get roles assigned to a user
 #[utoipa::path(
     get,
@@ -461,6 +786,7 @@ async fn create_permission(
     tag = "RBAC",
     params(
         ("user_id" = Uuid, Path, description = "User ID"),
+        Pagination,
     ),
     responses(
         (status = 200, description = "List of assigned roles", body = Vec<Role>),
@@ -471,17 +797,23 @@ async fn get_user_roles(
     State(state): State<AppState>,
     _auth: AuthUser,
     Path(user_id): Path<Uuid>,
+    Query(page): Query<Pagination>,
 ) -> Result<Json<Vec<Role>>, AppError> {
-    let rows = sqlx::query(
+    let rows = sqlx::query(&format!(
         r#"
         SELECT r.id, r.name, r.description, r.created_at, r.updated_at
         FROM roles r
         INNER JOIN user_roles ur ON r.id = ur.role_id
-        WHERE ur.user_id = ?
+        WHERE {}
         ORDER BY r.name
-        "#
-    )
+        LIMIT ? OFFSET ?
+        "#,
+        match_uuid_clause("ur.user_id")
+    ))
+    .bind(user_id.to_string())
     .bind(user_id.to_string())
+    .bind(page.limit())
+    .bind(page.offset())
     .fetch_all(&state.pool)
     .await?;
 
@@ -515,18 +847,11 @@ async fn assign_role_to_user(
     auth: AuthUser,
     headers: HeaderMap,
     Path(user_id): Path<Uuid>,
-    Json(req): Json<AssignRoleRequest>,
+    AppJson(req): AppJson<AssignRoleRequest>,
 ) -> Result<StatusCode, AppError> {
     let now = Utc::now();
 
-    sqlx::query(
-        "INSERT OR IGNORE INTO user_roles (user_id, role_id, created_at) VALUES (?, ?, ?)"
-    )
-    .bind(user_id.to_string())
-    .bind(req.role_id.to_string())
-    .bind(now)
-    .execute(&state.pool)
-    .await?;
+    insert_user_role(&mut *state.pool.acquire().await?, user_id, req.role_id, now).await?;
 
     let assignment = UserRole {
         user_id,
@@ -534,6 +859,8 @@ async fn assign_role_to_user(
         created_at: now,
     };
 
+    state.invalidate_permissions(user_id);
+
     log_activity_with_context(
         &state.event_bus,
         "assigned",
@@ -546,6 +873,98 @@ async fn assign_role_to_user(
     Ok(StatusCode::CREATED)
 }
 
+/// Replace a user's whole role set
+#[utoipa::path(
+    put,
+    path = "/rbac/users/{user_id}/roles",
+    tag = "RBAC",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+    ),
+    request_body = ReplaceUserRolesRequest,
+    responses(
+        (status = 200, description = "The user's roles after the replacement", body = Vec<Role>),
+        (status = 404, description = "Role not found"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn replace_user_roles(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    AppJson(req): AppJson<ReplaceUserRolesRequest>,
+) -> Result<Json<Vec<Role>>, AppError> {
+    let now = Utc::now();
+    let mut role_ids = req.role_ids;
+    role_ids.sort();
+    role_ids.dedup();
+
+    for role_id in &role_ids {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM roles WHERE id = ?)")
+            .bind(role_id.to_string())
+            .fetch_one(&state.pool)
+            .await?;
+        if !exists {
+            return Err(AppError::not_found(format!("Role {} not found", role_id)));
+        }
+    }
+
+    // Start with the delete so the transaction holds the write lock before it reads
+    let mut tx = state.pool.begin().await?;
+    let previous_ids: Vec<String> = sqlx::query_scalar(&format!("DELETE FROM user_roles WHERE {} RETURNING role_id", match_uuid_clause("user_id")))
+        .bind(user_id.to_string())
+        .bind(user_id.to_string())
+        .fetch_all(&mut *tx)
+        .await?;
+    for role_id in &role_ids {
+        insert_user_role(&mut tx, user_id, *role_id, now).await?;
+    }
+
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT r.id, r.name, r.description, r.created_at, r.updated_at
+        FROM roles r
+        INNER JOIN user_roles ur ON r.id = ur.role_id
+        WHERE {}
+        ORDER BY r.name
+        "#,
+        match_uuid_clause("ur.user_id")
+    ))
+    .bind(user_id.to_string())
+    .bind(user_id.to_string())
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let roles: Vec<Role> = rows.iter().map(|r| Role {
+        id: Uuid::parse_str(r.get::<&str, _>("id")).unwrap_or_default(),
+        name: r.get("name"),
+        description: r.get("description"),
+        created_at: r.get("created_at"),
+        updated_at: r.get("updated_at"),
+    }).collect();
+
+    state.invalidate_permissions(user_id);
+
+    let previous = UserRoleSet {
+        user_id,
+        role_ids: previous_ids.iter().filter_map(|id| Uuid::parse_str(id).ok()).collect(),
+    };
+    let replaced = UserRoleSet { user_id, role_ids };
+    log_activity_with_context(
+        &state.event_bus,
+        "replaced",
+        Some(auth.user_id),
+        &replaced,
+        Some(&previous),
+        Some(RequestContext::from_headers(&headers)),
+    );
+
+    Ok(Json(roles))
+}
+
 /// Revoke a role from a user
 #[utoipa::path(
     delete,
@@ -568,7 +987,8 @@ async fn revoke_role_from_user(
 ) -> Result<StatusCode, AppError> {
     let now = Utc::now();
 
-    sqlx::query("DELETE FROM user_roles WHERE user_id = ? AND role_id = ?")
+    sqlx::query(&format!("DELETE FROM user_roles WHERE {} AND role_id = ?", match_uuid_clause("user_id")))
+        .bind(user_id.to_string())
         .bind(user_id.to_string())
         .bind(role_id.to_string())
         .execute(&state.pool)
@@ -580,6 +1000,8 @@ async fn revoke_role_from_user(
         created_at: now,
     };
 
+    state.invalidate_permissions(user_id);
+
     log_activity_with_context(
         &state.event_bus,
         "revoked",
@@ -603,6 +1025,7 @@ async fn revoke_role_from_user(
     tag = "RBAC",
     params(
         ("user_id" = Uuid, Path, description = "User ID"),
+        Pagination,
     ),
     responses(
         (status = 200, description = "List of direct permissions", body = Vec<UserPermission>),
@@ -613,15 +1036,21 @@ async fn get_user_permissions(
     State(state): State<AppState>,
     _auth: AuthUser,
     Path(user_id): Path<Uuid>,
+    Query(page): Query<Pagination>,
 ) -> Result<Json<Vec<UserPermission>>, AppError> {
     let rows = sqlx::query(
         r#"
-        SELECT id, user_id, permission_id, scope, created_at
+        SELECT id, user_id, permission_id, scope, created_at, expires_at
         FROM user_permissions
-        WHERE user_id = ?
+        WHERE user_id = ? AND (expires_at IS NULL OR expires_at > ?)
+        ORDER BY created_at, id
+        LIMIT ? OFFSET ?
         "#
     )
     .bind(user_id.to_string())
+    .bind(Utc::now())
+    .bind(page.limit())
+    .bind(page.offset())
     .fetch_all(&state.pool)
     .await?;
 
@@ -633,6 +1062,7 @@ async fn get_user_permissions(
             permission_id: Uuid::parse_str(r.get::<&str, _>("permission_id")).unwrap_or_default(),
             scope: scope_str.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or(Value::Object(Default::default())),
             created_at: r.get("created_at"),
+            expires_at: r.get("expires_at"),
         }
     }).collect();
 
@@ -658,7 +1088,7 @@ async fn grant_permission_to_user(
     auth: AuthUser,
     headers: HeaderMap,
     Path(user_id): Path<Uuid>,
-    Json(req): Json<GrantPermissionRequest>,
+    AppJson(req): AppJson<GrantPermissionRequest>,
 ) -> Result<StatusCode, AppError> {
     let id = Uuid::new_v4();
     let now = Utc::now();
@@ -666,14 +1096,21 @@ async fn grant_permission_to_user(
     let scope_str = serde_json::to_string(&scope_val)
         .map_err(|e| AppError::bad_request(format!("Invalid scope JSON: {}", e)))?;
 
+    if let Some(expires_at) = req.expires_at {
+        if expires_at <= now {
+            return Err(AppError::bad_request("expires_at must be in the future"));
+        }
+    }
+
     sqlx::query(
-        "INSERT INTO user_permissions (id, user_id, permission_id, scope, created_at) VALUES (?, ?, ?, ?, ?)"
+        "INSERT INTO user_permissions (id, user_id, permission_id, scope, created_at, expires_at) VALUES (?, ?, ?, ?, ?, ?)"
     )
     .bind(id.to_string())
     .bind(user_id.to_string())
     .bind(req.permission_id.to_string())
     .bind(&scope_str)
     .bind(now)
+    .bind(req.expires_at)
     .execute(&state.pool)
     .await?;
 
@@ -683,8 +1120,11 @@ async fn grant_permission_to_user(
         permission_id: req.permission_id,
         scope: scope_val,
         created_at: now,
+        expires_at: req.expires_at,
     };
 
+    state.invalidate_permissions(user_id);
+
     log_activity_with_context(
         &state.event_bus,
         "granted",
@@ -719,15 +1159,21 @@ async fn get_effective_permissions(
     _auth: AuthUser,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<EffectivePermissions>, AppError> {
+    if let Some(cached) = state.permissions_cache.get(user_id) {
+        return Ok(Json(cached));
+    }
+
     // Fetch user's roles
-    let role_rows = sqlx::query(
+    let role_rows = sqlx::query(&format!(
         r#"
         SELECT r.name
         FROM roles r
         INNER JOIN user_roles ur ON r.id = ur.role_id
-        WHERE ur.user_id = ?
-        "#
-    )
+        WHERE {}
+        "#,
+        match_uuid_clause("ur.user_id")
+    ))
+    .bind(user_id.to_string())
     .bind(user_id.to_string())
     .fetch_all(&state.pool)
     .await?;
@@ -735,16 +1181,18 @@ async fn get_effective_permissions(
     let roles: Vec<String> = role_rows.iter().map(|r| r.get("name")).collect();
 
     // Fetch role permissions
-    let role_perm_rows = sqlx::query(
+    let role_perm_rows = sqlx::query(&format!(
         r#"
         SELECT p.name as permission_name, r.name as role_name
         FROM permissions p
         INNER JOIN role_permissions rp ON p.id = rp.permission_id
         INNER JOIN roles r ON r.id = rp.role_id
         INNER JOIN user_roles ur ON r.id = ur.role_id
-        WHERE ur.user_id = ?
-        "#
-    )
+        WHERE {}
+        "#,
+        match_uuid_clause("ur.user_id")
+    ))
+    .bind(user_id.to_string())
     .bind(user_id.to_string())
     .fetch_all(&state.pool)
     .await?;
@@ -752,13 +1200,14 @@ async fn get_effective_permissions(
     // Fetch direct permissions
     let direct_perm_rows = sqlx::query(
         r#"
-        SELECT p.name, up.scope
+        SELECT p.name, up.scope, up.expires_at
         FROM permissions p
         INNER JOIN user_permissions up ON p.id = up.permission_id
-        WHERE up.user_id = ?
+        WHERE up.user_id = ? AND (up.expires_at IS NULL OR up.expires_at > ?)
         "#
     )
     .bind(user_id.to_string())
+    .bind(Utc::now())
     .fetch_all(&state.pool)
     .await?;
 
@@ -774,6 +1223,12 @@ async fn get_effective_permissions(
         });
     }
 
+    // The cached result is only good until the first time-bound grant lapses
+    let valid_until = direct_perm_rows
+        .iter()
+        .filter_map(|p| p.get::<Option<chrono::DateTime<Utc>>, _>("expires_at"))
+        .min();
+
     // Add direct permissions
     for p in direct_perm_rows {
         let scope_str: Option<String> = p.get("scope");
@@ -786,9 +1241,12 @@ async fn get_effective_permissions(
         });
     }
 
-    Ok(Json(EffectivePermissions {
+    let effective = EffectivePermissions {
         user_id,
         roles,
         permissions,
-    }))
+    };
+    state.permissions_cache.insert(effective.clone(), valid_until);
+
+    Ok(Json(effective))
 }
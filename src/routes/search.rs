@@ -0,0 +1,125 @@
+//! Search across every project and task the caller can open.
+//!
+//! Access follows `fetch_project`: the caller's own projects plus the ones they
+//! were added to as a member. Soft-deleted projects hide their tasks as well.
+
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::db::uuid_sql::{case_uuid, match_uuid_clause};
+use crate::errors::{AppError, AppResult};
+use crate::jwt::AuthUser;
+use crate::routes::users::like_pattern;
+
+/// Shortest query accepted, in characters (after trimming).
+pub const SEARCH_MIN_QUERY_LEN: usize = 2;
+
+/// Most results returned by one search.
+pub const SEARCH_MAX_RESULTS: i64 = 50;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Case-insensitive substring of a project name/description or task title
+    pub q: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchResultKind {
+    Project,
+    Task,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub id: Uuid,
+    /// Project name or task title
+    pub title: String,
+    /// The project itself for project hits, the owning project for tasks
+    pub project_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "Projects",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matching projects first, then tasks, each by title; at most 50", body = [SearchResult]),
+        (status = 400, description = "Query shorter than 2 characters"),
+    )
+)]
+pub async fn search(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<SearchQuery>,
+) -> AppResult<Json<Vec<SearchResult>>> {
+    let term = query.q.trim();
+    if term.chars().count() < SEARCH_MIN_QUERY_LEN {
+        return Err(AppError::bad_request(format!(
+            "q must be at least {} characters",
+            SEARCH_MIN_QUERY_LEN
+        )));
+    }
+    let pattern = like_pattern(term);
+
+    // Owner or member of a live project; matched on either uuid storage form
+    let accessible = format!(
+        "p.deleted_at IS NULL AND ({} OR p.id IN (SELECT m.project_id FROM project_members m WHERE {}))",
+        match_uuid_clause("p.user_id"),
+        match_uuid_clause("m.user_id"),
+    );
+    // Column names come from the first SELECT of a compound, so tasks lead
+    let sql = format!(
+        "SELECT 'task' AS kind, {} , t.title AS title, {} FROM tasks t INNER JOIN projects p ON p.id = t.project_id \
+         WHERE {accessible} AND t.deleted_at IS NULL AND lower(t.title) LIKE ? ESCAPE '\\' \
+         UNION ALL \
+         SELECT 'project', {} , p.name, {} FROM projects p \
+         WHERE {accessible} AND (lower(p.name) LIKE ? ESCAPE '\\' OR lower(COALESCE(p.description, '')) LIKE ? ESCAPE '\\') \
+         ORDER BY kind ASC, title COLLATE NOCASE ASC LIMIT ?",
+        case_uuid("t.id"),
+        case_uuid("t.project_id"),
+        case_uuid("p.id"),
+        case_uuid("p.id"),
+    );
+
+    let user_id = auth.user_id.to_string();
+    let mut q = sqlx::query(&sql);
+    for _ in 0..4 {
+        q = q.bind(&user_id);
+    }
+    q = q.bind(&pattern);
+    for _ in 0..4 {
+        q = q.bind(&user_id);
+    }
+    let rows = q.bind(&pattern).bind(&pattern).bind(SEARCH_MAX_RESULTS).fetch_all(&state.pool).await?;
+
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        let kind = match row.try_get::<String, _>("kind")?.as_str() {
+            "project" => SearchResultKind::Project,
+            _ => SearchResultKind::Task,
+        };
+        let id: String = row.try_get("id")?;
+        let project_id: String = row.try_get("project_id")?;
+        results.push(SearchResult {
+            kind,
+            id: parse_uuid(&id)?,
+            title: row.try_get("title")?,
+            project_id: parse_uuid(&project_id)?,
+        });
+    }
+
+    Ok(Json(results))
+}
+
+fn parse_uuid(s: &str) -> AppResult<Uuid> {
+    Uuid::parse_str(s).map_err(|e| AppError::internal(format!("invalid uuid: {}", e)))
+}
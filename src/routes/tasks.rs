@@ -1,44 +1,91 @@
 use axum::extract::{Path, State, Query};
-use chrono::Utc;
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use utoipa::{IntoParams, ToSchema};
 use axum::http::StatusCode;
 use axum::Json;
+use axum::response::Response;
 use sqlx::SqlitePool;
 use uuid::Uuid;
 use crate::db::{uuid_sql, row_parsers};
 
 use crate::app::AppState;
-use crate::errors::{AppError, AppResult};
+use crate::errors::{AppError, AppResult, FieldError};
+use crate::extract::AppJson;
 use crate::jwt::AuthUser;
-use crate::models::task::{DbTask, Task, TaskCreateRequest, TaskUpdateRequest};
-use crate::models::dependency::{TaskDependency, DependencyCreateRequest};
-use crate::models::progress::DbProgress;
-use crate::utils::{utc_now, normalize_to_midnight};
+use crate::models::task::{
+    normalize_tags, DbTask, Task, TaskAssignRequest, TaskCreateRequest, TaskReplaceRequest, TaskTagsRequest,
+    TaskUpdateRequest,
+};
+use crate::models::dependency::{
+    DependencyBatchCreateRequest, DependencyCreateRequest, ScheduleImportDependency, ScheduleImportRequest, ScheduleImportResponse, TaskDependency,
+};
+use crate::models::progress::{DbProgress, Progress};
+use crate::routes::{etag, idempotency, preconditions};
+use crate::routes::members::is_project_member;
+use crate::routes::pagination;
+use crate::routes::projects::{unorderable_nodes, DependencyGraph, PurgeQuery};
+use crate::utils::{duration_days, utc_now, normalize_to_midnight};
 
 #[derive(Debug, Deserialize)]
 pub struct TaskListQuery {
     pub progress: Option<bool>,
     pub task_id: Option<Uuid>,
+    /// Wrap a plain task listing in the `{kind, items}` envelope as well
+    pub envelope: Option<bool>,
+    /// Only tasks carrying this tag (case-insensitive); ignored with `progress=true`
+    pub tag: Option<String>,
 }
+
+/// `{ "kind": "tasks" | "progress", "items": [...] }`
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "items", rename_all = "lowercase")]
+pub enum TaskListing {
+    Tasks(Vec<Task>),
+    Progress(Vec<Progress>),
+}
+
+/// Body of `GET /projects/{project_id}/tasks`.
+///
+/// `?progress=true` always returns the tagged `TaskListing`; a plain task listing
+/// stays a bare array unless `?envelope=true` is given, so existing clients are
+/// unaffected.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum TaskListResponse {
+    Plain(Vec<Task>),
+    Tagged(TaskListing),
+}
+
 #[utoipa::path(
     get,
     path = "/projects/{project_id}/tasks",
     tag = "Tasks",
-    params(("project_id" = Uuid, Path, description = "Project id")),
-    responses((status = 200, description = "List tasks", body = [Task]))
+    params(
+        ("project_id" = Uuid, Path, description = "Project id"),
+        ("tag" = Option<String>, Query, description = "Only tasks carrying this tag"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from an earlier response; a match returns 304")
+    ),
+    responses(
+        (status = 200, description = "List tasks (bare array) or a `{kind, items}` envelope; see TaskListResponse. Carries an `ETag` header", body = [Task]),
+        (status = 304, description = "Listing unchanged since the given ETag")
+    )
 )]
 pub async fn list_tasks(
     State(state): State<AppState>,
     Path(project_id): Path<Uuid>,
     Query(query): Query<TaskListQuery>,
     auth: AuthUser,
-) -> AppResult<Json<Vec<Task>>> {
+    headers: axum::http::HeaderMap,
+) -> AppResult<Response> {
     // If caller requested progress via query param, return progress entries instead
     if query.progress.unwrap_or(false) {
         // verify project membership
         ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
-    let _rows = if let Some(task_id) = query.task_id {
+        let rows = if let Some(task_id) = query.task_id {
             // ensure task belongs to project
             let _ = fetch_task(&state.pool, auth.user_id, project_id, task_id).await?;
             let simple = sqlx::query_as::<_, DbProgress>(
@@ -104,62 +151,76 @@ pub async fn list_tasks(
                     parsed
                 }
             }
-        }
-    ;
+        };
 
-        // Convert to Progress and then to Task-like JSON via serde Value? We will return empty Vec<Task> to satisfy signature
-        // But to avoid breaking the signature, we'll return an empty task list when progress=true — caller should use the progress endpoints.
-        // For now, return an empty Vec<Task> as placeholder.
-        let tasks: Vec<Task> = Vec::new();
-        return Ok(Json(tasks));
+        let items: Vec<Progress> = rows.into_iter().map(Progress::try_from).collect::<Result<_, _>>()?;
+        let tag = etag::weak_etag(items.iter().map(|p| &p.updated_at));
+        return Ok(etag::conditional_json(&headers, tag, TaskListResponse::Tagged(TaskListing::Progress(items))));
     }
 
     ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
+    let mut tasks = fetch_project_tasks(&state.pool, project_id).await?;
+    if let Some(wanted) = query.tag.as_deref() {
+        let wanted = wanted.trim().to_lowercase();
+        tasks.retain(|t| t.tags.contains(&wanted));
+    }
+    let tag = etag::weak_etag(tasks.iter().map(|t| &t.updated_at));
+    let body = if query.envelope.unwrap_or(false) {
+        TaskListResponse::Tagged(TaskListing::Tasks(tasks))
+    } else {
+        TaskListResponse::Plain(tasks)
+    };
+    Ok(etag::conditional_json(&headers, tag, body))
+}
+
+/// Soft-deleted tasks of a project, most recently deleted first
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/tasks/trash",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id")),
+    responses((status = 200, description = "Deleted tasks with `deleted_at` set", body = [Task]))
+)]
+pub async fn list_trashed_tasks(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    auth: AuthUser,
+) -> AppResult<Json<Vec<Task>>> {
+    // Same access rule as the live listing
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
-    // Try simple fast-path query first
     let simple = sqlx::query_as::<_, DbTask>(
-        "SELECT t.id, t.project_id, t.title, t.status, t.due_date, t.start_date, t.end_date, t.duration_days, t.assignee, t.parent_id, t.progress, t.created_at, t.updated_at, t.deleted_at
-         FROM tasks t
-         WHERE t.project_id = ? AND t.deleted_at IS NULL
-         ORDER BY t.start_date ASC, t.created_at DESC",
+        "SELECT id, project_id, title, status, due_date, start_date, end_date, duration_days, estimated_days, assignee, parent_id, progress, created_at, updated_at, deleted_at
+         FROM tasks
+         WHERE project_id = ? AND deleted_at IS NOT NULL
+         ORDER BY deleted_at DESC",
     )
     .bind(project_id)
     .fetch_all(&state.pool)
     .await;
 
-    let tasks_rows: Vec<DbTask> = match simple {
+    let rows: Vec<DbTask> = match simple {
         Ok(rows) => rows,
         Err(_) => {
-            // Fallback: select textified UUIDs and parse manually
-            let id_case = uuid_sql::case_uuid("id");
-            let project_case = uuid_sql::case_uuid("project_id");
-            let assignee_case = uuid_sql::case_uuid("assignee");
-            let parent_case = uuid_sql::case_uuid("parent_id");
             let sql = format!(
-                "SELECT {} , {} , title, status, due_date, start_date, end_date, duration_days, {} , {} , progress, created_at, updated_at, deleted_at FROM tasks t WHERE t.project_id = ? AND t.deleted_at IS NULL ORDER BY t.start_date ASC, t.created_at DESC",
-                id_case, project_case, assignee_case, parent_case
+                "SELECT {} , {} , title, status, due_date, start_date, end_date, duration_days, estimated_days, {} , {} , progress, created_at, updated_at, deleted_at FROM tasks WHERE {} AND deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+                uuid_sql::case_uuid("id"),
+                uuid_sql::case_uuid("project_id"),
+                uuid_sql::case_uuid("assignee"),
+                uuid_sql::case_uuid("parent_id"),
+                uuid_sql::match_uuid_clause("project_id")
             );
-
             let rows = sqlx::query(&sql)
+                .bind(project_id.to_string())
                 .bind(project_id.to_string())
                 .fetch_all(&state.pool)
                 .await?;
-
-                    let mut parsed = Vec::with_capacity(rows.len());
-            for row in rows {
-                parsed.push(row_parsers::db_task_from_row(&row)?);
-            }
-
-            parsed
+            rows.iter().map(row_parsers::db_task_from_row).collect::<Result<_, _>>()?
         }
     };
 
-    let tasks: Vec<Task> = tasks_rows
-        .into_iter()
-        .map(Task::try_from)
-        .collect::<Result<_, _>>()?;
-
+    let tasks: Vec<Task> = rows.into_iter().map(Task::try_from).collect::<Result<_, _>>()?;
     Ok(Json(tasks))
 }
 
@@ -167,52 +228,61 @@ pub async fn list_tasks(
     post,
     path = "/projects/{project_id}/tasks",
     tag = "Tasks",
-    params(("project_id" = Uuid, Path, description = "Project id")),
+    params(
+        ("project_id" = Uuid, Path, description = "Project id"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retry-safe key; repeating it returns the task it created")
+    ),
     request_body = TaskCreateRequest,
-    responses((status = 201, description = "Task created", body = Task))
+    responses(
+        (status = 201, description = "Task created", body = Task),
+        (status = 200, description = "Task previously created with the same Idempotency-Key", body = Task),
+        (status = 422, description = "Invalid timeline or progress; every problem is listed in `errors`")
+    )
 )]
 pub async fn create_task(
     State(state): State<AppState>,
     Path(project_id): Path<Uuid>,
     auth: AuthUser,
     headers: axum::http::HeaderMap,
-    Json(payload): Json<TaskCreateRequest>,
+    AppJson(payload): AppJson<TaskCreateRequest>,
 ) -> AppResult<(StatusCode, Json<Task>)> {
     ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
-    let task_id = Uuid::new_v4();
-    let now = utc_now();
-    let status = payload.status.clone().unwrap_or_else(|| "pending".to_string());
-
-    // Normalize dates to midnight UTC for consistent milestone detection
-    let start_date = payload.start_date.map(normalize_to_midnight);
-    let end_date = payload.end_date.map(normalize_to_midnight);
-
-    // Validate timeline fields
-    if let (Some(start), Some(end)) = (start_date, end_date) {
-        if end < start {
-            return Err(AppError::bad_request("end_date must be >= start_date"));
+    let idempotency_key = idempotency::key_from_headers(&headers)?;
+    let scope = format!("POST /projects/{}/tasks", project_id);
+    if let Some(key) = idempotency_key.as_deref() {
+        if let Some(existing) = idempotency::replay::<Task>(&state.pool, auth.user_id, &scope, key).await? {
+            return Ok((StatusCode::OK, Json(existing)));
         }
     }
 
-    if let Some(p) = payload.progress {
-        if p < 0 || p > 100 {
-            return Err(AppError::bad_request("progress must be between 0 and 100"));
-        }
+    let task_id = Uuid::new_v4();
+    let now = utc_now();
+    let status = payload.status.unwrap_or_default();
+    let (start_date, end_date) = validate_new_task(&payload)?;
+
+    // Checked before the transaction: a read-then-write transaction cannot
+    // upgrade its lock while the activity listener is writing
+    if let Some(parent_id) = payload.parent_id {
+        let mut conn = state.pool.acquire().await?;
+        validate_parent(&mut conn, project_id, None, parent_id).await?;
     }
 
+    let mut tx = state.pool.begin().await?;
+
     sqlx::query(
-        "INSERT INTO tasks (id, project_id, title, status, due_date, start_date, end_date, assignee, parent_id, progress, created_at, updated_at) \
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO tasks (id, project_id, title, status, due_date, start_date, end_date, duration_days, estimated_days, assignee, parent_id, progress, created_at, updated_at) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(task_id)
     .bind(project_id)
     .bind(&payload.title)
     .bind(status)
-    .bind(payload.due_date)
+    .bind(payload.due_date.map(normalize_to_midnight))
     .bind(start_date)
     .bind(end_date)
-
+    .bind(duration_days(start_date, end_date))
+    .bind(payload.estimated_days)
     .bind(payload.assignee)
     .bind(payload.parent_id)
     // default progress to 0 when not provided
@@ -220,11 +290,25 @@ pub async fn create_task(
     .bind(now)
     .bind(now)
     // ... [existing insert logic]
-    .execute(&state.pool)
+    .execute(&mut *tx)
     .await?;
 
+    if let Some(key) = idempotency_key.as_deref() {
+        if !idempotency::claim(&mut tx, auth.user_id, &scope, key, project_id, task_id).await? {
+            // A concurrent retry won the race; discard our insert and return its task
+            tx.rollback().await?;
+            let existing: Task = idempotency::replay_claimed(&state.pool, auth.user_id, &scope, key).await?;
+            return Ok((StatusCode::OK, Json(existing)));
+        }
+    }
+
+    tx.commit().await?;
+
     let task = fetch_task(&state.pool, auth.user_id, project_id, task_id).await?;
     let task_dto: Task = task.clone().try_into()?;
+    if let Some(key) = idempotency_key.as_deref() {
+        idempotency::record(&state.pool, auth.user_id, &scope, key, &task_dto).await?;
+    }
 
     // Log activity with request context (no old state for create)
     let ctx = crate::events::RequestContext::from_headers(&headers);
@@ -244,22 +328,67 @@ pub async fn create_task(
     put,
     path = "/projects/{project_id}/tasks/{id}",
     tag = "Tasks",
-    params(("project_id" = Uuid, Path, description = "Project id"), ("id" = Uuid, Path, description = "Task id")),
+    params(
+        ("project_id" = Uuid, Path, description = "Project id"),
+        ("id" = Uuid, Path, description = "Task id"),
+        ("If-Unmodified-Since" = Option<String>, Header, description = "HTTP date; the update fails with 409 if the task changed after it")
+    ),
+    request_body = TaskReplaceRequest,
+    responses(
+        (status = 200, description = "Task replaced", body = Task),
+        (status = 400, description = "Missing or invalid field"),
+        (status = 409, description = "Task changed since `expected_updated_at` / `If-Unmodified-Since`; refetch and retry"),
+        (status = 422, description = "Invalid timeline or progress; every problem is listed in `errors`")
+    )
+)]
+pub async fn replace_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    Path((project_id, id)): Path<(Uuid, Uuid)>,
+    AppJson(payload): AppJson<TaskReplaceRequest>,
+) -> AppResult<Json<Task>> {
+    let old_task = fetch_task(&state.pool, auth.user_id, project_id, id).await?;
+    let mut task = old_task.clone();
+
+    // PUT carries the whole representation: absent optional fields are cleared
+    task.title = payload.title;
+    task.status = payload.status;
+    task.due_date = payload.due_date.map(normalize_to_midnight);
+    task.start_date = payload.start_date.map(normalize_to_midnight);
+    task.end_date = payload.end_date.map(normalize_to_midnight);
+    task.estimated_days = payload.estimated_days;
+    task.assignee = payload.assignee;
+    task.parent_id = payload.parent_id;
+    task.progress = payload.progress;
+
+    save_task_update(&state, &auth, &headers, project_id, old_task, task, payload.expected_updated_at).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/projects/{project_id}/tasks/{id}",
+    tag = "Tasks",
+    params(
+        ("project_id" = Uuid, Path, description = "Project id"),
+        ("id" = Uuid, Path, description = "Task id"),
+        ("If-Unmodified-Since" = Option<String>, Header, description = "HTTP date; the update fails with 409 if the task changed after it")
+    ),
     request_body = TaskUpdateRequest,
-    responses((status = 200, description = "Task updated", body = Task))
+    responses(
+        (status = 200, description = "Task updated; omitted fields are left unchanged", body = Task),
+        (status = 409, description = "Task changed since `expected_updated_at` / `If-Unmodified-Since`; refetch and retry")
+    )
 )]
 pub async fn update_task(
     State(state): State<AppState>,
     auth: AuthUser,
     headers: axum::http::HeaderMap,
     Path((project_id, id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<TaskUpdateRequest>,
+    AppJson(payload): AppJson<TaskUpdateRequest>,
 ) -> AppResult<Json<Task>> {
-    // Capture old state BEFORE modifications
     let old_task = fetch_task(&state.pool, auth.user_id, project_id, id).await?;
-    let old_dto: Task = old_task.clone().try_into()?;
-
-    let mut task = old_task;
+    let mut task = old_task.clone();
 
     let TaskUpdateRequest {
         title,
@@ -267,9 +396,11 @@ pub async fn update_task(
         due_date,
         start_date,
         end_date,
+        estimated_days,
         assignee,
         parent_id,
         progress,
+        expected_updated_at,
     } = payload;
 
     if let Some(title) = title {
@@ -279,7 +410,7 @@ pub async fn update_task(
         task.status = status;
     }
     if let Some(due_date) = due_date {
-        task.due_date = Some(due_date);
+        task.due_date = Some(normalize_to_midnight(due_date));
     }
 
     if let Some(sd) = start_date {
@@ -288,6 +419,9 @@ pub async fn update_task(
     if let Some(ed) = end_date {
         task.end_date = Some(normalize_to_midnight(ed));
     }
+    if let Some(days) = estimated_days {
+        task.estimated_days = Some(days);
+    }
     if let Some(a) = assignee {
         task.assignee = Some(a);
     }
@@ -295,43 +429,78 @@ pub async fn update_task(
         task.parent_id = Some(pid);
     }
     if let Some(p) = progress {
-        if p < 0 || p > 100 {
-            return Err(AppError::bad_request("progress must be between 0 and 100"));
-        }
         task.progress = p;
     }
 
-    // Validate timeline fields if both are present
-    if let (Some(sd), Some(ed)) = (task.start_date, task.end_date) {
-        if ed < sd {
-            return Err(AppError::bad_request("end_date must be >= start_date"));
-        }
+    save_task_update(&state, &auth, &headers, project_id, old_task, task, expected_updated_at).await
+}
+
+/// Validate and persist an edited task, then log the change (shared by PUT and PATCH).
+///
+/// With `expected_updated_at` or `If-Unmodified-Since` the write only lands if
+/// the row is unchanged since `old_task` was read; see `preconditions`.
+async fn save_task_update(
+    state: &AppState,
+    auth: &AuthUser,
+    headers: &axum::http::HeaderMap,
+    project_id: Uuid,
+    old_task: DbTask,
+    task: DbTask,
+    expected_updated_at: Option<DateTime<Utc>>,
+) -> AppResult<Json<Task>> {
+    let mut old_dto: Task = old_task.try_into()?;
+    old_dto.tags = fetch_task_tags(&state.pool, old_dto.id).await?;
+    let expected = preconditions::expected_version(headers, expected_updated_at, old_dto.updated_at, "task")?;
+
+    let errors = timeline_errors(task.start_date, task.end_date, task.estimated_days, Some(task.progress));
+    if !errors.is_empty() {
+        return Err(AppError::validation(errors));
+    }
+    if let Some(parent_id) = task.parent_id.filter(|pid| Some(*pid) != old_dto.parent_id) {
+        let mut conn = state.pool.acquire().await?;
+        validate_parent(&mut conn, project_id, Some(task.id), parent_id).await?;
     }
 
     let now = utc_now();
 
-    sqlx::query(
-        "UPDATE tasks SET title = ?, status = ?, due_date = ?, start_date = ?, end_date = ?, assignee = ?, parent_id = ?, progress = ?, updated_at = ? WHERE id = ?",
-    )
-    .bind(&task.title)
-    .bind(&task.status)
-    .bind(task.due_date)
-    .bind(task.start_date)
-    .bind(task.end_date)
-    .bind(task.assignee)
-    .bind(task.parent_id)
-    .bind(task.progress)
-    .bind(now)
-    .bind(task.id)
-    .execute(&state.pool)
-    .await?;
+    // Compute duration here too so databases without the duration triggers agree
+    let mut sql = String::from(
+        "UPDATE tasks SET title = ?, status = ?, due_date = ?, start_date = ?, end_date = ?, duration_days = COALESCE(?, duration_days), estimated_days = ?, assignee = ?, parent_id = ?, progress = ?, updated_at = ? WHERE id = ?",
+    );
+    if expected.is_some() {
+        sql.push_str(" AND updated_at = ?");
+    }
+    let mut query = sqlx::query(&sql)
+        .bind(&task.title)
+        .bind(task.status)
+        .bind(task.due_date)
+        .bind(task.start_date)
+        .bind(task.end_date)
+        .bind(duration_days(task.start_date, task.end_date))
+        .bind(task.estimated_days)
+        .bind(task.assignee)
+        .bind(task.parent_id)
+        .bind(task.progress)
+        .bind(now)
+        .bind(task.id);
+    if let Some(version) = expected {
+        query = query.bind(version);
+    }
+    let result = query.execute(&state.pool).await?;
+
+    if expected.is_some() && result.rows_affected() == 0 {
+        // Deleted in the meantime stays a 404; anything else is a newer edit
+        fetch_task(&state.pool, auth.user_id, project_id, task.id).await?;
+        return Err(preconditions::stale("task"));
+    }
 
     // Re-fetch to get the DB-calculated fields (like duration_days from triggers)
     let task = fetch_task(&state.pool, auth.user_id, project_id, task.id).await?;
-    let task_dto: Task = task.clone().try_into()?;
+    let mut task_dto: Task = task.try_into()?;
+    task_dto.tags = old_dto.tags.clone();
 
     // Log activity with old/new tracking and request context
-    let ctx = crate::events::RequestContext::from_headers(&headers);
+    let ctx = crate::events::RequestContext::from_headers(headers);
     crate::events::log_activity_with_context(
         &state.event_bus,
         "updated",
@@ -348,17 +517,27 @@ pub async fn update_task(
     get,
     path = "/projects/{project_id}/tasks/{id}",
     tag = "Tasks",
-    params(("project_id" = Uuid, Path, description = "Project id"), ("id" = Uuid, Path, description = "Task id")),
-    responses((status = 200, description = "Task detail", body = Task))
+    params(
+        ("project_id" = Uuid, Path, description = "Project id"),
+        ("id" = Uuid, Path, description = "Task id"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from an earlier response; a match returns 304")
+    ),
+    responses(
+        (status = 200, description = "Task detail, with an `ETag` header", body = Task),
+        (status = 304, description = "Task unchanged since the given ETag")
+    )
 )]
 pub async fn get_task(
     State(state): State<AppState>,
     auth: AuthUser,
+    headers: axum::http::HeaderMap,
     Path((project_id, id)): Path<(Uuid, Uuid)>,
-) -> AppResult<Json<Task>> {
+) -> AppResult<Response> {
     let task = fetch_task(&state.pool, auth.user_id, project_id, id).await?;
-    let task: Task = task.try_into()?;
-    Ok(Json(task))
+    let mut task: Task = task.try_into()?;
+    task.tags = fetch_task_tags(&state.pool, id).await?;
+    let tag = etag::weak_etag([&task.updated_at]);
+    Ok(etag::conditional_json(&headers, tag, task))
 }
 
 #[utoipa::path(
@@ -391,6 +570,100 @@ pub async fn delete_task(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Permanently delete a soft-deleted task with its subtasks, progress and dependencies
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}/tasks/{id}/purge",
+    tag = "Tasks",
+    params(
+        ("project_id" = Uuid, Path, description = "Project id"),
+        ("id" = Uuid, Path, description = "Task id"),
+        PurgeQuery
+    ),
+    responses(
+        (status = 204, description = "Task and all its rows removed"),
+        (status = 400, description = "Missing ?confirm=true"),
+        (status = 404, description = "Task not found"),
+        (status = 409, description = "Task is not in the trash, or has live subtasks")
+    )
+)]
+pub async fn purge_task(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    Path((project_id, id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<PurgeQuery>,
+) -> AppResult<StatusCode> {
+    query.require_confirmation()?;
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
+
+    let db_task = sqlx::query_as::<_, DbTask>(
+        "SELECT id, project_id, title, status, due_date, start_date, end_date, duration_days, estimated_days, assignee, parent_id, progress, created_at, updated_at, deleted_at
+         FROM tasks WHERE id = ? AND project_id = ?",
+    )
+    .bind(id)
+    .bind(project_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or_else(|| AppError::not_found("task not found"))?;
+    if db_task.deleted_at.is_none() {
+        return Err(AppError::conflict("task is not deleted; delete it before purging"));
+    }
+
+    // Subtasks would be cascaded by parent_id; refuse rather than drop live ones
+    let subtree: Vec<(Uuid, bool)> = sqlx::query_as(
+        "WITH RECURSIVE subtree(node) AS (
+            SELECT ?
+            UNION
+            SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.node
+        )
+        SELECT t.id, t.deleted_at IS NOT NULL FROM tasks t JOIN subtree s ON t.id = s.node;",
+    )
+    .bind(id)
+    .fetch_all(&state.pool)
+    .await?;
+    if subtree.iter().any(|(_, deleted)| !deleted) {
+        return Err(AppError::conflict("task has live subtasks; delete or move them first"));
+    }
+
+    // Writes only from here, so the transaction takes the write lock with its first statement
+    let mut tx = state.pool.begin().await?;
+
+    // Children first, so nothing dangles even if FK enforcement is off
+    for (task_id, _) in &subtree {
+        sqlx::query("DELETE FROM task_dependencies WHERE source_task_id = ? OR target_task_id = ?")
+            .bind(task_id)
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await?;
+        // Stored create responses for the task and its progress entries go too
+        sqlx::query("DELETE FROM idempotency_keys WHERE resource_id = ? OR resource_id IN (SELECT id FROM task_progress WHERE task_id = ?)")
+            .bind(task_id)
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM task_progress WHERE task_id = ?").bind(task_id).execute(&mut *tx).await?;
+        sqlx::query("DELETE FROM task_tags WHERE task_id = ?").bind(task_id).execute(&mut *tx).await?;
+    }
+    for (task_id, _) in &subtree {
+        sqlx::query("DELETE FROM tasks WHERE id = ?").bind(task_id).execute(&mut *tx).await?;
+    }
+
+    tx.commit().await?;
+
+    let task: Task = db_task.try_into()?;
+    crate::events::log_activity_with_context(
+        &state.event_bus,
+        "purged",
+        Some(auth.user_id),
+        &task,
+        None,
+        Some(crate::events::RequestContext::from_headers(&headers)),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[utoipa::path(
     get,
     path = "/projects/{project_id}/dependencies",
@@ -405,34 +678,7 @@ pub async fn list_dependencies(
 ) -> AppResult<Json<Vec<TaskDependency>>> {
     ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
-    // Use a defensive manual SELECT that textifies UUIDs and parses rows explicitly.
-    let id_case = uuid_sql::case_uuid("d.id");
-    let source_case = uuid_sql::case_uuid("d.source_task_id");
-    let target_case = uuid_sql::case_uuid("d.target_task_id");
-    let project_match = uuid_sql::match_uuid_clause("t.project_id");
-    let sql = format!(
-        "SELECT {} , {} , {} , d.type, d.created_at FROM task_dependencies d INNER JOIN tasks t ON t.id = d.source_task_id WHERE {} AND t.deleted_at IS NULL",
-        id_case, source_case, target_case, project_match
-    );
-
-    let rows = sqlx::query(&sql)
-        .bind(project_id.to_string())
-        .bind(project_id.to_string())
-        .fetch_all(&state.pool)
-        .await?;
-
-    let mut parsed = Vec::with_capacity(rows.len());
-    for row in rows {
-        parsed.push(row_parsers::db_task_dependency_from_row(&row)?);
-    }
-
-    let deps_rows = parsed;
-
-    let deps: Vec<TaskDependency> = deps_rows
-        .into_iter()
-        .map(TaskDependency::try_from)
-        .collect::<Result<_, _>>()?;
-
+    let deps = fetch_project_dependencies(&state.pool, project_id).await?;
     Ok(Json(deps))
 }
 
@@ -448,7 +694,8 @@ pub async fn create_dependency(
     State(state): State<AppState>,
     Path(project_id): Path<Uuid>,
     auth: AuthUser,
-    Json(payload): Json<DependencyCreateRequest>,
+    headers: axum::http::HeaderMap,
+    AppJson(payload): AppJson<DependencyCreateRequest>,
 ) -> AppResult<(StatusCode, Json<TaskDependency>)> {
     ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
@@ -516,62 +763,574 @@ pub async fn create_dependency(
         created_at: now,
     };
 
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    crate::events::log_activity_with_context(&state.event_bus, "created", Some(auth.user_id), &dep, None, Some(ctx));
+
     Ok((StatusCode::CREATED, Json(dep)))
 }
 
+/// How many tasks a batch cycle error names before summarising the rest.
+const CYCLE_ERROR_TASKS: usize = 5;
+
 #[utoipa::path(
-    delete,
-    path = "/projects/{project_id}/dependencies/{id}",
+    post,
+    path = "/projects/{project_id}/dependencies/batch",
     tag = "Dependencies",
-    params(("project_id" = Uuid, Path, description = "Project id"), ("id" = Uuid, Path, description = "Dependency id")),
-    responses((status = 204, description = "Dependency deleted"))
+    params(("project_id" = Uuid, Path, description = "Project id")),
+    request_body = DependencyBatchCreateRequest,
+    responses(
+        (status = 201, description = "Dependencies created, in request order", body = [TaskDependency]),
+        (status = 400, description = "No dependencies, a self-link, a duplicate (within the batch or of an existing edge), or the edges would create a cycle; nothing is stored"),
+        (status = 403, description = "Caller is not a project member"),
+        (status = 404, description = "A task is not in the project")
+    )
 )]
-pub async fn delete_dependency(
+pub async fn create_dependencies_batch(
     State(state): State<AppState>,
-    Path((project_id, id)): Path<(Uuid, Uuid)>,
+    Path(project_id): Path<Uuid>,
     auth: AuthUser,
-) -> AppResult<StatusCode> {
+    headers: axum::http::HeaderMap,
+    AppJson(payload): AppJson<DependencyBatchCreateRequest>,
+) -> AppResult<(StatusCode, Json<Vec<TaskDependency>>)> {
     ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
-    // We need to verify the dependency belongs to a task in this project
-    // We can join tasks to verify
-    let affected = sqlx::query(
-        "DELETE FROM task_dependencies \
-         WHERE id = ? AND source_task_id IN (SELECT id FROM tasks WHERE project_id = ?)"
-    )
-    .bind(id)
-    .bind(project_id)
-    .execute(&state.pool)
-    .await?;
+    if payload.dependencies.is_empty() {
+        return Err(AppError::bad_request("dependencies must not be empty"));
+    }
 
-    if affected.rows_affected() == 0 {
-        return Err(AppError::not_found("Dependency not found or not in project"));
+    // One read of the project's tasks instead of a lookup per edge
+    let task_ids: HashSet<Uuid> = fetch_project_tasks(&state.pool, project_id).await?.into_iter().map(|t| t.id).collect();
+    // Edges already in the project count as seen, so re-adding one is a duplicate too
+    let mut seen: HashSet<(Uuid, Uuid)> =
+        project_dependency_edges(&mut *state.pool.acquire().await?, project_id).await?.into_iter().collect();
+    for (i, edge) in payload.dependencies.iter().enumerate() {
+        for task_id in [edge.source_task_id, edge.target_task_id] {
+            if !task_ids.contains(&task_id) {
+                return Err(AppError::not_found(format!("dependencies[{}]: task {} not found in project", i, task_id)));
+            }
+        }
+        if edge.source_task_id == edge.target_task_id {
+            return Err(AppError::bad_request(format!("dependencies[{}]: cannot link task to itself", i)));
+        }
+        if !seen.insert((edge.source_task_id, edge.target_task_id)) {
+            return Err(AppError::bad_request(format!("dependencies[{}]: duplicate dependency", i)));
+        }
     }
 
-    Ok(StatusCode::NO_CONTENT)
+    let now = utc_now();
+    let dependencies: Vec<TaskDependency> = payload
+        .dependencies
+        .into_iter()
+        .map(|edge| TaskDependency {
+            id: Uuid::new_v4(),
+            source_task_id: edge.source_task_id,
+            target_task_id: edge.target_task_id,
+            type_: edge.type_,
+            created_at: now,
+        })
+        .collect();
+
+    // Writing first takes SQLite's write lock before the read below
+    let mut tx = state.pool.begin().await?;
+    for dep in &dependencies {
+        sqlx::query(
+            "INSERT INTO task_dependencies (id, source_task_id, target_task_id, type, created_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(dep.id)
+        .bind(dep.source_task_id)
+        .bind(dep.target_task_id)
+        .bind(&dep.type_)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // A single cycle check over the project's whole graph, new edges included,
+    // instead of a reachability query per edge
+    let edges = project_dependency_edges(&mut *tx, project_id).await?;
+
+    let stuck = unorderable_nodes(&DependencyGraph::from_edges(task_ids, edges));
+    if !stuck.is_empty() {
+        // Dropping the transaction rolls every insert back
+        let mut named: Vec<String> = stuck.iter().take(CYCLE_ERROR_TASKS).map(Uuid::to_string).collect();
+        if stuck.len() > CYCLE_ERROR_TASKS {
+            named.push(format!("and {} more", stuck.len() - CYCLE_ERROR_TASKS));
+        }
+        return Err(AppError::bad_request(format!(
+            "Cycle detected: these dependencies would make tasks circular (on or after the cycle: {})",
+            named.join(", ")
+        )));
+    }
+
+    tx.commit().await?;
+
+    let ctx = crate::events::RequestContext::from_headers(&headers);
+    for dep in &dependencies {
+        crate::events::log_activity_with_context(&state.event_bus, "created", Some(auth.user_id), dep, None, Some(ctx.clone()));
+    }
+
+    Ok((StatusCode::CREATED, Json(dependencies)))
+}
+
+/// `(source, target)` of every dependency whose source is a live task of the project.
+async fn project_dependency_edges(conn: &mut sqlx::SqliteConnection, project_id: Uuid) -> AppResult<Vec<(Uuid, Uuid)>> {
+    let sql = format!(
+        "SELECT {} , {} FROM task_dependencies d INNER JOIN tasks t ON t.id = d.source_task_id WHERE {} AND t.deleted_at IS NULL",
+        uuid_sql::case_uuid("d.source_task_id"),
+        uuid_sql::case_uuid("d.target_task_id"),
+        uuid_sql::match_uuid_clause("t.project_id")
+    );
+    let rows = sqlx::query(&sql)
+        .bind(project_id.to_string())
+        .bind(project_id.to_string())
+        .fetch_all(conn)
+        .await?;
+    let mut edges = Vec::with_capacity(rows.len());
+    for row in rows.iter() {
+        let source: String = row.try_get("source_task_id")?;
+        let target: String = row.try_get("target_task_id")?;
+        if let (Ok(source), Ok(target)) = (Uuid::parse_str(&source), Uuid::parse_str(&target)) {
+            edges.push((source, target));
+        }
+    }
+    Ok(edges)
 }
 
 #[utoipa::path(
-    put,
-    path = "/projects/{project_id}/tasks/batch",
+    post,
+    path = "/projects/{project_id}/import-schedule",
     tag = "Tasks",
     params(("project_id" = Uuid, Path, description = "Project id")),
-    request_body = TaskBatchUpdatePayload,
-    responses((status = 200, description = "Tasks updated", body = [Task]))
+    request_body = ScheduleImportRequest,
+    responses(
+        (status = 201, description = "Tasks and dependencies created", body = ScheduleImportResponse),
+        (status = 400, description = "Invalid task, index out of range, or dependency cycle")
+    )
 )]
-pub async fn batch_update_tasks(
+pub async fn import_schedule(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    auth: AuthUser,
+    AppJson(payload): AppJson<ScheduleImportRequest>,
+) -> AppResult<(StatusCode, Json<ScheduleImportResponse>)> {
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
+
+    let count = payload.tasks.len();
+    let mut timelines = Vec::with_capacity(count);
+    let mut invalid = Vec::new();
+    for (index, task) in payload.tasks.iter().enumerate() {
+        match validate_new_task(task) {
+            Ok(timeline) => timelines.push(timeline),
+            Err(AppError::Validation(errors)) => {
+                let parent = format!("tasks[{}]", index);
+                invalid.extend(errors.into_iter().map(|e| e.within(&parent)));
+            }
+            Err(AppError::BadRequest(msg)) => return Err(AppError::bad_request(format!("tasks[{}]: {}", index, msg))),
+            Err(other) => return Err(other),
+        }
+    }
+    if !invalid.is_empty() {
+        return Err(AppError::validation(invalid));
+    }
+    validate_import_edges(count, &payload.dependencies)?;
+
+    // Parents must be live tasks already in the project; checked before the
+    // transaction for the same locking reason as in `create_task`
+    let mut conn = state.pool.acquire().await?;
+    for (index, task) in payload.tasks.iter().enumerate() {
+        if let Some(parent_id) = task.parent_id {
+            validate_parent(&mut conn, project_id, None, parent_id).await.map_err(|e| match e {
+                AppError::BadRequest(msg) => AppError::bad_request(format!("tasks[{}]: {}", index, msg)),
+                other => other,
+            })?;
+        }
+    }
+    drop(conn);
+
+    let now = utc_now();
+    let task_ids: Vec<Uuid> = (0..count).map(|_| Uuid::new_v4()).collect();
+    let mut tx = state.pool.begin().await?;
+
+    for ((task, (start_date, end_date)), task_id) in payload.tasks.iter().zip(timelines).zip(&task_ids) {
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, title, status, due_date, start_date, end_date, duration_days, estimated_days, assignee, parent_id, progress, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(task_id)
+        .bind(project_id)
+        .bind(&task.title)
+        .bind(task.status.unwrap_or_default())
+        .bind(task.due_date.map(normalize_to_midnight))
+        .bind(start_date)
+        .bind(end_date)
+        .bind(duration_days(start_date, end_date))
+        .bind(task.estimated_days)
+        .bind(task.assignee)
+        .bind(task.parent_id)
+        .bind(task.progress.unwrap_or(0))
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let mut dependencies = Vec::with_capacity(payload.dependencies.len());
+    for edge in payload.dependencies {
+        let dep = TaskDependency {
+            id: Uuid::new_v4(),
+            source_task_id: task_ids[edge.source_index],
+            target_task_id: task_ids[edge.target_index],
+            type_: edge.type_,
+            created_at: now,
+        };
+        sqlx::query(
+            "INSERT INTO task_dependencies (id, source_task_id, target_task_id, type, created_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(dep.id)
+        .bind(dep.source_task_id)
+        .bind(dep.target_task_id)
+        .bind(&dep.type_)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        dependencies.push(dep);
+    }
+
+    tx.commit().await?;
+
+    let mut tasks = Vec::with_capacity(count);
+    for task_id in task_ids {
+        let task: Task = fetch_task(&state.pool, auth.user_id, project_id, task_id).await?.try_into()?;
+        crate::events::log_activity(&state.event_bus, "created", Some(auth.user_id), &task);
+        tasks.push(task);
+    }
+
+    Ok((StatusCode::CREATED, Json(ScheduleImportResponse { tasks, dependencies })))
+}
+
+/// Check imported edges reference valid, distinct task positions and form a DAG.
+/// Every imported task is new, so a cycle can only come from the edges themselves.
+fn validate_import_edges(count: usize, edges: &[ScheduleImportDependency]) -> AppResult<()> {
+    let mut seen = HashSet::new();
+    let mut adjacency = vec![Vec::new(); count];
+    let mut in_degree = vec![0usize; count];
+
+    for (i, edge) in edges.iter().enumerate() {
+        if edge.source_index >= count || edge.target_index >= count {
+            return Err(AppError::bad_request(format!("dependencies[{}]: task index out of range", i)));
+        }
+        if edge.source_index == edge.target_index {
+            return Err(AppError::bad_request(format!("dependencies[{}]: cannot link task to itself", i)));
+        }
+        if !seen.insert((edge.source_index, edge.target_index)) {
+            return Err(AppError::bad_request(format!("dependencies[{}]: duplicate dependency", i)));
+        }
+        adjacency[edge.source_index].push(edge.target_index);
+        in_degree[edge.target_index] += 1;
+    }
+
+    // Kahn's algorithm: if we cannot visit every node, the remainder contains a cycle
+    let mut queue: Vec<usize> = (0..count).filter(|&n| in_degree[n] == 0).collect();
+    let mut visited = 0;
+    while let Some(node) = queue.pop() {
+        visited += 1;
+        for &next in &adjacency[node] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                queue.push(next);
+            }
+        }
+    }
+    if visited != count {
+        return Err(AppError::bad_request("Cycle detected: imported dependencies are circular"));
+    }
+
+    Ok(())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}/dependencies/{id}",
+    tag = "Dependencies",
+    params(("project_id" = Uuid, Path, description = "Project id"), ("id" = Uuid, Path, description = "Dependency id")),
+    responses((status = 204, description = "Dependency deleted"))
+)]
+pub async fn delete_dependency(
+    State(state): State<AppState>,
+    Path((project_id, id)): Path<(Uuid, Uuid)>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+) -> AppResult<StatusCode> {
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
+
+    // Capture the edge for the activity log before it disappears
+    let old_dep = find_project_dependency(&state.pool, project_id, id).await?;
+
+    // We need to verify the dependency belongs to a task in this project
+    // We can join tasks to verify
+    let affected = sqlx::query(
+        "DELETE FROM task_dependencies \
+         WHERE id = ? AND source_task_id IN (SELECT id FROM tasks WHERE project_id = ?)"
+    )
+    .bind(id)
+    .bind(project_id)
+    .execute(&state.pool)
+    .await?;
+
+    if affected.rows_affected() == 0 {
+        return Err(AppError::not_found("Dependency not found or not in project"));
+    }
+
+    if let Some(dep) = old_dep {
+        let ctx = crate::events::RequestContext::from_headers(&headers);
+        crate::events::log_activity_with_context(&state.event_bus, "deleted", Some(auth.user_id), &dep, None, Some(ctx));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+const DEFAULT_TASK_TREE_MAX_DEPTH: usize = 32;
+
+/// Server-side ceiling for `?max_depth=` on the task tree (`TASK_TREE_MAX_DEPTH`, default 32).
+fn task_tree_max_depth() -> usize {
+    std::env::var("TASK_TREE_MAX_DEPTH")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_TASK_TREE_MAX_DEPTH)
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskTreeQuery {
+    /// Levels to return, counting root tasks as level 1 (defaults to and is capped at `TASK_TREE_MAX_DEPTH`)
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskTreeNode {
+    #[serde(flatten)]
+    pub task: Task,
+    pub children: Vec<TaskTreeNode>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskTreeResponse {
+    pub roots: Vec<TaskTreeNode>,
+    /// Depth actually applied after capping
+    pub max_depth: usize,
+    /// True when some task below `max_depth` was left out
+    pub truncated: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/tasks/tree",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id"), TaskTreeQuery),
+    responses((status = 200, description = "Tasks nested under their parents", body = TaskTreeResponse))
+)]
+pub async fn get_task_tree(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<TaskTreeQuery>,
+    auth: AuthUser,
+) -> AppResult<Json<TaskTreeResponse>> {
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
+
+    let cap = task_tree_max_depth();
+    let max_depth = match query.max_depth {
+        Some(0) => return Err(AppError::bad_request("max_depth must be at least 1")),
+        Some(depth) => depth.min(cap),
+        None => cap,
+    };
+
+    let tasks = fetch_project_tasks(&state.pool, project_id).await?;
+    let ids: HashSet<Uuid> = tasks.iter().map(|t| t.id).collect();
+
+    // Tasks whose parent is missing (or deleted) are treated as roots
+    let mut roots = Vec::new();
+    let mut children: HashMap<Uuid, Vec<Task>> = HashMap::new();
+    for task in tasks {
+        match task.parent_id.filter(|p| ids.contains(p)) {
+            Some(parent) => children.entry(parent).or_default().push(task),
+            None => roots.push(task),
+        }
+    }
+
+    let mut truncated = false;
+    let roots = roots
+        .into_iter()
+        .map(|task| build_tree_node(task, 1, max_depth, &mut children, &mut truncated))
+        .collect();
+
+    Ok(Json(TaskTreeResponse { roots, max_depth, truncated }))
+}
+
+fn build_tree_node(
+    task: Task,
+    depth: usize,
+    max_depth: usize,
+    children: &mut HashMap<Uuid, Vec<Task>>,
+    truncated: &mut bool,
+) -> TaskTreeNode {
+    let kids = children.remove(&task.id).unwrap_or_default();
+    if depth >= max_depth {
+        *truncated |= !kids.is_empty();
+        return TaskTreeNode { task, children: Vec::new() };
+    }
+
+    // Removing each entry as it is visited also keeps parent cycles from recursing forever
+    let children = kids
+        .into_iter()
+        .map(|child| build_tree_node(child, depth + 1, max_depth, children, truncated))
+        .collect();
+    TaskTreeNode { task, children }
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/tasks/overdue",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id")),
+    responses((status = 200, description = "Open tasks whose due day (UTC) has passed, most overdue first", body = [Task]))
+)]
+pub async fn list_overdue_tasks(
+    State(state): State<AppState>,
+    Path(project_id): Path<Uuid>,
+    auth: AuthUser,
+) -> AppResult<Json<Vec<Task>>> {
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
+
+    // Due dates are days: a task only becomes late once its due day (UTC) has ended.
+    // Tasks without a due_date can't be late, so they're excluded outright
+    let today = utc_now().date_naive();
+    let mut overdue: Vec<Task> = fetch_project_tasks(&state.pool, project_id)
+        .await?
+        .into_iter()
+        .filter(|task| task.due_date.is_some_and(|due| due.date_naive() < today))
+        .filter(|task| !task.status.is_closed())
+        .collect();
+    overdue.sort_by(|a, b| a.due_date.cmp(&b.due_date).then_with(|| a.created_at.cmp(&b.created_at)));
+
+    Ok(Json(overdue))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskHistoryQuery {
+    /// Maximum number of entries, newest first (default 50, capped at 500)
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskHistoryEntry {
+    pub id: Uuid,
+    pub event_name: String,
+    pub description: String,
+    pub actor_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+    pub severity: String,
+    #[schema(value_type = Object)]
+    pub properties: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskHistoryResponse {
+    pub entries: Vec<TaskHistoryEntry>,
+    /// True when older entries exist beyond `limit`
+    pub truncated: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/projects/{project_id}/tasks/{id}/history",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id"), ("id" = Uuid, Path, description = "Task id"), TaskHistoryQuery),
+    responses((status = 200, description = "Activity recorded for the task, newest first", body = TaskHistoryResponse))
+)]
+pub async fn get_task_history(
+    State(state): State<AppState>,
+    Path((project_id, id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<TaskHistoryQuery>,
+    auth: AuthUser,
+) -> AppResult<Json<TaskHistoryResponse>> {
+    let _ = fetch_task(&state.pool, auth.user_id, project_id, id).await?;
+
+    let limit = query.limit.unwrap_or(pagination::DEFAULT_LIMIT).clamp(1, pagination::MAX_LIMIT);
+
+    let sql = format!(
+        "SELECT {} , event_name, description, {} , occurred_at, severity, properties FROM activity_log WHERE {} ORDER BY occurred_at DESC, rowid DESC LIMIT ?",
+        uuid_sql::case_uuid("id"),
+        uuid_sql::case_uuid("actor_id"),
+        uuid_sql::match_uuid_clause("subject_id")
+    );
+    // One extra row tells us whether the history was cut short
+    let rows = sqlx::query(&sql)
+        .bind(id.to_string())
+        .bind(id.to_string())
+        .bind(limit + 1)
+        .fetch_all(&state.pool)
+        .await?;
+
+    let truncated = rows.len() as i64 > limit;
+    let mut entries = Vec::with_capacity(rows.len().min(limit as usize));
+    for row in rows.iter().take(limit as usize) {
+        let id_s: String = row.try_get("id")?;
+        let actor_s: Option<String> = row.try_get("actor_id")?;
+        let properties: String = row.try_get("properties")?;
+        entries.push(TaskHistoryEntry {
+            id: Uuid::parse_str(&id_s).map_err(|e| AppError::internal(format!("invalid uuid: {}", e)))?,
+            event_name: row.try_get("event_name")?,
+            description: row.try_get("description")?,
+            actor_id: actor_s.and_then(|s| Uuid::parse_str(&s).ok()),
+            occurred_at: row.try_get("occurred_at")?,
+            severity: row.try_get("severity")?,
+            properties: serde_json::from_str(&properties).unwrap_or(serde_json::Value::Null),
+        });
+    }
+
+    Ok(Json(TaskHistoryResponse { entries, truncated }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/projects/{project_id}/tasks/batch",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id")),
+    request_body = TaskBatchUpdatePayload,
+    responses(
+        (status = 200, description = "Tasks updated", body = [Task]),
+        (status = 422, description = "Invalid timelines or progress across the batch; fields are `tasks[i].<field>`")
+    )
+)]
+pub async fn batch_update_tasks(
     State(state): State<AppState>,
     auth: AuthUser,
     Path(project_id): Path<Uuid>,
-    Json(payload): Json<crate::models::task::TaskBatchUpdatePayload>,
+    AppJson(payload): AppJson<crate::models::task::TaskBatchUpdatePayload>,
 ) -> AppResult<Json<Vec<Task>>> {
     ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
 
     let mut tx = state.pool.begin().await?;
     let now = utc_now();
-    let mut updated_ids = Vec::new();
+    let mut updated = Vec::with_capacity(payload.tasks.len());
+    let mut invalid = Vec::new();
+
+    // Stamp every listed task up front so the transaction holds SQLite's write
+    // lock before its first read; a reader cannot upgrade while the activity
+    // listener is writing. Any rejection below rolls this back.
+    if !payload.tasks.is_empty() {
+        let placeholders = vec!["?"; payload.tasks.len()].join(", ");
+        let sql = format!("UPDATE tasks SET updated_at = ? WHERE project_id = ? AND id IN ({})", placeholders);
+        let mut stamp = sqlx::query(&sql).bind(now).bind(project_id);
+        for update in &payload.tasks {
+            stamp = stamp.bind(update.id);
+        }
+        stamp.execute(&mut *tx).await?;
+    }
 
-    for update in payload.tasks {
+    for (index, update) in payload.tasks.into_iter().enumerate() {
         // Verify task belongs to project
         let exists: bool = sqlx::query_scalar(
             "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ? AND project_id = ? AND deleted_at IS NULL)"
@@ -596,35 +1355,44 @@ pub async fn batch_update_tasks(
         let start = update.start_date.map(normalize_to_midnight).or(current.start_date.map(|d| d.with_timezone(&Utc)));
         let end = update.end_date.map(normalize_to_midnight).or(current.end_date.map(|d| d.with_timezone(&Utc)));
 
-        if let (Some(s), Some(e)) = (start, end) {
-             if e < s {
-                return Err(AppError::bad_request(format!("Task {}: end_date must be >= start_date", update.id)));
-            }
+        // Keep going so every invalid task is reported; the transaction is dropped below
+        let task_errors = timeline_errors(start, end, update.estimated_days, update.progress);
+        if !task_errors.is_empty() {
+            let parent = format!("tasks[{}]", index);
+            invalid.extend(task_errors.into_iter().map(|e| e.within(&parent)));
+            continue;
         }
-
-        if let Some(p) = update.progress {
-             if p < 0 || p > 100 {
-                return Err(AppError::bad_request(format!("Task {}: progress must be between 0 and 100", update.id)));
-            }
+        if !invalid.is_empty() {
+            continue;
         }
 
         let title = update.title.unwrap_or(current.title);
         let status = update.status.unwrap_or(current.status);
-        let due_date = update.due_date.or(current.due_date.map(|d| d.with_timezone(&Utc)));
+        let due_date = update.due_date.map(normalize_to_midnight).or(current.due_date.map(|d| d.with_timezone(&Utc)));
         let start_date = update.start_date.map(normalize_to_midnight).or(current.start_date.map(|d| d.with_timezone(&Utc)));
         let end_date = update.end_date.map(normalize_to_midnight).or(current.end_date.map(|d| d.with_timezone(&Utc)));
+        let estimated_days = update.estimated_days.or(current.estimated_days);
         let assignee = update.assignee.or(current.assignee);
         let parent_id = update.parent_id.or(current.parent_id);
+        if let Some(pid) = update.parent_id.filter(|pid| Some(*pid) != current.parent_id) {
+            validate_parent(&mut tx, project_id, Some(update.id), pid).await.map_err(|e| match e {
+                AppError::BadRequest(msg) => AppError::bad_request(format!("Task {}: {}", update.id, msg)),
+                other => other,
+            })?;
+        }
         let progress = update.progress.unwrap_or(current.progress);
+        let days = duration_days(start_date, end_date);
 
         sqlx::query(
-            "UPDATE tasks SET title = ?, status = ?, due_date = ?, start_date = ?, end_date = ?, assignee = ?, parent_id = ?, progress = ?, updated_at = ? WHERE id = ?"
+            "UPDATE tasks SET title = ?, status = ?, due_date = ?, start_date = ?, end_date = ?, duration_days = COALESCE(?, duration_days), estimated_days = ?, assignee = ?, parent_id = ?, progress = ?, updated_at = ? WHERE id = ?"
         )
-        .bind(title)
+        .bind(&title)
         .bind(status)
         .bind(due_date)
         .bind(start_date)
         .bind(end_date)
+        .bind(days)
+        .bind(estimated_days)
         .bind(assignee)
         .bind(parent_id)
         .bind(progress)
@@ -633,39 +1401,344 @@ pub async fn batch_update_tasks(
         .execute(&mut *tx)
         .await?;
 
-        updated_ids.push(update.id);
+        // Mirror the UPDATE onto the row we already read so the response keeps
+        // the caller's order without re-selecting after commit
+        updated.push(DbTask {
+            title,
+            status,
+            due_date,
+            start_date,
+            end_date,
+            duration_days: days.or(current.duration_days),
+            estimated_days,
+            assignee,
+            parent_id,
+            progress,
+            updated_at: now,
+            ..current
+        });
     }
 
+    if !invalid.is_empty() {
+        return Err(AppError::validation(invalid));
+    }
     tx.commit().await?;
 
-    if updated_ids.is_empty() {
-        return Ok(Json(Vec::new()));
+    let tasks: Vec<Task> = updated
+        .into_iter()
+        .map(Task::try_from)
+        .collect::<Result<_, _>>()?;
+
+    Ok(Json(tasks))
+}
+
+/// Load all live tasks of a project (no ownership check), ordered by start date.
+pub(crate) async fn fetch_project_tasks(pool: &SqlitePool, project_id: Uuid) -> AppResult<Vec<Task>> {
+    // Try simple fast-path query first
+    let simple = sqlx::query_as::<_, DbTask>(
+        "SELECT t.id, t.project_id, t.title, t.status, t.due_date, t.start_date, t.end_date, t.duration_days, t.estimated_days, t.assignee, t.parent_id, t.progress, t.created_at, t.updated_at, t.deleted_at
+         FROM tasks t
+         WHERE t.project_id = ? AND t.deleted_at IS NULL
+         ORDER BY t.start_date ASC, t.created_at DESC",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await;
+
+    let tasks_rows: Vec<DbTask> = match simple {
+        Ok(rows) => rows,
+        Err(_) => {
+            // Fallback: select textified UUIDs and parse manually
+            let id_case = uuid_sql::case_uuid("id");
+            let project_case = uuid_sql::case_uuid("project_id");
+            let assignee_case = uuid_sql::case_uuid("assignee");
+            let parent_case = uuid_sql::case_uuid("parent_id");
+            let sql = format!(
+                "SELECT {} , {} , title, status, due_date, start_date, end_date, duration_days, estimated_days, {} , {} , progress, created_at, updated_at, deleted_at FROM tasks t WHERE t.project_id = ? AND t.deleted_at IS NULL ORDER BY t.start_date ASC, t.created_at DESC",
+                id_case, project_case, assignee_case, parent_case
+            );
+
+            let rows = sqlx::query(&sql)
+                .bind(project_id.to_string())
+                .fetch_all(pool)
+                .await?;
+
+            let mut parsed = Vec::with_capacity(rows.len());
+            for row in rows {
+                parsed.push(row_parsers::db_task_from_row(&row)?);
+            }
+
+            parsed
+        }
+    };
+
+    let mut tasks: Vec<Task> = tasks_rows
+        .into_iter()
+        .map(Task::try_from)
+        .collect::<Result<_, _>>()?;
+
+    let mut tags = fetch_project_tags(pool, project_id).await?;
+    for task in &mut tasks {
+        task.tags = tags.remove(&task.id).unwrap_or_default();
+    }
+
+    Ok(tasks)
+}
+
+/// Tags of every live task in a project, keyed by task id and sorted.
+async fn fetch_project_tags(pool: &SqlitePool, project_id: Uuid) -> AppResult<HashMap<Uuid, Vec<String>>> {
+    let rows: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT tt.task_id, tt.tag FROM task_tags tt
+         INNER JOIN tasks t ON t.id = tt.task_id
+         WHERE t.project_id = ? AND t.deleted_at IS NULL
+         ORDER BY tt.tag ASC",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (task_id, tag) in rows {
+        tags.entry(task_id).or_default().push(tag);
     }
+    Ok(tags)
+}
+
+/// Sorted tags of a single task (no ownership check).
+pub(crate) async fn fetch_task_tags(pool: &SqlitePool, task_id: Uuid) -> AppResult<Vec<String>> {
+    let tags: Vec<String> = sqlx::query_scalar("SELECT tag FROM task_tags WHERE task_id = ? ORDER BY tag ASC")
+        .bind(task_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(tags)
+}
 
-    let placeholders = std::iter::repeat("?").take(updated_ids.len()).collect::<Vec<_>>().join(",");
+/// Load all dependencies whose source task is a live task of the project (no ownership check).
+pub(crate) async fn fetch_project_dependencies(pool: &SqlitePool, project_id: Uuid) -> AppResult<Vec<TaskDependency>> {
+    // Use a defensive manual SELECT that textifies UUIDs and parses rows explicitly.
+    let id_case = uuid_sql::case_uuid("d.id");
+    let source_case = uuid_sql::case_uuid("d.source_task_id");
+    let target_case = uuid_sql::case_uuid("d.target_task_id");
+    let project_match = uuid_sql::match_uuid_clause("t.project_id");
     let sql = format!(
-        "SELECT t.id, t.project_id, t.title, t.status, t.due_date, t.start_date, t.end_date, t.duration_days, t.assignee, t.parent_id, t.progress, t.created_at, t.updated_at, t.deleted_at \
-         FROM tasks t \
-         WHERE t.id IN ({}) ORDER BY t.start_date ASC",
-        placeholders
+        "SELECT {} , {} , {} , d.type, d.created_at FROM task_dependencies d INNER JOIN tasks t ON t.id = d.source_task_id WHERE {} AND t.deleted_at IS NULL",
+        id_case, source_case, target_case, project_match
     );
 
-    let mut query = sqlx::query_as::<_, DbTask>(&sql);
-    for id in updated_ids {
-        query = query.bind(id);
+    let rows = sqlx::query(&sql)
+        .bind(project_id.to_string())
+        .bind(project_id.to_string())
+        .fetch_all(pool)
+        .await?;
+
+    let mut parsed = Vec::with_capacity(rows.len());
+    for row in rows {
+        parsed.push(row_parsers::db_task_dependency_from_row(&row)?);
     }
 
-    let rows = query.fetch_all(&state.pool).await?;
+    let deps_rows = parsed;
 
-    let tasks: Vec<Task> = rows
+    let deps: Vec<TaskDependency> = deps_rows
         .into_iter()
-        .map(Task::try_from)
+        .map(TaskDependency::try_from)
         .collect::<Result<_, _>>()?;
 
+    Ok(deps)
+}
+
+#[utoipa::path(
+    post,
+    path = "/projects/{project_id}/tasks/assign",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id")),
+    request_body = TaskAssignRequest,
+    responses(
+        (status = 200, description = "Tasks now assigned to the user", body = [Task]),
+        (status = 400, description = "Empty task list or unknown user"),
+        (status = 404, description = "A task is not in this project")
+    )
+)]
+pub async fn assign_tasks(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(project_id): Path<Uuid>,
+    AppJson(payload): AppJson<TaskAssignRequest>,
+) -> AppResult<Json<Vec<Task>>> {
+    ensure_project_membership(&state.pool, auth.user_id, project_id).await?;
+
+    let mut seen = HashSet::new();
+    let task_ids: Vec<Uuid> = payload.task_ids.into_iter().filter(|id| seen.insert(*id)).collect();
+    if task_ids.is_empty() {
+        return Err(AppError::bad_request("task_ids must not be empty"));
+    }
+
+    let user_exists: bool = sqlx::query_scalar(&format!(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE {} AND deleted_at IS NULL)",
+        uuid_sql::match_uuid_clause("id")
+    ))
+    .bind(payload.user_id.to_string())
+    .bind(payload.user_id.to_string())
+    .fetch_one(&state.pool)
+    .await?;
+    if !user_exists {
+        return Err(AppError::bad_request(format!("user {} does not exist", payload.user_id)));
+    }
+
+    let mut tx = state.pool.begin().await?;
+    let now = utc_now();
+
+    // All or nothing: any task outside the project rolls back the whole assignment
+    for id in &task_ids {
+        let affected = sqlx::query(
+            "UPDATE tasks SET assignee = ?, updated_at = ? WHERE id = ? AND project_id = ? AND deleted_at IS NULL",
+        )
+        .bind(payload.user_id)
+        .bind(now)
+        .bind(id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if affected.rows_affected() == 0 {
+            return Err(AppError::not_found(format!("Task {} not found in project", id)));
+        }
+    }
+
+    tx.commit().await?;
+
+    let mut tasks: Vec<Task> = Vec::with_capacity(task_ids.len());
+    for id in task_ids {
+        tasks.push(fetch_task(&state.pool, auth.user_id, project_id, id).await?.try_into()?);
+    }
+
     Ok(Json(tasks))
 }
 
-async fn ensure_project_membership(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> AppResult<()> {
+#[utoipa::path(
+    post,
+    path = "/projects/{project_id}/tasks/{id}/tags",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id"), ("id" = Uuid, Path, description = "Task id")),
+    request_body = TaskTagsRequest,
+    responses(
+        (status = 200, description = "Task with the tags added; tags it already had are ignored", body = Task),
+        (status = 400, description = "Empty tag list, or a blank or overlong tag")
+    )
+)]
+pub async fn add_task_tags(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    Path((project_id, id)): Path<(Uuid, Uuid)>,
+    AppJson(payload): AppJson<TaskTagsRequest>,
+) -> AppResult<Json<Task>> {
+    change_task_tags(&state, &auth, &headers, project_id, id, &payload.tags, true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/projects/{project_id}/tasks/{id}/tags",
+    tag = "Tasks",
+    params(("project_id" = Uuid, Path, description = "Project id"), ("id" = Uuid, Path, description = "Task id")),
+    request_body = TaskTagsRequest,
+    responses(
+        (status = 200, description = "Task with the tags removed; tags it did not have are ignored", body = Task),
+        (status = 400, description = "Empty tag list, or a blank or overlong tag")
+    )
+)]
+pub async fn remove_task_tags(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    headers: axum::http::HeaderMap,
+    Path((project_id, id)): Path<(Uuid, Uuid)>,
+    AppJson(payload): AppJson<TaskTagsRequest>,
+) -> AppResult<Json<Task>> {
+    change_task_tags(&state, &auth, &headers, project_id, id, &payload.tags, false).await
+}
+
+/// Add or remove tags on a task (shared by POST and DELETE).
+///
+/// Only actual changes are written; when there are none the task is returned
+/// as is, without touching `updated_at` or logging an event.
+async fn change_task_tags(
+    state: &AppState,
+    auth: &AuthUser,
+    headers: &axum::http::HeaderMap,
+    project_id: Uuid,
+    task_id: Uuid,
+    requested: &[String],
+    add: bool,
+) -> AppResult<Json<Task>> {
+    let requested = normalize_tags(requested)?;
+    if requested.is_empty() {
+        return Err(AppError::bad_request("tags must not be empty"));
+    }
+
+    let mut old_dto: Task = fetch_task(&state.pool, auth.user_id, project_id, task_id).await?.try_into()?;
+    old_dto.tags = fetch_task_tags(&state.pool, task_id).await?;
+
+    let changed: Vec<String> = requested.into_iter().filter(|tag| old_dto.tags.contains(tag) != add).collect();
+    if changed.is_empty() {
+        return Ok(Json(old_dto));
+    }
+
+    let now = utc_now();
+    let mut tx = state.pool.begin().await?;
+    for tag in &changed {
+        let query = if add {
+            sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag, created_at) VALUES (?, ?, ?)")
+                .bind(task_id)
+                .bind(tag)
+                .bind(now)
+        } else {
+            sqlx::query("DELETE FROM task_tags WHERE task_id = ? AND tag = ?").bind(task_id).bind(tag)
+        };
+        query.execute(&mut *tx).await?;
+    }
+    // Bump the task so ETags and listings see the change
+    sqlx::query("UPDATE tasks SET updated_at = ? WHERE id = ?")
+        .bind(now)
+        .bind(task_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    let mut task_dto: Task = fetch_task(&state.pool, auth.user_id, project_id, task_id).await?.try_into()?;
+    task_dto.tags = fetch_task_tags(&state.pool, task_id).await?;
+
+    let ctx = crate::events::RequestContext::from_headers(headers);
+    crate::events::log_activity_with_context(&state.event_bus, "updated", Some(auth.user_id), &task_dto, Some(&old_dto), Some(ctx));
+
+    Ok(Json(task_dto))
+}
+
+/// A single dependency whose source task is a live task of `project_id`.
+async fn find_project_dependency(pool: &SqlitePool, project_id: Uuid, id: Uuid) -> AppResult<Option<TaskDependency>> {
+    let sql = format!(
+        "SELECT {} , {} , {} , d.type, d.created_at FROM task_dependencies d INNER JOIN tasks t ON t.id = d.source_task_id WHERE {} AND {} AND t.deleted_at IS NULL",
+        uuid_sql::case_uuid("d.id"),
+        uuid_sql::case_uuid("d.source_task_id"),
+        uuid_sql::case_uuid("d.target_task_id"),
+        uuid_sql::match_uuid_clause("t.project_id"),
+        uuid_sql::match_uuid_clause("d.id")
+    );
+
+    let row = sqlx::query(&sql)
+        .bind(project_id.to_string())
+        .bind(project_id.to_string())
+        .bind(id.to_string())
+        .bind(id.to_string())
+        .fetch_optional(pool)
+        .await?;
+
+    match row {
+        Some(row) => Ok(Some(row_parsers::db_task_dependency_from_row(&row)?.try_into()?)),
+        None => Ok(None),
+    }
+}
+
+/// The project exists and `user_id` is its owner or one of its members.
+pub(crate) async fn ensure_project_membership(pool: &SqlitePool, user_id: Uuid, project_id: Uuid) -> AppResult<()> {
     let owner = sqlx::query_scalar::<_, Uuid>(
         "SELECT user_id FROM projects WHERE id = ? AND deleted_at IS NULL",
     )
@@ -673,26 +1746,149 @@ async fn ensure_project_membership(pool: &SqlitePool, user_id: Uuid, project_id:
     .fetch_optional(pool)
     .await?;
 
-    let owner = owner.ok_or_else(|| AppError::not_found("project not found"))?;
+    let owner = match owner {
+        Some(owner) => owner,
+        None => {
+            // Distinguish a soft-deleted project (for its owner) from one that never existed
+            let deleted_owner = sqlx::query_scalar::<_, Uuid>(
+                "SELECT user_id FROM projects WHERE id = ? AND deleted_at IS NOT NULL",
+            )
+            .bind(project_id)
+            .fetch_optional(pool)
+            .await?;
 
-    if owner != user_id {
+            if deleted_owner == Some(user_id) {
+                return Err(AppError::not_found("project is deleted; restore it first"));
+            }
+            return Err(AppError::not_found("project not found"));
+        }
+    };
+
+    if owner != user_id && !is_project_member(pool, project_id, user_id).await? {
         return Err(AppError::forbidden("not allowed to modify this project"));
     }
 
     Ok(())
 }
 
-async fn fetch_task(pool: &SqlitePool, user_id: Uuid, project_id: Uuid, task_id: Uuid) -> AppResult<DbTask> {
+/// Check that `parent_id` is a live task in the project and, for an existing task,
+/// that it is not the task itself or one of its descendants.
+///
+/// Mirrors the dependency cycle check: a recursive CTE walks up the parent chain
+/// from the proposed parent; reaching `task_id` means the hierarchy would loop.
+async fn validate_parent(
+    conn: &mut sqlx::SqliteConnection,
+    project_id: Uuid,
+    task_id: Option<Uuid>,
+    parent_id: Uuid,
+) -> AppResult<()> {
+    if task_id == Some(parent_id) {
+        return Err(AppError::bad_request("A task cannot be its own parent"));
+    }
+
+    let parent_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM tasks WHERE id = ? AND project_id = ? AND deleted_at IS NULL)",
+    )
+    .bind(parent_id)
+    .bind(project_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    if !parent_exists {
+        return Err(AppError::bad_request(format!("parent task {} not found in project", parent_id)));
+    }
+
+    // A brand-new task has no descendants, so only existing tasks can close a loop
+    let Some(task_id) = task_id else {
+        return Ok(());
+    };
+    let cycle_exists: bool = sqlx::query_scalar(
+        "WITH RECURSIVE ancestors(node) AS (
+            SELECT ?
+            UNION
+            SELECT t.parent_id FROM tasks t JOIN ancestors a ON t.id = a.node WHERE t.parent_id IS NOT NULL
+        )
+        SELECT EXISTS(SELECT 1 FROM ancestors WHERE node = ?);",
+    )
+    .bind(parent_id)
+    .bind(task_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    if cycle_exists {
+        return Err(AppError::bad_request("Cycle detected: task would become its own ancestor"));
+    }
+
+    Ok(())
+}
+
+/// A task's `(start_date, end_date)`.
+type TimelineDates = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Validate a create payload and return its start/end dates normalized to midnight UTC
+/// (for consistent milestone detection).
+fn validate_new_task(payload: &TaskCreateRequest) -> AppResult<TimelineDates> {
+    let start_date = payload.start_date.map(normalize_to_midnight);
+    let end_date = payload.end_date.map(normalize_to_midnight);
+
+    let errors = timeline_errors(start_date, end_date, payload.estimated_days, payload.progress);
+    if !errors.is_empty() {
+        return Err(AppError::validation(errors));
+    }
+
+    Ok((start_date, end_date))
+}
+
+/// Upper bound for `estimated_days` (about 100 years), so scheduled dates stay representable.
+const MAX_ESTIMATED_DAYS: i32 = 36_500;
+
+/// Every timeline and progress problem of a task, so clients can fix them in one go.
+fn timeline_errors(
+    start_date: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    estimated_days: Option<i32>,
+    progress: Option<i32>,
+) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if let (Some(start), Some(end)) = (start_date, end_date) {
+        if end < start {
+            errors.push(FieldError::new("end_date", "end_date must be >= start_date"));
+        }
+    }
+    if end_date.is_some() && start_date.is_none() && end_date_requires_start_date() {
+        errors.push(FieldError::new("start_date", "start_date is required when end_date is set"));
+    }
+    if estimated_days.is_some_and(|d| d < 0) {
+        errors.push(FieldError::new("estimated_days", "estimated_days must be >= 0"));
+    }
+    if estimated_days.is_some_and(|d| d > MAX_ESTIMATED_DAYS) {
+        errors.push(FieldError::new("estimated_days", format!("estimated_days must be <= {}", MAX_ESTIMATED_DAYS)));
+    }
+    if progress.is_some_and(|p| !(0..=100).contains(&p)) {
+        errors.push(FieldError::new("progress", "progress must be between 0 and 100"));
+    }
+    errors
+}
+
+/// When `REQUIRE_START_DATE_WITH_END_DATE` is enabled, a task may not carry an
+/// `end_date` without a `start_date` (scheduling needs both ends of the bar).
+fn end_date_requires_start_date() -> bool {
+    std::env::var("REQUIRE_START_DATE_WITH_END_DATE")
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+pub(crate) async fn fetch_task(pool: &SqlitePool, user_id: Uuid, project_id: Uuid, task_id: Uuid) -> AppResult<DbTask> {
     // Try simple direct mapping first
     let simple = sqlx::query_as::<_, DbTask>(
-        "SELECT t.id, t.project_id, t.title, t.status, t.due_date, t.start_date, t.end_date, t.duration_days, t.assignee, t.parent_id, t.progress, t.created_at, t.updated_at, t.deleted_at
+        "SELECT t.id, t.project_id, t.title, t.status, t.due_date, t.start_date, t.end_date, t.duration_days, t.estimated_days, t.assignee, t.parent_id, t.progress, t.created_at, t.updated_at, t.deleted_at
          FROM tasks t
          INNER JOIN projects p ON p.id = t.project_id
-         WHERE t.id = ? AND t.project_id = ? AND p.user_id = ? AND p.deleted_at IS NULL AND t.deleted_at IS NULL",
+         WHERE t.id = ? AND t.project_id = ? AND (p.user_id = ? OR p.id IN (SELECT project_id FROM project_members WHERE user_id = ?)) \
+           AND p.deleted_at IS NULL AND t.deleted_at IS NULL",
     )
     .bind(task_id)
     .bind(project_id)
     .bind(user_id)
+    .bind(user_id)
     .fetch_optional(pool)
     .await;
 
@@ -705,14 +1901,15 @@ async fn fetch_task(pool: &SqlitePool, user_id: Uuid, project_id: Uuid, task_id:
                 "SELECT \
                    CASE WHEN typeof(t.id)='blob' THEN lower(substr(hex(t.id),1,8) || '-' || substr(hex(t.id),9,4) || '-' || substr(hex(t.id),13,4) || '-' || substr(hex(t.id),17,4) || '-' || substr(hex(t.id),21)) ELSE t.id END as id, \
                    CASE WHEN typeof(t.project_id)='blob' THEN lower(substr(hex(t.project_id),1,8) || '-' || substr(hex(t.project_id),9,4) || '-' || substr(hex(t.project_id),13,4) || '-' || substr(hex(t.project_id),17,4) || '-' || substr(hex(t.project_id),21)) ELSE t.project_id END as project_id, \
-                   t.title, t.status, t.due_date, t.start_date, t.end_date, t.duration_days, \
+                   t.title, t.status, t.due_date, t.start_date, t.end_date, t.duration_days, t.estimated_days, \
                    CASE WHEN typeof(t.assignee)='blob' THEN lower(substr(hex(t.assignee),1,8) || '-' || substr(hex(t.assignee),9,4) || '-' || substr(hex(t.assignee),13,4) || '-' || substr(hex(t.assignee),17,4) || '-' || substr(hex(t.assignee),21)) ELSE t.assignee END as assignee, \
                    CASE WHEN typeof(t.parent_id)='blob' THEN lower(substr(hex(t.parent_id),1,8) || '-' || substr(hex(t.parent_id),9,4) || '-' || substr(hex(t.parent_id),13,4) || '-' || substr(hex(t.parent_id),17,4) || '-' || substr(hex(t.parent_id),21)) ELSE t.parent_id END as parent_id, \
                    t.progress, t.created_at, t.updated_at, t.deleted_at \
                  FROM tasks t INNER JOIN projects p ON p.id = t.project_id \
                  WHERE ((typeof(t.id)='blob' AND hex(t.id)=upper(replace(?,'-',''))) OR (typeof(t.id)='text' AND t.id = ?)) \
                    AND ((typeof(t.project_id)='blob' AND hex(t.project_id)=upper(replace(?,'-',''))) OR (typeof(t.project_id)='text' AND t.project_id = ?)) \
-                   AND ((typeof(p.user_id)='blob' AND hex(p.user_id)=upper(replace(?,'-',''))) OR (typeof(p.user_id)='text' AND p.user_id = ?)) \
+                   AND (((typeof(p.user_id)='blob' AND hex(p.user_id)=upper(replace(?,'-',''))) OR (typeof(p.user_id)='text' AND p.user_id = ?)) \
+                     OR p.id IN (SELECT m.project_id FROM project_members m WHERE (typeof(m.user_id)='blob' AND hex(m.user_id)=upper(replace(?,'-',''))) OR (typeof(m.user_id)='text' AND m.user_id = ?))) \
                    AND p.deleted_at IS NULL AND t.deleted_at IS NULL",
             )
             .bind(task_id.to_string())
@@ -721,6 +1918,8 @@ async fn fetch_task(pool: &SqlitePool, user_id: Uuid, project_id: Uuid, task_id:
             .bind(project_id.to_string())
             .bind(user_id.to_string())
             .bind(user_id.to_string())
+            .bind(user_id.to_string())
+            .bind(user_id.to_string())
             .fetch_optional(pool)
             .await?;
 
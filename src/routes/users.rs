@@ -0,0 +1,78 @@
+//! User directory, so admins can find the ids that RBAC assignments take.
+
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::app::AppState;
+use crate::authz::{permissions, DefaultPolicyEvaluator, PolicyEvaluator, Principal, ResourceContext};
+use crate::db::row_parsers;
+use crate::db::uuid_sql::case_uuid;
+use crate::errors::{AppError, AppResult};
+use crate::jwt::AuthUser;
+use crate::models::user::User;
+use crate::routes::pagination::Pagination;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserListQuery {
+    /// Case-insensitive substring match on name or email
+    pub q: Option<String>,
+}
+
+/// Escape `%`, `_` and `\` so user input is matched literally by `LIKE ... ESCAPE '\'`.
+pub(crate) fn like_pattern(term: &str) -> String {
+    let escaped = term.to_lowercase().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// List active users
+///
+/// Requires `user.view` (super admins always pass), whatever the `AUTHZ_MODE`:
+/// the directory exposes every email address. Password hashes are never returned.
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "Users",
+    params(UserListQuery, Pagination),
+    responses(
+        (status = 200, description = "Users ordered by name", body = [User]),
+        (status = 403, description = "Caller lacks user.view"),
+    ),
+    security(("bearerAuth" = []))
+)]
+pub async fn list_users(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<UserListQuery>,
+    Query(page): Query<Pagination>,
+) -> AppResult<Json<Vec<User>>> {
+    let principal = Principal::load(&state.pool, auth.user_id).await?;
+    if !DefaultPolicyEvaluator::new().can(&principal, permissions::USER_VIEW, &ResourceContext::new()).await {
+        return Err(AppError::forbidden("user.view permission required"));
+    }
+
+    let term = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+    // Ids are textified so rows with legacy text ids parse alongside blob ones
+    let sql = format!(
+        "SELECT {}, name, email, password_hash, provider, provider_id, created_at, updated_at, deleted_at \
+         FROM users WHERE deleted_at IS NULL{} \
+         ORDER BY lower(name), lower(email) LIMIT ? OFFSET ?",
+        case_uuid("id"),
+        if term.is_some() { " AND (lower(name) LIKE ? ESCAPE '\\' OR lower(email) LIKE ? ESCAPE '\\')" } else { "" },
+    );
+
+    let mut q = sqlx::query(&sql);
+    if let Some(term) = term {
+        let pattern = like_pattern(term);
+        q = q.bind(pattern.clone()).bind(pattern);
+    }
+    let rows = q.bind(page.limit()).bind(page.offset()).fetch_all(&state.pool).await?;
+
+    let users = rows
+        .iter()
+        .map(|row| row_parsers::db_user_from_row(row).and_then(User::try_from))
+        .collect::<AppResult<Vec<_>>>()?;
+    Ok(Json(users))
+}
@@ -0,0 +1,236 @@
+//! Webhook management.
+//!
+//! Webhooks receive every matching domain event regardless of who caused it, so
+//! managing them requires the `super_admin` role.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use chrono::Utc;
+use sqlx::Row;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::errors::{AppError, AppResult};
+use crate::extract::AppJson;
+use crate::jwt::AuthUser;
+use crate::events::EventFilter;
+use crate::models::webhook::{event_name_filter, Webhook, WebhookCreateRequest, WebhookUpdateRequest, MATCH_ALL_FILTER};
+use crate::routes::admin::require_super_admin;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_webhooks).post(create_webhook))
+        .route("/:webhook_id", get(get_webhook).patch(update_webhook).delete(delete_webhook))
+}
+
+fn validate_url(url: &str) -> AppResult<()> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(AppError::bad_request("url must start with http:// or https://"))
+    }
+}
+
+/// Compile the requested filter: a full expression or an event-name glob, not both.
+fn resolve_filter(filter: Option<&str>, glob: Option<&str>) -> AppResult<Option<EventFilter>> {
+    match (filter, glob) {
+        (Some(_), Some(_)) => Err(AppError::bad_request("use either filter or event_name_filter, not both")),
+        (Some(expr), None) => EventFilter::parse(expr.trim())
+            .map(Some)
+            .map_err(|e| AppError::bad_request(format!("invalid filter: {}", e))),
+        (None, Some(glob)) => event_name_filter(glob)
+            .map(Some)
+            .map_err(|e| AppError::bad_request(format!("invalid event_name_filter: {}", e))),
+        (None, None) => Ok(None),
+    }
+}
+
+fn webhook_from_row(row: &sqlx::sqlite::SqliteRow) -> Webhook {
+    Webhook {
+        id: Uuid::parse_str(row.get::<&str, _>("id")).unwrap_or_default(),
+        url: row.get("url"),
+        filter: row.get("filter"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+async fn fetch_webhook(state: &AppState, webhook_id: Uuid) -> AppResult<Webhook> {
+    let row = sqlx::query("SELECT id, url, filter, created_at, updated_at FROM webhooks WHERE id = ?")
+        .bind(webhook_id.to_string())
+        .fetch_optional(&state.pool)
+        .await?
+        .ok_or_else(|| AppError::not_found("Webhook not found"))?;
+    Ok(webhook_from_row(&row))
+}
+
+/// List registered webhooks
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "Webhooks",
+    responses(
+        (status = 200, description = "Registered webhooks", body = Vec<Webhook>),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn list_webhooks(State(state): State<AppState>, auth: AuthUser) -> AppResult<Json<Vec<Webhook>>> {
+    require_super_admin(&state, &auth).await?;
+
+    let rows = sqlx::query("SELECT id, url, filter, created_at, updated_at FROM webhooks ORDER BY created_at")
+        .fetch_all(&state.pool)
+        .await?;
+    Ok(Json(rows.iter().map(webhook_from_row).collect()))
+}
+
+/// Register a webhook
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "Webhooks",
+    request_body = WebhookCreateRequest,
+    responses(
+        (status = 201, description = "Webhook registered", body = Webhook),
+        (status = 400, description = "Invalid url or filter"),
+        (status = 403, description = "Caller is not a super admin"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn create_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    AppJson(req): AppJson<WebhookCreateRequest>,
+) -> AppResult<(StatusCode, Json<Webhook>)> {
+    require_super_admin(&state, &auth).await?;
+
+    validate_url(&req.url)?;
+    if req.secret.is_empty() {
+        return Err(AppError::bad_request("secret must not be empty"));
+    }
+    let filter = resolve_filter(req.filter.as_deref(), req.event_name_filter.as_deref())?
+        .map(|f| f.as_str().to_string())
+        .unwrap_or_else(|| MATCH_ALL_FILTER.to_string());
+
+    let now = Utc::now();
+    let webhook = Webhook { id: Uuid::new_v4(), url: req.url, filter, created_at: now, updated_at: now };
+
+    sqlx::query(
+        "INSERT INTO webhooks (id, url, secret, filter, created_by, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(webhook.id.to_string())
+    .bind(&webhook.url)
+    .bind(&req.secret)
+    .bind(&webhook.filter)
+    .bind(auth.user_id.to_string())
+    .bind(now)
+    .bind(now)
+    .execute(&state.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+/// Get a webhook by ID
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}",
+    tag = "Webhooks",
+    params(("webhook_id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Webhook details", body = Webhook),
+        (status = 404, description = "Webhook not found"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn get_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(webhook_id): Path<Uuid>,
+) -> AppResult<Json<Webhook>> {
+    require_super_admin(&state, &auth).await?;
+    Ok(Json(fetch_webhook(&state, webhook_id).await?))
+}
+
+/// Change a webhook's url, secret and/or filter
+#[utoipa::path(
+    patch,
+    path = "/webhooks/{webhook_id}",
+    tag = "Webhooks",
+    params(("webhook_id" = Uuid, Path, description = "Webhook ID")),
+    request_body = WebhookUpdateRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = Webhook),
+        (status = 400, description = "Invalid url or filter"),
+        (status = 404, description = "Webhook not found"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn update_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(webhook_id): Path<Uuid>,
+    AppJson(req): AppJson<WebhookUpdateRequest>,
+) -> AppResult<Json<Webhook>> {
+    require_super_admin(&state, &auth).await?;
+
+    let mut webhook = fetch_webhook(&state, webhook_id).await?;
+    if let Some(url) = req.url {
+        validate_url(&url)?;
+        webhook.url = url;
+    }
+    if let Some(filter) = resolve_filter(req.filter.as_deref(), req.event_name_filter.as_deref())? {
+        webhook.filter = filter.as_str().to_string();
+    }
+    if matches!(req.secret.as_deref(), Some("")) {
+        return Err(AppError::bad_request("secret must not be empty"));
+    }
+    webhook.updated_at = Utc::now();
+
+    sqlx::query(
+        "UPDATE webhooks SET url = ?, filter = ?, secret = COALESCE(?, secret), updated_at = ? WHERE id = ?",
+    )
+    .bind(&webhook.url)
+    .bind(&webhook.filter)
+    .bind(req.secret)
+    .bind(webhook.updated_at)
+    .bind(webhook_id.to_string())
+    .execute(&state.pool)
+    .await?;
+
+    Ok(Json(webhook))
+}
+
+/// Remove a webhook
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{webhook_id}",
+    tag = "Webhooks",
+    params(("webhook_id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 204, description = "Webhook removed"),
+        (status = 404, description = "Webhook not found"),
+    ),
+    security(("bearerAuth" = []))
+)]
+async fn delete_webhook(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(webhook_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    require_super_admin(&state, &auth).await?;
+
+    let result = sqlx::query("DELETE FROM webhooks WHERE id = ?")
+        .bind(webhook_id.to_string())
+        .execute(&state.pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::not_found("Webhook not found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
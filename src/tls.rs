@@ -0,0 +1,54 @@
+//! How the server terminates TLS, resolved once from the environment so the
+//! listener in `main.rs` and the `servers` advertised by `docs.rs` always agree.
+
+/// Listener mode derived from `CERT_PATH`, `KEY_PATH` and `USE_SELF_SIGNED_TLS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TlsMode {
+    /// Neither a cert/key pair nor `USE_SELF_SIGNED_TLS`
+    Plaintext,
+    /// `CERT_PATH` and `KEY_PATH` both set; they win over `USE_SELF_SIGNED_TLS`
+    Files { cert_path: String, key_path: String },
+    /// `USE_SELF_SIGNED_TLS=true` without a cert/key pair: an in-memory certificate
+    SelfSigned,
+}
+
+impl TlsMode {
+    pub fn from_env() -> Self {
+        let cert = std::env::var("CERT_PATH").ok().filter(|v| !v.is_empty());
+        let key = std::env::var("KEY_PATH").ok().filter(|v| !v.is_empty());
+        if let (Some(cert_path), Some(key_path)) = (cert, key) {
+            return TlsMode::Files { cert_path, key_path };
+        }
+
+        let self_signed = std::env::var("USE_SELF_SIGNED_TLS")
+            .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        if self_signed {
+            TlsMode::SelfSigned
+        } else {
+            TlsMode::Plaintext
+        }
+    }
+
+    pub fn is_tls(&self) -> bool {
+        !matches!(self, TlsMode::Plaintext)
+    }
+
+    /// URL scheme clients should use for this listener.
+    pub fn scheme(&self) -> &'static str {
+        if self.is_tls() { "https" } else { "http" }
+    }
+}
+
+/// Hosts the generated certificate is valid for.
+pub const SELF_SIGNED_HOSTS: &[&str] = &["localhost", "127.0.0.1", "::1"];
+
+/// Generate a throwaway self-signed certificate, returned as `(cert_pem, key_pem)`.
+///
+/// A new key pair is made on every start, so clients must accept it explicitly
+/// (e.g. `curl -k`); it is meant for local development only.
+pub fn self_signed_pem() -> anyhow::Result<(String, String)> {
+    let hosts: Vec<String> = SELF_SIGNED_HOSTS.iter().map(|h| h.to_string()).collect();
+    let certified = rcgen::generate_simple_self_signed(hosts)?;
+    Ok((certified.cert.pem(), certified.key_pair.serialize_pem()))
+}
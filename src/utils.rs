@@ -5,16 +5,61 @@ use chrono::{DateTime, Utc};
 
 use crate::errors::AppError;
 
-const MIN_PASSWORD_LENGTH: usize = 8;
+const DEFAULT_MIN_PASSWORD_LENGTH: usize = 8;
 
-pub fn hash_password(password: &str) -> Result<String, AppError> {
-    if password.len() < MIN_PASSWORD_LENGTH {
-        return Err(AppError::bad_request(format!(
-            "password must be at least {} characters",
-            MIN_PASSWORD_LENGTH
-        )));
+/// Well-known passwords that are refused regardless of length (compared case-insensitively).
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "password1", "passw0rd", "12345678", "123456789", "1234567890", "11111111", "00000000",
+    "qwerty123", "qwertyuiop", "iloveyou", "sunshine", "football", "baseball", "welcome1", "letmein1",
+    "admin123", "abc12345", "trustno1", "princess",
+];
+
+/// Minimum password length, `PASSWORD_MIN_LENGTH` (default 8).
+fn min_password_length() -> usize {
+    std::env::var("PASSWORD_MIN_LENGTH")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_MIN_PASSWORD_LENGTH)
+}
+
+/// Reject passwords that are too short or on the common-password list.
+///
+/// Messages never include the password itself.
+pub fn validate_password(password: &str) -> Result<(), AppError> {
+    let min = min_password_length();
+    if password.chars().count() < min {
+        return Err(AppError::bad_request(format!("password must be at least {} characters", min)));
     }
 
+    let lowered = password.to_lowercase();
+    if COMMON_PASSWORDS.contains(&lowered.as_str()) {
+        return Err(AppError::bad_request("password is too common; choose a less guessable one"));
+    }
+
+    Ok(())
+}
+
+/// Basic shape check for an email address: `local@domain.tld`, no whitespace.
+pub fn validate_email(email: &str) -> Result<(), AppError> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(AppError::bad_request("email must be a valid address"));
+    }
+    Ok(())
+}
+
+pub fn hash_password(password: &str) -> Result<String, AppError> {
     let salt = SaltString::generate(&mut OsRng);
     Argon2::default()
         .hash_password(password.as_bytes(), &salt)
@@ -41,3 +86,12 @@ pub fn utc_now() -> DateTime<Utc> {
 pub fn normalize_to_midnight(dt: DateTime<Utc>) -> DateTime<Utc> {
     dt.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc()
 }
+
+/// Whole days between two (midnight-normalized) timeline dates, mirroring the
+/// `trg_tasks_set_duration_*` triggers so databases without them stay consistent.
+pub fn duration_days(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> Option<i32> {
+    match (start, end) {
+        (Some(start), Some(end)) => i32::try_from((end - start).num_days()).ok(),
+        _ => None,
+    }
+}
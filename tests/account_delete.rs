@@ -0,0 +1,63 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn deleting_account_revokes_token_and_hides_projects() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Leaving", "email": "leaving@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, member) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Staying", "email": "staying@example.com", "password": "password123"}))).await?;
+    let member_token = member["token"].as_str().context("missing token")?.to_string();
+    let member_id = member["user"]["id"].as_str().context("missing user id")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Doomed"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let (status, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Orphan"}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", task);
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/members", project_id), Some(&token), Some(json!({"user_id": member_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Wrong confirmation password leaves everything in place
+    let (status, _) = send(&app, "DELETE", "/auth/me", Some(&token), Some(json!({"password": "not-my-password"}))).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "GET", "/auth/me", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "DELETE", "/auth/me", Some(&token), Some(json!({"password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{}", body);
+
+    // The old token no longer authenticates
+    let (status, _) = send(&app, "GET", "/auth/me", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "GET", "/projects", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "POST", "/auth/login", None, Some(json!({"email": "leaving@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The owned project is gone for collaborators too
+    let (status, _) = send(&app, "GET", &format!("/projects/{}", project_id), Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, listed) = send(&app, "GET", "/projects", Some(&member_token), None).await?;
+    assert!(!listed.to_string().contains(&project_id), "{}", listed);
+
+    let live_tasks: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM tasks WHERE deleted_at IS NULL").fetch_one(&pool).await?;
+    assert_eq!(live_tasks, 0);
+
+    // Revocation survives a restart
+    let restarted = create_app(pool.clone()).await?;
+    let (status, _) = send(&restarted, "GET", "/auth/me", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&restarted, "GET", "/auth/me", Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::{self, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::response::Response;
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::{json, Value};
+use tower::util::ServiceExt; // for `oneshot`
+use uuid::Uuid;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+/// GET the export and parse each line.
+async fn export(app: &axum::Router, uri: &str, token: &str) -> Result<Vec<Value>> {
+    let req = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())?;
+    let resp: Response = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()), Some("application/x-ndjson"));
+    let bytes = body::to_bytes(resp.into_body(), 10_485_760).await?;
+    let text = String::from_utf8(bytes.to_vec())?;
+    assert!(text.is_empty() || text.ends_with('\n'), "every line is terminated");
+    text.lines().map(|line| Ok(serde_json::from_str(line)?)).collect()
+}
+
+#[tokio::test]
+async fn super_admin_exports_activity_log_as_ndjson() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Auditor", "email": "auditor@example.com", "password": "password123"}))).await?;
+    let admin_token = admin["token"].as_str().context("missing token")?.to_string();
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Dev", "email": "dev@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?.to_string();
+
+    // Fixed timestamps well before anything the app itself logs, inserted out of order
+    let day = |d: u32| -> DateTime<Utc> { Utc.with_ymd_and_hms(2020, 1, d, 12, 0, 0).unwrap() };
+    let subject = Uuid::new_v4();
+    for (d, name) in [(3, "third"), (1, "first"), (2, "second"), (4, "fourth")] {
+        sqlx::query(
+            "INSERT INTO activity_log (id, event_name, description, actor_id, subject_id, occurred_at, properties, severity) VALUES (?, ?, 'System event', NULL, ?, ?, ?, 'important')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(format!("seed.{}", name))
+        .bind(subject)
+        .bind(day(d))
+        .bind(json!({"name": format!("seed.{}", name), "payload": {"day": d, "nested": {"keep": [1, 2]}}}))
+        .execute(&pool)
+        .await?;
+    }
+
+    let (status, _) = send(&app, "GET", "/activity/export", Some(&other_token), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Unfiltered: everything, oldest first, with the full properties
+    let all = export(&app, "/activity/export", &admin_token).await?;
+    assert!(all.len() >= 4);
+    let names: Vec<&str> = all.iter().take(4).map(|line| line["event_name"].as_str().unwrap_or_default()).collect();
+    assert_eq!(names, ["seed.first", "seed.second", "seed.third", "seed.fourth"]);
+    assert_eq!(all[0]["subject_id"].as_str(), Some(subject.to_string().as_str()));
+    assert_eq!(all[0]["properties"]["payload"]["nested"]["keep"], json!([1, 2]));
+    let times: Vec<DateTime<Utc>> = all
+        .iter()
+        .map(|line| serde_json::from_value(line["occurred_at"].clone()))
+        .collect::<Result<_, _>>()?;
+    assert!(times.windows(2).all(|w| w[0] <= w[1]));
+
+    // since is inclusive, until exclusive
+    let window = export(&app, "/activity/export?since=2020-01-02T12:00:00Z&until=2020-01-04T12:00:00Z", &admin_token).await?;
+    let names: Vec<&str> = window.iter().map(|line| line["event_name"].as_str().unwrap_or_default()).collect();
+    assert_eq!(names, ["seed.second", "seed.third"]);
+
+    let older = export(&app, "/activity/export?until=2020-01-02T00:00:00Z", &admin_token).await?;
+    assert_eq!(older.len(), 1);
+    assert_eq!(older[0]["event_name"], "seed.first");
+
+    let newer = export(&app, "/activity/export?since=2021-01-01T00:00:00Z", &admin_token).await?;
+    assert!(newer.iter().all(|line| !line["event_name"].as_str().unwrap_or_default().starts_with("seed.")));
+
+    let (status, _) = send(&app, "GET", "/activity/export?since=2020-01-03T00:00:00Z&until=2020-01-02T00:00:00Z", Some(&admin_token), None).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use common::migrated_pool;
+use s_curve::events;
+
+async fn wait_for_event(pool: &SqlitePool, name: &str) -> Result<()> {
+    for _ in 0..100 {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM activity_log WHERE event_name = ?")
+            .bind(name)
+            .fetch_one(pool)
+            .await?;
+        if count > 0 {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    anyhow::bail!("{} never reached activity_log", name)
+}
+
+#[tokio::test]
+async fn garbage_events_do_not_stop_the_activity_listener() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    let (bus, rx) = events::init_event_bus();
+    let listener = tokio::spawn(events::supervise_activity_listener(bus.downgrade(), rx, pool.clone()));
+
+    let garbage = [
+        Value::Null,
+        json!(42),
+        json!("task.created"),
+        json!([1, 2, 3]),
+        json!({}),
+        json!({"name": 7, "occurred_at": {"nested": true}, "actor_id": [], "subject_id": "not-a-uuid", "payload": "flat"}),
+        json!({"name": "task.updated", "occurred_at": "yesterday", "payload": {"severity": 3}}),
+    ];
+    for event in garbage {
+        bus.send(event).context("listener dropped")?;
+    }
+    bus.send(json!({
+        "name": "probe.fired",
+        "occurred_at": chrono::Utc::now().to_rfc3339(),
+        "payload": {"severity": "important"},
+    }))
+    .context("listener dropped")?;
+
+    // Everything after the garbage still lands, and the listener is still up
+    wait_for_event(&pool, "probe.fired").await?;
+    assert!(!listener.is_finished());
+
+    bus.send(json!({"name": "probe.again", "occurred_at": chrono::Utc::now().to_rfc3339()}))
+        .context("listener dropped")?;
+    wait_for_event(&pool, "probe.again").await?;
+
+    // Unrecognised shapes are still audited, under the fallback name
+    let unknown: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM activity_log WHERE event_name = 'unknown'")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(unknown, 6);
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM event_store").fetch_one(&pool).await?;
+    assert_eq!(stored, 9);
+    let verification = events::verify_event_chain(&pool).await?;
+    assert!(verification.valid, "{:?}", verification);
+
+    // Closing the bus ends the listener instead of restarting it
+    drop(bus);
+    tokio::time::timeout(Duration::from_secs(5), listener)
+        .await
+        .context("listener did not stop after the bus closed")??;
+
+    Ok(())
+}
@@ -12,7 +12,7 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use s_curve::create_app;
-use s_curve::models::task::TaskCreateRequest;
+use s_curve::models::task::{TaskCreateRequest, TaskStatus};
 
 #[tokio::test]
 async fn test_activity_log_flow() -> Result<()> {
@@ -75,10 +75,11 @@ async fn test_activity_log_flow() -> Result<()> {
     // 4. Create Task (should trigger "task.created" log)
     let task_payload = TaskCreateRequest {
         title: "Audit This Task".to_string(),
-        status: Some("pending".to_string()),
+        status: Some(TaskStatus::Pending),
         due_date: None,
         start_date: Some(Utc::now()),
         end_date: Some(Utc::now() + chrono::Duration::days(1)),
+        estimated_days: None,
         assignee: None,
         parent_id: None,
         progress: None,
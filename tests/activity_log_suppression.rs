@@ -0,0 +1,70 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::SqlitePool;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+async fn count(pool: &SqlitePool, table: &str, event_name: &str) -> Result<i64> {
+    let sql = format!("SELECT COUNT(*) FROM {} WHERE event_name = ?", table);
+    Ok(sqlx::query_scalar(&sql).bind(event_name).fetch_one(pool).await?)
+}
+
+#[tokio::test]
+async fn disabled_events_reach_neither_activity_log_nor_event_store() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    // Read once per process, so set before anything is logged
+    std::env::set_var("ACTIVITY_LOG_DISABLE", "progress.*, task.*, role.*, user_role.*");
+    std::env::set_var("ACTIVITY_LOG_ENABLE", "task.created");
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Quiet", "email": "quiet@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let user_id = auth["user"]["id"].as_str().context("missing user id")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Hushed"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+    let (_, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Pour"}))).await?;
+    let task_id = task["id"].as_str().context("missing task id")?.to_string();
+
+    let (status, _) = send(&app, "POST", &format!("{}/{}/progress", tasks_uri, task_id), Some(&token), Some(json!({"progress": 30}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    // RBAC changes are Critical and cannot be switched off
+    let (status, role) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "auditor"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("/rbac/users/{}/roles", user_id), Some(&token), Some(json!({"role_id": role["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", tasks_uri, task_id), Some(&token), None).await?;
+    assert!(status.is_success());
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}/purge?confirm=true", tasks_uri, task_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // The listener writes in order, so once the last event is in everything before it is too
+    for _ in 0..50 {
+        if count(&pool, "event_store", "task.purged").await? > 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    for table in ["activity_log", "event_store"] {
+        assert_eq!(count(&pool, table, "progress.created").await?, 0, "{}", table);
+        assert_eq!(count(&pool, table, "task.updated").await?, 0, "{}", table);
+        // Allowlisted past the task.* deny
+        assert_eq!(count(&pool, table, "task.created").await?, 1, "{}", table);
+        // Critical despite matching a deny glob
+        assert_eq!(count(&pool, table, "task.purged").await?, 1, "{}", table);
+        // RBAC changes are kept whatever their action
+        assert_eq!(count(&pool, table, "role.created").await?, 1, "{}", table);
+        assert_eq!(count(&pool, table, "user_role.assigned").await?, 1, "{}", table);
+        assert_eq!(count(&pool, table, "project.created").await?, 1, "{}", table);
+    }
+
+    Ok(())
+}
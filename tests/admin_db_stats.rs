@@ -0,0 +1,46 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use tempfile::tempdir;
+
+use common::{migrate, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn db_stats_reports_pool_configuration_for_super_admin() -> Result<()> {
+    let dir = tempdir().context("failed to create tempdir")?;
+    let db_path = dir.path().join("test.db");
+    use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+    let opts = SqliteConnectOptions::new()
+        .filename(db_path.as_path())
+        .create_if_missing(true)
+        .foreign_keys(true)
+        .journal_mode(SqliteJournalMode::Wal);
+    let pool = SqlitePoolOptions::new().max_connections(4).connect_with(opts).await?;
+
+    migrate(&pool).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Ops", "email": "ops@example.com", "password": "password123"}))).await?;
+    let admin_token = admin["token"].as_str().context("missing token")?.to_string();
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Dev", "email": "dev@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?.to_string();
+
+    let (status, stats) = send(&app, "GET", "/admin/db/stats", Some(&admin_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["pool"]["max_connections"].as_u64(), Some(4));
+    let size = stats["pool"]["size"].as_u64().context("missing size")?;
+    assert!(size >= 1 && size <= 4);
+    assert!(stats["sqlite"]["page_count"].as_i64().unwrap_or(0) > 0);
+    assert_eq!(stats["sqlite"]["journal_mode"], "wal");
+
+    let (status, _) = send(&app, "GET", "/admin/db/stats", Some(&other_token), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    Ok(())
+}
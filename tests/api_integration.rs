@@ -17,9 +17,12 @@ async fn full_api_flow() -> Result<()> {
     let db_path = dir.path().join("test.db");
     // create a Sqlite pool pointing at the temp file and ensure file is created
     use sqlx::sqlite::SqliteConnectOptions;
+    // mirror db::init(): FK enforcement and WAL journaling
     let opts = SqliteConnectOptions::new()
         .filename(db_path.as_path())
-        .create_if_missing(true);
+        .create_if_missing(true)
+        .foreign_keys(true)
+        .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal);
     let pool = SqlitePool::connect_with(opts).await?;
 
     // run migrations from crate migrations folder
@@ -139,8 +142,8 @@ async fn full_api_flow() -> Result<()> {
         panic!("progress list failed: {} - {}", status, String::from_utf8_lossy(&body_bytes));
     }
     let list_res: serde_json::Value = serde_json::from_slice(&body_bytes)?;
-    assert!(list_res.is_array());
-    assert!(list_res.as_array().unwrap().len() >= 1);
+    assert!(list_res["items"].is_array());
+    assert!(list_res["items"].as_array().unwrap().len() >= 1);
 
     // -- update progress
     let update_body = json!({"progress": 80});
@@ -187,7 +190,7 @@ async fn full_api_flow() -> Result<()> {
     }
     let list_res: serde_json::Value = serde_json::from_slice(&body_bytes)?;
     // ensure deleted entries are filtered out
-    assert!(!list_res.as_array().unwrap().iter().any(|v| v.get("id").and_then(|x| x.as_str()) == Some(&prog_id)));
+    assert!(!list_res["items"].as_array().unwrap().iter().any(|v| v.get("id").and_then(|x| x.as_str()) == Some(&prog_id)));
 
     Ok(())
 }
@@ -0,0 +1,54 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::{HeaderMap, StatusCode};
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send_with_headers};
+use s_curve::{create_app, API_PREFIX};
+
+async fn send(app: &axum::Router, method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> Result<(StatusCode, HeaderMap, Value)> {
+    send_with_headers(app, method, uri, token, &[], body).await
+}
+
+/// Prefix a route with the current API version.
+fn v1(path: &str) -> String {
+    format!("{}{}", API_PREFIX, path)
+}
+
+#[tokio::test]
+async fn versioned_routes_and_deprecated_aliases() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, _, user) = send(&app, "POST", &v1("/auth/register"), None, Some(json!({"name": "Versioned", "email": "v1@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = user["token"].as_str().context("missing token")?.to_string();
+
+    let (status, headers, project) = send(&app, "POST", &v1("/projects"), Some(&token), Some(json!({"name": "Prefixed"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(headers.get("deprecation").is_none());
+    let project_path = format!("/projects/{}", project["id"].as_str().context("missing project id")?);
+
+    // The unprefixed alias serves the same data but is flagged as deprecated
+    let (status, headers, alias) = send(&app, "GET", &project_path, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(alias["id"], project["id"]);
+    assert_eq!(headers.get("deprecation").and_then(|v| v.to_str().ok()), Some("true"));
+    let link = headers.get("link").and_then(|v| v.to_str().ok()).unwrap_or_default();
+    assert_eq!(link, format!("<{}>; rel=\"successor-version\"", v1(&project_path)));
+
+    let (status, headers, _) = send(&app, "GET", &v1(&project_path), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(headers.get("deprecation").is_none());
+
+    // Probes are not versioned
+    let (status, _, _) = send(&app, "GET", "/health/live", None, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, _) = send(&app, "GET", &v1("/health/live"), None, None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
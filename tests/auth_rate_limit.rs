@@ -0,0 +1,63 @@
+mod common;
+
+use anyhow::{Context, Result};
+use std::net::SocketAddr;
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::migrated_pool;
+use s_curve::create_app;
+
+fn login_from(peer: &str, forwarded_for: &str) -> Result<Request<Body>> {
+    let peer: SocketAddr = format!("{}:443", peer).parse()?;
+    Ok(Request::builder()
+        .method("POST")
+        .uri("/auth/login")
+        .header("content-type", "application/json")
+        .header("x-forwarded-for", forwarded_for)
+        .extension(ConnectInfo(peer))
+        .body(Body::from(json!({"email": "nobody@example.com", "password": "wrong-password"}).to_string()))?)
+}
+
+#[tokio::test]
+async fn login_is_throttled_per_client_ip_behind_trusted_proxy() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("AUTH_RATE_LIMIT_BURST", "2");
+    std::env::set_var("AUTH_RATE_LIMIT_PER_MINUTE", "1");
+    std::env::set_var("TRUSTED_PROXIES", "10.0.0.1, 10.0.0.2");
+    let app = create_app(pool.clone()).await?;
+
+    // Same proxies (10.0.0.1 then 10.0.0.2), two different real clients
+    for _ in 0..2 {
+        let resp = app.clone().oneshot(login_from("10.0.0.2", "203.0.113.5, 10.0.0.1")?).await?;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let resp = app.clone().oneshot(login_from("10.0.0.2", "203.0.113.5, 10.0.0.1")?).await?;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = resp
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .context("missing Retry-After")?;
+    assert!(retry_after >= 1 && retry_after <= 60);
+
+    let resp = app.clone().oneshot(login_from("10.0.0.2", "198.51.100.7, 10.0.0.1")?).await?;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // A direct client can't dodge its bucket by inventing forwarding headers
+    for (attempt, expected) in [StatusCode::UNAUTHORIZED, StatusCode::UNAUTHORIZED, StatusCode::TOO_MANY_REQUESTS].into_iter().enumerate() {
+        let spoofed = format!("192.0.2.{}", attempt + 1);
+        let resp = app.clone().oneshot(login_from("198.51.100.99", &spoofed)?).await?;
+        assert_eq!(resp.status(), expected);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::{migrated_pool, send};
+use s_curve::app::AppState;
+use s_curve::authz::{self, AuthzMode, ResourceContext};
+use s_curve::create_app;
+use s_curve::jwt::JwtConfig;
+
+#[tokio::test]
+async fn advisory_mode_allows_but_records_denials() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+    authz::seed(&pool).await?;
+
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 1);
+    let (event_bus, rx) = s_curve::events::init_event_bus();
+    tokio::spawn(s_curve::events::start_activity_listener(rx, pool.clone()));
+    let state = AppState::new(pool.clone(), jwt, event_bus);
+
+    let user_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let task_id = Uuid::new_v4();
+    let ctx = ResourceContext::for_task(project_id, task_id);
+
+    // Off: nothing evaluated, nothing recorded
+    state.set_authz_mode(AuthzMode::Off);
+    authz::authorize(&state, user_id, "task.update", &ctx, "/projects/x/tasks/y").await?;
+
+    // Advisory: allowed, but the would-be 403 is published
+    state.set_authz_mode(AuthzMode::Advisory);
+    authz::authorize(&state, user_id, "task.update", &ctx, "/projects/x/tasks/y").await?;
+
+    // Strict: the same check is rejected
+    state.set_authz_mode(AuthzMode::Strict);
+    let err = authz::authorize(&state, user_id, "task.update", &ctx, "/projects/x/tasks/y").await.unwrap_err();
+    assert_eq!(err.code(), "FORBIDDEN");
+
+    let mut rows: Vec<(String, String, String)> = Vec::new();
+    for _ in 0..15 {
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        rows = sqlx::query_as("SELECT event_name, severity, properties FROM activity_log WHERE event_name = 'authz.denied'")
+            .fetch_all(&pool)
+            .await?;
+        if !rows.is_empty() {
+            break;
+        }
+    }
+
+    assert_eq!(rows.len(), 1, "only the advisory denial is recorded");
+    let (_, severity, properties) = &rows[0];
+    assert_eq!(severity, "important");
+    let properties: Value = serde_json::from_str(properties)?;
+    let denial = &properties["payload"]["new"];
+    assert_eq!(denial["user_id"], user_id.to_string());
+    assert_eq!(denial["permission"], "task.update");
+    assert_eq!(denial["path"], "/projects/x/tasks/y");
+    assert_eq!(denial["mode"], "advisory");
+    assert_eq!(denial["context"]["resource_type"], "task");
+    assert_eq!(denial["context"]["resource_id"], task_id.to_string());
+    assert_eq!(denial["context"]["project_id"], project_id.to_string());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn advisory_mode_records_denials_for_real_requests() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("AUTHZ_MODE", "advisory");
+    let app = create_app(pool.clone()).await?;
+    std::env::remove_var("AUTHZ_MODE");
+
+    // A fresh account holds no roles, so every check fails but nothing is blocked
+    let (_, user) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Rookie", "email": "rookie@example.com", "password": "password123"}))).await?;
+    let token = user["token"].as_str().context("missing token")?.to_string();
+    let (status, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Dry run"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Survey"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let mut denials: Vec<Value> = Vec::new();
+    for _ in 0..15 {
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        let rows: Vec<String> = sqlx::query_scalar("SELECT properties FROM activity_log WHERE event_name = 'authz.denied' ORDER BY occurred_at")
+            .fetch_all(&pool)
+            .await?;
+        denials = rows.iter().map(|p| serde_json::from_str::<Value>(p).map(|v| v["payload"]["new"].clone())).collect::<Result<_, _>>()?;
+        if denials.len() >= 2 {
+            break;
+        }
+    }
+
+    assert_eq!(denials.len(), 2, "{:?}", denials);
+    assert_eq!(denials[0]["permission"], "project.create");
+    assert_eq!(denials[0]["path"], "/projects");
+    assert_eq!(denials[1]["permission"], "task.create");
+    assert_eq!(denials[1]["path"], format!("/projects/{}/tasks", project_id));
+    assert_eq!(denials[1]["context"]["project_id"], project_id);
+    assert_eq!(denials[1]["user_id"], user["user"]["id"]);
+
+    Ok(())
+}
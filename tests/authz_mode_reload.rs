@@ -0,0 +1,58 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn authz_mode_can_be_switched_at_runtime() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    std::env::set_var("AUTHZ_MODE", "advisory");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Ops", "email": "ops@example.com", "password": "password123"}))).await?;
+    let admin_token = admin["token"].as_str().context("missing token")?.to_string();
+    let (_, user) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Dev", "email": "dev@example.com", "password": "password123"}))).await?;
+    let user_token = user["token"].as_str().context("missing token")?.to_string();
+
+    let (status, body) = send(&app, "GET", "/admin/authz-mode", Some(&admin_token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["mode"], "advisory");
+
+    // Only super admins may change it
+    let (status, _) = send(&app, "PUT", "/admin/authz-mode", Some(&user_token), Some(json!({"mode": "off"}))).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(&app, "PUT", "/admin/authz-mode", Some(&admin_token), Some(json!({"mode": "enforcing"}))).await?;
+    assert!(status.is_client_error(), "unknown modes are rejected, got {}", status);
+
+    let (status, body) = send(&app, "PUT", "/admin/authz-mode", Some(&admin_token), Some(json!({"mode": "strict"}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["previous"], "advisory");
+    assert_eq!(body["mode"], "strict");
+
+    // The very next request sees the new mode
+    let (_, body) = send(&app, "GET", "/admin/authz-mode", Some(&admin_token), None).await?;
+    assert_eq!(body["mode"], "strict");
+
+    let mut severity = None;
+    for _ in 0..15 {
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        severity = sqlx::query_scalar::<_, String>("SELECT severity FROM activity_log WHERE event_name = 'authz_mode.updated'")
+            .fetch_optional(&pool)
+            .await?;
+        if severity.is_some() {
+            break;
+        }
+    }
+    assert_eq!(severity.as_deref(), Some("critical"));
+
+    std::env::remove_var("AUTHZ_MODE");
+    Ok(())
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use common::migrated_pool;
+use s_curve::authz::{DefaultPolicyEvaluator, PolicyEvaluator, Principal, ResourceContext};
+
+async fn create_permission(pool: &SqlitePool, name: &str) -> Result<String> {
+    let id = Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO permissions (id, name, created_at, updated_at) VALUES (?, ?, datetime('now'), datetime('now'))")
+        .bind(&id)
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(id)
+}
+
+#[tokio::test]
+async fn loaded_principal_applies_wildcard_allow_and_explicit_deny() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    let user_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'P', 'principal@example.com', 'hash', 'local', datetime('now'), datetime('now'))")
+        .bind(user_id.to_string())
+        .execute(&pool)
+        .await?;
+
+    let role_id = Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO roles (id, name, created_at, updated_at) VALUES (?, 'project_owner_no_delete', datetime('now'), datetime('now'))")
+        .bind(&role_id)
+        .execute(&pool)
+        .await?;
+
+    let allow = create_permission(&pool, "project.*").await?;
+    let deny = create_permission(&pool, "deny:project.delete").await?;
+
+    sqlx::query("INSERT INTO role_permissions (role_id, permission_id) VALUES (?, ?)")
+        .bind(&role_id)
+        .bind(&allow)
+        .execute(&pool)
+        .await?;
+    sqlx::query("INSERT INTO user_roles (user_id, role_id) VALUES (?, ?)")
+        .bind(user_id.to_string())
+        .bind(&role_id)
+        .execute(&pool)
+        .await?;
+    sqlx::query("INSERT INTO user_permissions (id, user_id, permission_id, scope) VALUES (?, ?, ?, '{}')")
+        .bind(Uuid::new_v4().to_string())
+        .bind(user_id.to_string())
+        .bind(&deny)
+        .execute(&pool)
+        .await?;
+
+    let principal = Principal::load(&pool, user_id).await?;
+    assert!(principal.has_role("project_owner_no_delete"));
+    assert!(principal.denied_permissions.contains("project.delete"));
+
+    let evaluator = DefaultPolicyEvaluator::new();
+    let ctx = ResourceContext::new();
+    assert!(evaluator.can(&principal, "project.create", &ctx).await);
+    assert!(evaluator.can(&principal, "project.view", &ctx).await);
+    assert!(evaluator.can(&principal, "project.update", &ctx).await);
+    assert!(!evaluator.can(&principal, "project.delete", &ctx).await);
+    assert!(!evaluator.can(&principal, "task.create", &ctx).await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn expired_scoped_grant_no_longer_satisfies_can() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    let user_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Temp', 'temp_access@example.com', 'hash', 'local', datetime('now'), datetime('now'))")
+        .bind(user_id.to_string())
+        .execute(&pool)
+        .await?;
+
+    let view = create_permission(&pool, "report.view").await?;
+    let edit = create_permission(&pool, "report.edit").await?;
+
+    let project_id = Uuid::new_v4();
+    let scope = serde_json::json!({"project_id": project_id.to_string()}).to_string();
+    let now = chrono::Utc::now();
+
+    // report.view expired an hour ago, report.edit is valid for another hour
+    for (perm, expires_at) in [(&view, now - chrono::Duration::hours(1)), (&edit, now + chrono::Duration::hours(1))] {
+        sqlx::query("INSERT INTO user_permissions (id, user_id, permission_id, scope, created_at, expires_at) VALUES (?, ?, ?, ?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(user_id.to_string())
+            .bind(perm)
+            .bind(&scope)
+            .bind(now - chrono::Duration::hours(2))
+            .bind(expires_at)
+            .execute(&pool)
+            .await?;
+    }
+
+    let principal = Principal::load(&pool, user_id).await?;
+    let evaluator = DefaultPolicyEvaluator::new();
+    let ctx = ResourceContext::new().with_project(project_id);
+
+    assert!(!evaluator.can(&principal, "report.view", &ctx).await, "expired grant must not apply");
+    assert!(evaluator.can(&principal, "report.edit", &ctx).await);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+use common::migrated_pool;
+use s_curve::authz::{self, permissions, roles};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn seeding_is_idempotent_and_creates_super_admin() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    // create_app seeds on startup
+    let _app = create_app(pool.clone()).await?;
+
+    let counts = |pool: SqlitePool| async move {
+        let roles: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM roles").fetch_one(&pool).await?;
+        let perms: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM permissions").fetch_one(&pool).await?;
+        let links: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM role_permissions").fetch_one(&pool).await?;
+        Ok::<_, sqlx::Error>((roles, perms, links))
+    };
+    let first = counts(pool.clone()).await?;
+    assert_eq!(first.0, 5);
+
+    // Simulate restarts
+    authz::seed(&pool).await?;
+    let _app = create_app(pool.clone()).await?;
+    assert_eq!(counts(pool.clone()).await?, first);
+
+    let super_admin_perms: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM role_permissions rp INNER JOIN roles r ON r.id = rp.role_id WHERE r.name = ?"
+    )
+    .bind(roles::SUPER_ADMIN)
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(super_admin_perms, first.1);
+
+    let member_has_task_update: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM role_permissions rp INNER JOIN roles r ON r.id = rp.role_id INNER JOIN permissions p ON p.id = rp.permission_id WHERE r.name = ? AND p.name = ?"
+    )
+    .bind(roles::MEMBER)
+    .bind(permissions::TASK_UPDATE)
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(member_has_task_update, 1);
+
+    Ok(())
+}
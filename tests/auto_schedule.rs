@@ -0,0 +1,133 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+fn date(value: &Value) -> Result<chrono::DateTime<chrono::Utc>> {
+    let s = value.as_str().context("expected a date string")?;
+    Ok(chrono::DateTime::parse_from_rfc3339(s)?.with_timezone(&chrono::Utc))
+}
+
+fn find<'a>(tasks: &'a Value, id: &Value) -> Result<&'a Value> {
+    tasks.as_array().context("expected a task list")?.iter().find(|t| &t["id"] == id).context("task missing")
+}
+
+#[tokio::test]
+async fn schedule_places_tasks_after_their_predecessors() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Planner", "email": "planner@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Launch"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, design) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Design", "estimated_days": 3}))).await?;
+    let (_, build) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({
+        "title": "Build", "start_date": "2024-01-10T00:00:00Z", "end_date": "2024-01-12T00:00:00Z"
+    }))).await?;
+    let (_, launch) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Launch"}))).await?;
+    let (_, docs) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Docs", "estimated_days": 1}))).await?;
+
+    let deps_uri = format!("/projects/{}/dependencies", project_id);
+    for (source, target) in [(&design, &build), (&build, &launch), (&docs, &launch)] {
+        let (status, _) = send(&app, "POST", &deps_uri, Some(&token), Some(json!({"source_task_id": source["id"], "target_task_id": target["id"]}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // A start with a time of day is moved to midnight
+    let schedule_uri = format!("/projects/{}/schedule", project_id);
+    let (status, tasks) = send(&app, "POST", &schedule_uri, Some(&token), Some(json!({"project_start": "2025-10-01T15:30:00Z"}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", tasks);
+    assert_eq!(tasks.as_array().map(|t| t.len()), Some(4));
+
+    let expect = |task: &Value, start: &str, end: &str| -> Result<()> {
+        let task = find(&tasks, &task["id"])?;
+        assert_eq!(date(&task["start_date"])?, chrono::DateTime::parse_from_rfc3339(start)?, "{}", task["title"]);
+        assert_eq!(date(&task["end_date"])?, chrono::DateTime::parse_from_rfc3339(end)?, "{}", task["title"]);
+        Ok(())
+    };
+    expect(&design, "2025-10-01T00:00:00Z", "2025-10-04T00:00:00Z")?;
+    expect(&build, "2025-10-04T00:00:00Z", "2025-10-06T00:00:00Z")?;
+    expect(&docs, "2025-10-01T00:00:00Z", "2025-10-02T00:00:00Z")?;
+    // Zero-length task stays a milestone at its predecessors' latest end
+    expect(&launch, "2025-10-06T00:00:00Z", "2025-10-06T00:00:00Z")?;
+    assert_eq!(find(&tasks, &design["id"])?["duration_days"], 3);
+
+    // The new dates are persisted
+    let (_, listed) = send(&app, "GET", &tasks_uri, Some(&token), None).await?;
+    assert_eq!(date(&find(&listed, &build["id"])?["start_date"])?, date(&find(&tasks, &build["id"])?["start_date"])?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn schedule_refuses_cyclic_dependencies() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Cycler", "email": "cycler@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Loop"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, a) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "A", "estimated_days": 1}))).await?;
+    let (_, b) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "B", "estimated_days": 1}))).await?;
+    let a_id = uuid::Uuid::parse_str(a["id"].as_str().unwrap())?;
+    let b_id = uuid::Uuid::parse_str(b["id"].as_str().unwrap())?;
+
+    // The API rejects cycles, so write one directly
+    for (source, target) in [(a_id, b_id), (b_id, a_id)] {
+        sqlx::query("INSERT INTO task_dependencies (id, source_task_id, target_task_id, created_at) VALUES (?, ?, ?, datetime('now'))")
+            .bind(uuid::Uuid::new_v4()).bind(source).bind(target).execute(&pool).await?;
+    }
+
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/schedule", project_id), Some(&token), Some(json!({"project_start": "2025-10-01T00:00:00Z"}))).await?;
+    let (critical_status, _) = send(&app, "GET", &format!("/projects/{}/critical-path", project_id), Some(&token), None).await?;
+    assert!(!status.is_success());
+    assert_eq!(status, critical_status);
+
+    let unscheduled: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE start_date IS NULL").fetch_one(&pool).await?;
+    assert_eq!(unscheduled, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn schedule_rejects_dates_past_the_supported_range() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Dreamer", "email": "dreamer@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Forever"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (status, body) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Eternity", "estimated_days": 100_000_000}))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+
+    // Rows written before the bound existed can still carry huge estimates
+    let (_, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Legacy", "estimated_days": 1}))).await?;
+    let task_id = uuid::Uuid::parse_str(task["id"].as_str().context("missing task id")?)?;
+    sqlx::query("UPDATE tasks SET estimated_days = ? WHERE id = ?").bind(2_000_000_000i32).bind(task_id).execute(&pool).await?;
+
+    let (status, body) = send(&app, "POST", &format!("/projects/{}/schedule", project_id), Some(&token), Some(json!({"project_start": "2025-10-01T00:00:00Z"}))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    let unscheduled: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE start_date IS NULL").fetch_one(&pool).await?;
+    assert_eq!(unscheduled, 1);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn oversized_bodies_are_rejected_with_413() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Big", "email": "big@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+
+    // Default cap is 1 MiB
+    let huge = "x".repeat(3 * 1024 * 1024);
+    let (status, err) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": huge}))).await?;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE, "{}", err);
+    assert_eq!(err["code"], "PAYLOAD_TOO_LARGE");
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM projects").fetch_one(&pool).await?;
+    assert_eq!(stored, 0);
+
+    let (status, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Small"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let project_id = project["id"].as_str().context("missing project id")?;
+
+    // Task bodies share the default cap, including on the /v1 prefix
+    let (status, _) = send(&app, "POST", &format!("/v1/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "x".repeat(2 * 1024 * 1024)}))).await?;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Imports get a larger cap: a 2 MiB import is parsed (and judged on content), not refused for size
+    let (status, _) = send(&app, "POST", "/projects/import", Some(&token), Some(json!({"padding": "x".repeat(2 * 1024 * 1024)}))).await?;
+    assert_ne!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+    Ok(())
+}
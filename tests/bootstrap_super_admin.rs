@@ -0,0 +1,49 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::authz::{roles, Principal};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn only_first_registered_user_becomes_super_admin() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, first) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "First", "email": "first@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, second) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Second", "email": "second@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let first_id = uuid::Uuid::parse_str(first["user"]["id"].as_str().context("missing user id")?)?;
+    let second_id = uuid::Uuid::parse_str(second["user"]["id"].as_str().context("missing user id")?)?;
+
+    let first_principal = Principal::load(&pool, first_id).await?;
+    assert!(first_principal.roles.contains(roles::SUPER_ADMIN));
+
+    let second_principal = Principal::load(&pool, second_id).await?;
+    assert!(second_principal.roles.is_empty());
+
+    // The RBAC endpoints see the bootstrapped assignment like any other
+    let token = first["token"].as_str().context("missing token")?.to_string();
+    let roles_uri = format!("/rbac/users/{}/roles", first_id);
+    let (status, roles) = send(&app, "GET", &roles_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(roles[0]["name"], roles::SUPER_ADMIN);
+    let permission_id: String = sqlx::query_scalar("SELECT id FROM permissions LIMIT 1").fetch_one(&pool).await?;
+    let (status, holders) = send(&app, "GET", &format!("/rbac/permissions/{}/users", permission_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", holders);
+
+    let (status, roles) = send(&app, "PUT", &roles_uri, Some(&token), Some(json!({"role_ids": []}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(roles, json!([]));
+    assert!(Principal::load(&pool, first_id).await?.roles.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! Helpers shared by the integration tests: a migrated temp database and a
+//! one-shot request sender that decodes JSON responses.
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use axum::body::{self, Body};
+use axum::http::{HeaderMap, Request, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use serde_json::Value;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::SqlitePool;
+use tempfile::{tempdir, TempDir};
+use tower::util::ServiceExt; // for `oneshot`
+
+/// A fresh SQLite file with every migration applied; keep the `TempDir` alive for the test.
+pub async fn migrated_pool(foreign_keys: bool) -> Result<(TempDir, SqlitePool)> {
+    let dir = tempdir().context("failed to create tempdir")?;
+    let opts = SqliteConnectOptions::new()
+        .filename(dir.path().join("test.db"))
+        .create_if_missing(true)
+        .foreign_keys(foreign_keys);
+    let pool = SqlitePool::connect_with(opts).await?;
+    migrate(&pool).await?;
+    Ok((dir, pool))
+}
+
+/// Run the crate's migrations against `pool`.
+pub async fn migrate(pool: &SqlitePool) -> Result<()> {
+    let migrator = sqlx::migrate::Migrator::new(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations")).await?;
+    migrator.run(pool).await?;
+    Ok(())
+}
+
+/// Send a JSON request, optionally as the bearer of `token`.
+pub async fn send(app: &Router, method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> Result<(StatusCode, Value)> {
+    let (status, _, value) = send_with_headers(app, method, uri, token, &[], body).await?;
+    Ok((status, value))
+}
+
+/// Like `send`, with extra request headers; the response headers are returned too.
+pub async fn send_with_headers(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    headers: &[(&str, &str)],
+    body: Option<Value>,
+) -> Result<(StatusCode, HeaderMap, Value)> {
+    let mut builder = Request::builder().method(method).uri(uri);
+    if let Some(t) = token {
+        builder = builder.header("authorization", format!("Bearer {}", t));
+    }
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    let req = match body {
+        Some(b) => builder.header("content-type", "application/json").body(Body::from(b.to_string()))?,
+        None => builder.body(Body::empty())?,
+    };
+    send_request(app, req).await
+}
+
+/// Send a prepared request; an empty response body decodes as `Value::Null`.
+pub async fn send_request(app: &Router, req: Request<Body>) -> Result<(StatusCode, HeaderMap, Value)> {
+    let resp: Response = app.clone().oneshot(req).await?;
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let bytes = body::to_bytes(resp.into_body(), 10_485_760).await?;
+    let value = if bytes.is_empty() { Value::Null } else { serde_json::from_slice(&bytes)? };
+    Ok((status, headers, value))
+}
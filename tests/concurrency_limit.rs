@@ -0,0 +1,72 @@
+mod common;
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt; // for `oneshot`
+use uuid::Uuid;
+
+use common::migrated_pool;
+use s_curve::create_app;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn heavy_endpoints_shed_load_with_503() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("HEAVY_ENDPOINT_CONCURRENCY", "1");
+    let app = create_app(pool.clone()).await?;
+
+    let user_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Busy', 'busy@example.com', 'hash', 'local', datetime('now'), datetime('now'))")
+        .bind(user_id).execute(&pool).await?;
+    sqlx::query("INSERT INTO projects (id, user_id, name, theme_color, created_at, updated_at) VALUES (?, ?, 'Busy', '#000', datetime('now'), datetime('now'))")
+        .bind(project_id).bind(user_id).execute(&pool).await?;
+
+    // A long dependency chain keeps each critical-path request busy for a while
+    let mut prev: Option<Uuid> = None;
+    for i in 0..300 {
+        let id = Uuid::new_v4();
+        sqlx::query("INSERT INTO tasks (id, project_id, title, status, duration_days, created_at, updated_at) VALUES (?, ?, ?, 'pending', 1, datetime('now'), datetime('now'))")
+            .bind(id).bind(project_id).bind(format!("T{}", i)).execute(&pool).await?;
+        if let Some(p) = prev {
+            sqlx::query("INSERT INTO task_dependencies (id, source_task_id, target_task_id, created_at) VALUES (?, ?, ?, datetime('now'))")
+                .bind(Uuid::new_v4()).bind(p).bind(id).execute(&pool).await?;
+        }
+        prev = Some(id);
+    }
+
+    let jwt = s_curve::jwt::JwtConfig::new(b"test-secret".to_vec(), 1);
+    let token = jwt.encode(user_id)?;
+
+    let mut handles = Vec::new();
+    for _ in 0..32 {
+        let app = app.clone();
+        let token = token.clone();
+        handles.push(tokio::spawn(async move {
+            let req = Request::builder()
+                .method("GET")
+                .uri(format!("/projects/{}/critical-path", project_id))
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap();
+            app.oneshot(req).await.map(|r| r.status())
+        }));
+    }
+
+    let mut ok = 0;
+    let mut busy = 0;
+    for h in handles {
+        match h.await?? {
+            StatusCode::OK => ok += 1,
+            StatusCode::SERVICE_UNAVAILABLE => busy += 1,
+            other => panic!("unexpected status {}", other),
+        }
+    }
+
+    assert!(ok >= 1, "at least one request should get through");
+    assert!(busy >= 1, "requests beyond the limit should be rejected with 503");
+
+    Ok(())
+}
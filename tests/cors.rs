@@ -0,0 +1,68 @@
+mod common;
+
+use anyhow::Result;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::migrated_pool;
+use s_curve::create_app;
+
+#[tokio::test]
+async fn preflight_to_projects_returns_configured_cors_headers() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("CORS_ALLOWED_ORIGINS", "http://localhost:5173, https://app.example.com");
+    let app = create_app(pool.clone()).await?;
+
+    let preflight = |origin: &str| {
+        Request::builder()
+            .method("OPTIONS")
+            .uri("/projects")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .header("access-control-request-headers", "authorization,content-type")
+            .body(Body::empty())
+    };
+
+    let resp = app.clone().oneshot(preflight("https://app.example.com")?).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let headers = resp.headers();
+    assert_eq!(headers.get("access-control-allow-origin").and_then(|v| v.to_str().ok()), Some("https://app.example.com"));
+    assert_eq!(headers.get("access-control-allow-credentials").and_then(|v| v.to_str().ok()), Some("true"));
+    let allow_headers = headers.get("access-control-allow-headers").and_then(|v| v.to_str().ok()).unwrap_or_default().to_lowercase();
+    assert!(allow_headers.contains("authorization"));
+    assert!(allow_headers.contains("content-type"));
+    let allow_methods = headers.get("access-control-allow-methods").and_then(|v| v.to_str().ok()).unwrap_or_default();
+    assert!(allow_methods.contains("POST"));
+
+    // Origins outside the list get no allow-origin header
+    let resp = app.clone().oneshot(preflight("https://evil.example.com")?).await?;
+    assert!(resp.headers().get("access-control-allow-origin").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn preflight_to_protected_route_is_answered_without_token() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("CORS_MAX_AGE_SECS", "1200");
+    let app = create_app(pool.clone()).await?;
+
+    // /auth/me requires a bearer token for GET, but the preflight must not hit AuthUser
+    let req = Request::builder()
+        .method("OPTIONS")
+        .uri("/auth/me")
+        .header("origin", "http://localhost:5173")
+        .header("access-control-request-method", "GET")
+        .header("access-control-request-headers", "authorization")
+        .body(Body::empty())?;
+    let resp = app.clone().oneshot(req).await?;
+    assert!(resp.status() == StatusCode::OK || resp.status() == StatusCode::NO_CONTENT);
+    assert_eq!(resp.headers().get("access-control-max-age").and_then(|v| v.to_str().ok()), Some("1200"));
+
+    Ok(())
+}
@@ -17,8 +17,15 @@ async fn test_critical_path_basic() -> anyhow::Result<()> {
         id TEXT PRIMARY KEY, user_id TEXT NOT NULL, name TEXT NOT NULL, description TEXT, theme_color TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
 
+    sqlx::query("CREATE TABLE IF NOT EXISTS project_members (
+        project_id TEXT NOT NULL, user_id TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'member', created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (project_id, user_id)
+    );").execute(&pool).await?;
+    sqlx::query("CREATE TABLE IF NOT EXISTS task_tags (
+        task_id TEXT NOT NULL, tag TEXT NOT NULL, created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (task_id, tag)
+    );").execute(&pool).await?;
+
     sqlx::query("CREATE TABLE IF NOT EXISTS tasks (
-        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
+        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, estimated_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
 
     sqlx::query("CREATE TABLE IF NOT EXISTS task_dependencies (
@@ -64,10 +71,10 @@ async fn test_critical_path_basic() -> anyhow::Result<()> {
     use s_curve::jwt::{JwtConfig, AuthUser};
     use axum::extract::{State as AxState, Path as AxPath};
 
-    let jwt = JwtConfig { secret: std::sync::Arc::new(b"test-secret".to_vec()), exp_hours: 24 };
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
     let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
     let app_state = AppState::new(pool.clone(), jwt, event_bus);
-    let auth = AuthUser { user_id };
+    let auth = AuthUser { user_id, roles: Vec::new() };
 
     // Call critical path endpoint
     let path = AxPath(project_id);
@@ -15,8 +15,15 @@ async fn test_cycle_detection_returns_error() -> anyhow::Result<()> {
     sqlx::query("CREATE TABLE IF NOT EXISTS projects (
         id TEXT PRIMARY KEY, user_id TEXT NOT NULL, name TEXT NOT NULL, description TEXT, theme_color TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS project_members (
+        project_id TEXT NOT NULL, user_id TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'member', created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (project_id, user_id)
+    );").execute(&pool).await?;
+    sqlx::query("CREATE TABLE IF NOT EXISTS task_tags (
+        task_id TEXT NOT NULL, tag TEXT NOT NULL, created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (task_id, tag)
+    );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS tasks (
-        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
+        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, estimated_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS task_dependencies (
         id TEXT PRIMARY KEY, source_task_id TEXT NOT NULL, target_task_id TEXT NOT NULL, type TEXT NOT NULL DEFAULT 'finish_to_start', created_at TEXT NOT NULL,
@@ -56,10 +63,10 @@ async fn test_cycle_detection_returns_error() -> anyhow::Result<()> {
     use s_curve::jwt::{JwtConfig, AuthUser};
     use axum::extract::{State as AxState, Path as AxPath};
 
-    let jwt = JwtConfig { secret: std::sync::Arc::new(b"test-secret".to_vec()), exp_hours: 24 };
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
     let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
     let app_state = AppState::new(pool.clone(), jwt, event_bus);
-    let auth = AuthUser { user_id };
+    let auth = AuthUser { user_id, roles: Vec::new() };
 
     let path = AxPath(project_id);
     let res = get_project_critical_path(AxState(app_state.clone()), auth.clone(), path).await;
@@ -83,8 +90,15 @@ async fn test_disconnected_graph_picks_longest_component() -> anyhow::Result<()>
     sqlx::query("CREATE TABLE IF NOT EXISTS projects (
         id TEXT PRIMARY KEY, user_id TEXT NOT NULL, name TEXT NOT NULL, description TEXT, theme_color TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS project_members (
+        project_id TEXT NOT NULL, user_id TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'member', created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (project_id, user_id)
+    );").execute(&pool).await?;
+    sqlx::query("CREATE TABLE IF NOT EXISTS task_tags (
+        task_id TEXT NOT NULL, tag TEXT NOT NULL, created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (task_id, tag)
+    );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS tasks (
-        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
+        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, estimated_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS task_dependencies (
         id TEXT PRIMARY KEY, source_task_id TEXT NOT NULL, target_task_id TEXT NOT NULL, type TEXT NOT NULL DEFAULT 'finish_to_start', created_at TEXT NOT NULL,
@@ -131,10 +145,10 @@ async fn test_disconnected_graph_picks_longest_component() -> anyhow::Result<()>
     use s_curve::jwt::{JwtConfig, AuthUser};
     use axum::extract::{State as AxState, Path as AxPath};
 
-    let jwt = JwtConfig { secret: std::sync::Arc::new(b"test-secret".to_vec()), exp_hours: 24 };
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
     let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
     let app_state = AppState::new(pool.clone(), jwt, event_bus);
-    let auth = AuthUser { user_id };
+    let auth = AuthUser { user_id, roles: Vec::new() };
 
     // call endpoint
     let path = AxPath(project_id);
@@ -165,8 +179,15 @@ async fn test_equal_length_paths_returns_valid_path_of_expected_length() -> anyh
     sqlx::query("CREATE TABLE IF NOT EXISTS projects (
         id TEXT PRIMARY KEY, user_id TEXT NOT NULL, name TEXT NOT NULL, description TEXT, theme_color TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS project_members (
+        project_id TEXT NOT NULL, user_id TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'member', created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (project_id, user_id)
+    );").execute(&pool).await?;
+    sqlx::query("CREATE TABLE IF NOT EXISTS task_tags (
+        task_id TEXT NOT NULL, tag TEXT NOT NULL, created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (task_id, tag)
+    );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS tasks (
-        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
+        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, estimated_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS task_dependencies (
         id TEXT PRIMARY KEY, source_task_id TEXT NOT NULL, target_task_id TEXT NOT NULL, type TEXT NOT NULL DEFAULT 'finish_to_start', created_at TEXT NOT NULL,
@@ -214,10 +235,10 @@ async fn test_equal_length_paths_returns_valid_path_of_expected_length() -> anyh
     use s_curve::jwt::{JwtConfig, AuthUser};
     use axum::extract::{State as AxState, Path as AxPath};
 
-    let jwt = JwtConfig { secret: std::sync::Arc::new(b"test-secret".to_vec()), exp_hours: 24 };
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
     let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
     let app_state = AppState::new(pool.clone(), jwt, event_bus);
-    let auth = AuthUser { user_id };
+    let auth = AuthUser { user_id, roles: Vec::new() };
 
     let path = AxPath(project_id);
     let res = get_project_critical_path(AxState(app_state.clone()), auth.clone(), path).await?;
@@ -260,8 +281,15 @@ async fn test_zero_duration_tasks() -> anyhow::Result<()> {
     sqlx::query("CREATE TABLE IF NOT EXISTS projects (
         id TEXT PRIMARY KEY, user_id TEXT NOT NULL, name TEXT NOT NULL, description TEXT, theme_color TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS project_members (
+        project_id TEXT NOT NULL, user_id TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'member', created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (project_id, user_id)
+    );").execute(&pool).await?;
+    sqlx::query("CREATE TABLE IF NOT EXISTS task_tags (
+        task_id TEXT NOT NULL, tag TEXT NOT NULL, created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (task_id, tag)
+    );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS tasks (
-        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
+        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, estimated_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
     sqlx::query("CREATE TABLE IF NOT EXISTS task_dependencies (
         id TEXT PRIMARY KEY, source_task_id TEXT NOT NULL, target_task_id TEXT NOT NULL, type TEXT NOT NULL DEFAULT 'finish_to_start', created_at TEXT NOT NULL,
@@ -297,10 +325,10 @@ async fn test_zero_duration_tasks() -> anyhow::Result<()> {
     use s_curve::jwt::{JwtConfig, AuthUser};
     use axum::extract::{State as AxState, Path as AxPath};
 
-    let jwt = JwtConfig { secret: std::sync::Arc::new(b"test-secret".to_vec()), exp_hours: 24 };
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
     let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
     let app_state = AppState::new(pool.clone(), jwt, event_bus);
-    let auth = AuthUser { user_id };
+    let auth = AuthUser { user_id, roles: Vec::new() };
 
     let path = AxPath(project_id);
     let res = get_project_critical_path(AxState(app_state.clone()), auth.clone(), path).await?;
@@ -164,3 +164,102 @@ async fn project_dashboard_returns_plan_and_actual() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn dashboard_velocity_uses_calendar_days() -> Result<()> {
+    let dir = tempdir().context("failed to create tempdir")?;
+    let db_path = dir.path().join("test.db");
+    use sqlx::sqlite::SqliteConnectOptions;
+    let opts = SqliteConnectOptions::new()
+        .filename(db_path.as_path())
+        .create_if_missing(true);
+    let pool = SqlitePool::connect_with(opts).await?;
+
+    let migrator = sqlx::migrate::Migrator::new(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations")).await?;
+    migrator.run(&pool).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    async fn call(app: &axum::Router, method: &str, uri: &str, token: Option<&str>, body: Option<serde_json::Value>) -> Result<(StatusCode, serde_json::Value)> {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(t) = token {
+            builder = builder.header("authorization", format!("Bearer {}", t));
+        }
+        let req = match body {
+            Some(b) => builder.header("content-type", "application/json").body(Body::from(b.to_string()))?,
+            None => builder.body(Body::empty())?,
+        };
+        let resp: Response = app.clone().oneshot(req).await?;
+        let status = resp.status();
+        let bytes = body::to_bytes(resp.into_body(), 10_485_760).await?;
+        let value = if bytes.is_empty() { serde_json::Value::Null } else { serde_json::from_slice(&bytes)? };
+        Ok((status, value))
+    }
+
+    let (_, auth_res) = call(&app, "POST", "/auth/register", None, Some(json!({"name": "Velocity", "email": "velocity@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = call(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Velocity Project"}))).await?;
+    let project_id: Uuid = project["id"].as_str().context("missing project id")?.parse()?;
+    let (_, task) = call(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Pour"}))).await?;
+    let task_id: Uuid = task["id"].as_str().context("missing task id")?.parse()?;
+
+    let dashboard = |days: Option<i64>| {
+        let uri = match days {
+            Some(d) => format!("/projects/{}/dashboard?velocity_days={}", project_id, d),
+            None => format!("/projects/{}/dashboard", project_id),
+        };
+        let app = app.clone();
+        let token = token.clone();
+        async move { call(&app, "GET", &uri, Some(&token), None).await }
+    };
+
+    // Nothing recorded yet
+    let (status, dash) = dashboard(None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(dash["velocity"].as_f64(), Some(0.0));
+
+    let insert = |days_ago: i64, progress: i32| {
+        let pool = pool.clone();
+        async move {
+            let at = (Utc::now() - chrono::Duration::days(days_ago)).to_rfc3339();
+            sqlx::query("INSERT INTO task_progress (id, project_id, task_id, progress, note, created_at, updated_at) VALUES (?, ?, ?, ?, NULL, ?, ?)")
+                .bind(Uuid::new_v4())
+                .bind(project_id)
+                .bind(task_id)
+                .bind(progress)
+                .bind(&at)
+                .bind(&at)
+                .execute(&pool)
+                .await
+        }
+    };
+
+    // A single day of history is not enough for a trend, however many rows it has
+    insert(3, 20).await?;
+    insert(3, 40).await?;
+    let (_, dash) = dashboard(None).await?;
+    assert_eq!(dash["velocity"].as_f64(), Some(0.0));
+
+    // Day -3 averages to 30, day -1 is 60: +30 over two calendar days
+    insert(1, 60).await?;
+    // Outside the default 7-day window
+    insert(20, 0).await?;
+    let (_, dash) = dashboard(None).await?;
+    assert_eq!(dash["velocity"].as_f64(), Some(15.0));
+
+    // A wider window reaches back to the older entry: +60 over 19 days
+    let (_, dash) = dashboard(Some(30)).await?;
+    let velocity = dash["velocity"].as_f64().context("missing velocity")?;
+    assert!((velocity - 60.0 / 19.0).abs() < 1e-9, "unexpected velocity {}", velocity);
+
+    let (status, _) = dashboard(Some(0)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Huge windows are clamped rather than overflowing the date arithmetic
+    let (status, dash) = dashboard(Some(i64::MAX)).await?;
+    assert_eq!(status, StatusCode::OK, "{}", dash);
+    assert!((dash["velocity"].as_f64().context("missing velocity")? - 60.0 / 19.0).abs() < 1e-9);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn adding_task_to_deleted_project_explains_restore() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Deleter", "email": "deleter@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (status, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Doomed"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+
+    let (status, _) = send(&app, "DELETE", &format!("/projects/{}", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, err) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Too late"}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let message = err["message"].as_str().unwrap_or_default();
+    assert!(message.contains("project is deleted; restore it first"), "unexpected message: {}", message);
+
+    // A project that never existed keeps the generic message
+    let (status, err) = send(&app, "POST", &format!("/projects/{}/tasks", uuid::Uuid::new_v4()), Some(&token), Some(json!({"title": "Nowhere"}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(err["message"].as_str().unwrap_or_default().contains("project not found"));
+
+    Ok(())
+}
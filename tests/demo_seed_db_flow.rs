@@ -157,7 +157,7 @@ async fn seeded_db_is_visible_via_api() -> Result<()> {
         panic!("progress list failed: {} - {}", status, String::from_utf8_lossy(&body_bytes));
     }
     let progress_list: serde_json::Value = serde_json::from_slice(&body_bytes)?;
-    let ids: Vec<&str> = progress_list.as_array().unwrap().iter().filter_map(|v| v.get("id").and_then(|x| x.as_str())).collect();
+    let ids: Vec<&str> = progress_list["items"].as_array().unwrap().iter().filter_map(|v| v.get("id").and_then(|x| x.as_str())).collect();
     assert!(ids.contains(&prog1_id.as_str()));
     assert!(ids.contains(&prog2_id.as_str()));
 
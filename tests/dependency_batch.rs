@@ -0,0 +1,97 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+fn edge(source: &str, target: &str) -> Value {
+    json!({"source_task_id": source, "target_task_id": target})
+}
+
+#[tokio::test]
+async fn batch_creates_many_dependencies_and_rejects_any_cycle() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Planner", "email": "planner@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Outsider", "email": "outsider@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Big plan"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+
+    let tasks: Vec<Value> = (0..201).map(|n| json!({"title": format!("Step {}", n)})).collect();
+    let (status, imported) = send(&app, "POST", &format!("/projects/{}/import-schedule", project_id), Some(&token), Some(json!({"tasks": tasks}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let ids: Vec<String> = imported["tasks"]
+        .as_array()
+        .context("tasks should be an array")?
+        .iter()
+        .map(|t| t["id"].as_str().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(ids.len(), 201);
+
+    let batch_uri = format!("/projects/{}/dependencies/batch", project_id);
+    let deps_uri = format!("/projects/{}/dependencies", project_id);
+    let count = || async {
+        let (_, deps) = send(&app, "GET", &deps_uri, Some(&token), None).await?;
+        anyhow::Ok(deps.as_array().map(Vec::len).unwrap_or(0))
+    };
+
+    // A 200-edge chain goes in as one request
+    let chain: Vec<Value> = ids.windows(2).map(|pair| edge(&pair[0], &pair[1])).collect();
+    let (status, created) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": chain}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", created);
+    let created = created.as_array().context("response should be an array")?;
+    assert_eq!(created.len(), 200);
+    assert_eq!(created[0]["source_task_id"].as_str(), Some(ids[0].as_str()));
+    assert_eq!(created[199]["target_task_id"].as_str(), Some(ids[200].as_str()));
+    assert_eq!(count().await?, 200);
+
+    // Closing the chain creates a cycle through existing edges; nothing is kept
+    let (status, err) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": [edge(&ids[0], &ids[200]), edge(&ids[200], &ids[0])]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("Cycle detected"), "{}", err);
+    assert_eq!(count().await?, 200);
+
+    // A cycle made entirely of new edges is caught too
+    let (_, more) = send(&app, "POST", &format!("/projects/{}/import-schedule", project_id), Some(&token), Some(json!({"tasks": [{"title": "X"}, {"title": "Y"}, {"title": "Z"}]}))).await?;
+    let fresh: Vec<String> = more["tasks"]
+        .as_array()
+        .context("tasks should be an array")?
+        .iter()
+        .map(|t| t["id"].as_str().unwrap_or_default().to_string())
+        .collect();
+    let (status, err) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": [edge(&fresh[0], &fresh[1]), edge(&fresh[1], &fresh[2]), edge(&fresh[2], &fresh[0])]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    for id in &fresh {
+        assert!(err["message"].as_str().unwrap_or_default().contains(id.as_str()), "{}", err);
+    }
+    assert_eq!(count().await?, 200);
+
+    // Bad edges are rejected before anything is written
+    let (status, _) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": []}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": [edge(&fresh[0], &fresh[0])]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": [edge(&fresh[0], &fresh[1]), edge(&fresh[0], &fresh[1])]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    // Re-adding an edge the project already has is a duplicate as well
+    let (status, err) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": [edge(&fresh[0], &fresh[1]), edge(&ids[0], &ids[1])]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("dependencies[1]: duplicate dependency"), "{}", err);
+    let (status, err) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"dependencies": [edge(&fresh[0], &fresh[1]), edge(&fresh[1], &uuid::Uuid::new_v4().to_string())]}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(err["message"].as_str().unwrap_or_default().contains("dependencies[1]"), "{}", err);
+    let (status, _) = send(&app, "POST", &batch_uri, Some(&other_token), Some(json!({"dependencies": [edge(&fresh[0], &fresh[1])]}))).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(count().await?, 200);
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send_with_headers};
+use s_curve::create_app;
+
+async fn send(app: &axum::Router, method: &str, uri: &str, token: &str, etag: Option<&str>, body: Option<Value>) -> Result<(StatusCode, Option<String>, Value)> {
+    let headers: Vec<(&str, &str)> = etag.map(|tag| ("if-none-match", tag)).into_iter().collect();
+    let (status, headers, value) = send_with_headers(app, method, uri, Some(token), &headers, body).await?;
+    let tag = headers.get("etag").and_then(|v| v.to_str().ok()).map(str::to_string);
+    Ok((status, tag, value))
+}
+
+#[tokio::test]
+async fn conditional_gets_return_304_until_the_resource_changes() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, _, auth) = send_with_headers(&app, "POST", "/auth/register", None, &[], Some(json!({"name": "Poller", "email": "poller@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+
+    let (_, _, project) = send(&app, "POST", "/projects", &token, None, Some(json!({"name": "Polled"}))).await?;
+    let project_uri = format!("/projects/{}", project["id"].as_str().context("missing project id")?);
+    let tasks_uri = format!("{}/tasks", project_uri);
+    let (_, _, task) = send(&app, "POST", &tasks_uri, &token, None, Some(json!({"title": "Watched"}))).await?;
+    let task_uri = format!("{}/{}", tasks_uri, task["id"].as_str().context("missing task id")?);
+
+    // Project
+    let (status, project_tag, _) = send(&app, "GET", &project_uri, &token, None, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let project_tag = project_tag.context("missing project etag")?;
+    assert!(project_tag.starts_with("W/\""), "{}", project_tag);
+    let (status, tag, body) = send(&app, "GET", &project_uri, &token, Some(&project_tag), None).await?;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(tag.as_deref(), Some(project_tag.as_str()));
+    assert!(body.is_null());
+
+    // Task: unchanged -> 304, updated -> new body and new tag
+    let (_, task_tag, _) = send(&app, "GET", &task_uri, &token, None, None).await?;
+    let task_tag = task_tag.context("missing task etag")?;
+    let (status, _, _) = send(&app, "GET", &task_uri, &token, Some(&task_tag), None).await?;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+    let (_, list_tag, _) = send(&app, "GET", &tasks_uri, &token, None, None).await?;
+    let list_tag = list_tag.context("missing list etag")?;
+    let (status, _, _) = send(&app, "GET", &tasks_uri, &token, Some(&list_tag), None).await?;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+    let (status, _, _) = send(&app, "PATCH", &task_uri, &token, None, Some(json!({"title": "Renamed"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, new_tag, body) = send(&app, "GET", &task_uri, &token, Some(&task_tag), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["title"], "Renamed");
+    let new_tag = new_tag.context("missing task etag")?;
+    assert_ne!(new_tag, task_tag);
+    let (status, _, _) = send(&app, "GET", &task_uri, &token, Some(&new_tag), None).await?;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+    let (status, new_list_tag, body) = send(&app, "GET", &tasks_uri, &token, Some(&list_tag), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["title"], "Renamed");
+    assert_ne!(new_list_tag.as_deref(), Some(list_tag.as_str()));
+
+    // A removed task changes the listing's tag too
+    let new_list_tag = new_list_tag.context("missing list etag")?;
+    let (status, _, _) = send(&app, "DELETE", &task_uri, &token, None, None).await?;
+    assert!(status.is_success());
+    let (status, _, body) = send(&app, "GET", &tasks_uri, &token, Some(&new_list_tag), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!([]));
+
+    // Unrelated tags and `*`
+    let (status, _, _) = send(&app, "GET", &project_uri, &token, Some("W/\"0-0\", \"other\""), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, _) = send(&app, "GET", &project_uri, &token, Some("*"), None).await?;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+
+    Ok(())
+}
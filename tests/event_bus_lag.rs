@@ -0,0 +1,44 @@
+mod common;
+
+use anyhow::{Context, Result};
+use serde_json::json;
+
+use common::migrated_pool;
+use s_curve::events;
+
+#[tokio::test]
+async fn lagging_activity_listener_counts_missed_events_and_keeps_going() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("EVENT_BUS_CAPACITY", "4");
+    assert_eq!(events::event_bus_capacity(), 4);
+    let (bus, rx) = events::init_event_bus();
+    let lagged_before = s_curve::metrics::registry().event_bus_lagged_events();
+
+    // Publish a burst before the listener runs, so the oldest six are overwritten
+    for n in 0..10 {
+        bus.send(json!({
+            "name": "burst.fired",
+            "occurred_at": chrono::Utc::now().to_rfc3339(),
+            "payload": {"n": n, "severity": "noise"},
+        }))
+        .context("listener dropped")?;
+    }
+    drop(bus);
+    events::start_activity_listener(rx, pool.clone()).await;
+
+    // The listener skipped past the gap and stored what was still buffered
+    let stored: Vec<String> = sqlx::query_scalar("SELECT properties FROM activity_log WHERE event_name = 'burst.fired' ORDER BY rowid")
+        .fetch_all(&pool)
+        .await?;
+    assert_eq!(stored.len(), 4);
+    let first: serde_json::Value = serde_json::from_str(&stored[0])?;
+    assert_eq!(first["payload"]["n"], 6);
+    assert_eq!(s_curve::metrics::registry().event_bus_lagged_events() - lagged_before, 6);
+
+    std::env::set_var("EVENT_BUS_CAPACITY", "0");
+    assert_eq!(events::event_bus_capacity(), events::DEFAULT_EVENT_BUS_CAPACITY);
+    std::env::remove_var("EVENT_BUS_CAPACITY");
+
+    Ok(())
+}
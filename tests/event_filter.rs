@@ -0,0 +1,50 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use s_curve::events::{init_event_bus, log_activity, EventFilter, Loggable, Severity};
+
+#[derive(Serialize)]
+struct FakeTask {
+    id: Uuid,
+}
+
+impl Loggable for FakeTask {
+    fn entity_type() -> &'static str { "task" }
+    fn subject_id(&self) -> Uuid { self.id }
+}
+
+#[derive(Serialize)]
+struct FakeProject {
+    id: Uuid,
+}
+
+impl Loggable for FakeProject {
+    fn entity_type() -> &'static str { "project" }
+    fn subject_id(&self) -> Uuid { self.id }
+    fn severity(&self) -> Severity { Severity::Critical }
+}
+
+#[tokio::test]
+async fn filter_delivers_only_critical_task_events() -> anyhow::Result<()> {
+    let filter = EventFilter::parse("event_name LIKE 'task.*' AND severity = critical")?;
+
+    let (bus, mut rx) = init_event_bus();
+    let actor = Some(Uuid::new_v4());
+    let task = FakeTask { id: Uuid::new_v4() };
+    let project = FakeProject { id: Uuid::new_v4() };
+
+    log_activity(&bus, "created", actor, &task); // task, important
+    log_activity(&bus, "deleted", actor, &task); // task, critical
+    log_activity(&bus, "created", actor, &project); // project, critical
+    drop(bus);
+
+    let mut delivered = Vec::new();
+    while let Ok(event) = rx.recv().await {
+        if filter.matches(&event) {
+            delivered.push(event["name"].as_str().unwrap_or_default().to_string());
+        }
+    }
+
+    assert_eq!(delivered, vec!["task.deleted".to_string()]);
+    Ok(())
+}
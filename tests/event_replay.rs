@@ -0,0 +1,73 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use tempfile::tempdir;
+
+use common::{migrate, send};
+use s_curve::create_app;
+
+async fn projection_snapshot(pool: &sqlx::SqlitePool) -> Result<Vec<(String, String, String)>> {
+    let rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT event_name, description, severity FROM activity_log ORDER BY occurred_at, event_name, description",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+#[tokio::test]
+async fn replay_rebuilds_activity_log_from_event_store() -> Result<()> {
+    let dir = tempdir().context("failed to create tempdir")?;
+    let db_path = dir.path().join("test.db");
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    let opts = SqliteConnectOptions::new()
+        .filename(db_path.as_path())
+        .create_if_missing(true)
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new().max_connections(4).connect_with(opts).await?;
+
+    migrate(&pool).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Replayer", "email": "replay@example.com", "password": "password123"}))).await?;
+    let admin_token = admin["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&admin_token), Some(json!({"name": "Replayed"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    for title in ["One", "Two", "Three"] {
+        let (status, _) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&admin_token), Some(json!({"title": title}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // Wait for the listener to write every event to both tables: the bootstrap
+    // role grant, the project and the three tasks, plus registration
+    let mut stored = 0i64;
+    for _ in 0..50 {
+        stored = sqlx::query_scalar("SELECT COUNT(*) FROM event_store").fetch_one(&pool).await?;
+        let projected: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM activity_log").fetch_one(&pool).await?;
+        if stored >= 6 && stored == projected {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    let before = projection_snapshot(&pool).await?;
+    assert_eq!(before.len() as i64, stored);
+
+    // Corrupt the projection
+    sqlx::query("DELETE FROM activity_log WHERE rowid IN (SELECT rowid FROM activity_log LIMIT 2)").execute(&pool).await?;
+    sqlx::query("UPDATE activity_log SET description = 'garbled'").execute(&pool).await?;
+
+    let (status, report) = send(&app, "POST", "/admin/events/replay", Some(&admin_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["replayed"].as_i64(), Some(stored));
+
+    let after = projection_snapshot(&pool).await?;
+    assert_eq!(after.len() as i64, stored);
+    assert_eq!(after, before);
+
+    Ok(())
+}
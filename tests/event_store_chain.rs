@@ -0,0 +1,70 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use tempfile::tempdir;
+
+use common::{migrate, send};
+use s_curve::create_app;
+use s_curve::events;
+
+#[tokio::test]
+async fn event_store_chain_stays_linear_under_concurrent_writers() -> Result<()> {
+    let dir = tempdir().context("failed to create tempdir")?;
+    let db_path = dir.path().join("test.db");
+    use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+    let opts = SqliteConnectOptions::new()
+        .filename(db_path.as_path())
+        .create_if_missing(true)
+        .foreign_keys(true)
+        .journal_mode(SqliteJournalMode::Wal);
+    let pool = SqlitePoolOptions::new().max_connections(8).connect_with(opts).await?;
+
+    migrate(&pool).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Auditor", "email": "auditor@example.com", "password": "password123"}))).await?;
+    let admin_token = admin["token"].as_str().context("missing token")?.to_string();
+
+    // Two extra listeners on the same database race each other for the chain tail
+    let (bus_a, rx_a) = events::init_event_bus();
+    let (bus_b, rx_b) = events::init_event_bus();
+    let listener_a = tokio::spawn(events::start_activity_listener(rx_a, pool.clone()));
+    let listener_b = tokio::spawn(events::start_activity_listener(rx_b, pool.clone()));
+
+    for n in 0..100 {
+        let bus = if n % 2 == 0 { &bus_a } else { &bus_b };
+        let event = json!({
+            "name": "load.fired",
+            "occurred_at": chrono::Utc::now().to_rfc3339(),
+            "payload": {"n": n, "severity": "noise"},
+        });
+        bus.send(event).context("listener dropped")?;
+    }
+    drop(bus_a);
+    drop(bus_b);
+    listener_a.await?;
+    listener_b.await?;
+
+    let fired: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM event_store WHERE event_name = 'load.fired'")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(fired, 100);
+
+    // A fork shows up as two events sharing a prev_hash
+    let forks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM (SELECT prev_hash FROM event_store GROUP BY prev_hash HAVING COUNT(*) > 1)")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(forks, 0);
+
+    let (status, report) = send(&app, "GET", "/admin/events/verify", Some(&admin_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["valid"], true, "chain broken at {:?}", report["first_broken_id"]);
+    assert!(report["events"].as_i64().unwrap_or(0) >= 100);
+
+    Ok(())
+}
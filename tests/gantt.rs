@@ -0,0 +1,63 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn gantt_combines_tasks_dependencies_and_critical_path() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Gantt", "email": "gantt@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Chart"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, a) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "A", "estimated_days": 2}))).await?;
+    let (_, b) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "B", "estimated_days": 3}))).await?;
+    let (_, c) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "C", "estimated_days": 10}))).await?;
+    let (_, side) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Side", "estimated_days": 1}))).await?;
+
+    let deps_uri = format!("/projects/{}/dependencies", project_id);
+    for (source, target) in [(&a, &b), (&b, &c)] {
+        let (status, _) = send(&app, "POST", &deps_uri, Some(&token), Some(json!({"source_task_id": source["id"], "target_task_id": target["id"]}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let gantt_uri = format!("/projects/{}/gantt", project_id);
+    let (status, gantt) = send(&app, "GET", &gantt_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(gantt["tasks"].as_array().map(|t| t.len()), Some(4));
+    assert_eq!(gantt["dependencies"].as_array().map(|d| d.len()), Some(2));
+    assert_eq!(gantt["critical_task_ids"], json!([a["id"], b["id"], c["id"]]));
+
+    // Deleting the tail task drops it, its incoming edge and its place on the critical path
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", tasks_uri, c["id"].as_str().unwrap()), Some(&token), None).await?;
+    assert!(status.is_success());
+
+    let (_, gantt) = send(&app, "GET", &gantt_uri, Some(&token), None).await?;
+    let ids: Vec<&Value> = gantt["tasks"].as_array().context("missing tasks")?.iter().map(|t| &t["id"]).collect();
+    assert_eq!(ids.len(), 3);
+    assert!(!ids.contains(&&c["id"]));
+    assert!(ids.contains(&&side["id"]));
+    for dep in gantt["dependencies"].as_array().context("missing dependencies")? {
+        assert!(ids.contains(&&dep["source_task_id"]) && ids.contains(&&dep["target_task_id"]), "{}", dep);
+    }
+    assert_eq!(gantt["dependencies"].as_array().map(|d| d.len()), Some(1));
+    assert_eq!(gantt["critical_task_ids"], json!([a["id"], b["id"]]));
+
+    // Same access rule as the other project views
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Other", "email": "other@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?;
+    let (status, _) = send(&app, "GET", &gantt_uri, Some(other_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::migrated_pool;
+use s_curve::app::create_app_with_listener;
+
+#[tokio::test]
+async fn activity_listener_drains_buffered_events_after_router_drops() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let (app, listener) = create_app_with_listener(pool.clone()).await?;
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/register")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"name": "Bye", "email": "shutdown@example.com", "password": "password123"}).to_string()))?;
+    let resp = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // Simulate shutdown: the server drops the router, then waits for the listener
+    drop(app);
+    tokio::time::timeout(std::time::Duration::from_secs(10), listener)
+        .await
+        .context("listener did not finish after the router was dropped")??;
+
+    let logged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM activity_log WHERE event_name = 'user.registered'")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(logged, 1);
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM event_store WHERE event_name = 'user.registered'")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(stored, 1);
+
+    Ok(())
+}
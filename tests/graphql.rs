@@ -0,0 +1,89 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn graphql_loads_project_tasks_and_latest_progress_in_one_request() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Graph", "email": "graph@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Gantt"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, framing) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Framing", "start_date": "2025-03-01T00:00:00Z"}))).await?;
+    let (_, roofing) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Roofing", "start_date": "2025-04-01T00:00:00Z"}))).await?;
+    let framing_id = framing["id"].as_str().context("missing task id")?.to_string();
+    let progress_uri = format!("{}/{}/progress", tasks_uri, framing_id);
+    send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 20}))).await?;
+    send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 55, "note": "walls up"}))).await?;
+
+    let query = json!({
+        "query": "query($id: ID!) { project(id: $id) { name tasks { id title latestProgress { progress note } } } }",
+        "variables": {"id": project_id}
+    });
+    let (status, body) = send(&app, "POST", "/graphql", Some(&token), Some(query)).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("errors").is_none(), "{}", body);
+    assert_eq!(body["data"]["project"]["name"], "Gantt");
+    let tasks = body["data"]["project"]["tasks"].as_array().context("missing tasks")?;
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0]["id"], framing["id"]);
+    assert_eq!(tasks[0]["latestProgress"]["progress"], 55);
+    assert_eq!(tasks[0]["latestProgress"]["note"], "walls up");
+    assert_eq!(tasks[1]["id"], roofing["id"]);
+    assert!(tasks[1]["latestProgress"].is_null());
+
+    // Root-level progress and dashboard queries
+    let query = json!({
+        "query": "query($task: ID!, $project: ID!) { progress(taskId: $task) { progress } dashboard(id: $project) { velocity actual { date actual } } }",
+        "variables": {"task": framing_id, "project": project_id}
+    });
+    let (_, body) = send(&app, "POST", "/graphql", Some(&token), Some(query)).await?;
+    assert!(body.get("errors").is_none(), "{}", body);
+    let progress: Vec<i64> = body["data"]["progress"]
+        .as_array()
+        .context("missing progress")?
+        .iter()
+        .filter_map(|p| p["progress"].as_i64())
+        .collect();
+    assert_eq!(progress, vec![55, 20]);
+    assert!(body["data"]["dashboard"]["velocity"].is_number());
+
+    // Another user cannot read the project
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Other", "email": "graph-other@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?;
+    let query = json!({"query": format!("{{ tasks(projectId: \"{}\") {{ id }} }}", project_id)});
+    let (status, body) = send(&app, "POST", "/graphql", Some(other_token), Some(query)).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["errors"][0]["extensions"]["code"], "NOT_FOUND", "{}", body);
+    assert!(body["data"].is_null());
+
+    // Oversized queries are refused before any resolver runs
+    let aliased: String = (0..150).map(|i| format!("p{}: project(id: $id) {{ name }} ", i)).collect();
+    let query = json!({"query": format!("query($id: ID!) {{ {} }}", aliased), "variables": {"id": project_id}});
+    let (_, body) = send(&app, "POST", "/graphql", Some(&token), Some(query)).await?;
+    assert!(body["errors"][0]["message"].as_str().unwrap_or_default().contains("too complex"), "{}", body);
+    let deep = "{ __schema { types { fields { type { ofType { ofType { ofType { ofType { ofType { ofType { name } } } } } } } } } } }";
+    let (_, body) = send(&app, "POST", "/graphql", Some(&token), Some(json!({"query": deep}))).await?;
+    assert!(body["errors"][0]["message"].as_str().unwrap_or_default().contains("nested too deep"), "{}", body);
+
+    // Read-only: no mutations are exposed
+    let (_, body) = send(&app, "POST", "/graphql", Some(&token), Some(json!({"query": "mutation { deleteProject(id: \"x\") }"}))).await?;
+    assert!(body.get("errors").is_some());
+
+    // Anonymous callers are rejected before the query runs
+    let (status, _) = send(&app, "POST", "/graphql", None, Some(json!({"query": "{ __typename }"}))).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use tempfile::tempdir;
+
+use common::{migrate, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn readiness_fails_when_pool_is_exhausted() -> Result<()> {
+    let dir = tempdir().context("failed to create tempdir")?;
+    let db_path = dir.path().join("test.db");
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    let opts = SqliteConnectOptions::new()
+        .filename(db_path.as_path())
+        .create_if_missing(true)
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new().max_connections(1).connect_with(opts).await?;
+
+    migrate(&pool).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("HEALTH_READY_TIMEOUT_MS", "200");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, body) = send(&app, "GET", "/health/ready", None, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["db_ok"], true);
+
+    // Hold the only connection so readiness cannot acquire one
+    let held = pool.acquire().await?;
+    let (status, body) = send(&app, "GET", "/health/ready", None, None).await?;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "unavailable");
+    assert_eq!(body["db_ok"], false);
+
+    // Liveness does not look at the database, so it claims nothing about it
+    let (status, body) = send(&app, "GET", "/health/live", None, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    assert!(body.get("db_ok").is_none(), "{}", body);
+
+    drop(held);
+    let (status, _) = send(&app, "GET", "/health/ready", None, None).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "GET", "/health", None, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn import_schedule_creates_tasks_and_indexed_dependencies() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Importer", "email": "import@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Imported"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let import_uri = format!("/projects/{}/import-schedule", project_id);
+
+    // A cycle is rejected and nothing is written
+    let (status, _) = send(&app, "POST", &import_uri, Some(&token), Some(json!({
+        "tasks": [{"title": "X"}, {"title": "Y"}],
+        "dependencies": [
+            {"source_index": 0, "target_index": 1},
+            {"source_index": 1, "target_index": 0}
+        ]
+    }))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, imported) = send(&app, "POST", &import_uri, Some(&token), Some(json!({
+        "tasks": [
+            {"title": "Design", "start_date": "2025-05-01T00:00:00Z", "end_date": "2025-05-05T00:00:00Z"},
+            {"title": "Build", "start_date": "2025-05-05T00:00:00Z", "end_date": "2025-05-12T00:00:00Z"}
+        ],
+        "dependencies": [{"source_index": 0, "target_index": 1, "type": "finish_to_start"}]
+    }))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let design_id = imported["tasks"][0]["id"].as_str().context("missing task id")?.to_string();
+    let build_id = imported["tasks"][1]["id"].as_str().context("missing task id")?.to_string();
+
+    let (_, tasks) = send(&app, "GET", &format!("/projects/{}/tasks", project_id), Some(&token), None).await?;
+    assert_eq!(tasks.as_array().map(|t| t.len()), Some(2));
+
+    let (_, deps) = send(&app, "GET", &format!("/projects/{}/dependencies", project_id), Some(&token), None).await?;
+    let deps = deps.as_array().context("expected dependency array")?;
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0]["source_task_id"], design_id.as_str());
+    assert_eq!(deps[0]["target_task_id"], build_id.as_str());
+
+    // Parents must be live tasks of this project
+    let (status, err) = send(&app, "POST", &import_uri, Some(&token), Some(json!({
+        "tasks": [{"title": "Fine", "parent_id": design_id}, {"title": "Orphan", "parent_id": uuid::Uuid::new_v4()}]
+    }))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("tasks[1]"), "{}", err);
+    let (_, tasks) = send(&app, "GET", &format!("/projects/{}/tasks", project_id), Some(&token), None).await?;
+    assert_eq!(tasks.as_array().map(|t| t.len()), Some(2), "a rejected import writes nothing");
+
+    let (status, imported) = send(&app, "POST", &import_uri, Some(&token), Some(json!({
+        "tasks": [{"title": "Design review", "parent_id": design_id}]
+    }))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", imported);
+    assert_eq!(imported["tasks"][0]["parent_id"], design_id.as_str());
+
+    Ok(())
+}
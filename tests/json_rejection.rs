@@ -0,0 +1,50 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::{self, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::Value;
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::migrated_pool;
+use s_curve::create_app;
+
+async fn post_raw(app: &axum::Router, uri: &str, content_type: &str, raw: &str) -> Result<(StatusCode, Value)> {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", content_type)
+        .body(Body::from(raw.to_string()))?;
+    let resp = app.clone().oneshot(req).await?;
+    let status = resp.status();
+    let bytes = body::to_bytes(resp.into_body(), 10_485_760).await?;
+    let value: Value = serde_json::from_slice(&bytes).context("error body is not JSON")?;
+    Ok((status, value))
+}
+
+#[tokio::test]
+async fn malformed_json_bodies_get_json_validation_errors() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    // Trailing comma: syntax error
+    let (status, body) = post_raw(&app, "/auth/register", "application/json", r#"{"name": "A", "email": "a@example.com", "password": "password123",}"#).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "VALIDATION");
+    assert_eq!(body["error"], "bad_request");
+    assert!(body["message"].as_str().unwrap_or_default().contains("malformed JSON"));
+
+    // Well-formed but missing a required field
+    let (status, body) = post_raw(&app, "/auth/register", "application/json", r#"{"name": "A"}"#).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "VALIDATION");
+
+    // Wrong content type
+    let (status, body) = post_raw(&app, "/auth/login", "text/plain", "{}").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "VALIDATION");
+
+    Ok(())
+}
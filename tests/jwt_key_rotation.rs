@@ -0,0 +1,67 @@
+use anyhow::Result;
+use uuid::Uuid;
+
+use s_curve::jwt::JwtConfig;
+
+fn keys(pairs: &[(&str, &str)]) -> Vec<(String, Vec<u8>)> {
+    pairs.iter().map(|(id, secret)| (id.to_string(), secret.as_bytes().to_vec())).collect()
+}
+
+#[test]
+fn rotated_keys_keep_old_tokens_valid_until_removed() -> Result<()> {
+    let user_id = Uuid::new_v4();
+
+    let before = JwtConfig::from_keys(keys(&[("k1", "first-secret")]), "k1", 1)?;
+    let old_token = before.encode(user_id)?;
+    assert_eq!(jsonwebtoken::decode_header(&old_token)?.kid.as_deref(), Some("k1"));
+
+    // Rotate: k2 signs, k1 still verifies
+    let rotated = JwtConfig::from_keys(keys(&[("k1", "first-secret"), ("k2", "second-secret")]), "k2", 1)?;
+    assert_eq!(rotated.decode(&old_token)?.sub, user_id);
+    let new_token = rotated.encode(user_id)?;
+    assert_eq!(jsonwebtoken::decode_header(&new_token)?.kid.as_deref(), Some("k2"));
+    assert_eq!(rotated.decode(&new_token)?.sub, user_id);
+
+    // Retire k1
+    let retired = JwtConfig::from_keys(keys(&[("k2", "second-secret")]), "k2", 1)?;
+    assert!(retired.decode(&old_token).is_err());
+    assert!(retired.decode(&new_token).is_ok());
+
+    // A kid does not let a token pick a different secret
+    let forged = JwtConfig::from_keys(keys(&[("k2", "attacker-secret")]), "k2", 1)?.encode(user_id)?;
+    assert!(rotated.decode(&forged).is_err());
+
+    // Keyed configs only accept kid-less tokens when a JWT_SECRET is kept for them
+    let unkeyed = JwtConfig::new(b"legacy-secret".to_vec(), 1).encode(user_id)?;
+    assert!(jsonwebtoken::decode_header(&unkeyed)?.kid.is_none());
+    assert!(rotated.decode(&unkeyed).is_err());
+
+    assert!(JwtConfig::from_keys(keys(&[("k1", "a")]), "k9", 1).is_err());
+    assert!(JwtConfig::from_keys(keys(&[("k1", "a"), ("k1", "b")]), "k1", 1).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn keys_load_from_env() -> Result<()> {
+    std::env::set_var("JWT_SECRET", "legacy-secret");
+    std::env::set_var("JWT_KEYS", "k1:first-secret, k2:second:with:colons");
+    std::env::set_var("JWT_CURRENT_KID", "k2");
+
+    let config = JwtConfig::from_env()?;
+    let user_id = Uuid::new_v4();
+    let token = config.encode(user_id)?;
+    assert_eq!(jsonwebtoken::decode_header(&token)?.kid.as_deref(), Some("k2"));
+
+    let old = JwtConfig::from_keys(keys(&[("k1", "first-secret")]), "k1", 1)?.encode(user_id)?;
+    assert_eq!(config.decode(&old)?.sub, user_id);
+    let unkeyed = JwtConfig::new(b"legacy-secret".to_vec(), 1).encode(user_id)?;
+    assert_eq!(config.decode(&unkeyed)?.sub, user_id);
+
+    // Several keys need an explicit current one
+    std::env::remove_var("JWT_CURRENT_KID");
+    assert!(JwtConfig::from_env().is_err());
+
+    std::env::remove_var("JWT_KEYS");
+    Ok(())
+}
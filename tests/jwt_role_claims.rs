@@ -0,0 +1,62 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::authz::roles;
+use s_curve::create_app;
+use s_curve::jwt::{JwtConfig, MAX_ROLE_CLAIMS};
+
+#[tokio::test]
+async fn role_claims_allow_admin_without_db_roles() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    std::env::set_var("JWT_ROLE_CLAIMS", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, first) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Root", "email": "root@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = first["token"].as_str().context("missing token")?.to_string();
+    let user_id = uuid::Uuid::parse_str(first["user"]["id"].as_str().context("missing user id")?)?;
+
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 1);
+    let claims = jwt.decode(&token)?;
+    assert_eq!(claims.roles, vec![roles::SUPER_ADMIN.to_string()]);
+
+    let (status, _) = send(&app, "GET", "/admin/db/stats", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    // Drop the role from the DB: the claim still allows until the token expires ...
+    sqlx::query("DELETE FROM user_roles").execute(&pool).await?;
+    let (status, _) = send(&app, "GET", "/admin/db/stats", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    // ... while a fresh token reflects the change and falls back to the DB
+    let (_, login) = send(&app, "POST", "/auth/login", None, Some(json!({"email": "root@example.com", "password": "password123"}))).await?;
+    let fresh = login["token"].as_str().context("missing token")?.to_string();
+    assert!(jwt.decode(&fresh)?.roles.is_empty());
+    let (status, _) = send(&app, "GET", "/admin/db/stats", Some(&fresh), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Tokens without the claim keep working as before
+    let plain = jwt.encode(user_id)?;
+    let (status, _) = send(&app, "GET", "/auth/me", Some(&plain), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
+
+#[test]
+fn embedded_roles_are_capped_with_super_admin_first() -> Result<()> {
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 1);
+    let mut many: Vec<String> = (0..20).map(|i| format!("role_{:02}", i)).collect();
+    many.push(roles::SUPER_ADMIN.to_string());
+
+    let claims = jwt.decode(&jwt.encode_with_roles(uuid::Uuid::new_v4(), many)?)?;
+    assert_eq!(claims.roles.len(), MAX_ROLE_CLAIMS);
+    assert_eq!(claims.roles[0], roles::SUPER_ADMIN);
+    Ok(())
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn me_export_contains_every_project_with_nested_data() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Exporter", "email": "export@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let mut project_ids = Vec::new();
+    for name in ["Alpha", "Beta"] {
+        let (status, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": name}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let project_id = project["id"].as_str().context("missing project id")?.to_string();
+        let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+        let (_, t1) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": format!("{} design", name)}))).await?;
+        let (_, t2) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": format!("{} build", name)}))).await?;
+        let t1_id = t1["id"].as_str().context("missing task id")?.to_string();
+
+        let (status, _) = send(&app, "POST", &format!("/projects/{}/dependencies", project_id), Some(&token), Some(json!({"source_task_id": t1["id"], "target_task_id": t2["id"]}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = send(&app, "POST", &format!("/projects/{}/plan", project_id), Some(&token), Some(json!([{"date": "2025-01-01T00:00:00Z", "planned_progress": 50}]))).await?;
+        assert!(status.is_success());
+        let (status, _) = send(&app, "POST", &format!("/projects/{}/tasks/{}/progress", project_id, t1_id), Some(&token), Some(json!({"progress": 40}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+
+        project_ids.push(project_id);
+    }
+
+    let (status, export) = send(&app, "GET", "/me/export", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(export["exported_at"].is_string());
+    let bundles = export["projects"].as_array().context("projects should be an array")?;
+    assert_eq!(bundles.len(), 2);
+
+    for project_id in &project_ids {
+        let bundle = bundles
+            .iter()
+            .find(|b| b["project"]["id"].as_str() == Some(project_id.as_str()))
+            .context("project missing from export")?;
+        assert_eq!(bundle["tasks"].as_array().map(|t| t.len()), Some(2));
+        assert_eq!(bundle["dependencies"].as_array().map(|d| d.len()), Some(1));
+        assert_eq!(bundle["plan"].as_array().map(|p| p.len()), Some(1));
+        let progress = bundle["progress"].as_array().context("progress should be an array")?;
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0]["progress"].as_i64(), Some(40));
+    }
+
+    // The export is scoped to the caller
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Other", "email": "other-export@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?.to_string();
+    let (status, empty) = send(&app, "GET", "/me/export", Some(&other_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(empty["projects"].as_array().map(|p| p.len()), Some(0));
+
+    Ok(())
+}
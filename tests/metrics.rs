@@ -0,0 +1,82 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::{self, Body};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use serde_json::json;
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+async fn scrape(app: &axum::Router, token: Option<&str>) -> Result<(StatusCode, String)> {
+    let mut builder = Request::builder().method("GET").uri("/metrics");
+    if let Some(t) = token {
+        builder = builder.header("authorization", format!("Bearer {}", t));
+    }
+    let resp: Response = app.clone().oneshot(builder.body(Body::empty())?).await?;
+    let status = resp.status();
+    let bytes = body::to_bytes(resp.into_body(), 10_485_760).await?;
+    Ok((status, String::from_utf8(bytes.to_vec())?))
+}
+
+fn sample(text: &str, name: &str) -> Option<f64> {
+    text.lines()
+        .find(|line| line.starts_with(name) && line[name.len()..].starts_with(' '))
+        .and_then(|line| line.rsplit(' ').next())
+        .and_then(|value| value.parse().ok())
+}
+
+#[tokio::test]
+async fn metrics_report_requests_and_activity_log_failures() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::remove_var("METRICS_TOKEN");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, user) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Metrics", "email": "metrics@example.com", "password": "password123"}))).await?;
+    let token = user["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Observed"}))).await?;
+    let project_uri = format!("/projects/{}", project["id"].as_str().context("missing project id")?);
+    let (status, _) = send(&app, "GET", &project_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, text) = scrape(&app, None).await?;
+    assert_eq!(status, StatusCode::OK);
+    // Labelled by route pattern, not the concrete id
+    assert!(text.contains(r#"http_requests_total{method="GET",route="/projects/:id",status="200"} 1"#), "{}", text);
+    assert!(text.contains(r#"http_request_duration_seconds_count{method="GET",route="/projects/:id"} 1"#));
+    assert!(!text.contains(&project_uri));
+    assert!(sample(&text, "db_pool_connections").is_some());
+    assert!(sample(&text, "event_bus_queued_events").is_some());
+    let failures_before = sample(&text, "activity_log_write_failures_total").context("missing failure counter")?;
+
+    // Break the projection so the listener's next write fails
+    sqlx::query("DROP TABLE activity_log").execute(&pool).await?;
+    let (status, _) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Unlogged"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let mut failures = failures_before;
+    for _ in 0..100 {
+        let (_, text) = scrape(&app, None).await?;
+        failures = sample(&text, "activity_log_write_failures_total").unwrap_or(failures_before);
+        if failures > failures_before {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(failures > failures_before, "activity log failure was not counted");
+
+    std::env::set_var("METRICS_TOKEN", "scrape-me");
+    let (status, _) = scrape(&app, None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = scrape(&app, Some(&token)).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = scrape(&app, Some("scrape-me")).await?;
+    assert_eq!(status, StatusCode::OK);
+    std::env::remove_var("METRICS_TOKEN");
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use serde_json::{json, Value};
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::migrated_pool;
+use s_curve::create_app;
+use s_curve::routes::oauth::{upsert_google_user, GoogleIdentity};
+
+fn identity(sub: &str, email: &str, verified: bool) -> GoogleIdentity {
+    GoogleIdentity { sub: sub.to_string(), email: email.to_string(), email_verified: verified, name: Some("Gina".to_string()) }
+}
+
+#[tokio::test]
+async fn returning_google_user_gets_the_same_account() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    let (first, created) = upsert_google_user(&pool, &identity("google-sub-1", "gina@example.com", true)).await?;
+    assert!(created);
+    assert_eq!(first.provider, "google");
+    assert_eq!(first.provider_id.as_deref(), Some("google-sub-1"));
+
+    // Same subject, even with a changed email, resolves to the same account
+    let (again, created) = upsert_google_user(&pool, &identity("google-sub-1", "gina.new@example.com", true)).await?;
+    assert!(!created);
+    assert_eq!(again.id, first.id);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM users").fetch_one(&pool).await?;
+    assert_eq!(count, 1);
+
+    // A different subject claiming the same email is refused
+    let err = upsert_google_user(&pool, &identity("google-sub-2", "gina@example.com", true)).await.unwrap_err();
+    assert_eq!(err.code(), "CONFLICT");
+    Ok(())
+}
+
+#[tokio::test]
+async fn google_sign_in_links_local_account_by_email() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/register")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"name": "Local", "email": "local@example.com", "password": "password123"}).to_string()))?;
+    let resp = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let bytes = axum::body::to_bytes(resp.into_body(), 1_048_576).await?;
+    let registered: Value = serde_json::from_slice(&bytes)?;
+    let local_id = registered["user"]["id"].as_str().context("missing user id")?.to_string();
+
+    // An unverified Google email must not take over the account
+    let err = upsert_google_user(&pool, &identity("google-sub-9", "local@example.com", false)).await.unwrap_err();
+    assert_eq!(err.code(), "CONFLICT");
+
+    let (linked, created) = upsert_google_user(&pool, &identity("google-sub-9", "local@example.com", true)).await?;
+    assert!(!created);
+    assert_eq!(linked.id.to_string(), local_id);
+    assert_eq!(linked.provider_id.as_deref(), Some("google-sub-9"));
+
+    // The password still works after linking
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/login")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"email": "local@example.com", "password": "password123"}).to_string()))?;
+    assert_eq!(app.clone().oneshot(req).await?.status(), StatusCode::OK);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM users").fetch_one(&pool).await?;
+    assert_eq!(count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn google_sign_in_links_local_account_whatever_the_email_case() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/register")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"name": "Alice", "email": "Alice@Example.com", "password": "password123"}).to_string()))?;
+    let resp = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let bytes = axum::body::to_bytes(resp.into_body(), 1_048_576).await?;
+    let registered: Value = serde_json::from_slice(&bytes)?;
+    let local_id = registered["user"]["id"].as_str().context("missing user id")?.to_string();
+
+    let (linked, created) = upsert_google_user(&pool, &identity("google-sub-alice", "alice@example.com", true)).await?;
+    assert!(!created);
+    assert_eq!(linked.id.to_string(), local_id);
+    assert_eq!(linked.provider_id.as_deref(), Some("google-sub-alice"));
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM users").fetch_one(&pool).await?;
+    assert_eq!(count, 1, "linking must not create a second account with the same email");
+    Ok(())
+}
+
+#[tokio::test]
+async fn oauth_endpoints_follow_configuration_and_state() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+    let get = |uri: &str, cookie: Option<&str>| {
+        let mut builder = Request::builder().method("GET").uri(uri);
+        if let Some(c) = cookie {
+            builder = builder.header(header::COOKIE, c);
+        }
+        builder.body(Body::empty()).unwrap()
+    };
+
+    std::env::remove_var("GOOGLE_CLIENT_ID");
+    let resp = app.clone().oneshot(get("/auth/oauth/google", None)).await?;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    std::env::set_var("GOOGLE_CLIENT_ID", "client-123");
+    std::env::set_var("GOOGLE_CLIENT_SECRET", "shh");
+    std::env::set_var("GOOGLE_REDIRECT_URI", "http://localhost:8080/v1/auth/oauth/google/callback");
+
+    let resp = app.clone().oneshot(get("/auth/oauth/google", None)).await?;
+    assert_eq!(resp.status(), StatusCode::FOUND);
+    let location = resp.headers().get(header::LOCATION).context("missing location")?.to_str()?.to_string();
+    assert!(location.starts_with("https://accounts.google.com/"), "{}", location);
+    assert!(location.contains("client_id=client-123"), "{}", location);
+    let cookie = resp.headers().get(header::SET_COOKIE).context("missing state cookie")?.to_str()?.to_string();
+    let state = cookie
+        .split(';')
+        .next()
+        .and_then(|pair| pair.strip_prefix("oauth_state="))
+        .context("malformed state cookie")?
+        .to_string();
+    assert!(location.contains(&format!("state={}", state)), "{}", location);
+
+    // Missing or mismatched state is rejected before any code exchange
+    let resp = app.clone().oneshot(get(&format!("/auth/oauth/google/callback?code=abc&state={}", state), None)).await?;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = app
+        .clone()
+        .oneshot(get("/auth/oauth/google/callback?code=abc&state=forged", Some(&format!("oauth_state={}", state))))
+        .await?;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // Declined consent
+    let resp = app
+        .clone()
+        .oneshot(get(&format!("/auth/oauth/google/callback?error=access_denied&state={}", state), Some(&format!("oauth_state={}", state))))
+        .await?;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    for name in ["GOOGLE_CLIENT_ID", "GOOGLE_CLIENT_SECRET", "GOOGLE_REDIRECT_URI"] {
+        std::env::remove_var(name);
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use anyhow::Context;
 use serde_json::Value;
 
 #[test]
@@ -26,3 +27,41 @@ fn openapi_has_task_timeline_fields() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn openapi_paths_target_v1() -> anyhow::Result<()> {
+    let doc = s_curve::docs::build_openapi(8000)?;
+    let v = serde_json::to_value(&doc)?;
+    let paths = v.get("paths").and_then(Value::as_object).expect("paths must exist");
+
+    assert!(paths.contains_key("/v1/projects"));
+    assert!(paths.contains_key("/v1/projects/{project_id}/tasks"));
+    assert!(!paths.contains_key("/projects"), "deprecated aliases should not be documented");
+    // Operational endpoints stay unversioned
+    assert!(paths.contains_key("/health/ready"));
+    assert!(paths.contains_key("/metrics"));
+
+    Ok(())
+}
+
+#[test]
+fn openapi_documents_task_status_values() -> anyhow::Result<()> {
+    let doc = s_curve::docs::build_openapi(8000)?;
+    let v = serde_json::to_value(&doc)?;
+
+    let values = v
+        .pointer("/components/schemas/TaskStatus/enum")
+        .and_then(Value::as_array)
+        .context("TaskStatus should list its allowed values")?;
+    assert!(values.iter().any(|s| s == "in_progress"));
+    assert_eq!(values.len(), s_curve::models::task::TASK_STATUSES.len());
+
+    for schema in ["Task", "TaskCreateRequest", "TaskUpdateRequest", "TaskReplaceRequest", "TaskBatchUpdateRequest"] {
+        let status = v
+            .pointer(&format!("/components/schemas/{}/properties/status", schema))
+            .unwrap_or_else(|| panic!("{} should have a status property", schema));
+        assert!(status.to_string().contains("#/components/schemas/TaskStatus"), "{}.status should reference TaskStatus", schema);
+    }
+
+    Ok(())
+}
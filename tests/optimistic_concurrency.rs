@@ -0,0 +1,84 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send_with_headers};
+use s_curve::create_app;
+
+async fn send(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    headers: &[(&str, &str)],
+    body: Option<Value>,
+) -> Result<(StatusCode, Value)> {
+    let (status, _, value) = send_with_headers(app, method, uri, Some(token), headers, body).await?;
+    Ok((status, value))
+}
+
+#[tokio::test]
+async fn stale_updates_are_rejected_and_unconditional_ones_still_win() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, _, reg) = send_with_headers(&app, "POST", "/auth/register", None, &[], Some(json!({"name": "Editor", "email": "editor@example.com", "password": "password123"}))).await?;
+    let token = reg["token"].as_str().context("missing token")?.to_string();
+
+    // Project: both editors read the same version, the second save is stale
+    let (_, project) = send(&app, "POST", "/projects", &token, &[], Some(json!({"name": "Shared"}))).await?;
+    let project_uri = format!("/projects/{}", project["id"].as_str().context("missing project id")?);
+    let read_version = project["updated_at"].clone();
+
+    let (status, first) = send(&app, "PUT", &project_uri, &token, &[], Some(json!({"name": "First", "expected_updated_at": read_version}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", first);
+    let (status, err) = send(&app, "PUT", &project_uri, &token, &[], Some(json!({"name": "Second", "expected_updated_at": read_version}))).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(err["message"].as_str().unwrap_or_default().contains("refetch"));
+    let (_, current) = send(&app, "GET", &project_uri, &token, &[], None).await?;
+    assert_eq!(current["name"], "First");
+
+    // Without a precondition the last write still wins
+    let (status, last) = send(&app, "PUT", &project_uri, &token, &[], Some(json!({"name": "Last"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(last["name"], "Last");
+
+    // Task: same via PATCH and PUT
+    let tasks_uri = format!("{}/tasks", project_uri);
+    let (_, task) = send(&app, "POST", &tasks_uri, &token, &[], Some(json!({"title": "Draft"}))).await?;
+    let task_uri = format!("{}/{}", tasks_uri, task["id"].as_str().context("missing task id")?);
+    let read_version = task["updated_at"].clone();
+
+    let (status, patched) = send(&app, "PATCH", &task_uri, &token, &[], Some(json!({"progress": 30, "expected_updated_at": read_version}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", patched);
+    let (status, _) = send(&app, "PATCH", &task_uri, &token, &[], Some(json!({"progress": 90, "expected_updated_at": read_version}))).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(&app, "PUT", &task_uri, &token, &[], Some(json!({"title": "Stale", "status": "pending", "progress": 0, "expected_updated_at": read_version}))).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, replaced) = send(&app, "PUT", &task_uri, &token, &[], Some(json!({"title": "Fresh", "status": "pending", "progress": 40, "expected_updated_at": patched["updated_at"]}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", replaced);
+    let (_, current) = send(&app, "GET", &task_uri, &token, &[], None).await?;
+    assert_eq!(current["title"], "Fresh");
+    assert_eq!(current["progress"], 40);
+
+    // If-Unmodified-Since: a date before the last edit is stale, one after it is fine
+    let (status, _) = send(&app, "PATCH", &task_uri, &token, &[("if-unmodified-since", "Sat, 01 Jan 2000 00:00:00 GMT")], Some(json!({"progress": 50}))).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(&app, "PATCH", &task_uri, &token, &[("if-unmodified-since", "Fri, 01 Jan 2100 00:00:00 GMT")], Some(json!({"progress": 50}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "PATCH", &task_uri, &token, &[("if-unmodified-since", "yesterday")], Some(json!({"progress": 60}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A deleted task is still reported as missing, not as a conflict
+    let (status, _) = send(&app, "DELETE", &task_uri, &token, &[], None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "PATCH", &task_uri, &token, &[], Some(json!({"progress": 70, "expected_updated_at": patched["updated_at"]}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use anyhow::Result;
+use axum::body::{self, Body};
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use serde_json::{json, Value};
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::migrated_pool;
+use s_curve::create_app;
+
+async fn register(app: &axum::Router, email: &str, password: &str) -> Result<(StatusCode, String)> {
+    let body = json!({"name": "Pw", "email": email, "password": password});
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/register")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))?;
+    let resp: Response = app.clone().oneshot(req).await?;
+    let status = resp.status();
+    let bytes = body::to_bytes(resp.into_body(), 1_048_576).await?;
+    Ok((status, String::from_utf8(bytes.to_vec())?))
+}
+
+#[tokio::test]
+async fn weak_passwords_are_rejected_before_any_write() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, body) = register(&app, "short@example.com", "Xy7!q").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert!(!body.contains("Xy7!q"), "error must not echo the password: {}", body);
+    let parsed: Value = serde_json::from_str(&body)?;
+    assert!(parsed.to_string().contains("at least 8 characters"), "{}", parsed);
+
+    let (status, body) = register(&app, "common@example.com", "Password1").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    assert!(!body.contains("Password1"), "error must not echo the password: {}", body);
+
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(1) FROM users").fetch_one(&pool).await?;
+    assert_eq!(users, 0, "rejected registrations must not create users");
+
+    let (status, body) = register(&app, "ok@example.com", "correct-horse-battery").await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    Ok(())
+}
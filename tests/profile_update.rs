@@ -0,0 +1,47 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn update_profile_name_and_email() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, ada) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Ada", "email": "ada@example.com", "password": "password123"}))).await?;
+    let token = ada["token"].as_str().context("missing token")?.to_string();
+    send(&app, "POST", "/auth/register", None, Some(json!({"name": "Bob", "email": "bob@example.com", "password": "password123"}))).await?;
+
+    // Re-submitting the caller's own email is a no-op success
+    let (status, same) = send(&app, "PUT", "/auth/me", Some(&token), Some(json!({"email": "ada@example.com"}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", same);
+    assert_eq!(same["updated_at"], ada["user"]["updated_at"]);
+
+    // Another active user's email is taken, regardless of case
+    let (status, body) = send(&app, "PUT", "/auth/me", Some(&token), Some(json!({"email": "BOB@example.com"}))).await?;
+    assert_eq!(status, StatusCode::CONFLICT, "{}", body);
+
+    let (status, body) = send(&app, "PUT", "/auth/me", Some(&token), Some(json!({"email": "not-an-email"}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    let (status, body) = send(&app, "PUT", "/auth/me", Some(&token), Some(json!({"name": "  "}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+
+    let (status, updated) = send(&app, "PUT", "/auth/me", Some(&token), Some(json!({"name": "Ada King", "email": "ada.king@example.com"}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", updated);
+    assert_eq!(updated["name"], "Ada King");
+    assert_eq!(updated["email"], "ada.king@example.com");
+
+    let (_, me) = send(&app, "GET", "/auth/me", Some(&token), None).await?;
+    assert_eq!(me["email"], "ada.king@example.com");
+
+    // The new email is what logs in now
+    let (status, _) = send(&app, "POST", "/auth/login", None, Some(json!({"email": "ada.king@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn backdated_progress_lands_on_its_own_day() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Historian", "email": "historian@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Archive"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let (_, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Foundation"}))).await?;
+    let task_uri = format!("/projects/{}/tasks/{}", project_id, task["id"].as_str().context("missing task id")?);
+    let progress_uri = format!("{}/progress", task_uri);
+
+    let today = chrono::Utc::now();
+    let (status, current) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 50}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", current);
+
+    let past = today - chrono::Duration::days(10);
+    let (status, backdated) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 20, "occurred_at": past.to_rfc3339()}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", backdated);
+    let created_at = chrono::DateTime::parse_from_rfc3339(backdated["created_at"].as_str().context("missing created_at")?)?;
+    assert_eq!(created_at.with_timezone(&chrono::Utc), past);
+    let updated_at = chrono::DateTime::parse_from_rfc3339(backdated["updated_at"].as_str().context("missing updated_at")?)?;
+    assert!(updated_at > created_at);
+
+    // The dashboard's daily series has the entry on its own day
+    let (status, dashboard) = send(&app, "GET", &format!("/projects/{}/dashboard", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let actual = dashboard["actual"].as_array().context("missing actual")?;
+    let past_day = past.date_naive().to_string();
+    let today_day = today.date_naive().to_string();
+    assert!(actual.iter().any(|p| p["date"] == past_day.as_str() && p["actual"] == 20), "{:?}", actual);
+    assert!(actual.iter().any(|p| p["date"] == today_day.as_str() && p["actual"] == 50), "{:?}", actual);
+
+    // An older entry does not roll the task's progress back
+    let (_, fetched) = send(&app, "GET", &task_uri, Some(&token), None).await?;
+    assert_eq!(fetched["progress"], 50);
+
+    let future = today + chrono::Duration::days(1);
+    let (status, _) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 90, "occurred_at": future.to_rfc3339()}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn progress_batch_is_all_or_nothing() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Backfill", "email": "backfill@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "History"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let (_, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Backfilled"}))).await?;
+    let task_id = task["id"].as_str().context("missing task id")?.to_string();
+    let batch_uri = format!("/projects/{}/tasks/{}/progress/batch", project_id, task_id);
+
+    // The third entry is out of range, so none of them are stored
+    let (status, err) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"entries": [
+        {"progress": 10, "occurred_at": "2025-01-01T09:00:00Z"},
+        {"progress": 20, "occurred_at": "2025-01-02T09:00:00Z"},
+        {"progress": 150, "occurred_at": "2025-01-03T09:00:00Z"}
+    ]}))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err["errors"].as_array().map(|e| e.len()), Some(1));
+    assert_eq!(err["errors"][0]["field"], "entries[2].progress");
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM task_progress").fetch_one(&pool).await?;
+    assert_eq!(stored, 0);
+
+    let (status, _) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"entries": []}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Entries may arrive in any order; the newest one becomes the task's progress
+    let (status, created) = send(&app, "POST", &batch_uri, Some(&token), Some(json!({"entries": [
+        {"progress": 40, "occurred_at": "2025-01-03T09:00:00Z", "note": "day three"},
+        {"progress": 10, "occurred_at": "2025-01-01T09:00:00Z"},
+        {"progress": 25, "occurred_at": "2025-01-02T09:00:00Z"}
+    ]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let created = created.as_array().context("expected a list")?;
+    assert_eq!(created.iter().map(|p| p["progress"].as_i64().unwrap()).collect::<Vec<_>>(), vec![40, 10, 25]);
+    assert_eq!(created[0]["note"], "day three");
+    assert!(created.iter().all(|p| p["task_id"] == task["id"]));
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM task_progress").fetch_one(&pool).await?;
+    assert_eq!(stored, 3);
+    let (_, task) = send(&app, "GET", &format!("/projects/{}/tasks/{}", project_id, task_id), Some(&token), None).await?;
+    assert_eq!(task["progress"], 40);
+
+    // Someone outside the project cannot write to it
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Other", "email": "other@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?;
+    let (status, _) = send(&app, "POST", &batch_uri, Some(other_token), Some(json!({"entries": [{"progress": 5}]}))).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    Ok(())
+}
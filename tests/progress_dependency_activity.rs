@@ -0,0 +1,66 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::SqlitePool;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+async fn severity_of(pool: &SqlitePool, event_name: &str) -> Result<Option<String>> {
+    // The activity listener writes asynchronously
+    for _ in 0..50 {
+        let severity: Option<String> = sqlx::query_scalar("SELECT severity FROM activity_log WHERE event_name = ? LIMIT 1")
+            .bind(event_name)
+            .fetch_optional(pool)
+            .await?;
+        if severity.is_some() {
+            return Ok(severity);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    Ok(None)
+}
+
+#[tokio::test]
+async fn progress_and_dependency_mutations_are_logged() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Logger", "email": "logger@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Logged"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+    let (_, t1) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "First"}))).await?;
+    let (_, t2) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Second"}))).await?;
+    let t1_id = t1["id"].as_str().context("missing task id")?.to_string();
+
+    let progress_uri = format!("/projects/{}/tasks/{}/progress", project_id, t1_id);
+    let (status, entry) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 10}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let entry_uri = format!("{}/{}", progress_uri, entry["id"].as_str().context("missing progress id")?);
+    let (status, _) = send(&app, "PUT", &entry_uri, Some(&token), Some(json!({"progress": 20}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "DELETE", &entry_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let deps_uri = format!("/projects/{}/dependencies", project_id);
+    let (status, dep) = send(&app, "POST", &deps_uri, Some(&token), Some(json!({"source_task_id": t1["id"], "target_task_id": t2["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", deps_uri, dep["id"].as_str().context("missing dependency id")?), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Progress churn is prunable noise, even when deleted
+    assert_eq!(severity_of(&pool, "progress.created").await?.as_deref(), Some("noise"));
+    assert_eq!(severity_of(&pool, "progress.updated").await?.as_deref(), Some("noise"));
+    assert_eq!(severity_of(&pool, "progress.deleted").await?.as_deref(), Some("noise"));
+    // Dependency changes are kept for longer
+    assert_eq!(severity_of(&pool, "dependency.created").await?.as_deref(), Some("important"));
+    assert_eq!(severity_of(&pool, "dependency.deleted").await?.as_deref(), Some("critical"));
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn latest_progress_returns_newest_entry_per_task() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Latest", "email": "latest@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Board"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+
+    let mut expected = Vec::new();
+    for (title, entries) in [("A", vec![10, 40, 70]), ("B", vec![5, 25])] {
+        let (status, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": title}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let task_id = task["id"].as_str().context("missing task id")?.to_string();
+        for p in &entries {
+            let (status, _) = send(&app, "POST", &format!("/projects/{}/tasks/{}/progress", project_id, task_id), Some(&token), Some(json!({"progress": p}))).await?;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+        expected.push((task_id, *entries.last().unwrap()));
+    }
+
+    // A task without progress entries is omitted
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "C"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, latest) = send(&app, "GET", &format!("/projects/{}/progress/latest", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let latest = latest.as_object().context("expected a task_id -> progress map")?;
+    assert_eq!(latest.len(), 2);
+
+    for (task_id, progress) in expected {
+        assert_eq!(latest.get(&task_id).and_then(|v| v.as_i64()), Some(progress as i64));
+    }
+
+    Ok(())
+}
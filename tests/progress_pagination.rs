@@ -0,0 +1,67 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn progress_listing_is_paginated_newest_first() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Pager", "email": "pager@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "History"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let (_, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Long running"}))).await?;
+    let progress_uri = format!("/projects/{}/tasks/{}/progress", project_id, task["id"].as_str().context("missing task id")?);
+
+    for value in [10, 20, 30, 40, 50] {
+        let (status, _) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": value}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    // Soft-deleted entries count neither in items nor in total
+    let (_, extra) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 60}))).await?;
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", progress_uri, extra["id"].as_str().context("missing id")?), Some(&token), None).await?;
+    assert!(status.is_success());
+
+    fn values(page: &Value) -> Vec<i64> {
+        page["items"].as_array().map(|items| items.iter().filter_map(|p| p["progress"].as_i64()).collect()).unwrap_or_default()
+    }
+
+    let (status, page) = send(&app, "GET", &progress_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(values(&page), vec![50, 40, 30, 20, 10]);
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["limit"], 50);
+    assert_eq!(page["offset"], 0);
+
+    let (_, page) = send(&app, "GET", &format!("{}?limit=2&offset=2", progress_uri), Some(&token), None).await?;
+    assert_eq!(values(&page), vec![30, 20]);
+    assert_eq!(page["total"], 5);
+
+    let (_, page) = send(&app, "GET", &format!("{}?limit=2&order=asc", progress_uri), Some(&token), None).await?;
+    assert_eq!(values(&page), vec![10, 20]);
+
+    let (_, page) = send(&app, "GET", &format!("{}?limit=1000", progress_uri), Some(&token), None).await?;
+    assert_eq!(page["limit"], 200);
+
+    let (status, err) = send(&app, "GET", &format!("{}?order=sideways", progress_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("invalid query string"), "{}", err);
+
+    // Entries whose task id is stored as text (older builds) are both counted and listed
+    let task_id = task["id"].as_str().context("missing task id")?;
+    sqlx::query("UPDATE task_progress SET task_id = ?").bind(task_id).execute(&pool).await?;
+    let (status, page) = send(&app, "GET", &format!("{}?limit=2", progress_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", page);
+    assert_eq!(values(&page), vec![50, 40]);
+    assert_eq!(page["total"], 5);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn progress_entry_updates_task_column_when_sync_enabled() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    // Syncing is the default
+    std::env::remove_var("SYNC_TASK_PROGRESS");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Sync", "email": "sync@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Synced"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let (_, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Pour concrete", "progress": 0}))).await?;
+    let task_id = task["id"].as_str().context("missing task id")?.to_string();
+
+    let progress_uri = format!("/projects/{}/tasks/{}/progress", project_id, task_id);
+    let (status, _) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 35}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 60}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // The task column follows the most recent entry
+    let task_uuid = uuid::Uuid::parse_str(&task_id)?;
+    let column: i64 = sqlx::query_scalar("SELECT progress FROM tasks WHERE id = ?")
+        .bind(task_uuid)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(column, 60);
+
+    let (status, fetched) = send(&app, "GET", &format!("/projects/{}/tasks/{}", project_id, task_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fetched["progress"].as_i64(), Some(60));
+
+    // The task change is recorded as its own event
+    let mut task_updates = 0i64;
+    for _ in 0..50 {
+        task_updates = sqlx::query_scalar("SELECT COUNT(*) FROM activity_log WHERE event_name = 'task.updated'")
+            .fetch_one(&pool)
+            .await?;
+        if task_updates >= 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(task_updates, 2);
+
+    // Opting out keeps the task column independent of new entries
+    std::env::set_var("SYNC_TASK_PROGRESS", "false");
+    let (status, _) = send(&app, "POST", &progress_uri, Some(&token), Some(json!({"progress": 90}))).await?;
+    std::env::remove_var("SYNC_TASK_PROGRESS");
+    assert_eq!(status, StatusCode::CREATED);
+    let (_, fetched) = send(&app, "GET", &format!("/projects/{}/tasks/{}", project_id, task_id), Some(&token), None).await?;
+    assert_eq!(fetched["progress"].as_i64(), Some(60));
+
+    Ok(())
+}
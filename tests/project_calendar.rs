@@ -0,0 +1,110 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::{self, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+/// Event blocks keyed by UID, with folded lines joined back together.
+fn events_by_uid(ics: &str) -> Vec<(String, Vec<String>)> {
+    let unfolded = ics.replace("\r\n ", "");
+    let mut events = Vec::new();
+    let mut current: Option<Vec<String>> = None;
+    for line in unfolded.split("\r\n") {
+        match line {
+            "BEGIN:VEVENT" => current = Some(Vec::new()),
+            "END:VEVENT" => {
+                if let Some(lines) = current.take() {
+                    let uid = lines.iter().find_map(|l| l.strip_prefix("UID:")).unwrap_or_default().to_string();
+                    events.push((uid, lines));
+                }
+            }
+            other => {
+                if let Some(lines) = current.as_mut() {
+                    lines.push(other.to_string());
+                }
+            }
+        }
+    }
+    events
+}
+
+#[tokio::test]
+async fn project_tasks_export_as_icalendar() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Cal", "email": "cal@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, stranger) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Other", "email": "other-cal@example.com", "password": "password123"}))).await?;
+    let stranger_token = stranger["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Site build"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let mut ids = Vec::new();
+    for body in [
+        json!({"title": "Handover", "start_date": "2025-06-10T00:00:00Z", "end_date": "2025-06-10T00:00:00Z"}),
+        json!({"title": "Pour, cure; inspect", "start_date": "2025-06-01T00:00:00Z", "end_date": "2025-06-05T00:00:00Z"}),
+        json!({"title": "Permit deadline", "due_date": "2025-07-01T15:00:00Z"}),
+        json!({"title": "Someday"}),
+    ] {
+        let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(body)).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        ids.push(task["id"].as_str().context("missing task id")?.to_string());
+    }
+    // Soft-deleted tasks are not exported
+    let (status, deleted) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Scrapped", "due_date": "2025-08-01T00:00:00Z"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let deleted_id = deleted["id"].as_str().context("missing task id")?.to_string();
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", tasks_uri, deleted_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let req = Request::builder()
+        .method("GET")
+        .uri(format!("/projects/{}/tasks.ics", project_id))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())?;
+    let resp = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let content_type = resp.headers().get("content-type").and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+    assert!(content_type.starts_with("text/calendar"), "unexpected content type {}", content_type);
+    let ics = String::from_utf8(body::to_bytes(resp.into_body(), 10_485_760).await?.to_vec())?;
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+
+    let events = events_by_uid(&ics);
+    assert_eq!(events.len(), 3, "only dated, live tasks become events");
+    let event = |id: &str| events.iter().find(|(uid, _)| uid == &format!("{}@s-curve", id)).map(|(_, lines)| lines.clone());
+
+    // Milestone: same-day start and end render as a single all-day event
+    let milestone = event(&ids[0]).context("milestone missing")?;
+    assert!(milestone.contains(&"DTSTART;VALUE=DATE:20250610".to_string()));
+    assert!(milestone.contains(&"DTEND;VALUE=DATE:20250611".to_string()));
+    assert!(milestone.contains(&"SUMMARY:Handover".to_string()));
+
+    let span = event(&ids[1]).context("span missing")?;
+    assert!(span.contains(&"DTSTART;VALUE=DATE:20250601".to_string()));
+    assert!(span.contains(&"DTEND;VALUE=DATE:20250606".to_string()));
+    assert!(span.contains(&"SUMMARY:Pour\\, cure\\; inspect".to_string()));
+
+    let due = event(&ids[2]).context("due-date task missing")?;
+    assert!(due.contains(&"DTSTART;VALUE=DATE:20250701".to_string()));
+
+    assert!(event(&ids[3]).is_none());
+    assert!(event(&deleted_id).is_none());
+
+    let (status, _) = send(&app, "GET", &format!("/projects/{}/tasks.ics", project_id), Some(&stranger_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::response::Response;
+use std::collections::HashMap;
+
+use serde_json::json;
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn duplicate_remaps_tasks_and_dependencies_without_progress() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Copier", "email": "copier@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Stranger", "email": "stranger@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Template"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, phase) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Phase"}))).await?;
+    let phase_id = phase["id"].as_str().context("missing task id")?.to_string();
+    let (_, dig) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Dig", "parent_id": phase_id}))).await?;
+    let dig_id = dig["id"].as_str().context("missing task id")?.to_string();
+    let (_, pour) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Pour", "parent_id": phase_id}))).await?;
+    let pour_id = pour["id"].as_str().context("missing task id")?.to_string();
+
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/dependencies", project_id), Some(&token), Some(json!({"source_task_id": dig_id, "target_task_id": pour_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("{}/{}/progress", tasks_uri, dig_id), Some(&token), Some(json!({"progress": 40}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/plan", project_id), Some(&token), Some(json!([
+        {"date": "2025-03-01T00:00:00Z", "planned_progress": 0},
+        {"date": "2025-03-20T00:00:00Z", "planned_progress": 100}
+    ]))).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let duplicate_uri = format!("/projects/{}/duplicate", project_id);
+    let (status, _) = send(&app, "POST", &duplicate_uri, Some(&other_token), Some(json!({}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, copy) = send(&app, "POST", &duplicate_uri, Some(&token), Some(json!({}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", copy);
+    assert_ne!(copy["project"]["id"].as_str(), Some(project_id.as_str()));
+    assert_eq!(copy["project"]["name"], "Template (copy)");
+    assert_eq!(copy["project"]["user_id"], owner["user"]["id"]);
+
+    let ids_by_title: HashMap<String, String> = copy["tasks"]
+        .as_array()
+        .context("tasks should be an array")?
+        .iter()
+        .map(|t| (t["title"].as_str().unwrap_or_default().to_string(), t["id"].as_str().unwrap_or_default().to_string()))
+        .collect();
+    assert_eq!(ids_by_title.len(), 3);
+    for old in [&phase_id, &dig_id, &pour_id] {
+        assert!(!ids_by_title.values().any(|id| id == old), "copied task kept its old id");
+    }
+    for task in copy["tasks"].as_array().context("tasks should be an array")? {
+        match task["title"].as_str().unwrap_or_default() {
+            "Phase" => assert!(task["parent_id"].is_null()),
+            _ => assert_eq!(task["parent_id"].as_str(), Some(ids_by_title["Phase"].as_str())),
+        }
+    }
+
+    // Edges point at the cloned tasks, not the originals
+    let deps = copy["dependencies"].as_array().context("dependencies should be an array")?;
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0]["source_task_id"].as_str(), Some(ids_by_title["Dig"].as_str()));
+    assert_eq!(deps[0]["target_task_id"].as_str(), Some(ids_by_title["Pour"].as_str()));
+
+    assert_eq!(copy["plan"].as_array().map(Vec::len), Some(2));
+    assert_eq!(copy["progress"].as_array().map(Vec::len), Some(0));
+
+    // An explicit name wins; a blank one is rejected
+    let (status, named) = send(&app, "POST", &duplicate_uri, Some(&token), Some(json!({"name": "Next quarter"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(named["project"]["name"], "Next quarter");
+    let (status, _) = send(&app, "POST", &duplicate_uri, Some(&token), Some(json!({"name": "  "}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // The body is optional
+    let (status, bare) = send(&app, "POST", &duplicate_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", bare);
+    assert_eq!(bare["project"]["name"], "Template (copy)");
+
+    // ...but one that is sent must be valid; nothing is copied otherwise
+    let (status, err) = send(&app, "POST", &duplicate_uri, Some(&token), Some(json!({"name": 5}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("invalid JSON body"), "{}", err);
+    let req = Request::builder()
+        .method("POST")
+        .uri(&duplicate_uri)
+        .header("authorization", format!("Bearer {}", token))
+        .header("content-type", "application/json")
+        .body(Body::from("{not json"))?;
+    let resp: Response = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let (_, projects) = send(&app, "GET", "/projects", Some(&token), None).await?;
+    assert_eq!(projects.as_array().map(Vec::len), Some(4));
+
+    // The source keeps its own progress history
+    let (_, original) = send(&app, "GET", &format!("/projects/{}/export", project_id), Some(&token), None).await?;
+    assert_eq!(original["progress"].as_array().map(Vec::len), Some(1));
+    assert_eq!(original["dependencies"][0]["source_task_id"].as_str(), Some(dig_id.as_str()));
+
+    // Trashing Dig's parent and its dependency target leaves links to them behind; the copy drops those
+    for trashed in [&phase_id, &pour_id] {
+        let (status, _) = send(&app, "DELETE", &format!("{}/{}", tasks_uri, trashed), Some(&token), None).await?;
+        assert!(status.is_success());
+    }
+    let (status, pruned) = send(&app, "POST", &duplicate_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", pruned);
+    let tasks = pruned["tasks"].as_array().context("tasks should be an array")?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["title"], "Dig");
+    assert!(tasks[0]["parent_id"].is_null());
+    assert_eq!(pruned["dependencies"].as_array().map(Vec::len), Some(0));
+
+    Ok(())
+}
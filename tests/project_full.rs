@@ -0,0 +1,66 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn project_full_view_returns_requested_sections() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Full User", "email": "full_user@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (status, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Full Project"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+
+    let (status, t1) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Design", "status": "done", "progress": 100}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, t2) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Build", "status": "pending", "progress": 20}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/dependencies", project_id), Some(&token), Some(json!({"source_task_id": t1["id"], "target_task_id": t2["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/plan", project_id), Some(&token), Some(json!([{"date": "2025-01-01T00:00:00Z", "planned_progress": 50}]))).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    // Default: every section
+    let (status, full) = send(&app, "GET", &format!("/projects/{}/full", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(full["project"]["id"], project_id.as_str());
+    let tasks = full["tasks"].as_array().context("missing tasks")?;
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(full["dependencies"].as_array().map(|d| d.len()), Some(1));
+    assert_eq!(full["plan"].as_array().map(|p| p.len()), Some(1));
+    assert_eq!(full["stats"]["total_tasks"].as_i64(), Some(tasks.len() as i64));
+    assert_eq!(full["stats"]["completed_tasks"].as_i64(), Some(1));
+    assert_eq!(full["stats"]["average_progress"].as_f64(), Some(60.0));
+
+    // Restricted scope
+    let (status, partial) = send(&app, "GET", &format!("/projects/{}/full?include=tasks,stats", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(partial.get("tasks").is_some());
+    assert!(partial.get("stats").is_some());
+    assert!(partial.get("dependencies").is_none());
+    assert!(partial.get("plan").is_none());
+
+    let (status, _) = send(&app, "GET", &format!("/projects/{}/full?include=bogus", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Another user cannot read the project
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Other", "email": "other_full@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?.to_string();
+    let (status, _) = send(&app, "GET", &format!("/projects/{}/full", project_id), Some(&other_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
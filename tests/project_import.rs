@@ -0,0 +1,158 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn project_round_trips_through_export_and_import() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Backup", "email": "backup@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Restore", "email": "restore@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Original", "description": "to be copied"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, phase) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Phase", "start_date": "2025-03-01T00:00:00Z", "end_date": "2025-03-20T00:00:00Z"}))).await?;
+    let phase_id = phase["id"].as_str().context("missing task id")?.to_string();
+    let (_, dig) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Dig", "parent_id": phase_id}))).await?;
+    let dig_id = dig["id"].as_str().context("missing task id")?.to_string();
+    let (_, pour) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Pour", "parent_id": phase_id}))).await?;
+    let pour_id = pour["id"].as_str().context("missing task id")?.to_string();
+
+    let deps_uri = format!("/projects/{}/dependencies", project_id);
+    let (status, _) = send(&app, "POST", &deps_uri, Some(&token), Some(json!({"source_task_id": dig_id, "target_task_id": pour_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("{}/{}/progress", tasks_uri, dig_id), Some(&token), Some(json!({"progress": 60, "note": "halfway"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/plan", project_id), Some(&token), Some(json!([
+        {"date": "2025-03-01T00:00:00Z", "planned_progress": 0},
+        {"date": "2025-03-20T00:00:00Z", "planned_progress": 100}
+    ]))).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, bundle) = send(&app, "GET", &format!("/projects/{}/export", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bundle["tasks"].as_array().map(Vec::len), Some(3));
+    let (status, _) = send(&app, "GET", &format!("/projects/{}/export", project_id), Some(&other_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Another user restores the bundle as their own project
+    let (status, imported) = send(&app, "POST", "/projects/import", Some(&other_token), Some(bundle.clone())).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", imported);
+    assert_ne!(imported["project"]["id"], bundle["project"]["id"]);
+    assert_eq!(imported["project"]["name"], "Original");
+    assert_eq!(imported["project"]["user_id"], other["user"]["id"]);
+
+    let ids_by_title: HashMap<String, String> = imported["tasks"]
+        .as_array()
+        .context("tasks should be an array")?
+        .iter()
+        .map(|t| (t["title"].as_str().unwrap_or_default().to_string(), t["id"].as_str().unwrap_or_default().to_string()))
+        .collect();
+    assert_eq!(ids_by_title.len(), 3);
+    for old in [&phase_id, &dig_id, &pour_id] {
+        assert!(!ids_by_title.values().any(|id| id == old), "imported task kept its old id");
+    }
+
+    // Hierarchy survives the remapping
+    for task in imported["tasks"].as_array().context("tasks should be an array")? {
+        match task["title"].as_str().unwrap_or_default() {
+            "Phase" => assert!(task["parent_id"].is_null()),
+            _ => assert_eq!(task["parent_id"].as_str(), Some(ids_by_title["Phase"].as_str())),
+        }
+    }
+
+    // So do dependency edges
+    let deps = imported["dependencies"].as_array().context("dependencies should be an array")?;
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0]["source_task_id"].as_str(), Some(ids_by_title["Dig"].as_str()));
+    assert_eq!(deps[0]["target_task_id"].as_str(), Some(ids_by_title["Pour"].as_str()));
+
+    let progress = imported["progress"].as_array().context("progress should be an array")?;
+    assert_eq!(progress.len(), 1);
+    assert_eq!(progress[0]["task_id"].as_str(), Some(ids_by_title["Dig"].as_str()));
+    assert_eq!(progress[0]["created_at"], bundle["progress"][0]["created_at"]);
+    assert_eq!(imported["plan"].as_array().map(Vec::len), Some(2));
+
+    // The original is untouched
+    let (_, original) = send(&app, "GET", &format!("/projects/{}/export", project_id), Some(&token), None).await?;
+    assert_eq!(original["tasks"], bundle["tasks"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn inconsistent_bundles_are_rejected_without_partial_writes() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Bad", "email": "bad-import@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Source"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+    let (_, a) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "A"}))).await?;
+    let (_, b) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "B"}))).await?;
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/dependencies", project_id), Some(&token), Some(json!({"source_task_id": a["id"], "target_task_id": b["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (_, bundle) = send(&app, "GET", &format!("/projects/{}/export", project_id), Some(&token), None).await?;
+
+    // Dangling dependency endpoint
+    let mut dangling = bundle.clone();
+    dangling["dependencies"][0]["target_task_id"] = json!("00000000-0000-0000-0000-000000000001");
+    let (status, err) = send(&app, "POST", "/projects/import", Some(&token), Some(dangling)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("outside the bundle"));
+
+    // Parent cycle
+    let mut cyclic = bundle.clone();
+    cyclic["tasks"][0]["parent_id"] = cyclic["tasks"][1]["id"].clone();
+    cyclic["tasks"][1]["parent_id"] = cyclic["tasks"][0]["id"].clone();
+    let (status, _) = send(&app, "POST", "/projects/import", Some(&token), Some(cyclic)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Dependency cycle: B -> A alongside the exported A -> B
+    let mut circular = bundle.clone();
+    let mut back = circular["dependencies"][0].clone();
+    back["id"] = json!("00000000-0000-0000-0000-000000000002");
+    back["source_task_id"] = b["id"].clone();
+    back["target_task_id"] = a["id"].clone();
+    circular["dependencies"].as_array_mut().context("dependencies should be an array")?.push(back);
+    let (status, err) = send(&app, "POST", "/projects/import", Some(&token), Some(circular)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("Cycle detected"));
+
+    // The same edge twice
+    let mut duplicated = bundle.clone();
+    let mut again = duplicated["dependencies"][0].clone();
+    again["id"] = json!("00000000-0000-0000-0000-000000000003");
+    duplicated["dependencies"].as_array_mut().context("dependencies should be an array")?.push(again);
+    let (status, err) = send(&app, "POST", "/projects/import", Some(&token), Some(duplicated)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("duplicates"));
+
+    // Missing required structure
+    let (status, _) = send(&app, "POST", "/projects/import", Some(&token), Some(json!({"project": bundle["project"], "tasks": []}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, projects) = send(&app, "GET", "/projects", Some(&token), None).await?;
+    assert_eq!(projects.as_array().map(Vec::len), Some(1), "rejected imports must not leave projects behind");
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn members_can_work_on_a_shared_project_but_not_delete_it() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Owner", "email": "owner-share@example.com", "password": "password123"}))).await?;
+    let owner_token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, member) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Member", "email": "member-share@example.com", "password": "password123"}))).await?;
+    let member_token = member["token"].as_str().context("missing token")?.to_string();
+    let member_id = member["user"]["id"].as_str().context("missing user id")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&owner_token), Some(json!({"name": "Shared"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let project_uri = format!("/projects/{}", project_id);
+    let members_uri = format!("{}/members", project_uri);
+
+    // Not shared yet
+    let (status, _) = send(&app, "GET", &project_uri, Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&app, "POST", &format!("{}/tasks", project_uri), Some(&member_token), Some(json!({"title": "Sneaky"}))).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Only the owner manages membership
+    let (status, _) = send(&app, "POST", &members_uri, Some(&member_token), Some(json!({"user_id": member_id}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, added) = send(&app, "POST", &members_uri, Some(&owner_token), Some(json!({"user_id": member_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", added);
+    assert_eq!(added["role"], "member");
+    let (status, _) = send(&app, "POST", &members_uri, Some(&owner_token), Some(json!({"user_id": member_id}))).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(&app, "POST", &members_uri, Some(&owner_token), Some(json!({"user_id": member_id, "role": "admin"}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "POST", &members_uri, Some(&member_token), Some(json!({"user_id": owner["user"]["id"]}))).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // The member sees the project and can work on tasks, progress and the plan
+    let (status, listed) = send(&app, "GET", "/projects", Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(listed.as_array().context("expected array")?.iter().any(|p| p["id"] == project["id"]));
+    let (status, _) = send(&app, "GET", &project_uri, Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, task) = send(&app, "POST", &format!("{}/tasks", project_uri), Some(&member_token), Some(json!({"title": "Member task"}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", task);
+    let task_uri = format!("{}/tasks/{}", project_uri, task["id"].as_str().context("missing task id")?);
+    let (status, _) = send(&app, "GET", &task_uri, Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "POST", &format!("{}/progress", task_uri), Some(&member_token), Some(json!({"progress": 30}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("{}/plan", project_uri), Some(&member_token), Some(json!([{"date": "2025-05-01T00:00:00Z", "planned_progress": 50}]))).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    // ...but cannot edit or delete the project itself
+    let (status, _) = send(&app, "PUT", &project_uri, Some(&member_token), Some(json!({"name": "Mine now"}))).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(&app, "DELETE", &project_uri, Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, members) = send(&app, "GET", &members_uri, Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(members.as_array().map(Vec::len), Some(1));
+
+    // Removing the member revokes access
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", members_uri, member_id), Some(&owner_token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "GET", &task_uri, Some(&member_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", members_uri, member_id), Some(&owner_token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // A deleted account cannot be added back
+    sqlx::query("UPDATE users SET deleted_at = CURRENT_TIMESTAMP WHERE email = 'member-share@example.com'").execute(&pool).await?;
+    let (status, _) = send(&app, "POST", &members_uri, Some(&owner_token), Some(json!({"user_id": member_id}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The owner can still delete it
+    let (status, _) = send(&app, "DELETE", &project_uri, Some(&owner_token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    Ok(())
+}
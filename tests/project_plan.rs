@@ -36,10 +36,7 @@ async fn test_project_plan_management(pool: SqlitePool) {
         .await
         .unwrap();
 
-    let jwt_config = jwt::JwtConfig {
-        secret: std::sync::Arc::new(b"test_secret".to_vec()),
-        exp_hours: 1,
-    };
+    let jwt_config = jwt::JwtConfig::new(b"test_secret".to_vec(), 1);
     let token = jwt_config.encode(user_id).unwrap();
 
     // 2. Create Plan (Update)
@@ -160,3 +157,261 @@ async fn test_project_plan_management(pool: SqlitePool) {
     let dashboard_plan = dashboard["plan"].as_array().unwrap();
     assert_eq!(dashboard_plan.len(), 0);
 }
+
+#[sqlx::test]
+async fn test_project_plan_rejects_too_many_points(pool: SqlitePool) {
+    std::env::set_var("JWT_SECRET", "test_secret");
+    let app = app::create_app(pool.clone()).await.unwrap();
+
+    let user_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let now = chrono::Utc::now();
+
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Test User', 'cap@example.com', 'hash', 'local', ?, ?)")
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("INSERT INTO projects (id, user_id, name, theme_color, created_at, updated_at) VALUES (?, ?, 'Capped Project', '#000000', ?, ?)")
+        .bind(project_id)
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let jwt_config = jwt::JwtConfig::new(b"test_secret".to_vec(), 1);
+    let token = jwt_config.encode(user_id).unwrap();
+
+    // One point over the default cap of 1000
+    let start = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let points: Vec<Value> = (0..1001)
+        .map(|i| {
+            let date = start + chrono::Duration::days(i);
+            json!({ "date": format!("{}T00:00:00Z", date), "planned_progress": (i % 101) as i32 })
+        })
+        .collect();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/projects/{}/plan", project_id))
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(Value::Array(points).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM project_plan WHERE project_id = ?")
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[sqlx::test]
+async fn test_project_plan_rejects_duplicate_dates(pool: SqlitePool) {
+    std::env::set_var("JWT_SECRET", "test_secret");
+    let app = app::create_app(pool.clone()).await.unwrap();
+
+    let user_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let now = chrono::Utc::now();
+
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Test User', 'dupe@example.com', 'hash', 'local', ?, ?)")
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("INSERT INTO projects (id, user_id, name, theme_color, created_at, updated_at) VALUES (?, ?, 'Dated Project', '#000000', ?, ?)")
+        .bind(project_id)
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let jwt_config = jwt::JwtConfig::new(b"test_secret".to_vec(), 1);
+    let token = jwt_config.encode(user_id).unwrap();
+
+    // Same day once normalized to midnight
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/projects/{}/plan", project_id))
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!([
+                        { "date": "2025-12-01T00:00:00Z", "planned_progress": 10 },
+                        { "date": "2025-12-01T15:30:00Z", "planned_progress": 20 }
+                    ])
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: Value = serde_json::from_slice(&body).unwrap();
+    assert!(error["message"].as_str().unwrap().contains("2025-12-01"), "{}", error);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM project_plan WHERE project_id = ?")
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    // Distinct days are stored at midnight, in date order
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/projects/{}/plan", project_id))
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!([
+                        { "date": "2025-12-02T09:00:00Z", "planned_progress": 40 },
+                        { "date": "2025-12-01T15:30:00Z", "planned_progress": 20 }
+                    ])
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let plan: Value = serde_json::from_slice(&body).unwrap();
+    let plan = plan.as_array().unwrap();
+    assert_eq!(plan.len(), 2);
+    let first = chrono::DateTime::parse_from_rfc3339(plan[0]["date"].as_str().unwrap()).unwrap();
+    assert_eq!(first, chrono::DateTime::parse_from_rfc3339("2025-12-01T00:00:00Z").unwrap());
+    assert_eq!(plan[0]["planned_progress"], 20);
+    assert_eq!(plan[1]["planned_progress"], 40);
+}
+
+#[sqlx::test]
+async fn test_project_plan_enforce_monotonic(pool: SqlitePool) {
+    std::env::set_var("JWT_SECRET", "test_secret");
+    let app = app::create_app(pool.clone()).await.unwrap();
+
+    let user_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let now = chrono::Utc::now();
+
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Test User', 'mono@example.com', 'hash', 'local', ?, ?)")
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("INSERT INTO projects (id, user_id, name, theme_color, created_at, updated_at) VALUES (?, ?, 'Curve Project', '#000000', ?, ?)")
+        .bind(project_id)
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let jwt_config = jwt::JwtConfig::new(b"test_secret".to_vec(), 1);
+    let token = jwt_config.encode(user_id).unwrap();
+
+    // Sent out of order; after sorting, progress drops on 2025-03-01
+    let dipping = json!([
+        { "date": "2025-03-01T00:00:00Z", "planned_progress": 30 },
+        { "date": "2025-01-01T00:00:00Z", "planned_progress": 10 },
+        { "date": "2025-02-01T00:00:00Z", "planned_progress": 50 }
+    ]);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/projects/{}/plan?enforce_monotonic=true", project_id))
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(dipping.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: Value = serde_json::from_slice(&body).unwrap();
+    assert!(error["message"].as_str().unwrap().contains("2025-03-01"), "{}", error);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM project_plan WHERE project_id = ?")
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    // Without the flag the same plan is accepted
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/projects/{}/plan", project_id))
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(dipping.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // A non-decreasing plan (equal steps included) passes with the flag
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/projects/{}/plan?enforce_monotonic=true", project_id))
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!([
+                        { "date": "2025-01-01T00:00:00Z", "planned_progress": 10 },
+                        { "date": "2025-02-01T00:00:00Z", "planned_progress": 50 },
+                        { "date": "2025-03-01T00:00:00Z", "planned_progress": 50 }
+                    ])
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
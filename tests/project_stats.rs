@@ -0,0 +1,47 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn project_stats_can_roll_subtasks_into_parents() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Stats", "email": "stats@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Rollup"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, parent) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Parent", "progress": 0}))).await?;
+    let parent_id = parent["id"].as_str().context("missing parent id")?.to_string();
+    let (status, _) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Child A", "parent_id": parent_id, "progress": 20, "status": "in_progress"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Child B", "parent_id": parent_id, "progress": 80, "status": "done"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Flat: every task counts on its own
+    let (status, flat) = send(&app, "GET", &format!("/projects/{}/stats", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(flat["total_tasks"].as_i64(), Some(3));
+    let flat_avg = flat["average_progress"].as_f64().context("missing average")?;
+    assert!((flat_avg - 100.0 / 3.0).abs() < 1e-9);
+
+    // Rolled up: the parent carries the mean of its children
+    let (status, rolled) = send(&app, "GET", &format!("/projects/{}/stats?include_subtasks=true", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(rolled["total_tasks"].as_i64(), Some(1));
+    assert_eq!(rolled["average_progress"].as_f64(), Some(50.0));
+    assert_eq!(rolled["completed_tasks"].as_i64(), Some(0));
+
+    Ok(())
+}
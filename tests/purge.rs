@@ -0,0 +1,87 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::SqlitePool;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+async fn count(pool: &SqlitePool, table: &str) -> Result<i64> {
+    Ok(sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table)).fetch_one(pool).await?)
+}
+
+#[tokio::test]
+async fn purging_requires_confirmation_and_a_soft_deleted_row() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Owner", "email": "owner@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, member) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Member", "email": "member@example.com", "password": "password123"}))).await?;
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Doomed"}))).await?;
+    let project_uri = format!("/projects/{}", project["id"].as_str().context("missing project id")?);
+    let (_, t1) = send(&app, "POST", &format!("{}/tasks", project_uri), Some(&token), Some(json!({"title": "Parent"}))).await?;
+    let (_, t2) = send(&app, "POST", &format!("{}/tasks", project_uri), Some(&token), Some(json!({"title": "Child", "parent_id": t1["id"]}))).await?;
+    let (_, t3) = send(&app, "POST", &format!("{}/tasks", project_uri), Some(&token), Some(json!({"title": "Other"}))).await?;
+    let (status, _) = send(&app, "POST", &format!("{}/tasks/{}/progress", project_uri, t2["id"].as_str().unwrap_or_default()), Some(&token), Some(json!({"progress": 40}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("{}/dependencies", project_uri), Some(&token), Some(json!({"source_task_id": t2["id"], "target_task_id": t3["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("{}/plan", project_uri), Some(&token), Some(json!([{"date": "2025-01-01T00:00:00Z", "planned_progress": 10}]))).await?;
+    assert!(status.is_success());
+    let (status, _) = send(&app, "POST", &format!("{}/members", project_uri), Some(&token), Some(json!({"user_id": member["user"]["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Task purge: only from the trash, and never taking live subtasks with it
+    let parent_uri = format!("{}/tasks/{}", project_uri, t1["id"].as_str().unwrap_or_default());
+    let (status, _) = send(&app, "DELETE", &format!("{}/purge?confirm=true", parent_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+    send(&app, "DELETE", &parent_uri, Some(&token), None).await?;
+    let (status, _) = send(&app, "DELETE", &format!("{}/purge?confirm=true", parent_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::CONFLICT, "live child blocks the purge");
+
+    let child_uri = format!("{}/tasks/{}", project_uri, t2["id"].as_str().unwrap_or_default());
+    send(&app, "DELETE", &child_uri, Some(&token), None).await?;
+    let (status, _) = send(&app, "DELETE", &format!("{}/purge", parent_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "confirmation is required");
+    let (status, body) = send(&app, "DELETE", &format!("{}/purge?confirm=true", parent_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{}", body);
+    assert_eq!(count(&pool, "tasks").await?, 1);
+    assert_eq!(count(&pool, "task_progress").await?, 0);
+    assert_eq!(count(&pool, "task_dependencies").await?, 0);
+
+    // Project purge
+    let (status, _) = send(&app, "DELETE", &format!("{}/purge?confirm=true", project_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::CONFLICT, "live projects cannot be purged");
+    let (status, _) = send(&app, "DELETE", &project_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "DELETE", &format!("{}/purge?confirm=false", project_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, body) = send(&app, "DELETE", &format!("{}/purge?confirm=true", project_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT, "{}", body);
+
+    for table in ["projects", "tasks", "task_progress", "task_dependencies", "project_plan", "project_members", "idempotency_keys"] {
+        assert_eq!(count(&pool, table).await?, 0, "{} should be empty", table);
+    }
+    let (status, _) = send(&app, "DELETE", &format!("{}/purge?confirm=true", project_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let mut severity = None;
+    for _ in 0..15 {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        severity = sqlx::query_scalar::<_, String>("SELECT severity FROM activity_log WHERE event_name = 'project.purged'")
+            .fetch_optional(&pool)
+            .await?;
+        if severity.is_some() {
+            break;
+        }
+    }
+    assert_eq!(severity.as_deref(), Some("critical"));
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use anyhow::Result;
+use sqlx::{Row, SqlitePool};
+use uuid::Uuid;
+
+use common::migrated_pool;
+
+async fn plan(pool: &SqlitePool, sql: &str, id: Uuid) -> Result<String> {
+    let rows = sqlx::query(&format!("EXPLAIN QUERY PLAN {}", sql)).bind(id).fetch_all(pool).await?;
+    Ok(rows.iter().map(|row| row.get::<String, _>("detail")).collect::<Vec<_>>().join("\n"))
+}
+
+#[tokio::test]
+async fn hot_queries_use_composite_indexes() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    let id = Uuid::new_v4();
+
+    let dashboard = plan(
+        &pool,
+        "SELECT DATE(created_at) as date, CAST(ROUND(AVG(progress)) AS INTEGER) as actual FROM task_progress WHERE project_id = ? AND deleted_at IS NULL GROUP BY DATE(created_at) ORDER BY DATE(created_at) ASC",
+        id,
+    )
+    .await?;
+    assert!(dashboard.contains("idx_task_progress_project_created"), "{}", dashboard);
+
+    let progress = plan(
+        &pool,
+        "SELECT id, progress FROM task_progress WHERE task_id = ? AND deleted_at IS NULL ORDER BY created_at DESC",
+        id,
+    )
+    .await?;
+    assert!(progress.contains("idx_task_progress_task_deleted_created"), "{}", progress);
+    assert!(!progress.contains("TEMP B-TREE"), "ORDER BY should come from the index: {}", progress);
+
+    let tasks = plan(
+        &pool,
+        "SELECT id, title FROM tasks WHERE project_id = ? AND deleted_at IS NULL ORDER BY start_date",
+        id,
+    )
+    .await?;
+    assert!(tasks.contains("idx_tasks_project_deleted_start"), "{}", tasks);
+    assert!(!tasks.contains("TEMP B-TREE"), "ORDER BY should come from the index: {}", tasks);
+    Ok(())
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn get_or_create_returns_existing_role_and_permission() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Provisioner", "email": "provision@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (status, role) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "ops"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // plain create of the same name conflicts
+    let (status, _) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "ops"}))).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, again) = send(&app, "POST", "/rbac/roles?get_or_create=true", Some(&token), Some(json!({"name": "ops"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(again["id"], role["id"]);
+
+    let (status, perm) = send(&app, "POST", "/rbac/permissions", Some(&token), Some(json!({"name": "ops.deploy"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, perm_again) = send(&app, "POST", "/rbac/permissions?get_or_create=true", Some(&token), Some(json!({"name": "ops.deploy"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(perm_again["id"], perm["id"]);
+
+    let roles: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM roles WHERE name = 'ops'").fetch_one(&pool).await?;
+    assert_eq!(roles, 1);
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+
+use common::{migrated_pool, send_request};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn role_permissions_listing_is_paginated() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let register_body = json!({"name": "Page User", "email": "page_user@example.com", "password": "password123"});
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/register")
+        .header("content-type", "application/json")
+        .body(Body::from(register_body.to_string()))?;
+    let (status, _, auth_res) = send_request(&app, req).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res.get("token").and_then(|v| v.as_str()).context("missing token")?.to_string();
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/rbac/roles")
+        .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::from(json!({"name": "paged_role"}).to_string()))?;
+    let (status, _, role) = send_request(&app, req).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let role_id = role.get("id").and_then(|v| v.as_str()).context("missing role id")?.to_string();
+
+    // Create and assign 12 permissions named so that ORDER BY name is predictable
+    for i in 0..12 {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/rbac/permissions")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::from(json!({"name": format!("paged.perm.{:02}", i)}).to_string()))?;
+        let (status, _, perm) = send_request(&app, req).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let perm_id = perm.get("id").and_then(|v| v.as_str()).context("missing permission id")?.to_string();
+
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("/rbac/roles/{}/permissions", role_id))
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::from(json!({"permission_id": perm_id}).to_string()))?;
+        let (status, _, _) = send_request(&app, req).await?;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let req = Request::builder()
+        .method("GET")
+        .uri(format!("/rbac/roles/{}/permissions?limit=5&offset=5", role_id))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())?;
+    let (status, _, page) = send_request(&app, req).await?;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = page
+        .as_array()
+        .context("expected array")?
+        .iter()
+        .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
+        .collect();
+    assert_eq!(names, vec!["paged.perm.05", "paged.perm.06", "paged.perm.07", "paged.perm.08", "paged.perm.09"]);
+
+    // Without parameters the default limit covers all 12 assignments
+    let req = Request::builder()
+        .method("GET")
+        .uri(format!("/rbac/roles/{}/permissions", role_id))
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::empty())?;
+    let (status, _, all) = send_request(&app, req).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(all.as_array().map(|a| a.len()), Some(12));
+
+    Ok(())
+}
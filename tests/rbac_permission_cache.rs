@@ -0,0 +1,55 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use uuid::Uuid;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn effective_permissions_reflect_rbac_changes_immediately() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let user_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Cached', 'cached@example.com', 'hash', 'local', datetime('now'), datetime('now'))")
+        .bind(user_id.to_string())
+        .execute(&pool)
+        .await?;
+    let jwt = s_curve::jwt::JwtConfig::new(b"test-secret".to_vec(), 1);
+    let token = jwt.encode(user_id)?;
+    let effective_uri = format!("/rbac/users/{}/effective-permissions", user_id);
+
+    let (_, role) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "auditor"}))).await?;
+    let role_id = role["id"].as_str().context("missing role id")?.to_string();
+    let (_, perm) = send(&app, "POST", "/rbac/permissions", Some(&token), Some(json!({"name": "audit.read"}))).await?;
+    let perm_id = perm["id"].as_str().context("missing permission id")?.to_string();
+
+    // Prime the cache with an empty result
+    let (status, before) = send(&app, "GET", &effective_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(before["roles"].as_array().map(|r| r.len()), Some(0));
+
+    let (status, _) = send(&app, "POST", &format!("/rbac/users/{}/roles", user_id), Some(&token), Some(json!({"role_id": role_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (_, assigned) = send(&app, "GET", &effective_uri, Some(&token), None).await?;
+    assert_eq!(assigned["roles"], json!(["auditor"]));
+
+    // Role-level change is visible to users holding the role
+    let (status, _) = send(&app, "POST", &format!("/rbac/roles/{}/permissions", role_id), Some(&token), Some(json!({"permission_id": perm_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (_, with_perm) = send(&app, "GET", &effective_uri, Some(&token), None).await?;
+    assert_eq!(with_perm["permissions"][0]["name"], "audit.read");
+
+    let (status, _) = send(&app, "DELETE", &format!("/rbac/users/{}/roles/{}", user_id, role_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, revoked) = send(&app, "GET", &effective_uri, Some(&token), None).await?;
+    assert_eq!(revoked["roles"].as_array().map(|r| r.len()), Some(0));
+    assert_eq!(revoked["permissions"].as_array().map(|p| p.len()), Some(0));
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use uuid::Uuid;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn delete_permission_cleans_up_grants() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    // Admin user stored with a text id so RBAC join rows satisfy their FKs
+    let admin_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Admin', 'perm_admin@example.com', 'hash', 'local', datetime('now'), datetime('now'))")
+        .bind(admin_id.to_string())
+        .execute(&pool)
+        .await?;
+    let jwt = s_curve::jwt::JwtConfig::new(b"test-secret".to_vec(), 1);
+    let token = jwt.encode(admin_id)?;
+
+    let (status, perm) = send(&app, "POST", "/rbac/permissions", Some(&token), Some(json!({"name": "report.export"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let perm_id = perm["id"].as_str().context("missing permission id")?.to_string();
+
+    let (status, role) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "exporter"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let role_id = role["id"].as_str().context("missing role id")?.to_string();
+
+    let (status, _) = send(&app, "POST", &format!("/rbac/roles/{}/permissions", role_id), Some(&token), Some(json!({"permission_id": perm_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("/rbac/users/{}/roles", admin_id), Some(&token), Some(json!({"role_id": role_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send(&app, "POST", &format!("/rbac/users/{}/permissions", admin_id), Some(&token), Some(json!({"permission_id": perm_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = send(&app, "DELETE", &format!("/rbac/permissions/{}", perm_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let role_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM role_permissions WHERE permission_id = ?")
+        .bind(&perm_id)
+        .fetch_one(&pool)
+        .await?;
+    let user_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM user_permissions WHERE permission_id = ?")
+        .bind(&perm_id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!((role_rows, user_rows), (0, 0));
+
+    let (status, effective) = send(&app, "GET", &format!("/rbac/users/{}/effective-permissions", admin_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = effective["permissions"]
+        .as_array()
+        .context("missing permissions")?
+        .iter()
+        .filter_map(|p| p["name"].as_str())
+        .collect();
+    assert!(!names.contains(&"report.export"));
+
+    let (status, _) = send(&app, "DELETE", &format!("/rbac/permissions/{}", perm_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use uuid::Uuid;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn permission_holders_union_direct_and_role_grants() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let mut users = Vec::new();
+    for name in ["both", "role-only", "direct-only", "nobody"] {
+        let id = Uuid::new_v4();
+        sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, ?, ?, 'hash', 'local', datetime('now'), datetime('now'))")
+            .bind(id.to_string())
+            .bind(name)
+            .bind(format!("{}@example.com", name))
+            .execute(&pool)
+            .await?;
+        users.push(id);
+    }
+    let (both, role_only, direct_only, nobody) = (users[0], users[1], users[2], users[3]);
+    let jwt = s_curve::jwt::JwtConfig::new(b"test-secret".to_vec(), 1);
+    let token = jwt.encode(both)?;
+
+    let (_, perm) = send(&app, "POST", "/rbac/permissions", Some(&token), Some(json!({"name": "audit.read"}))).await?;
+    let perm_id = perm["id"].as_str().context("missing permission id")?.to_string();
+    let mut role_ids = Vec::new();
+    for name in ["auditor", "compliance"] {
+        let (_, role) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": name}))).await?;
+        let role_id = role["id"].as_str().context("missing role id")?.to_string();
+        send(&app, "POST", &format!("/rbac/roles/{}/permissions", role_id), Some(&token), Some(json!({"permission_id": perm_id}))).await?;
+        role_ids.push(role_id);
+    }
+
+    // "both" holds it through two roles and a scoped direct grant
+    for role_id in &role_ids {
+        let (status, _) = send(&app, "POST", &format!("/rbac/users/{}/roles", both), Some(&token), Some(json!({"role_id": role_id}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    let project_id = Uuid::new_v4();
+    send(&app, "POST", &format!("/rbac/users/{}/permissions", both), Some(&token), Some(json!({"permission_id": perm_id, "scope": {"project_id": project_id}}))).await?;
+    send(&app, "POST", &format!("/rbac/users/{}/roles", role_only), Some(&token), Some(json!({"role_id": role_ids[0]}))).await?;
+    send(&app, "POST", &format!("/rbac/users/{}/permissions", direct_only), Some(&token), Some(json!({"permission_id": perm_id}))).await?;
+    // Expired grants do not count
+    send(&app, "POST", &format!("/rbac/users/{}/permissions", nobody), Some(&token), Some(json!({"permission_id": perm_id, "expires_at": "2000-01-01T00:00:00Z"}))).await?;
+
+    let (status, holders) = send(&app, "GET", &format!("/rbac/permissions/{}/users", perm_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", holders);
+    let holders = holders.as_array().context("expected array")?;
+
+    let entries_for = |id: Uuid| holders.iter().filter(|h| h["user_id"] == id.to_string()).collect::<Vec<_>>();
+
+    let both_entries = entries_for(both);
+    assert_eq!(both_entries.len(), 2, "once per source: {:?}", both_entries);
+    assert_eq!(both_entries[0]["source"], "direct");
+    assert_eq!(both_entries[0]["scopes"], json!([{"project_id": project_id}]));
+    assert_eq!(both_entries[1]["source"], "role");
+    assert_eq!(both_entries[1]["role_names"], json!(["auditor", "compliance"]));
+
+    let role_entries = entries_for(role_only);
+    assert_eq!(role_entries.len(), 1);
+    assert_eq!(role_entries[0]["role_names"], json!(["auditor"]));
+    assert!(role_entries[0].get("scopes").is_none());
+
+    let direct_entries = entries_for(direct_only);
+    assert_eq!(direct_entries.len(), 1);
+    assert_eq!(direct_entries[0]["source"], "direct");
+    assert!(direct_entries[0].get("role_names").is_none());
+
+    assert!(entries_for(nobody).is_empty());
+
+    let (status, _) = send(&app, "GET", &format!("/rbac/permissions/{}/users", Uuid::new_v4()), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+
+use common::{migrated_pool, send_request};
+use s_curve::create_app;
+
+fn json_req(method: &str, uri: &str, token: &str, body: serde_json::Value) -> Result<Request<Body>> {
+    Ok(Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("authorization", format!("Bearer {}", token))
+        .body(Body::from(body.to_string()))?)
+}
+
+#[tokio::test]
+async fn update_role_applies_partial_changes_and_rejects_duplicate_names() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/register")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"name": "Role Admin", "email": "role_admin@example.com", "password": "password123"}).to_string()))?;
+    let (status, _, auth_res) = send_request(&app, req).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res.get("token").and_then(|v| v.as_str()).context("missing token")?.to_string();
+
+    let (status, role) = send(&app, json_req("POST", "/rbac/roles", &token, json!({"name": "reviewer", "description": "Reviews"}))?).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let role_id = role.get("id").and_then(|v| v.as_str()).context("missing role id")?.to_string();
+
+    let (status, _) = send(&app, json_req("POST", "/rbac/roles", &token, json!({"name": "auditor"}))?).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // description-only update keeps the existing name
+    let (status, updated) = send(&app, json_req("PUT", &format!("/rbac/roles/{}", role_id), &token, json!({"description": "Reviews everything"}))?).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["name"], "reviewer");
+    assert_eq!(updated["description"], "Reviews everything");
+
+    // renaming onto an existing role name conflicts
+    let (status, _) = send(&app, json_req("PUT", &format!("/rbac/roles/{}", role_id), &token, json!({"name": "auditor"}))?).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, renamed) = send(&app, json_req("PUT", &format!("/rbac/roles/{}", role_id), &token, json!({"name": "senior_reviewer"}))?).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(renamed["name"], "senior_reviewer");
+    assert_eq!(renamed["description"], "Reviews everything");
+
+    let (status, _) = send(&app, json_req("PUT", &format!("/rbac/roles/{}", uuid::Uuid::new_v4()), &token, json!({"name": "ghost"}))?).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn replacing_user_roles_swaps_the_whole_set() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let user_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Roles', 'roles@example.com', 'hash', 'local', datetime('now'), datetime('now'))")
+        .bind(user_id.to_string())
+        .execute(&pool)
+        .await?;
+    let jwt = s_curve::jwt::JwtConfig::new(b"test-secret".to_vec(), 1);
+    let token = jwt.encode(user_id)?;
+    let roles_uri = format!("/rbac/users/{}/roles", user_id);
+    let effective_uri = format!("/rbac/users/{}/effective-permissions", user_id);
+
+    let (_, auditor) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "auditor"}))).await?;
+    let (_, planner) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "planner"}))).await?;
+    let (_, reviewer) = send(&app, "POST", "/rbac/roles", Some(&token), Some(json!({"name": "reviewer"}))).await?;
+
+    let (status, _) = send(&app, "POST", &roles_uri, Some(&token), Some(json!({"role_id": reviewer["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    // Prime the permission cache with the old role set
+    let (_, before) = send(&app, "GET", &effective_uri, Some(&token), None).await?;
+    assert_eq!(before["roles"], json!(["reviewer"]));
+
+    let (status, roles) = send(&app, "PUT", &roles_uri, Some(&token), Some(json!({"role_ids": [planner["id"], auditor["id"], planner["id"]]}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", roles);
+    let names: Vec<&str> = roles.as_array().context("expected roles")?.iter().filter_map(|r| r["name"].as_str()).collect();
+    assert_eq!(names, vec!["auditor", "planner"]);
+
+    let (_, after) = send(&app, "GET", &effective_uri, Some(&token), None).await?;
+    let mut effective: Vec<&str> = after["roles"].as_array().context("expected roles")?.iter().filter_map(Value::as_str).collect();
+    effective.sort();
+    assert_eq!(effective, vec!["auditor", "planner"]);
+
+    let mut events = 0i64;
+    for _ in 0..15 {
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        events = sqlx::query_scalar("SELECT COUNT(*) FROM activity_log WHERE event_name = 'user_role.replaced'")
+            .fetch_one(&pool)
+            .await?;
+        if events > 0 {
+            break;
+        }
+    }
+    assert_eq!(events, 1);
+    let severity: String = sqlx::query_scalar("SELECT severity FROM activity_log WHERE event_name = 'user_role.replaced'")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(severity, "critical");
+
+    // An unknown role rejects the whole request
+    let (status, _) = send(&app, "PUT", &roles_uri, Some(&token), Some(json!({"role_ids": [auditor["id"], Uuid::new_v4()]}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, unchanged) = send(&app, "GET", &roles_uri, Some(&token), None).await?;
+    assert_eq!(unchanged.as_array().map(|r| r.len()), Some(2));
+
+    let (status, roles) = send(&app, "PUT", &roles_uri, Some(&token), Some(json!({"role_ids": []}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(roles, json!([]));
+    let (_, cleared) = send(&app, "GET", &effective_uri, Some(&token), None).await?;
+    assert_eq!(cleared["roles"].as_array().map(|r| r.len()), Some(0));
+
+    Ok(())
+}
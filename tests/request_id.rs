@@ -0,0 +1,48 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body::{self, Body};
+use axum::http::{Request, StatusCode};
+use serde_json::{json, Value};
+use tower::util::ServiceExt; // for `oneshot`
+
+use common::migrated_pool;
+use s_curve::create_app;
+
+#[tokio::test]
+async fn request_id_is_echoed_and_included_in_error_bodies() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/auth/register")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"name": "Rid", "email": "rid@example.com", "password": "password123"}).to_string()))?;
+    let resp = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    // A generated id is returned even when the client sent none
+    let generated = resp.headers().get("x-request-id").and_then(|v| v.to_str().ok()).context("missing x-request-id")?;
+    assert!(uuid::Uuid::parse_str(generated).is_ok());
+    let bytes = body::to_bytes(resp.into_body(), 10_485_760).await?;
+    let auth: Value = serde_json::from_slice(&bytes)?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+
+    // Client-supplied id is propagated into the error body
+    let req = Request::builder()
+        .method("GET")
+        .uri(format!("/projects/{}", uuid::Uuid::new_v4()))
+        .header("authorization", format!("Bearer {}", token))
+        .header("x-request-id", "trace-me-123")
+        .body(Body::empty())?;
+    let resp = app.clone().oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(resp.headers().get("x-request-id").and_then(|v| v.to_str().ok()), Some("trace-me-123"));
+    let bytes = body::to_bytes(resp.into_body(), 10_485_760).await?;
+    let error: Value = serde_json::from_slice(&bytes)?;
+    assert_eq!(error["request_id"], "trace-me-123");
+
+    Ok(())
+}
@@ -55,7 +55,7 @@ async fn parse_progress_row_text_uuid() {
 async fn parse_task_row_text_uuid() {
     let pool = setup_pool().await;
     sqlx::query(
-        "CREATE TABLE tasks (id TEXT, project_id TEXT, title TEXT, status TEXT, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER, created_at TEXT, updated_at TEXT, deleted_at TEXT)",
+        "CREATE TABLE tasks (id TEXT, project_id TEXT, title TEXT, status TEXT, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, estimated_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER, created_at TEXT, updated_at TEXT, deleted_at TEXT)",
     )
     .execute(&pool)
     .await
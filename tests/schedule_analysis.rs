@@ -0,0 +1,73 @@
+mod common;
+
+use anyhow::Context;
+use uuid::Uuid;
+
+use common::migrated_pool;
+
+#[tokio::test]
+async fn test_schedule_analysis_forward_and_backward_pass() -> anyhow::Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    // Setup Data
+    let user_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let a = Uuid::new_v4();
+    let b = Uuid::new_v4();
+    let c = Uuid::new_v4();
+    let d = Uuid::new_v4();
+
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'T', 'sched@example.com', 'hash', 'local', datetime('now'), datetime('now'))")
+        .bind(user_id).execute(&pool).await?;
+
+    sqlx::query("INSERT INTO projects (id, user_id, name, theme_color, created_at, updated_at) VALUES (?, ?, 'P', '#000', datetime('now'), datetime('now'))")
+        .bind(project_id).bind(user_id).execute(&pool).await?;
+
+    for (id, title, dur) in [(a, "A", 2i64), (b, "B", 3), (c, "C", 1), (d, "D", 1)] {
+        sqlx::query("INSERT INTO tasks (id, project_id, title, status, duration_days, created_at, updated_at) VALUES (?, ?, ?, 'todo', ?, datetime('now'), datetime('now'))")
+            .bind(id).bind(project_id).bind(title).bind(dur).execute(&pool).await?;
+    }
+
+    // Dependencies: A->B, A->C, B->D, C->D
+    for (src, tgt) in [(a, b), (a, c), (b, d), (c, d)] {
+        sqlx::query("INSERT INTO task_dependencies (id, source_task_id, target_task_id, created_at) VALUES (?, ?, ?, datetime('now'))")
+            .bind(Uuid::new_v4()).bind(src).bind(tgt).execute(&pool).await?;
+    }
+
+    use s_curve::app::AppState;
+    use s_curve::routes::projects::get_project_schedule_analysis;
+    use s_curve::jwt::{JwtConfig, AuthUser};
+    use axum::extract::{State as AxState, Path as AxPath};
+
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
+    let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
+    let app_state = AppState::new(pool.clone(), jwt, event_bus);
+    let auth = AuthUser { user_id, roles: Vec::new() };
+
+    let res = get_project_schedule_analysis(AxState(app_state), auth, AxPath(project_id)).await?;
+    let analysis = res.0;
+
+    // Hand-computed:
+    //   A: ES 0 EF 2 LS 0 LF 2 slack 0
+    //   B: ES 2 EF 5 LS 2 LF 5 slack 0
+    //   C: ES 2 EF 3 LS 4 LF 5 slack 2
+    //   D: ES 5 EF 6 LS 5 LF 6 slack 0
+    assert_eq!(analysis.project_duration, 6);
+    assert_eq!(analysis.tasks.len(), 4);
+
+    let expected = [
+        (a, 0, 2, 0, 2, 0),
+        (b, 2, 5, 2, 5, 0),
+        (c, 2, 3, 4, 5, 2),
+        (d, 5, 6, 5, 6, 0),
+    ];
+    for (id, es, ef, ls, lf, slack) in expected {
+        let t = analysis.tasks.iter().find(|t| t.task_id == id).context("task missing from analysis")?;
+        assert_eq!((t.early_start, t.early_finish), (es, ef), "forward pass mismatch for {}", id);
+        assert_eq!((t.late_start, t.late_finish), (ls, lf), "backward pass mismatch for {}", id);
+        assert_eq!(t.slack, slack, "slack mismatch for {}", id);
+        assert_eq!(t.is_critical, slack == 0);
+    }
+
+    Ok(())
+}
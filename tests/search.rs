@@ -0,0 +1,80 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+/// `(kind, title)` pairs in response order.
+fn hits(results: &Value) -> Vec<(String, String)> {
+    results
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .map(|r| (r["kind"].as_str().unwrap_or_default().to_string(), r["title"].as_str().unwrap_or_default().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn search_covers_accessible_live_projects_and_tasks_only() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, alice) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Alice", "email": "alice-search@example.com", "password": "password123"}))).await?;
+    let alice_token = alice["token"].as_str().context("missing token")?.to_string();
+    let (_, bob) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Bob", "email": "bob-search@example.com", "password": "password123"}))).await?;
+    let bob_token = bob["token"].as_str().context("missing token")?.to_string();
+    let bob_id = bob["user"]["id"].as_str().context("missing user id")?.to_string();
+
+    let (_, website) = send(&app, "POST", "/projects", Some(&alice_token), Some(json!({"name": "Website", "description": "Marketing REDESIGN"}))).await?;
+    let website_id = website["id"].as_str().context("missing project id")?.to_string();
+    let (_, task) = send(&app, "POST", &format!("/projects/{}/tasks", website_id), Some(&alice_token), Some(json!({"title": "Redesign header"}))).await?;
+    let task_id = task["id"].as_str().context("missing task id")?.to_string();
+    let (_, gone) = send(&app, "POST", &format!("/projects/{}/tasks", website_id), Some(&alice_token), Some(json!({"title": "Redesign footer"}))).await?;
+    let (status, _) = send(&app, "DELETE", &format!("/projects/{}/tasks/{}", website_id, gone["id"].as_str().unwrap_or_default()), Some(&alice_token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, archived) = send(&app, "POST", "/projects", Some(&alice_token), Some(json!({"name": "Old redesign"}))).await?;
+    let archived_id = archived["id"].as_str().context("missing project id")?.to_string();
+    let (_, _) = send(&app, "POST", &format!("/projects/{}/tasks", archived_id), Some(&alice_token), Some(json!({"title": "Redesign logo"}))).await?;
+    let (status, _) = send(&app, "DELETE", &format!("/projects/{}", archived_id), Some(&alice_token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, _) = send(&app, "POST", "/projects", Some(&bob_token), Some(json!({"name": "Bob's redesign"}))).await?;
+
+    // Case-insensitive; projects (by name or description) come before tasks; deleted rows are skipped
+    let (status, results) = send(&app, "GET", "/search?q=ReDeSiGn", Some(&alice_token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", results);
+    assert_eq!(hits(&results), vec![("project".to_string(), "Website".to_string()), ("task".to_string(), "Redesign header".to_string())]);
+    assert_eq!(results[0]["id"].as_str(), Some(website_id.as_str()));
+    assert_eq!(results[0]["project_id"].as_str(), Some(website_id.as_str()));
+    assert_eq!(results[1]["id"].as_str(), Some(task_id.as_str()));
+    assert_eq!(results[1]["project_id"].as_str(), Some(website_id.as_str()));
+
+    // Bob only sees his own project until he is added to Alice's
+    let (_, results) = send(&app, "GET", "/search?q=redesign", Some(&bob_token), None).await?;
+    assert_eq!(hits(&results), vec![("project".to_string(), "Bob's redesign".to_string())]);
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/members", website_id), Some(&alice_token), Some(json!({"user_id": bob_id}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (_, results) = send(&app, "GET", "/search?q=redesign", Some(&bob_token), None).await?;
+    assert_eq!(results.as_array().map(Vec::len), Some(3));
+
+    // LIKE wildcards in the query are literal
+    let (_, results) = send(&app, "GET", "/search?q=%25%25", Some(&alice_token), None).await?;
+    assert_eq!(results, json!([]));
+
+    let (status, _) = send(&app, "GET", "/search?q=%20r%20", Some(&alice_token), None).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "GET", "/search?q=redesign", None, None).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
@@ -36,10 +36,7 @@ async fn test_batch_update_tasks(pool: SqlitePool) {
         .await
         .unwrap();
 
-    let jwt_config = jwt::JwtConfig {
-        secret: std::sync::Arc::new(b"test_secret".to_vec()),
-        exp_hours: 1,
-    };
+    let jwt_config = jwt::JwtConfig::new(b"test_secret".to_vec(), 1);
     let token = jwt_config.encode(user_id).unwrap();
 
     // 2. Create Two Tasks
@@ -64,7 +61,7 @@ async fn test_batch_update_tasks(pool: SqlitePool) {
         .await
         .unwrap();
 
-    // 3. Batch Update: Move both tasks to 'doing' and set progress
+    // 3. Batch Update: Move both tasks to 'in_progress' and set progress
     let response = app
         .clone()
         .oneshot(
@@ -78,12 +75,12 @@ async fn test_batch_update_tasks(pool: SqlitePool) {
                         "tasks": [
                             {
                                 "id": task1_id,
-                                "status": "doing",
+                                "status": "in_progress",
                                 "progress": 50
                             },
                             {
                                 "id": task2_id,
-                                "status": "doing",
+                                "status": "in_progress",
                                 "progress": 20
                             }
                         ]
@@ -103,9 +100,9 @@ async fn test_batch_update_tasks(pool: SqlitePool) {
     let t1 = tasks.iter().find(|t| t["id"].as_str().unwrap() == task1_id.to_string()).unwrap();
     let t2 = tasks.iter().find(|t| t["id"].as_str().unwrap() == task2_id.to_string()).unwrap();
 
-    assert_eq!(t1["status"], "doing");
+    assert_eq!(t1["status"], "in_progress");
     assert_eq!(t1["progress"], 50);
-    assert_eq!(t2["status"], "doing");
+    assert_eq!(t2["status"], "in_progress");
     assert_eq!(t2["progress"], 20);
 
     // 4. Test Transactional Failure (One valid, one invalid)
@@ -159,3 +156,84 @@ async fn test_batch_update_tasks(pool: SqlitePool) {
 
     assert_eq!(task3_status, "todo");
 }
+
+#[sqlx::test]
+async fn test_batch_update_preserves_submission_order(pool: SqlitePool) {
+    std::env::set_var("JWT_SECRET", "test_secret");
+    let app = app::create_app(pool.clone()).await.unwrap();
+
+    let user_id = Uuid::new_v4();
+    let project_id = Uuid::new_v4();
+    let now = chrono::Utc::now();
+
+    sqlx::query("INSERT INTO users (id, name, email, password_hash, provider, created_at, updated_at) VALUES (?, 'Order User', 'order@example.com', 'hash', 'local', ?, ?)")
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("INSERT INTO projects (id, user_id, name, theme_color, created_at, updated_at) VALUES (?, ?, 'Order Project', '#000000', ?, ?)")
+        .bind(project_id)
+        .bind(user_id)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let jwt_config = jwt::JwtConfig::new(b"test_secret".to_vec(), 1);
+    let token = jwt_config.encode(user_id).unwrap();
+
+    // Start dates run opposite to the order the tasks are submitted in
+    let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+    for (offset, id) in ids.iter().enumerate() {
+        let start = now + chrono::Duration::days(10 - offset as i64 * 3);
+        sqlx::query("INSERT INTO tasks (id, project_id, title, status, start_date, created_at, updated_at) VALUES (?, ?, ?, 'pending', ?, ?, ?)")
+            .bind(id)
+            .bind(project_id)
+            .bind(format!("Task {}", offset))
+            .bind(start)
+            .bind(now)
+            .bind(now)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/projects/{}/tasks/batch", project_id))
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "tasks": [
+                            {"id": ids[0], "progress": 10},
+                            {"id": ids[1], "title": "Renamed"},
+                            {"id": ids[2], "status": "done"}
+                        ]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let tasks: Vec<Value> = serde_json::from_slice(&body).unwrap();
+
+    let returned: Vec<Uuid> = tasks.iter().map(|t| t["id"].as_str().unwrap().parse().unwrap()).collect();
+    assert_eq!(returned, ids);
+
+    // The response reflects the stored rows
+    assert_eq!(tasks[0]["progress"], 10);
+    assert_eq!(tasks[1]["title"], "Renamed");
+    assert_eq!(tasks[2]["status"], "done");
+    assert!(tasks[0]["start_date"].is_string());
+}
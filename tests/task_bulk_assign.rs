@@ -0,0 +1,58 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn assigns_one_user_to_many_tasks() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, owner) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Lead", "email": "lead@example.com", "password": "password123"}))).await?;
+    let token = owner["token"].as_str().context("missing token")?.to_string();
+    let (_, worker) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Worker", "email": "worker@example.com", "password": "password123"}))).await?;
+    let worker_id = worker["user"]["id"].as_str().context("missing user id")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Crew"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let mut task_ids = Vec::new();
+    for title in ["Dig", "Pour", "Cure", "Inspect"] {
+        let (_, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": title}))).await?;
+        task_ids.push(task["id"].as_str().context("missing task id")?.to_string());
+    }
+
+    let (status, assigned) = send(&app, "POST", &format!("{}/assign", tasks_uri), Some(&token), Some(json!({"user_id": worker_id, "task_ids": &task_ids[..3]}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(assigned.as_array().map(|a| a.len()), Some(3));
+
+    let (_, tasks) = send(&app, "GET", &tasks_uri, Some(&token), None).await?;
+    for task in tasks.as_array().context("tasks should be an array")? {
+        let id = task["id"].as_str().unwrap_or_default().to_string();
+        if task_ids[..3].contains(&id) {
+            assert_eq!(task["assignee"].as_str(), Some(worker_id.as_str()));
+        } else {
+            assert!(task["assignee"].is_null());
+        }
+    }
+
+    // A task from another project aborts the whole call
+    let (_, other) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Elsewhere"}))).await?;
+    let (_, stray) = send(&app, "POST", &format!("/projects/{}/tasks", other["id"].as_str().context("missing project id")?), Some(&token), Some(json!({"title": "Stray"}))).await?;
+    let (status, _) = send(&app, "POST", &format!("{}/assign", tasks_uri), Some(&token), Some(json!({"user_id": worker_id, "task_ids": [task_ids[3], stray["id"]]}))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, untouched) = send(&app, "GET", &format!("{}/{}", tasks_uri, task_ids[3]), Some(&token), None).await?;
+    assert!(untouched["assignee"].is_null());
+
+    let (status, _) = send(&app, "POST", &format!("{}/assign", tasks_uri), Some(&token), Some(json!({"user_id": uuid::Uuid::new_v4(), "task_ids": [task_ids[3]]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
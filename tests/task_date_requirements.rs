@@ -0,0 +1,42 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn end_date_without_start_date_is_rejected_when_configured() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("REQUIRE_START_DATE_WITH_END_DATE", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Dates", "email": "dates@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Timeline"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let uri = format!("/projects/{}/tasks", project_id);
+
+    let (status, body) = send(&app, "POST", &uri, Some(&token), Some(json!({"title": "Only end", "end_date": "2025-03-10T00:00:00Z"}))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body.to_string().contains("start_date"));
+
+    let (status, task) = send(&app, "POST", &uri, Some(&token), Some(json!({"title": "Both", "start_date": "2025-03-01T00:00:00Z", "end_date": "2025-03-10T00:00:00Z"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Updating an undated task with only an end date is rejected as well
+    let (status, bare) = send(&app, "POST", &uri, Some(&token), Some(json!({"title": "Bare"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let bare_id = bare["id"].as_str().context("missing task id")?;
+    let (status, _) = send(&app, "PATCH", &format!("{}/{}", uri, bare_id), Some(&token), Some(json!({"end_date": "2025-03-10T00:00:00Z"}))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    assert!(task["id"].is_string());
+    Ok(())
+}
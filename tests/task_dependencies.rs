@@ -17,8 +17,15 @@ async fn test_task_dependencies() -> anyhow::Result<()> {
         id TEXT PRIMARY KEY, user_id TEXT NOT NULL, name TEXT NOT NULL, theme_color TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
 
+    sqlx::query("CREATE TABLE IF NOT EXISTS project_members (
+        project_id TEXT NOT NULL, user_id TEXT NOT NULL, role TEXT NOT NULL DEFAULT 'member', created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (project_id, user_id)
+    );").execute(&pool).await?;
+    sqlx::query("CREATE TABLE IF NOT EXISTS task_tags (
+        task_id TEXT NOT NULL, tag TEXT NOT NULL, created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY (task_id, tag)
+    );").execute(&pool).await?;
+
     sqlx::query("CREATE TABLE IF NOT EXISTS tasks (
-        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
+        id TEXT PRIMARY KEY, project_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL, due_date TEXT, start_date TEXT, end_date TEXT, duration_days INTEGER, estimated_days INTEGER, assignee TEXT, parent_id TEXT, progress INTEGER NOT NULL DEFAULT 0, created_at TEXT NOT NULL, updated_at TEXT NOT NULL, deleted_at TEXT
     );").execute(&pool).await?;
 
     sqlx::query("CREATE TABLE IF NOT EXISTS task_dependencies (
@@ -54,12 +61,13 @@ async fn test_task_dependencies() -> anyhow::Result<()> {
     use s_curve::models::dependency::DependencyCreateRequest;
     use s_curve::jwt::{JwtConfig, AuthUser};
     use axum::extract::{State as AxState, Path as AxPath};
-    use axum::Json as AxJson;
+    use s_curve::extract::AppJson as AxJson;
+    use axum::http::HeaderMap;
 
-    let jwt = JwtConfig { secret: std::sync::Arc::new(b"test-secret".to_vec()), exp_hours: 24 };
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
     let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
     let app_state = AppState::new(pool.clone(), jwt, event_bus);
-    let auth = AuthUser { user_id };
+    let auth = AuthUser { user_id, roles: Vec::new() };
 
     // 1. Create Dependency T1 -> T2
     let payload = DependencyCreateRequest {
@@ -68,7 +76,7 @@ async fn test_task_dependencies() -> anyhow::Result<()> {
         type_: "finish_to_start".to_string(),
     };
     let path = AxPath(project_id);
-    let (status, json) = create_dependency(AxState(app_state.clone()), path, auth.clone(), AxJson(payload)).await?;
+    let (status, json) = create_dependency(AxState(app_state.clone()), path, auth.clone(), HeaderMap::new(), AxJson(payload)).await?;
     assert_eq!(status, axum::http::StatusCode::CREATED);
     let dep_id = json.0.id;
 
@@ -94,7 +102,7 @@ async fn test_task_dependencies() -> anyhow::Result<()> {
         type_: "finish_to_start".to_string(),
     };
     let path = AxPath(project_id);
-    let res = create_dependency(AxState(app_state.clone()), path, auth.clone(), AxJson(payload)).await;
+    let res = create_dependency(AxState(app_state.clone()), path, auth.clone(), HeaderMap::new(), AxJson(payload)).await;
     assert!(res.is_err()); // Should fail with bad request
 
     // 3b. Test deeper cycle: create T2 -> T3, then attempt T3 -> T1 when T1->T2 exists
@@ -105,7 +113,7 @@ async fn test_task_dependencies() -> anyhow::Result<()> {
         type_: "finish_to_start".to_string(),
     };
     let path = AxPath(project_id);
-    let (status, _json) = create_dependency(AxState(app_state.clone()), path, auth.clone(), AxJson(payload)).await?;
+    let (status, _json) = create_dependency(AxState(app_state.clone()), path, auth.clone(), HeaderMap::new(), AxJson(payload)).await?;
     assert_eq!(status, axum::http::StatusCode::CREATED);
 
     // Now attempt to create T3 -> T1 which would form a cycle T1->T2->T3->T1
@@ -115,7 +123,7 @@ async fn test_task_dependencies() -> anyhow::Result<()> {
         type_: "finish_to_start".to_string(),
     };
     let path = AxPath(project_id);
-    let res = create_dependency(AxState(app_state.clone()), path, auth.clone(), AxJson(payload)).await;
+    let res = create_dependency(AxState(app_state.clone()), path, auth.clone(), HeaderMap::new(), AxJson(payload)).await;
     assert!(res.is_err()); // Should fail with deep cycle detection
 
     // 4. Try Self Dependency T1 -> T1
@@ -125,12 +133,12 @@ async fn test_task_dependencies() -> anyhow::Result<()> {
         type_: "finish_to_start".to_string(),
     };
     let path = AxPath(project_id);
-    let res = create_dependency(AxState(app_state.clone()), path, auth.clone(), AxJson(payload)).await;
+    let res = create_dependency(AxState(app_state.clone()), path, auth.clone(), HeaderMap::new(), AxJson(payload)).await;
     assert!(res.is_err());
 
     // 5. Delete Dependency
     let path = AxPath((project_id, dep_id));
-    let status = delete_dependency(AxState(app_state.clone()), path, auth.clone()).await?;
+    let status = delete_dependency(AxState(app_state.clone()), path, auth.clone(), HeaderMap::new()).await?;
     assert_eq!(status, axum::http::StatusCode::NO_CONTENT);
 
     // 6. Verify Deletion
@@ -0,0 +1,65 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn due_date_is_normalized_to_midnight() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Due", "email": "due@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Deadlines"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    // Compare instants rather than strings so serialization details do not matter
+    fn due(task: &Value) -> Result<chrono::DateTime<chrono::Utc>> {
+        let raw = task["due_date"].as_str().context("missing due_date")?;
+        Ok(chrono::DateTime::parse_from_rfc3339(raw)?.with_timezone(&chrono::Utc))
+    }
+    let midnight = |s: &str| -> Result<chrono::DateTime<chrono::Utc>> {
+        Ok(chrono::DateTime::parse_from_rfc3339(s)?.with_timezone(&chrono::Utc))
+    };
+
+    let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({
+        "title": "Submit permit",
+        "due_date": "2025-07-01T15:45:00Z",
+        "start_date": "2025-06-20T08:00:00Z",
+        "end_date": "2025-07-01T17:00:00Z"
+    }))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(due(&task)?, midnight("2025-07-01T00:00:00Z")?);
+    // Consistent with the other two date fields
+    assert_eq!(task["due_date"], task["end_date"]);
+    let task_uri = format!("{}/{}", tasks_uri, task["id"].as_str().context("missing task id")?);
+
+    let (status, patched) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"due_date": "2025-07-03T09:15:00Z"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(due(&patched)?, midnight("2025-07-03T00:00:00Z")?);
+
+    let (status, replaced) = send(&app, "PUT", &task_uri, Some(&token), Some(json!({
+        "title": "Submit permit",
+        "status": "pending",
+        "progress": 0,
+        "due_date": "2025-07-04T23:59:59Z"
+    }))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(due(&replaced)?, midnight("2025-07-04T00:00:00Z")?);
+
+    let (status, batch) = send(&app, "PUT", &format!("{}/batch", tasks_uri), Some(&token), Some(json!({
+        "tasks": [{"id": task["id"], "due_date": "2025-07-05T12:00:00Z"}]
+    }))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", batch);
+    let (_, fetched) = send(&app, "GET", &task_uri, Some(&token), None).await?;
+    assert_eq!(due(&fetched)?, midnight("2025-07-05T00:00:00Z")?);
+
+    Ok(())
+}
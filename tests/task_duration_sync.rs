@@ -0,0 +1,39 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn duration_is_computed_without_database_triggers() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    // Simulate an environment where the duration triggers were never installed
+    sqlx::query("DROP TRIGGER IF EXISTS trg_tasks_set_duration_insert").execute(&pool).await?;
+    sqlx::query("DROP TRIGGER IF EXISTS trg_tasks_set_duration_update").execute(&pool).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Dur", "email": "duration@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "No triggers"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let uri = format!("/projects/{}/tasks", project_id);
+
+    let (status, created) = send(&app, "POST", &uri, Some(&token), Some(json!({"title": "Build", "start_date": "2025-04-01T00:00:00Z", "end_date": "2025-04-04T00:00:00Z"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["duration_days"].as_i64(), Some(3));
+    let task_id = created["id"].as_str().context("missing task id")?;
+
+    let (status, updated) = send(&app, "PATCH", &format!("{}/{}", uri, task_id), Some(&token), Some(json!({"end_date": "2025-04-11T00:00:00Z"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["duration_days"].as_i64(), Some(10));
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn estimated_tasks_join_the_critical_path() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Est", "email": "est@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Estimates"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    // Only an estimate, no timeline
+    let (status, estimated) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Estimated", "estimated_days": 5}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(estimated["estimated_days"], 5);
+    assert!(estimated["duration_days"].is_null());
+
+    // A dated two-day task after it, and a parallel one-day task
+    let (_, dated) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({
+        "title": "Dated", "start_date": "2025-10-01T00:00:00Z", "end_date": "2025-10-03T00:00:00Z"
+    }))).await?;
+    let (_, short) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({
+        "title": "Short", "start_date": "2025-10-01T00:00:00Z", "end_date": "2025-10-02T00:00:00Z"
+    }))).await?;
+
+    let deps_uri = format!("/projects/{}/dependencies", project_id);
+    let (status, _) = send(&app, "POST", &deps_uri, Some(&token), Some(json!({"source_task_id": estimated["id"], "target_task_id": dated["id"]}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, path) = send(&app, "GET", &format!("/projects/{}/critical-path", project_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(path["task_ids"], json!([estimated["id"], dated["id"]]));
+
+    let (_, analysis) = send(&app, "GET", &format!("/projects/{}/schedule-analysis", project_id), Some(&token), None).await?;
+    assert_eq!(analysis["project_duration"], 7);
+    let short_row = analysis["tasks"].as_array().context("missing tasks")?
+        .iter().find(|t| t["task_id"] == short["id"]).context("short task missing")?;
+    assert_eq!(short_row["slack"], 6);
+
+    // The estimate can be changed and is validated
+    let task_uri = format!("{}/{}", tasks_uri, estimated["id"].as_str().unwrap());
+    let (status, patched) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"estimated_days": 8}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(patched["estimated_days"], 8);
+    let (_, analysis) = send(&app, "GET", &format!("/projects/{}/schedule-analysis", project_id), Some(&token), None).await?;
+    assert_eq!(analysis["project_duration"], 10);
+
+    let (status, err) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"estimated_days": -1}))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(err["errors"][0]["field"], "estimated_days");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn due_date_only_tasks_span_from_creation() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Due", "email": "due@example.com", "password": "password123"}))).await?;
+    let token = auth["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Deadlines"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+
+    let due = chrono::Utc::now() + chrono::Duration::days(4);
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Deadline only", "due_date": due}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (_, analysis) = send(&app, "GET", &format!("/projects/{}/schedule-analysis", project_id), Some(&token), None).await?;
+    // due_date is normalized to midnight, so the span is 3 or 4 whole days depending on the time of day
+    let duration = analysis["project_duration"].as_i64().context("missing duration")?;
+    assert!((3..=4).contains(&duration), "unexpected duration {}", duration);
+
+    Ok(())
+}
@@ -36,10 +36,7 @@ async fn test_task_hierarchy(pool: SqlitePool) {
         .await
         .unwrap();
 
-    let jwt_config = jwt::JwtConfig {
-        secret: std::sync::Arc::new(b"test_secret".to_vec()),
-        exp_hours: 1,
-    };
+    let jwt_config = jwt::JwtConfig::new(b"test_secret".to_vec(), 1);
     let token = jwt_config.encode(user_id).unwrap();
 
     // 2. Create Parent Task
@@ -119,8 +116,7 @@ async fn test_task_hierarchy(pool: SqlitePool) {
     assert_eq!(fetched_child["parent_id"], parent_id);
 
     // 5. Delete Parent Task and verify Cascade (if enabled) or Orphan
-    // Note: SQLite FKs are disabled by default in SQLx unless explicitly enabled in connect options or PRAGMA.
-    // We'll check if the child is deleted or if we need to handle it manually.
+    // Note: db::init() enables foreign keys, so ON DELETE CASCADE fires for hard deletes.
     // For this test, let's just verify we can delete the parent.
     let response = app
         .clone()
@@ -0,0 +1,118 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use common::{migrated_pool, send_with_headers};
+use s_curve::create_app;
+
+async fn send(app: &axum::Router, method: &str, uri: &str, token: Option<&str>, idempotency_key: Option<&str>, body: Option<Value>) -> Result<(StatusCode, Value)> {
+    let headers: Vec<(&str, &str)> = idempotency_key.map(|k| ("idempotency-key", k)).into_iter().collect();
+    let (status, _, value) = send_with_headers(app, method, uri, token, &headers, body).await?;
+    Ok((status, value))
+}
+
+async fn setup() -> Result<(tempfile::TempDir, SqlitePool, axum::Router)> {
+    let (dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+    Ok((dir, pool, app))
+}
+
+async fn count(pool: &SqlitePool, table: &str) -> Result<i64> {
+    Ok(sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM {}", table)).fetch_one(pool).await?)
+}
+
+#[tokio::test]
+async fn create_task_with_repeated_idempotency_key_returns_same_task() -> Result<()> {
+    let (_dir, _pool, app) = setup().await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, None, Some(json!({"name": "Retry", "email": "retry@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), None, Some(json!({"name": "Retries"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let uri = format!("/projects/{}/tasks", project_id);
+
+    let (status, first) = send(&app, "POST", &uri, Some(&token), Some("create-abc"), Some(json!({"title": "Once"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, second) = send(&app, "POST", &uri, Some(&token), Some("create-abc"), Some(json!({"title": "Once"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(second, first);
+
+    let (_, tasks) = send(&app, "GET", &uri, Some(&token), None, None).await?;
+    assert_eq!(tasks.as_array().map(|a| a.len()), Some(1));
+
+    // A different key creates a new task
+    let (status, third) = send(&app, "POST", &uri, Some(&token), Some("create-def"), Some(json!({"title": "Once"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(third["id"], first["id"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_project_and_progress_replay_the_stored_response() -> Result<()> {
+    let (_dir, pool, app) = setup().await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, None, Some(json!({"name": "Mobile", "email": "mobile@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let body = json!({"name": "Offline"});
+    let (status, first) = send(&app, "POST", "/projects", Some(&token), Some("project-1"), Some(body.clone())).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, second) = send(&app, "POST", "/projects", Some(&token), Some("project-1"), Some(body)).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(second, first);
+    assert_eq!(count(&pool, "projects").await?, 1);
+
+    let project_id = first["id"].as_str().context("missing project id")?;
+    let (_, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), None, Some(json!({"title": "Pour"}))).await?;
+    let task_id = task["id"].as_str().context("missing task id")?;
+    let uri = format!("/projects/{}/tasks/{}/progress", project_id, task_id);
+
+    let body = json!({"progress": 40, "note": "retry me"});
+    let (status, first) = send(&app, "POST", &uri, Some(&token), Some("progress-1"), Some(body.clone())).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, second) = send(&app, "POST", &uri, Some(&token), Some("progress-1"), Some(body)).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(second, first);
+    assert_eq!(count(&pool, "task_progress").await?, 1);
+
+    // The same key on another endpoint is independent
+    let (status, _) = send(&app, "POST", "/projects", Some(&token), Some("progress-1"), Some(json!({"name": "Second"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(count(&pool, "projects").await?, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn expired_keys_are_not_replayed() -> Result<()> {
+    let (_dir, pool, app) = setup().await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, None, Some(json!({"name": "Late", "email": "late@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (status, first) = send(&app, "POST", "/projects", Some(&token), Some("old-key"), Some(json!({"name": "Old"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Age the stored key past the default 24h window
+    sqlx::query("UPDATE idempotency_keys SET created_at = ?")
+        .bind(chrono::Utc::now() - chrono::Duration::hours(25))
+        .execute(&pool)
+        .await?;
+
+    let (status, second) = send(&app, "POST", "/projects", Some(&token), Some("old-key"), Some(json!({"name": "Old"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_ne!(second["id"], first["id"]);
+    assert_eq!(count(&pool, "idempotency_keys").await?, 1);
+
+    Ok(())
+}
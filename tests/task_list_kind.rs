@@ -0,0 +1,53 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn task_listing_is_discriminated_by_kind() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Lister", "email": "lister@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Listing"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Pour slab"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let task_id = task["id"].as_str().context("missing task id")?.to_string();
+    let (status, entry) = send(&app, "POST", &format!("{}/{}/progress", tasks_uri, task_id), Some(&token), Some(json!({"progress": 40}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Default stays a bare array
+    let (status, plain) = send(&app, "GET", &tasks_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plain.as_array().map(Vec::len), Some(1));
+
+    let (status, tasks) = send(&app, "GET", &format!("{}?envelope=true", tasks_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tasks["kind"], "tasks");
+    assert_eq!(tasks["items"][0]["id"], task["id"]);
+
+    let (status, progress) = send(&app, "GET", &format!("{}?progress=true", tasks_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(progress["kind"], "progress");
+    let items = progress["items"].as_array().context("items should be an array")?;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["id"], entry["id"]);
+    assert_eq!(items[0]["progress"], 40);
+
+    let (status, filtered) = send(&app, "GET", &format!("{}?progress=true&task_id={}", tasks_uri, task_id), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(filtered["kind"], "progress");
+    assert_eq!(filtered["items"].as_array().map(Vec::len), Some(1));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn overdue_lists_open_late_tasks_most_overdue_first() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Late", "email": "late@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Behind"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let days_from_now = |days: i64| (chrono::Utc::now() + chrono::Duration::days(days)).to_rfc3339();
+    let create = |title: &'static str, body: Value| {
+        let app = app.clone();
+        let token = token.clone();
+        let uri = tasks_uri.clone();
+        async move {
+            let mut body = body;
+            body["title"] = json!(title);
+            let (status, task) = send(&app, "POST", &uri, Some(&token), Some(body)).await?;
+            assert_eq!(status, StatusCode::CREATED, "{}", task);
+            Ok::<_, anyhow::Error>(task)
+        }
+    };
+
+    let slightly_late = create("Slightly late", json!({"due_date": days_from_now(-2)})).await?;
+    let very_late = create("Very late", json!({"due_date": days_from_now(-10), "status": "in_progress"})).await?;
+    create("Done late", json!({"due_date": days_from_now(-5), "status": "done"})).await?;
+    create("Cancelled late", json!({"due_date": days_from_now(-5), "status": "cancelled"})).await?;
+    create("Upcoming", json!({"due_date": days_from_now(5)})).await?;
+    // Due earlier today: not late until the day is over
+    let start_of_today = chrono::Utc::now().date_naive().and_time(chrono::NaiveTime::MIN).and_utc().to_rfc3339();
+    create("Due today", json!({"due_date": start_of_today})).await?;
+    create("No due date", json!({})).await?;
+    let deleted = create("Deleted late", json!({"due_date": days_from_now(-3)})).await?;
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", tasks_uri, deleted["id"].as_str().context("missing id")?), Some(&token), None).await?;
+    assert!(status.is_success());
+
+    let (status, overdue) = send(&app, "GET", &format!("{}/overdue", tasks_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", overdue);
+    let ids: Vec<&Value> = overdue.as_array().context("expected array")?.iter().map(|t| &t["id"]).collect();
+    assert_eq!(ids, vec![&very_late["id"], &slightly_late["id"]]);
+    // Same shape as the plain task listing
+    assert_eq!(overdue[0]["title"], "Very late");
+    assert!(overdue[0].get("progress").is_some());
+
+    // Non-members are refused
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Other", "email": "other-late@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?;
+    let (status, _) = send(&app, "GET", &format!("{}/overdue", tasks_uri), Some(other_token), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    Ok(())
+}
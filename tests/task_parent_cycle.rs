@@ -0,0 +1,68 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn parent_id_cannot_create_cycles() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Tree", "email": "tree-cycle@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Hierarchy"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let mut ids = Vec::new();
+    let mut parent: Option<String> = None;
+    for title in ["A", "B", "C"] {
+        let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": title, "parent_id": parent}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = task["id"].as_str().context("missing task id")?.to_string();
+        parent = Some(id.clone());
+        ids.push(id);
+    }
+    let (a, b, c) = (&ids[0], &ids[1], &ids[2]);
+    let uri = |id: &str| format!("{}/{}", tasks_uri, id);
+
+    // Own parent
+    let (status, err) = send(&app, "PATCH", &uri(a), Some(&token), Some(json!({"parent_id": a}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("own parent"));
+
+    // Own grandchild (A -> B -> C, then C as A's parent)
+    let (status, err) = send(&app, "PATCH", &uri(a), Some(&token), Some(json!({"parent_id": c}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("Cycle"));
+
+    // Full replacement goes through the same check
+    let (status, _) = send(&app, "PUT", &uri(a), Some(&token), Some(json!({"title": "A", "status": "pending", "progress": 0, "parent_id": b}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, err) = send(&app, "PUT", &format!("{}/batch", tasks_uri), Some(&token), Some(json!({"tasks": [{"id": a, "parent_id": b}]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains(a.as_str()));
+
+    let (_, unchanged) = send(&app, "GET", &uri(a), Some(&token), None).await?;
+    assert!(unchanged["parent_id"].is_null());
+
+    // Re-parenting that keeps the hierarchy acyclic is fine
+    let (status, moved) = send(&app, "PATCH", &uri(c), Some(&token), Some(json!({"parent_id": a}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(moved["parent_id"].as_str(), Some(a.as_str()));
+
+    // Parents must live in the same project
+    let (_, other) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Elsewhere"}))).await?;
+    let other_tasks_uri = format!("/projects/{}/tasks", other["id"].as_str().context("missing project id")?);
+    let (status, _) = send(&app, "POST", &other_tasks_uri, Some(&token), Some(json!({"title": "Orphan", "parent_id": a}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
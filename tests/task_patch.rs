@@ -0,0 +1,57 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn patch_is_partial_and_put_requires_full_representation() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Patcher", "email": "patch@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Semantics"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({
+        "title": "Frame walls",
+        "status": "pending",
+        "start_date": "2025-05-01T00:00:00Z",
+        "end_date": "2025-05-06T00:00:00Z",
+        "progress": 25
+    }))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let task_uri = format!("{}/{}", tasks_uri, task["id"].as_str().context("missing task id")?);
+
+    // PATCH touches only what is sent
+    let (status, patched) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"status": "in_progress"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(patched["status"], "in_progress");
+    for field in ["title", "start_date", "end_date", "duration_days", "progress", "parent_id", "assignee"] {
+        assert_eq!(patched[field], task[field], "PATCH changed {}", field);
+    }
+
+    // PUT without a required field is rejected and changes nothing
+    let (status, err) = send(&app, "PUT", &task_uri, Some(&token), Some(json!({"status": "done", "progress": 100}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("title"));
+    let (_, unchanged) = send(&app, "GET", &task_uri, Some(&token), None).await?;
+    assert_eq!(unchanged["status"], "in_progress");
+
+    // A full PUT replaces the representation, clearing omitted optional fields
+    let (status, replaced) = send(&app, "PUT", &task_uri, Some(&token), Some(json!({"title": "Frame walls (east)", "status": "done", "progress": 100}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replaced["title"], "Frame walls (east)");
+    assert_eq!(replaced["progress"].as_i64(), Some(100));
+    assert!(replaced["start_date"].is_null());
+    assert!(replaced["end_date"].is_null());
+
+    Ok(())
+}
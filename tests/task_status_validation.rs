@@ -0,0 +1,113 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::SqlitePool;
+use tempfile::tempdir;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn task_status_must_be_known() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Status", "email": "status@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Statuses"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (status, err) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Typo", "status": "in progres"}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("in_progress"), "error should list allowed values");
+
+    let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Valid", "status": "blocked"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let task_id = task["id"].as_str().context("missing task id")?.to_string();
+    let task_uri = format!("{}/{}", tasks_uri, task_id);
+
+    let (status, _) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"status": "finished"}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "PUT", &task_uri, Some(&token), Some(json!({"title": "Valid", "status": "Done", "progress": 100}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, err) = send(&app, "PUT", &format!("{}/batch", tasks_uri), Some(&token), Some(json!({"tasks": [{"id": task_id, "status": "wip"}]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(err["message"].as_str().unwrap_or_default().contains("invalid status 'wip'"), "{}", err);
+    // Rejected while parsing the body, so nothing was written
+    let stored: String = sqlx::query_scalar("SELECT status FROM tasks").fetch_one(&pool).await?;
+    assert_eq!(stored, "blocked");
+
+    let (status, done) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"status": "done"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(done["status"], "done");
+
+    // A legacy alias written behind the API's back reads as its canonical status
+    sqlx::query("UPDATE tasks SET status = 'Doing'").execute(&pool).await?;
+    let (status, legacy) = send(&app, "GET", &task_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(legacy["status"], "in_progress");
+    let (status, listed) = send(&app, "GET", &tasks_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed.as_array().map(Vec::len), Some(1));
+    let (status, edited) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"progress": 10}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(edited["progress"], 10);
+
+    // Anything else reads as pending instead of breaking the task
+    sqlx::query("UPDATE tasks SET status = 'legacy-status'").execute(&pool).await?;
+    let (status, legacy) = send(&app, "GET", &task_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(legacy["status"], "pending");
+    let (status, listed) = send(&app, "GET", &tasks_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed.as_array().map(Vec::len), Some(1));
+
+    Ok(())
+}
+
+/// Migrate a database up to (not including) the status normalization, seed
+/// `statuses` as raw task rows, then run the remaining migrations.
+async fn migrate_with_legacy_statuses(
+    statuses: &[Option<&str>],
+) -> Result<(tempfile::TempDir, SqlitePool)> {
+    let dir = tempdir().context("failed to create tempdir")?;
+    use sqlx::sqlite::SqliteConnectOptions;
+    let opts = SqliteConnectOptions::new()
+        .filename(dir.path().join("test.db"))
+        .create_if_missing(true)
+        .foreign_keys(false);
+    let pool = SqlitePool::connect_with(opts).await?;
+
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+    let full = sqlx::migrate::Migrator::new(path.clone()).await?;
+    let mut before = sqlx::migrate::Migrator::new(path).await?;
+    before.migrations = full.migrations.iter().filter(|m| m.version < 202512230001).cloned().collect::<Vec<_>>().into();
+    before.run(&pool).await?;
+
+    for (i, stored) in statuses.iter().enumerate() {
+        sqlx::query("INSERT INTO tasks (id, project_id, title, status) VALUES (?, 'p', ?, ?)")
+            .bind(format!("t{}", i))
+            .bind(format!("Task {}", i))
+            .bind(*stored)
+            .execute(&pool)
+            .await?;
+    }
+
+    full.run(&pool).await?;
+    Ok((dir, pool))
+}
+
+#[tokio::test]
+async fn migration_maps_legacy_aliases_and_unknown_statuses() -> Result<()> {
+    let (_dir, pool) =
+        migrate_with_legacy_statuses(&[Some("Doing"), Some("completed"), Some(" In-Progress "), Some("canceled"), None, Some("legacy-status")]).await?;
+    let stored: Vec<String> = sqlx::query_scalar("SELECT status FROM tasks ORDER BY id").fetch_all(&pool).await?;
+    assert_eq!(stored, ["in_progress", "done", "in_progress", "cancelled", "pending", "pending"]);
+
+    Ok(())
+}
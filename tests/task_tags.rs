@@ -0,0 +1,89 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+fn titles(tasks: &Value) -> Vec<String> {
+    let mut titles: Vec<String> = tasks
+        .as_array()
+        .map(|items| items.iter().filter_map(|t| t["title"].as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    titles.sort();
+    titles
+}
+
+#[tokio::test]
+async fn tags_are_normalized_deduped_and_filterable() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, reg) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Tagger", "email": "tagger@example.com", "password": "password123"}))).await?;
+    let token = reg["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Tagged"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let (_, login) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Login form"}))).await?;
+    let (_, api) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Login API"}))).await?;
+    let (_, crash) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Crash on save"}))).await?;
+    assert_eq!(login["tags"], json!([]));
+    let login_tags = format!("{}/{}/tags", tasks_uri, login["id"].as_str().context("missing task id")?);
+    let api_tags = format!("{}/{}/tags", tasks_uri, api["id"].as_str().context("missing task id")?);
+    let crash_id = crash["id"].as_str().context("missing task id")?.to_string();
+    let crash_tags = format!("{}/{}/tags", tasks_uri, crash_id);
+
+    let (status, task) = send(&app, "POST", &login_tags, Some(&token), Some(json!({"tags": [" Frontend ", "frontend", "AUTH"]}))).await?;
+    assert_eq!(status, StatusCode::OK, "{}", task);
+    assert_eq!(task["tags"], json!(["auth", "frontend"]));
+    let (status, _) = send(&app, "POST", &api_tags, Some(&token), Some(json!({"tags": ["backend", "auth"]}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "POST", &crash_tags, Some(&token), Some(json!({"tags": ["bug", "frontend"]}))).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    // Re-adding an existing tag changes nothing, not even updated_at
+    let (status, again) = send(&app, "POST", &login_tags, Some(&token), Some(json!({"tags": ["FRONTEND"]}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(again["tags"], json!(["auth", "frontend"]));
+    assert_eq!(again["updated_at"], task["updated_at"]);
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM task_tags WHERE tag = 'frontend'").fetch_one(&pool).await?;
+    assert_eq!(stored, 2);
+
+    let (status, _) = send(&app, "POST", &login_tags, Some(&token), Some(json!({"tags": ["  "]}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "POST", &login_tags, Some(&token), Some(json!({"tags": []}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, listed) = send(&app, "GET", &format!("{}?tag=Frontend", tasks_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&listed), vec!["Crash on save", "Login form"]);
+    let (_, listed) = send(&app, "GET", &format!("{}?tag=auth", tasks_uri), Some(&token), None).await?;
+    assert_eq!(titles(&listed), vec!["Login API", "Login form"]);
+    let (_, listed) = send(&app, "GET", &format!("{}?tag=design", tasks_uri), Some(&token), None).await?;
+    assert_eq!(listed, json!([]));
+
+    // Tags show up on single-task reads and unfiltered listings too
+    let (_, fetched) = send(&app, "GET", &format!("{}/{}", tasks_uri, crash_id), Some(&token), None).await?;
+    assert_eq!(fetched["tags"], json!(["bug", "frontend"]));
+
+    // Removing is the mirror image; unknown tags are ignored
+    let (status, task) = send(&app, "DELETE", &crash_tags, Some(&token), Some(json!({"tags": ["Frontend", "design"]}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(task["tags"], json!(["bug"]));
+    let (_, listed) = send(&app, "GET", &format!("{}?tag=frontend", tasks_uri), Some(&token), None).await?;
+    assert_eq!(titles(&listed), vec!["Login form"]);
+
+    // Deleted tasks drop out of the filter
+    let (status, _) = send(&app, "DELETE", &format!("{}/{}", tasks_uri, login["id"].as_str().unwrap_or_default()), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, listed) = send(&app, "GET", &format!("{}?tag=auth", tasks_uri), Some(&token), None).await?;
+    assert_eq!(titles(&listed), vec!["Login API"]);
+
+    Ok(())
+}
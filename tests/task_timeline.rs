@@ -43,6 +43,25 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
         .execute(&pool)
         .await?;
 
+    sqlx::query("CREATE TABLE IF NOT EXISTS project_members (
+        project_id TEXT NOT NULL,
+        user_id TEXT NOT NULL,
+        role TEXT NOT NULL DEFAULT 'member',
+        created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (project_id, user_id)
+    );")
+        .execute(&pool)
+        .await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS task_tags (
+        task_id TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (task_id, tag)
+    );")
+        .execute(&pool)
+        .await?;
+
     sqlx::query("CREATE TABLE IF NOT EXISTS tasks (
         id TEXT PRIMARY KEY,
         project_id TEXT NOT NULL,
@@ -52,6 +71,7 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
         start_date TEXT,
         end_date TEXT,
         duration_days INTEGER,
+        estimated_days INTEGER,
         assignee TEXT,
         parent_id TEXT,
         progress INTEGER NOT NULL DEFAULT 0,
@@ -106,10 +126,10 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
     use s_curve::models::task::{TaskCreateRequest, TaskUpdateRequest};
     use s_curve::jwt::JwtConfig;
     use axum::extract::State as AxState;
-    use axum::Json as AxJson;
+    use s_curve::extract::AppJson as AxJson;
     use axum::extract::Path as AxPath;
 
-    let jwt = JwtConfig { secret: std::sync::Arc::new(b"test-secret".to_vec()), exp_hours: 24 };
+    let jwt = JwtConfig::new(b"test-secret".to_vec(), 24);
     let (event_bus, _rx) = tokio::sync::broadcast::channel(16);
     let app_state = AppState::new(pool.clone(), jwt, event_bus);
 
@@ -120,13 +140,14 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
         due_date: None,
         start_date: Some(chrono::DateTime::parse_from_rfc3339("2025-10-01T09:00:00Z")?.with_timezone(&chrono::Utc)),
         end_date: Some(chrono::DateTime::parse_from_rfc3339("2025-10-05T17:00:00Z")?.with_timezone(&chrono::Utc)),
+        estimated_days: None,
         assignee: None,
         parent_id: None,
         progress: Some(5),
     };
 
     let path = AxPath(project_id);
-    let auth = s_curve::jwt::AuthUser { user_id };
+    let auth = s_curve::jwt::AuthUser { user_id, roles: Vec::new() };
 
     let (status, json_resp) = create_task(AxState(app_state.clone()), path, auth.clone(), axum::http::HeaderMap::new(), AxJson(payload)).await?;
     assert_eq!(status, axum::http::StatusCode::CREATED);
@@ -137,14 +158,14 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
 
     // Update with invalid date range
     // Update with invalid date range
-    let bad_update = TaskUpdateRequest { title: None, status: None, due_date: None, start_date: Some(chrono::DateTime::parse_from_rfc3339("2025-10-10T00:00:00Z")?.with_timezone(&chrono::Utc)), end_date: Some(chrono::DateTime::parse_from_rfc3339("2025-10-05T00:00:00Z")?.with_timezone(&chrono::Utc)), assignee: None, parent_id: None, progress: None };
+    let bad_update = TaskUpdateRequest { title: None, status: None, due_date: None, start_date: Some(chrono::DateTime::parse_from_rfc3339("2025-10-10T00:00:00Z")?.with_timezone(&chrono::Utc)), end_date: Some(chrono::DateTime::parse_from_rfc3339("2025-10-05T00:00:00Z")?.with_timezone(&chrono::Utc)), estimated_days: None, assignee: None, parent_id: None, progress: None, expected_updated_at: None };
 
     let path = AxPath((project_id, created.id));
     let res = update_task(AxState(app_state.clone()), auth.clone(), axum::http::HeaderMap::new(), path, AxJson(bad_update)).await;
     assert!(res.is_err());
 
     // Update with invalid progress
-    let bad_progress = TaskUpdateRequest { title: None, status: None, due_date: None, start_date: None, end_date: None, assignee: None, parent_id: None, progress: Some(150) };
+    let bad_progress = TaskUpdateRequest { title: None, status: None, due_date: None, start_date: None, end_date: None, estimated_days: None, assignee: None, parent_id: None, progress: Some(150), expected_updated_at: None };
     let path = AxPath((project_id, created.id));
     let res = update_task(AxState(app_state.clone()), auth, axum::http::HeaderMap::new(), path, AxJson(bad_progress)).await;
     assert!(res.is_err());
@@ -156,11 +177,13 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
         due_date: None,
         start_date: Some(chrono::DateTime::parse_from_rfc3339("2025-11-01T09:00:00Z")?.with_timezone(&chrono::Utc)),
         end_date: Some(chrono::DateTime::parse_from_rfc3339("2025-11-03T17:00:00Z")?.with_timezone(&chrono::Utc)),
+        estimated_days: None,
         assignee: None,
         parent_id: None,
         progress: Some(50),
+        expected_updated_at: None,
     };
-    let auth = s_curve::jwt::AuthUser { user_id };
+    let auth = s_curve::jwt::AuthUser { user_id, roles: Vec::new() };
     let path = AxPath((project_id, created.id));
     let res = update_task(AxState(app_state.clone()), auth.clone(), axum::http::HeaderMap::new(), path, AxJson(valid_update)).await?;
     let updated_task = res.0;
@@ -179,6 +202,7 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
         due_date: None,
         start_date: Some(chrono::DateTime::parse_from_rfc3339("2025-09-01T09:00:00Z")?.with_timezone(&chrono::Utc)),
         end_date: Some(chrono::DateTime::parse_from_rfc3339("2025-09-05T17:00:00Z")?.with_timezone(&chrono::Utc)),
+        estimated_days: None,
         assignee: None,
         parent_id: None,
         progress: Some(0),
@@ -188,10 +212,12 @@ async fn create_update_task_with_timeline() -> anyhow::Result<()> {
     assert_eq!(status, axum::http::StatusCode::CREATED);
 
     // List tasks
-    let query = TaskListQuery { progress: None, task_id: None };
+    let query = TaskListQuery { progress: None, task_id: None, envelope: None, tag: None };
     let path = AxPath(project_id);
-    let res = list_tasks(AxState(app_state.clone()), path, axum::extract::Query(query), auth).await?;
-    let tasks = res.0;
+    let res = list_tasks(AxState(app_state.clone()), path, axum::extract::Query(query), auth, axum::http::HeaderMap::new()).await?;
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX).await?;
+    // A plain listing is a bare task array
+    let tasks: Vec<s_curve::models::task::Task> = serde_json::from_slice(&body)?;
 
     assert_eq!(tasks.len(), 2);
     // Should be sorted by start_date ASC. Early Task (Sept) first, Updated Task (Nov) second.
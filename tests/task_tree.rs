@@ -0,0 +1,98 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn task_tree_stops_at_max_depth_and_flags_truncation() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (status, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Tree", "email": "tree@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Deep"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    // Level 1 -> 2 -> 3 -> 4 -> 5
+    let mut parent: Option<String> = None;
+    for level in 1..=5 {
+        let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": format!("Level {}", level), "parent_id": parent}))).await?;
+        assert_eq!(status, StatusCode::CREATED);
+        parent = Some(task["id"].as_str().context("missing task id")?.to_string());
+    }
+
+    let (status, tree) = send(&app, "GET", &format!("{}/tree?max_depth=2", tasks_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tree["truncated"], true);
+    assert_eq!(tree["max_depth"].as_u64(), Some(2));
+    let roots = tree["roots"].as_array().context("roots should be an array")?;
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0]["title"], "Level 1");
+    let level_two = roots[0]["children"].as_array().context("children should be an array")?;
+    assert_eq!(level_two.len(), 1);
+    assert_eq!(level_two[0]["title"], "Level 2");
+    assert_eq!(level_two[0]["children"].as_array().map(|c| c.len()), Some(0));
+
+    // Deep enough for the whole hierarchy: nothing is cut off
+    let (status, full) = send(&app, "GET", &format!("{}/tree?max_depth=5", tasks_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(full["truncated"], false);
+    let mut node = &full["roots"][0];
+    for level in 2..=5 {
+        node = &node["children"][0];
+        assert_eq!(node["title"], format!("Level {}", level));
+    }
+
+    let (status, _) = send(&app, "GET", &format!("{}/tree?max_depth=0", tasks_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn task_history_honours_limit() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "History", "email": "history@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Logged"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let (_, task) = send(&app, "POST", &format!("/projects/{}/tasks", project_id), Some(&token), Some(json!({"title": "Audit me"}))).await?;
+    let task_uri = format!("/projects/{}/tasks/{}", project_id, task["id"].as_str().context("missing task id")?);
+
+    for title in ["Audit me twice", "Audit me thrice"] {
+        let (status, _) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({"title": title}))).await?;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Activity is written asynchronously; wait for create + two updates
+    let mut full = Value::Null;
+    for _ in 0..50 {
+        let (_, body) = send(&app, "GET", &format!("{}/history", task_uri), Some(&token), None).await?;
+        if body["entries"].as_array().map(|e| e.len()).unwrap_or(0) >= 3 {
+            full = body;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(full["truncated"], false);
+
+    let (status, limited) = send(&app, "GET", &format!("{}/history?limit=1", task_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(limited["entries"].as_array().map(|e| e.len()), Some(1));
+    assert_eq!(limited["truncated"], true);
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn timeline_and_progress_problems_are_reported_together() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, auth_res) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Val", "email": "val@example.com", "password": "password123"}))).await?;
+    let token = auth_res["token"].as_str().context("missing token")?.to_string();
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Checks"}))).await?;
+    let project_id = project["id"].as_str().context("missing project id")?.to_string();
+    let tasks_uri = format!("/projects/{}/tasks", project_id);
+
+    let fields = |body: &Value| -> Vec<String> {
+        body["errors"]
+            .as_array()
+            .map(|errors| errors.iter().filter_map(|e| e["field"].as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    };
+
+    // Both a reversed date range and out-of-range progress in one response
+    let (status, body) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({
+        "title": "Broken",
+        "start_date": "2025-06-10T00:00:00Z",
+        "end_date": "2025-06-01T00:00:00Z",
+        "progress": 150
+    }))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    assert_eq!(body["code"], "VALIDATION");
+    assert_eq!(fields(&body), vec!["end_date", "progress"]);
+
+    let (_, first) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "First"}))).await?;
+    let (_, second) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Second"}))).await?;
+    let task_uri = format!("{}/{}", tasks_uri, first["id"].as_str().context("missing task id")?);
+
+    let (status, body) = send(&app, "PATCH", &task_uri, Some(&token), Some(json!({
+        "start_date": "2025-06-10T00:00:00Z",
+        "end_date": "2025-06-01T00:00:00Z",
+        "progress": -5
+    }))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    assert_eq!(fields(&body), vec!["end_date", "progress"]);
+
+    // The batch reports every invalid task and applies none of them
+    let (status, body) = send(&app, "PUT", &format!("{}/batch", tasks_uri), Some(&token), Some(json!({"tasks": [
+        {"id": first["id"], "title": "Renamed", "start_date": "2025-06-10T00:00:00Z", "end_date": "2025-06-01T00:00:00Z"},
+        {"id": second["id"], "progress": 101}
+    ]}))).await?;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    assert_eq!(fields(&body), vec!["tasks[0].end_date", "tasks[1].progress"]);
+
+    let (_, unchanged) = send(&app, "GET", &task_uri, Some(&token), None).await?;
+    assert_eq!(unchanged["title"], "First");
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+
+use s_curve::tls::{self, TlsMode};
+
+fn set(name: &str, value: Option<&str>) {
+    match value {
+        Some(v) => std::env::set_var(name, v),
+        None => std::env::remove_var(name),
+    }
+}
+
+fn advertised_scheme() -> Result<String> {
+    let doc = s_curve::docs::build_openapi(8000)?;
+    let servers = doc.servers.unwrap_or_default();
+    let local = servers.iter().find(|s| s.url.contains("localhost:8000")).map(|s| s.url.clone()).unwrap_or_default();
+    Ok(local.split("://").next().unwrap_or_default().to_string())
+}
+
+// One test so the env var combinations cannot race each other
+#[test]
+fn listener_and_swagger_agree_on_the_scheme() -> Result<()> {
+    let cases: &[(Option<&str>, Option<&str>, Option<&str>, &str)] = &[
+        (None, None, None, "http"),
+        (Some("/c.pem"), None, None, "http"),
+        (None, Some("/k.pem"), None, "http"),
+        (Some("/c.pem"), Some("/k.pem"), None, "https"),
+        (None, None, Some("true"), "https"),
+        (None, None, Some("0"), "http"),
+        (Some("/c.pem"), None, Some("1"), "https"),
+        (Some("/c.pem"), Some("/k.pem"), Some("yes"), "https"),
+    ];
+
+    for (cert, key, self_signed, expected) in cases {
+        set("CERT_PATH", *cert);
+        set("KEY_PATH", *key);
+        set("USE_SELF_SIGNED_TLS", *self_signed);
+
+        let mode = TlsMode::from_env();
+        assert_eq!(mode.scheme(), *expected, "{:?}/{:?}/{:?}", cert, key, self_signed);
+        assert_eq!(advertised_scheme()?, *expected, "swagger for {:?}", mode);
+        if cert.is_some() && key.is_some() {
+            assert!(matches!(mode, TlsMode::Files { .. }), "a cert/key pair wins over self-signed");
+        }
+    }
+
+    set("CERT_PATH", None);
+    set("KEY_PATH", None);
+    set("USE_SELF_SIGNED_TLS", None);
+    Ok(())
+}
+
+#[test]
+fn self_signed_certificate_is_valid_pem() -> Result<()> {
+    let (cert, key) = tls::self_signed_pem()?;
+    assert!(cert.starts_with("-----BEGIN CERTIFICATE-----"));
+    assert!(key.contains("PRIVATE KEY-----"));
+    Ok(())
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+async fn register(app: &axum::Router, name: &str) -> Result<String> {
+    let (status, body) = send(app, "POST", "/auth/register", None, Some(json!({"name": name, "email": format!("{}@example.com", name), "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    Ok(body["token"].as_str().context("missing token")?.to_string())
+}
+
+#[tokio::test]
+async fn trash_lists_only_the_callers_deleted_rows() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    let app = create_app(pool.clone()).await?;
+
+    let owner = register(&app, "owner").await?;
+    let stranger = register(&app, "stranger").await?;
+
+    let mut project_ids = Vec::new();
+    for name in ["Kept", "Old", "Newer"] {
+        let (_, project) = send(&app, "POST", "/projects", Some(&owner), Some(json!({"name": name}))).await?;
+        project_ids.push(project["id"].as_str().context("missing project id")?.to_string());
+    }
+    let (_, other) = send(&app, "POST", "/projects", Some(&stranger), Some(json!({"name": "Not yours"}))).await?;
+    let other_id = other["id"].as_str().context("missing project id")?.to_string();
+
+    for id in [&project_ids[1], &project_ids[2], &other_id] {
+        let token = if *id == other_id { &stranger } else { &owner };
+        let (status, _) = send(&app, "DELETE", &format!("/projects/{}", id), Some(token), None).await?;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let (status, trash) = send(&app, "GET", "/projects/trash", Some(&owner), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", trash);
+    let names: Vec<&str> = trash.as_array().context("expected array")?.iter().filter_map(|p| p["name"].as_str()).collect();
+    assert_eq!(names, vec!["Newer", "Old"], "most recently deleted first, other users' projects excluded");
+    assert!(trash[0]["deleted_at"].is_string());
+
+    let (_, live) = send(&app, "GET", "/projects", Some(&owner), None).await?;
+    let names: Vec<&str> = live.as_array().context("expected array")?.iter().filter_map(|p| p["name"].as_str()).collect();
+    assert_eq!(names, vec!["Kept"]);
+
+    // Task trash in the live project
+    let kept = &project_ids[0];
+    let mut task_ids = Vec::new();
+    for title in ["Stays", "Gone first", "Gone second"] {
+        let (status, task) = send(&app, "POST", &format!("/projects/{}/tasks", kept), Some(&owner), Some(json!({"title": title}))).await?;
+        assert_eq!(status, StatusCode::CREATED, "{}", task);
+        task_ids.push(task["id"].as_str().context("missing task id")?.to_string());
+    }
+    for id in &task_ids[1..] {
+        let (status, _) = send(&app, "DELETE", &format!("/projects/{}/tasks/{}", kept, id), Some(&owner), None).await?;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let (status, trash) = send(&app, "GET", &format!("/projects/{}/tasks/trash", kept), Some(&owner), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", trash);
+    let titles: Vec<&str> = trash.as_array().context("expected array")?.iter().filter_map(|t| t["title"].as_str()).collect();
+    assert_eq!(titles, vec!["Gone second", "Gone first"]);
+    assert!(trash[0]["deleted_at"].is_string());
+
+    let (_, live) = send(&app, "GET", &format!("/projects/{}/tasks", kept), Some(&owner), None).await?;
+    let titles: Vec<&str> = live.as_array().context("expected array")?.iter().filter_map(|t| t["title"].as_str()).collect();
+    assert_eq!(titles, vec!["Stays"]);
+
+    // Same access rule as the live task listing
+    let (status, _) = send(&app, "GET", &format!("/projects/{}/tasks/trash", kept), Some(&stranger), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    Ok(())
+}
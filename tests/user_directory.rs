@@ -0,0 +1,58 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn admins_can_list_and_search_users() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Ops", "email": "ops@example.com", "password": "password123"}))).await?;
+    let admin_token = admin["token"].as_str().context("missing token")?.to_string();
+
+    let mut tokens = Vec::new();
+    for (name, email) in [("Alice Doe", "alice@example.com"), ("Bob Roe", "bob@corp.test"), ("Carol 100%", "carol@example.com"), ("Dan Gone", "dan@example.com")] {
+        let (status, body) = send(&app, "POST", "/auth/register", None, Some(json!({"name": name, "email": email, "password": "password123"}))).await?;
+        assert_eq!(status, StatusCode::CREATED, "{}", body);
+        tokens.push(body["token"].as_str().context("missing token")?.to_string());
+    }
+    let (status, _) = send(&app, "DELETE", "/auth/me", Some(&tokens[3]), Some(json!({"password": "password123"}))).await?;
+    assert!(status.is_success());
+
+    // Ordinary users have no user.view
+    let (status, _) = send(&app, "GET", "/users", Some(&tokens[0]), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = send(&app, "GET", "/users", Some(&admin_token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let users = body.as_array().context("expected array")?;
+    let names: Vec<&str> = users.iter().filter_map(|u| u["name"].as_str()).collect();
+    assert_eq!(names, vec!["Alice Doe", "Bob Roe", "Carol 100%", "Ops"], "soft-deleted users are excluded");
+    for user in users {
+        assert!(user.get("password_hash").is_none());
+        assert!(user["id"].as_str().is_some());
+    }
+
+    let (_, body) = send(&app, "GET", "/users?q=CORP", Some(&admin_token), None).await?;
+    assert_eq!(body.as_array().map(|a| a.len()), Some(1));
+    assert_eq!(body[0]["email"], "bob@corp.test");
+
+    // LIKE wildcards in the search term match literally
+    let (_, body) = send(&app, "GET", "/users?q=%25", Some(&admin_token), None).await?;
+    assert_eq!(body.as_array().map(|a| a.len()), Some(1));
+    assert_eq!(body[0]["name"], "Carol 100%");
+
+    let (_, body) = send(&app, "GET", "/users?limit=2&offset=1", Some(&admin_token), None).await?;
+    let names: Vec<&str> = body.as_array().context("expected array")?.iter().filter_map(|u| u["name"].as_str()).collect();
+    assert_eq!(names, vec!["Bob Roe", "Carol 100%"]);
+
+    Ok(())
+}
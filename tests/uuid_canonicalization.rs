@@ -30,7 +30,7 @@ async fn uuid_storage_is_text_for_new_rows() -> Result<()> {
     let register_body = json!({
         "name": "UUID Test",
         "email": "uuid-test@example.com",
-        "password": "password"
+        "password": "uuid-test-pass"
     });
 
     let req = Request::builder()
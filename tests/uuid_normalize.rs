@@ -0,0 +1,76 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::http::StatusCode;
+use serde_json::json;
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+
+#[tokio::test]
+async fn normalizing_text_uuids_restores_the_fast_path() -> Result<()> {
+    let (_dir, pool) = migrated_pool(false).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Ops", "email": "ops@example.com", "password": "password123"}))).await?;
+    let token = admin["token"].as_str().context("missing token")?.to_string();
+    let user_id = uuid::Uuid::parse_str(admin["user"]["id"].as_str().context("missing user id")?)?;
+
+    // A legacy project and task written with text UUIDs (the text ids do not
+    // satisfy the blob foreign keys until normalized)
+    let project_id = uuid::Uuid::new_v4();
+    let task_id = uuid::Uuid::new_v4();
+    let mut conn = pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+    sqlx::query("INSERT INTO projects (id, user_id, name, theme_color, created_at, updated_at) VALUES (?, ?, 'Legacy', '#000', datetime('now'), datetime('now'))")
+        .bind(project_id.to_string().to_uppercase())
+        .bind(user_id.to_string())
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("INSERT INTO tasks (id, project_id, title, status, created_at, updated_at) VALUES (?, ?, 'Old task', 'todo', datetime('now'), datetime('now'))")
+        .bind(task_id.to_string())
+        .bind(project_id.to_string())
+        .execute(&mut *conn)
+        .await?;
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+    drop(conn);
+
+    let fast_path = "SELECT COUNT(*) FROM projects WHERE id = ? AND user_id = ?";
+    let before: i64 = sqlx::query_scalar(fast_path).bind(project_id).bind(user_id).fetch_one(&pool).await?;
+    assert_eq!(before, 0, "blob binds cannot see text rows");
+
+    let (status, report) = send(&app, "POST", "/admin/normalize-uuids", Some(&token), None).await?;
+    assert_eq!(status, StatusCode::OK, "{}", report);
+    let tables = report["tables"].as_array().context("missing tables")?;
+    let projects = tables.iter().find(|t| t["table"] == "projects").context("missing projects entry")?;
+    assert_eq!(projects["converted"], 2);
+    assert_eq!(projects["rows_before"], projects["rows_after"]);
+    let tasks = tables.iter().find(|t| t["table"] == "tasks").context("missing tasks entry")?;
+    assert_eq!(tasks["converted"], 2);
+
+    let after: i64 = sqlx::query_scalar(fast_path).bind(project_id).bind(user_id).fetch_one(&pool).await?;
+    assert_eq!(after, 1);
+    let task_row: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE id = ? AND project_id = ?")
+        .bind(task_id)
+        .bind(project_id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(task_row, 1);
+
+    let (_, listed) = send(&app, "GET", "/projects", Some(&token), None).await?;
+    assert!(listed.to_string().contains(&project_id.to_string()), "{}", listed);
+
+    // Re-running is a no-op
+    let (_, again) = send(&app, "POST", "/admin/normalize-uuids", Some(&token), None).await?;
+    assert!(again["tables"].as_array().context("missing tables")?.iter().all(|t| t["converted"] == 0), "{}", again);
+
+    // Non-admins are refused
+    let (_, other) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Dev", "email": "dev@example.com", "password": "password123"}))).await?;
+    let other_token = other["token"].as_str().context("missing token")?;
+    let (status, _) = send(&app, "POST", "/admin/normalize-uuids", Some(other_token), None).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    Ok(())
+}
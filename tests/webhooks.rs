@@ -0,0 +1,142 @@
+mod common;
+
+use anyhow::{Context, Result};
+use axum::body;
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use std::sync::{Arc, Mutex};
+
+use common::{migrated_pool, send};
+use s_curve::create_app;
+use s_curve::events::webhooks::{sign_payload, EVENT_HEADER, SIGNATURE_HEADER};
+
+type Received = Arc<Mutex<Vec<(String, String, Vec<u8>)>>>;
+
+/// Local receiver recording (event header, signature header, body) per delivery.
+async fn spawn_receiver() -> Result<(String, Received)> {
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let sink = received.clone();
+    let app = axum::Router::new().route(
+        "/hook",
+        axum::routing::post(move |headers: axum::http::HeaderMap, body: axum::body::Bytes| {
+            let sink = sink.clone();
+            async move {
+                let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+                sink.lock().unwrap().push((header(EVENT_HEADER), header(SIGNATURE_HEADER), body.to_vec()));
+                StatusCode::OK
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}/hook", listener.local_addr()?);
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    Ok((url, received))
+}
+
+#[tokio::test]
+async fn webhook_receives_only_matching_events_with_signature() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Hooks", "email": "hooks@example.com", "password": "password123"}))).await?;
+    let token = admin["token"].as_str().context("missing token")?.to_string();
+
+    let (url, received) = spawn_receiver().await?;
+    let (status, hook) = send(&app, "POST", "/webhooks", Some(&token), Some(json!({"url": url, "secret": "s3cret", "event_name_filter": "task.*"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(hook["filter"], "event_name LIKE 'task.*'");
+    assert!(hook.get("secret").is_none());
+
+    let (status, _) = send(&app, "POST", "/webhooks", Some(&token), Some(json!({"url": url, "secret": "x", "event_name_filter": "task' OR name LIKE '*"}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // user.login must not be delivered; task.created must
+    let (status, _) = send(&app, "POST", "/auth/login", None, Some(json!({"email": "hooks@example.com", "password": "password123"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Hooked"}))).await?;
+    let (status, _) = send(&app, "POST", &format!("/projects/{}/tasks", project["id"].as_str().context("missing project id")?), Some(&token), Some(json!({"title": "Notify me"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    for _ in 0..100 {
+        if !received.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    // Give any wrongly-routed deliveries a moment to arrive too
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let deliveries = received.lock().unwrap().clone();
+    assert_eq!(deliveries.len(), 1, "unexpected deliveries: {:?}", deliveries.iter().map(|d| &d.0).collect::<Vec<_>>());
+    let (event, signature, body) = &deliveries[0];
+    assert_eq!(event, "task.created");
+    assert_eq!(signature, &format!("sha256={}", sign_payload(b"s3cret", body)));
+    let payload: Value = serde_json::from_slice(body)?;
+    assert_eq!(payload["name"], "task.created");
+
+    let hook_uri = format!("/webhooks/{}", hook["id"].as_str().context("missing webhook id")?);
+    let (status, updated) = send(&app, "PATCH", &hook_uri, Some(&token), Some(json!({"event_name_filter": "project.*"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["filter"], "event_name LIKE 'project.*'");
+    let (status, updated) = send(&app, "PATCH", &hook_uri, Some(&token), Some(json!({"filter": "severity >= critical"}))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["filter"], "severity >= critical");
+    let (status, _) = send(&app, "PATCH", &hook_uri, Some(&token), Some(json!({"filter": "severity >= loud"}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "PATCH", &hook_uri, Some(&token), Some(json!({"filter": "severity >= critical", "event_name_filter": "task.*"}))).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "DELETE", &hook_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "GET", &hook_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn webhook_filter_expressions_can_select_by_severity() -> Result<()> {
+    let (_dir, pool) = migrated_pool(true).await?;
+
+    std::env::set_var("JWT_SECRET", "test-secret");
+    std::env::set_var("BOOTSTRAP_SUPER_ADMIN", "true");
+    let app = create_app(pool.clone()).await?;
+
+    let (_, admin) = send(&app, "POST", "/auth/register", None, Some(json!({"name": "Severe", "email": "severe-hooks@example.com", "password": "password123"}))).await?;
+    let token = admin["token"].as_str().context("missing token")?.to_string();
+
+    let (url, received) = spawn_receiver().await?;
+    let filter = "event_name LIKE 'task.*' AND severity >= critical";
+    let (status, hook) = send(&app, "POST", "/webhooks", Some(&token), Some(json!({"url": url, "secret": "s3cret", "filter": filter}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(hook["filter"], filter);
+
+    // task.created is important and must not be delivered; task.purged is critical
+    let (_, project) = send(&app, "POST", "/projects", Some(&token), Some(json!({"name": "Severity"}))).await?;
+    let tasks_uri = format!("/projects/{}/tasks", project["id"].as_str().context("missing project id")?);
+    let (status, task) = send(&app, "POST", &tasks_uri, Some(&token), Some(json!({"title": "Short-lived"}))).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    let task_uri = format!("{}/{}", tasks_uri, task["id"].as_str().context("missing task id")?);
+    let (status, _) = send(&app, "DELETE", &task_uri, Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "DELETE", &format!("{}/purge?confirm=true", task_uri), Some(&token), None).await?;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    for _ in 0..100 {
+        if !received.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+    let deliveries = received.lock().unwrap().clone();
+    assert_eq!(deliveries.iter().map(|d| d.0.as_str()).collect::<Vec<_>>(), ["task.purged"]);
+
+    Ok(())
+}